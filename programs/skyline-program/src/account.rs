@@ -5,6 +5,9 @@
 
 use crate::*;
 
+/// Domain separator mixed into every bridging transaction hash.
+const BRIDGING_TRANSACTION_HASH_DOMAIN: &[u8] = b"skyline:bridging-transaction";
+
 /// Represents the validator set that controls bridge operations.
 ///
 /// The `ValidatorSet` account stores the list of validators authorized to perform
@@ -12,29 +15,601 @@ use crate::*;
 /// This account is initialized once and can be updated through the validator set
 /// change instruction with proper consensus.
 ///
+/// The account is allocated for the current number of validators (see
+/// [`ValidatorSet::space`]) and grows as validators are added.
+///
 /// # Fields
 ///
-/// * `signers` - Vector of validator public keys (max 128 validators)
+/// * `version` - Layout version of the account, see [`ACCOUNT_VERSION`]
+/// * `signers` - Vector of validator public keys (max `MAX_VALIDATORS` validators)
+/// * `weights` - Voting weight of each validator, parallel to `signers`
 /// * `threshold` - Number of signatures required for consensus (automatically calculated)
+/// * `threshold_weight` - Approval weight required for consensus (automatically calculated)
 /// * `bump` - Bump seed for the PDA derivation
 /// * `last_batch_id` - The last processed batch ID to prevent replay attacks
 /// * `bridge_request_count` - Total count of bridge requests processed
+/// * `guardian` - Key allowed to veto timelocked proposals (`Pubkey::default()` if unset)
+/// * `config` - Governance-controlled bridge configuration
+/// * `pending_update` - Batch ID of the scheduled validator set update, if any
+/// * `churn_window_start` - Slot at which the current churn window started
+/// * `churn_window_base` - Validator set size at the start of the current churn window
+/// * `churn_in_window` - Number of validator seats changed in the current churn window
+/// * `suspended` - Validators that are temporarily excluded from consensus
+/// * `removal_queue` - Validators proven to have equivocated, awaiting removal
+/// * `epoch` - Number of the current validator set, see [`ValidatorSetSnapshot`]
+/// * `paused` - Whether the guardian council has paused bridging
+/// * `last_quorum_slot` - Slot at which the validators last reached a quorum
+/// * `initializer` - The key that initialized the bridge (upgrade authority or authorized initializer)
+/// * `deployment` - Deployment configuration fixed at initialization
 #[account]
 #[derive(InitSpace)]
 pub struct ValidatorSet {
+    /// Layout version of the account, [`ACCOUNT_VERSION`] once created or migrated
+    pub version: u8,
     /// List of validator public keys that can sign bridge operations
-    /// Maximum length is constrained by `MAX_VALIDATORS` constant
-    #[max_len(MAX_VALIDATORS)]
+    /// Maximum length is constrained by `MAX_VALIDATORS` constant, the account is sized
+    /// for the actual length (see [`ValidatorSet::space`])
+    #[max_len(0)]
     pub signers: Vec<Pubkey>,
+    /// Voting weight of each validator, `weights[i]` belongs to `signers[i]`
+    #[max_len(0)]
+    pub weights: Vec<u64>,
     /// Consensus threshold - number of validator signatures required
-    /// Automatically calculated using the formula: num_signers - floor((num_signers - 1) / 3)
-    pub threshold: u8,
+    /// Automatically calculated using the formula: num_active - floor((num_active - 1) / 3),
+    /// where `num_active` is the number of validators that are not suspended.
+    /// Informational since votes are weighted, see `threshold_weight`.
+    pub threshold: u16,
+    /// Consensus threshold - sum of validator weights required
+    /// Automatically calculated as `quorum_weight_bps` of the total weight of the validators
+    /// that are not suspended, rounded up
+    pub threshold_weight: u64,
     /// Bump seed for the Program Derived Address (PDA)
     pub bump: u8,
     /// Last batch ID processed to prevent replay attacks and ensure sequential processing
     pub last_batch_id: u64,
     /// Total count of bridge requests processed since initialization
     pub bridge_request_count: u64,
+    /// Key allowed to veto timelocked proposals (`Pubkey::default()` if unset)
+    pub guardian: Pubkey,
+    /// Governance-controlled bridge configuration
+    pub config: BridgeConfig,
+    /// Batch ID of the scheduled (not yet activated) validator set update, if any
+    pub pending_update: Option<u64>,
+    /// Slot at which the current churn window started
+    pub churn_window_start: u64,
+    /// Validator set size at the start of the current churn window
+    pub churn_window_base: u32,
+    /// Number of validator seats changed (scheduled) in the current churn window
+    pub churn_in_window: u32,
+    /// Validators that are temporarily excluded from consensus (a subset of `signers`)
+    #[max_len(0)]
+    pub suspended: Vec<Suspension>,
+    /// Validators proven to have equivocated (a subset of `signers`), removed by the next
+    /// validator set update that activates
+    #[max_len(0)]
+    pub removal_queue: Vec<Pubkey>,
+    /// Number of the current validator set, starting at 0 at initialization and incremented
+    /// whenever the validators or their weights change
+    pub epoch: u64,
+    /// Whether the guardian council has paused bridging (`bridge_request` and
+    /// `bridge_transaction` are rejected while paused)
+    pub paused: bool,
+    /// Slot at which the validators last reached a quorum on a bridge transaction or a
+    /// governance proposal, used to detect a stalled validator set
+    pub last_quorum_slot: u64,
+    /// The key that initialized the bridge: the program upgrade authority, or the key of
+    /// the initializer grant of the instance
+    pub initializer: Pubkey,
+    /// Deployment configuration fixed at initialization
+    pub deployment: DeploymentConfig,
+}
+
+impl ValidatorSet {
+    /// Returns the account space (without the discriminator) needed for `num_validators`
+    /// validators, including room for all of them to be suspended and queued for removal.
+    pub fn space(num_validators: usize) -> usize {
+        Self::INIT_SPACE + num_validators * (32 + 8 + Suspension::INIT_SPACE + 32)
+    }
+}
+
+/// Represents an archived validator set.
+///
+/// A `ValidatorSetSnapshot` is created for every validator set the bridge has had: at
+/// initialization (epoch 0) and whenever the validators or their weights change, through a
+/// validator set update, a key rotation or a weight change. Snapshots are never closed, so
+/// auditors and contracts on other chains can prove which validators were authorized when a
+/// past batch was executed: batches with an ID above `activation_batch_id` were approved by
+/// this set, up to the `activation_batch_id` of the next epoch.
+///
+/// Suspensions are not archived; `threshold` and `threshold_weight` are the values in effect
+/// when the set was activated.
+///
+/// # Fields
+///
+/// * `version` - Layout version of the account, see [`ACCOUNT_VERSION`]
+/// * `instance_id` - The bridge instance of the archived validator set
+/// * `epoch` - Number of the archived validator set
+/// * `signers` - Validator public keys of the set
+/// * `weights` - Voting weight of each validator, parallel to `signers`
+/// * `threshold` - Consensus threshold when the set was activated
+/// * `threshold_weight` - Consensus threshold weight when the set was activated
+/// * `activation_batch_id` - Last batch ID consumed when the set was activated
+/// * `activation_slot` - Slot at which the set was activated
+/// * `set_hash` - Hash committing to the set (see [`ValidatorSetSnapshot::compute_hash`])
+/// * `bump` - Bump seed for the PDA derivation
+#[account]
+#[derive(InitSpace)]
+pub struct ValidatorSetSnapshot {
+    /// Layout version of the account, [`ACCOUNT_VERSION`] once created or migrated
+    pub version: u8,
+    /// The bridge instance of the archived validator set (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// Number of the archived validator set
+    pub epoch: u64,
+    /// Validator public keys of the set, the account is sized for the actual length
+    /// (see [`ValidatorSetSnapshot::space`])
+    #[max_len(0)]
+    pub signers: Vec<Pubkey>,
+    /// Voting weight of each validator, `weights[i]` belongs to `signers[i]`
+    #[max_len(0)]
+    pub weights: Vec<u64>,
+    /// Consensus threshold when the set was activated
+    pub threshold: u16,
+    /// Consensus threshold weight when the set was activated
+    pub threshold_weight: u64,
+    /// Last batch ID consumed when the set was activated
+    pub activation_batch_id: u64,
+    /// Slot at which the set was activated
+    pub activation_slot: u64,
+    /// Hash committing to the instance, the epoch, the signers, their weights and the
+    /// threshold weight
+    pub set_hash: [u8; 32],
+    /// Bump seed for the Program Derived Address (PDA)
+    pub bump: u8,
+}
+
+impl ValidatorSetSnapshot {
+    /// Returns the account space (without the discriminator) needed for `num_validators`
+    /// validators.
+    pub fn space(num_validators: usize) -> usize {
+        Self::INIT_SPACE + num_validators * (32 + 8)
+    }
+}
+
+/// Suspension of a single validator.
+///
+/// A suspended validator keeps its seat in `ValidatorSet.signers`, but cannot approve
+/// operations and does not count towards the active set size the threshold is
+/// calculated from. Reinstating it does not consume churn or require a new admission.
+///
+/// # Fields
+///
+/// * `validator` - The suspended validator key
+/// * `by_consensus` - Whether the suspension was imposed through governance
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct Suspension {
+    /// The suspended validator key
+    pub validator: Pubkey,
+    /// `true` if the suspension was imposed through governance (the validator is jailed
+    /// and only governance can reinstate it), `false` if the validator suspended itself
+    pub by_consensus: bool,
+}
+
+/// Governance-controlled configuration of the bridge.
+///
+/// The configuration is set at initialization and can afterwards only be replaced
+/// through a `SetConfig` governance proposal.
+///
+/// # Fields
+///
+/// * `timelock_slots` - Number of slots a timelocked proposal waits before it can be activated
+/// * `max_churn_bps` - Maximum share of the validator set that can change per churn window
+/// * `churn_window_slots` - Length of the churn window in slots
+/// * `rotation_timelock_slots` - Number of slots a validator key rotation waits before activation
+/// * `unbonding_slots` - Number of slots unbonded lamports stay slashable before withdrawal
+/// * `slash_reporter_bps` - Share of a slashed bond paid to the reporter
+/// * `quorum_weight_bps` - Share of the total active validator weight required for consensus
+/// * `recovery_timelock_slots` - Number of slots a validator set recovery waits before activation
+/// * `recovery_stall_slots` - Number of slots without a validator quorum or live quorum before a recovery is allowed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct BridgeConfig {
+    /// Number of slots a timelocked proposal (e.g. a validator set update) waits between
+    /// reaching quorum and activation. During this window the proposal can be vetoed.
+    /// A value of zero activates proposals as soon as they reach quorum.
+    pub timelock_slots: u64,
+    /// Maximum share of the validator set, in basis points of the set size at the start
+    /// of the churn window, whose seats can change within one churn window. At least one
+    /// seat can always change. A value of `BPS_DENOMINATOR` (100%) disables the limit.
+    pub max_churn_bps: u16,
+    /// Length of the churn window in slots
+    pub churn_window_slots: u64,
+    /// Number of slots a validator key rotation waits before activation. During this
+    /// window the guardian can veto the rotation. A value of zero activates rotations
+    /// immediately.
+    pub rotation_timelock_slots: u64,
+    /// Number of slots unbonded lamports stay slashable before they can be withdrawn
+    pub unbonding_slots: u64,
+    /// Share of a slashed bond paid to the reporter, in basis points. The rest goes to
+    /// the treasury.
+    pub slash_reporter_bps: u16,
+    /// Share of the total weight of the active validators that must approve an operation,
+    /// in basis points. Must be above 50% so two conflicting operations cannot both
+    /// reach quorum.
+    pub quorum_weight_bps: u16,
+    /// Number of slots a validator set recovery by the guardian council waits between
+    /// reaching the council quorum and activation
+    pub recovery_timelock_slots: u64,
+    /// Number of slots the validators must have gone without reaching a quorum, and without
+    /// the threshold weight sending heartbeats or approvals, before the guardian council can
+    /// recover the validator set. Checked both when the recovery is proposed and when it is
+    /// activated.
+    pub recovery_stall_slots: u64,
+}
+
+impl Default for BridgeConfig {
+    fn default() -> Self {
+        Self {
+            timelock_slots: DEFAULT_TIMELOCK_SLOTS,
+            max_churn_bps: DEFAULT_MAX_CHURN_BPS,
+            churn_window_slots: DEFAULT_CHURN_WINDOW_SLOTS,
+            rotation_timelock_slots: DEFAULT_ROTATION_TIMELOCK_SLOTS,
+            unbonding_slots: DEFAULT_UNBONDING_SLOTS,
+            slash_reporter_bps: DEFAULT_SLASH_REPORTER_BPS,
+            quorum_weight_bps: DEFAULT_QUORUM_WEIGHT_BPS,
+            recovery_timelock_slots: DEFAULT_RECOVERY_TIMELOCK_SLOTS,
+            recovery_stall_slots: DEFAULT_RECOVERY_STALL_SLOTS,
+        }
+    }
+}
+
+/// Deployment configuration of a bridge instance.
+///
+/// The deployment configuration is set at initialization and never changes. It tells
+/// validators and other chains which chain and which bridge deployment the program serves.
+///
+/// # Fields
+///
+/// * `chain_id` - Chain ID of Solana in the bridge network
+/// * `instance_id` - Identifier of this bridge deployment
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct DeploymentConfig {
+    /// Chain ID the bridge network assigns to this chain, the counterpart of
+    /// `destination_chain` in bridge requests
+    pub chain_id: u8,
+    /// Identifier of this bridge deployment, distinguishing several bridges on the same chain
+    pub instance_id: u64,
+}
+
+/// Represents a scheduled rotation of a single validator key.
+///
+/// The `KeyRotation` account is created when a validator and its new key both sign a
+/// `rotate_validator_key` instruction. Once the rotation timelock has expired the new key
+/// replaces the old key in place in `ValidatorSet.signers` and the account is closed.
+///
+/// # Fields
+///
+/// * `version` - Layout version of the account, see [`ACCOUNT_VERSION`]
+/// * `old_key` - The validator key being rotated out
+/// * `new_key` - The key replacing `old_key`
+/// * `activation_slot` - Slot from which the rotation can be activated
+/// * `bump` - Bump seed for the PDA derivation
+#[account]
+#[derive(InitSpace)]
+pub struct KeyRotation {
+    /// Layout version of the account, [`ACCOUNT_VERSION`] once created or migrated
+    pub version: u8,
+    /// The validator key being rotated out
+    pub old_key: Pubkey,
+    /// The key replacing `old_key`
+    pub new_key: Pubkey,
+    /// Slot from which the rotation can be activated
+    pub activation_slot: u64,
+    /// Bump seed for the Program Derived Address (PDA)
+    pub bump: u8,
+}
+
+/// Public keys of a validator on the foreign chains connected to the bridge.
+///
+/// Other chains mirror the validator set from Solana, so they need to know which key
+/// each validator uses to sign on their side. An all-zero key means "not set".
+///
+/// # Fields
+///
+/// * `cardano` - Cardano ed25519 verification key
+/// * `evm` - EVM secp256k1 public key (SEC1 compressed)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct ForeignKeys {
+    /// Cardano ed25519 verification key
+    pub cardano: [u8; 32],
+    /// EVM secp256k1 public key (SEC1 compressed)
+    pub evm: [u8; 33],
+}
+
+impl Default for ForeignKeys {
+    fn default() -> Self {
+        Self {
+            cardano: [0; 32],
+            evm: [0; 33],
+        }
+    }
+}
+
+/// Descriptive and foreign-chain data of a single validator.
+///
+/// # Fields
+///
+/// * `name` - Display name of the validator (max 32 bytes)
+/// * `endpoint` - Endpoint URL of the validator operator (max 128 bytes)
+/// * `foreign_keys` - Public keys of the validator on foreign chains
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct ValidatorMetadata {
+    /// Display name of the validator
+    #[max_len(MAX_VALIDATOR_NAME_LEN)]
+    pub name: String,
+    /// Endpoint URL of the validator operator
+    #[max_len(MAX_VALIDATOR_ENDPOINT_LEN)]
+    pub endpoint: String,
+    /// Public keys of the validator on foreign chains
+    pub foreign_keys: ForeignKeys,
+}
+
+/// Represents the registry record of a single validator.
+///
+/// The `ValidatorInfo` account is created the first time a validator's metadata is set,
+/// either by the validator itself (`update_validator_info`), through a `SetValidatorInfo`
+/// governance proposal, or when a validator set update carrying foreign keys is executed.
+///
+/// # Fields
+///
+/// * `version` - Layout version of the account, see [`ACCOUNT_VERSION`]
+/// * `validator` - The validator key the record belongs to
+/// * `metadata` - Descriptive and foreign-chain data of the validator
+/// * `bump` - Bump seed for the PDA derivation
+#[account]
+#[derive(InitSpace)]
+pub struct ValidatorInfo {
+    /// Layout version of the account, [`ACCOUNT_VERSION`] once created or migrated
+    pub version: u8,
+    /// The validator key the record belongs to
+    pub validator: Pubkey,
+    /// Descriptive and foreign-chain data of the validator
+    pub metadata: ValidatorMetadata,
+    /// Bump seed for the Program Derived Address (PDA)
+    pub bump: u8,
+}
+
+/// Participation statistics of a single validator.
+///
+/// # Fields
+///
+/// * `validator` - The validator key the statistics belong to
+/// * `approvals` - Number of approvals submitted for bridging transactions and proposals
+/// * `last_approval_slot` - Slot of the most recent approval (0 if none)
+/// * `missed_batches` - Number of batches that reached quorum without the validator's approval
+/// * `last_heartbeat_slot` - Slot of the most recent heartbeat (0 if none)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct ValidatorActivity {
+    /// The validator key the statistics belong to
+    pub validator: Pubkey,
+    /// Number of approvals submitted for bridging transactions and governance proposals
+    pub approvals: u64,
+    /// Slot of the most recent approval (0 if none)
+    pub last_approval_slot: u64,
+    /// Number of batches that reached quorum while the validator was active, but without its approval
+    pub missed_batches: u64,
+    /// Slot of the most recent heartbeat (0 if none)
+    pub last_heartbeat_slot: u64,
+}
+
+/// Represents the participation statistics of the validator set.
+///
+/// The `ValidatorStats` account is created at initialization and updated whenever validators
+/// approve a bridging transaction or governance proposal, a batch reaches quorum, or a
+/// validator sends a `heartbeat`. Governance and dashboards use it to decide on rotations
+/// and suspensions.
+///
+/// An entry is added the first time a validator is recorded. The account holds at most one
+/// entry per current validator (see [`ValidatorStats::space`]): entries of keys that have left
+/// the validator set are dropped once it runs out of room, and a rotated key starts
+/// with fresh statistics.
+///
+/// # Fields
+///
+/// * `version` - Layout version of the account, see [`ACCOUNT_VERSION`]
+/// * `entries` - Participation statistics per validator key
+/// * `bump` - Bump seed for the PDA derivation
+#[account]
+#[derive(InitSpace)]
+pub struct ValidatorStats {
+    /// Layout version of the account, [`ACCOUNT_VERSION`] once created or migrated
+    pub version: u8,
+    /// Participation statistics per validator key, the account is sized for the current
+    /// validator set (see [`ValidatorStats::space`])
+    #[max_len(0)]
+    pub entries: Vec<ValidatorActivity>,
+    /// Bump seed for the Program Derived Address (PDA)
+    pub bump: u8,
+}
+
+impl ValidatorStats {
+    /// Returns the account space (without the discriminator) needed for `num_entries` entries.
+    pub fn space(num_entries: usize) -> usize {
+        Self::INIT_SPACE + num_entries * ValidatorActivity::INIT_SPACE
+    }
+}
+
+/// Represents the SOL bond of a single validator.
+///
+/// The bond is held as lamports of the program-owned `ValidatorBond` account, on top of its
+/// rent-exempt minimum. Unbonded lamports stay slashable for `config.unbonding_slots`
+/// before the validator can withdraw them. A proven equivocation slashes the whole bond,
+/// including lamports that are unbonding.
+///
+/// # Fields
+///
+/// * `version` - Layout version of the account, see [`ACCOUNT_VERSION`]
+/// * `validator` - The validator key the bond belongs to
+/// * `amount` - Bonded lamports
+/// * `unbonding_amount` - Lamports that are unbonding
+/// * `unbonding_slot` - Slot from which the unbonding lamports can be withdrawn
+/// * `bump` - Bump seed for the PDA derivation
+#[account]
+#[derive(InitSpace)]
+pub struct ValidatorBond {
+    /// Layout version of the account, [`ACCOUNT_VERSION`] once created or migrated
+    pub version: u8,
+    /// The validator key the bond belongs to
+    pub validator: Pubkey,
+    /// Bonded lamports
+    pub amount: u64,
+    /// Lamports that are unbonding (still slashable)
+    pub unbonding_amount: u64,
+    /// Slot from which the unbonding lamports can be withdrawn
+    pub unbonding_slot: u64,
+    /// Bump seed for the Program Derived Address (PDA)
+    pub bump: u8,
+}
+
+/// Represents the treasury of the bridge.
+///
+/// The `Treasury` account holds lamports on top of its rent-exempt minimum. It receives the
+/// share of slashed bonds not paid to the reporter, and is spent only through a
+/// `WithdrawTreasury` governance proposal.
+///
+/// # Fields
+///
+/// * `version` - Layout version of the account, see [`ACCOUNT_VERSION`]
+/// * `bump` - Bump seed for the PDA derivation
+#[account]
+#[derive(InitSpace)]
+pub struct Treasury {
+    /// Layout version of the account, [`ACCOUNT_VERSION`] once created or migrated
+    pub version: u8,
+    /// Bump seed for the Program Derived Address (PDA)
+    pub bump: u8,
+}
+
+/// Represents the fee vault of the bridge.
+///
+/// The `FeeVault` account collects the fees charged by `bridge_request`: SOL fees are held
+/// on top of its rent-exempt minimum, token fees in its associated token account for each
+/// mint. It is created by the first bridge request or `SetRewards` governance proposal and
+/// spent through `WithdrawFees` governance proposals and reward claims.
+///
+/// Executed bridge transactions credit the relayer and the approving validators with the
+/// configured rewards, paid from the SOL fees (pro rata while the fee vault cannot cover
+/// them all). Credited rewards stay reserved in the fee vault until they are claimed.
+///
+/// # Fields
+///
+/// * `version` - Layout version of the account, see [`ACCOUNT_VERSION`]
+/// * `relayer_reward` - Lamports credited to the payer of an executed bridge transaction
+/// * `validator_reward` - Lamports credited to each approving validator of an executed
+///   bridge transaction
+/// * `reserved_rewards` - Lamports credited to participants and not claimed yet
+/// * `bump` - Bump seed for the PDA derivation
+#[account]
+#[derive(InitSpace)]
+pub struct FeeVault {
+    /// Layout version of the account, [`ACCOUNT_VERSION`] once created
+    pub version: u8,
+    /// Lamports credited to the payer of an executed bridge transaction
+    pub relayer_reward: u64,
+    /// Lamports credited to each approving validator of an executed bridge transaction
+    pub validator_reward: u64,
+    /// Lamports credited to participants and not claimed yet, they cannot be withdrawn
+    pub reserved_rewards: u64,
+    /// Bump seed for the Program Derived Address (PDA)
+    pub bump: u8,
+}
+
+impl FeeVault {
+    /// Loads the fee vault from its account.
+    ///
+    /// The address of the account is checked by the caller.
+    ///
+    /// # Errors
+    /// * `AccountOwnedByWrongProgram` - If the account is not owned by the program
+    /// * `AccountNotMigrated` - If the account is not at the current layout version
+    pub fn load(info: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(
+            *info.owner,
+            crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+        let fee_vault = Self::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(
+            fee_vault.version == ACCOUNT_VERSION,
+            CustomError::AccountNotMigrated
+        );
+
+        Ok(fee_vault)
+    }
+
+    /// Returns the lamports of the fee vault account `info` beyond its rent-exempt minimum
+    /// and the reserved rewards.
+    pub fn available(&self, info: &AccountInfo) -> Result<u64> {
+        let rent_exempt = Rent::get()?.minimum_balance(info.data_len());
+        Ok(info
+            .lamports()
+            .saturating_sub(rent_exempt)
+            .saturating_sub(self.reserved_rewards))
+    }
+}
+
+/// Represents the rewards of a relayer or validator.
+///
+/// A `RewardAccount` is created the first time the participant is credited with a reward
+/// (see [`FeeVault`]), funded by the payer of the bridge transaction. The participant
+/// claims the accrued lamports with `claim_rewards`.
+///
+/// # Fields
+///
+/// * `version` - Layout version of the account, see [`ACCOUNT_VERSION`]
+/// * `participant` - The relayer or validator key the rewards belong to
+/// * `accrued` - Lamports credited and not claimed yet
+/// * `claimed` - Lamports claimed so far
+/// * `bump` - Bump seed for the PDA derivation
+#[account]
+#[derive(InitSpace)]
+pub struct RewardAccount {
+    /// Layout version of the account, [`ACCOUNT_VERSION`] once created
+    pub version: u8,
+    /// The relayer or validator key the rewards belong to
+    pub participant: Pubkey,
+    /// Lamports credited and not claimed yet
+    pub accrued: u64,
+    /// Lamports claimed so far
+    pub claimed: u64,
+    /// Bump seed for the Program Derived Address (PDA)
+    pub bump: u8,
+}
+
+/// Authorizes a key to initialize a bridge instance.
+///
+/// Only the program upgrade authority can initialize a bridge instance. Once the upgrade
+/// authority has been handed to the upgrade authority PDA, nobody can sign as it, so new
+/// instances are initialized by a key the validators of the governing instance (see
+/// [`GOVERNING_INSTANCE_ID`]) authorize through an `AuthorizeInitializer` governance action.
+/// `initialize` consumes (closes) the grant.
+///
+/// # Fields
+///
+/// * `version` - Layout version of the account, see [`ACCOUNT_VERSION`]
+/// * `instance_id` - The bridge instance the key may initialize
+/// * `initializer` - The key allowed to initialize the instance
+/// * `bump` - Bump seed for the PDA derivation
+#[account]
+#[derive(InitSpace)]
+pub struct InitializerGrant {
+    /// Layout version of the account, [`ACCOUNT_VERSION`] once created
+    pub version: u8,
+    /// The bridge instance the key may initialize
+    pub instance_id: u64,
+    /// The key allowed to initialize the instance
+    pub initializer: Pubkey,
+    /// Bump seed for the Program Derived Address (PDA)
+    pub bump: u8,
 }
 
 /// Represents the vault account that holds bridged tokens.
@@ -46,11 +621,14 @@ pub struct ValidatorSet {
 ///
 /// # Fields
 ///
+/// * `version` - Layout version of the account, see [`ACCOUNT_VERSION`]
 /// * `address` - The public key of the vault account (same as the account's key)
 /// * `bump` - Bump seed for the PDA derivation
 #[account]
 #[derive(InitSpace)]
 pub struct Vault {
+    /// Layout version of the account, [`ACCOUNT_VERSION`] once created or migrated
+    pub version: u8,
     /// Bump seed for the Program Derived Address (PDA)
     pub bump: u8,
 }
@@ -64,6 +642,7 @@ pub struct Vault {
 ///
 /// # Fields
 ///
+/// * `version` - Layout version of the account, see [`ACCOUNT_VERSION`]
 /// * `id` - Unique identifier for the transaction (same as the account's key)
 /// * `amount` - The amount of tokens to transfer to the recipient
 /// * `receiver` - The public key of the recipient on the destination chain
@@ -71,9 +650,13 @@ pub struct Vault {
 /// * `signers` - List of validator public keys that have approved this transaction
 /// * `bump` - Bump seed for the PDA derivation
 /// * `batch_id` - The batch ID of this transaction (must be greater than last_batch_id)
+/// * `source_chain` - The chain ID of the chain the tokens are bridged from
+/// * `source_amount` - The amount in the smallest unit of the token on the source chain
 #[account]
 #[derive(InitSpace)]
 pub struct BridgingTransaction {
+    /// Layout version of the account, [`ACCOUNT_VERSION`] once created or migrated
+    pub version: u8,
     /// Unique identifier for the transaction
     pub id: Pubkey,
     /// The amount of tokens to transfer to the recipient
@@ -82,49 +665,660 @@ pub struct BridgingTransaction {
     pub receiver: Pubkey,
     /// The public key of the token mint being bridged
     pub mint_token: Pubkey,
-    /// List of validator public keys that have approved this transaction
-    #[max_len(MAX_VALIDATORS)]
+    /// List of validator public keys that have approved this transaction, the account
+    /// grows with every approval (see [`BridgingTransaction::space`])
+    #[max_len(0)]
     pub signers: Vec<Pubkey>,
     /// Bump seed for the Program Derived Address (PDA)
     pub bump: u8,
     /// The batch ID of this transaction (must be greater than last_batch_id)
     pub batch_id: u64,
+    /// The chain ID of the chain the tokens are bridged from
+    pub source_chain: u8,
+    /// The amount in the smallest unit of the token on the source chain, before its
+    /// conversion to `amount`; zero for transactions migrated from version 1, which recorded
+    /// neither (the next approval records both and clears the version 1 `signers`)
+    pub source_amount: u128,
+}
+
+impl BridgingTransaction {
+    /// Returns the account space (without the discriminator) needed for `num_signers` approvals.
+    pub fn space(num_signers: usize) -> usize {
+        Self::INIT_SPACE + num_signers * 32
+    }
+
+    /// Computes the operation hash validators attest to when approving a bridging transaction.
+    ///
+    /// The hash is taken over a domain separator and the transaction details as they are
+    /// observed on the source chain, so an approval is bound to the amount before its
+    /// conversion to the decimals of the mint.
+    ///
+    /// # Arguments
+    /// * `amount` - The amount in the smallest unit of the token on the source chain
+    /// * `source_chain` - The chain ID of the chain the tokens are bridged from
+    /// * `receiver` - The recipient of the bridged tokens
+    /// * `mint` - The mint of the bridged tokens
+    pub fn operation_hash(
+        amount: u128,
+        source_chain: u8,
+        receiver: &Pubkey,
+        mint: &Pubkey,
+    ) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(BRIDGING_TRANSACTION_HASH_DOMAIN);
+        hasher.update(&amount.to_le_bytes());
+        hasher.update(&[source_chain]);
+        hasher.update(receiver.as_ref());
+        hasher.update(mint.as_ref());
+        *hasher.finalize().as_bytes()
+    }
 }
 
-/// Represents a pending validator set update that requires consensus.
+/// Typed action carried by a governance proposal.
 ///
-/// The `ValidatorDelta` account tracks a proposed change to the validator set that
-/// requires approval from the current validators. Once enough validators have approved
-/// the proposal (meeting the threshold), the changes are applied to the validator set,
-/// and the account is closed.
+/// Each variant describes one administrative change the validator set can vote on.
+/// All actions share the same approval accumulator ([`GovernanceProposal`]), hashing
+/// and batch-id replay protection, and are dispatched once the threshold is reached.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub enum GovernanceAction {
+    /// Add and/or remove validators from the validator set
+    UpdateValidators {
+        /// List of new validator public keys to add (max 10 per change)
+        #[max_len(MAX_VALIDATORS_CHANGE)]
+        added: Vec<Pubkey>,
+        /// List of validator public keys to remove (max 10 per change)
+        #[max_len(MAX_VALIDATORS_CHANGE)]
+        removed: Vec<Pubkey>,
+        /// Foreign-chain keys of the added validators, in the order of `added`
+        /// (empty if the validators set them later themselves)
+        #[max_len(MAX_VALIDATORS_CHANGE)]
+        foreign_keys: Vec<ForeignKeys>,
+        /// Voting weights of the added validators, in the order of `added`
+        /// (empty to give every added validator `DEFAULT_VALIDATOR_WEIGHT`)
+        #[max_len(MAX_VALIDATORS_CHANGE)]
+        weights: Vec<u64>,
+    },
+    /// Replace the bridge configuration
+    SetConfig {
+        /// The new bridge configuration
+        config: BridgeConfig,
+    },
+    /// Set the guardian key allowed to veto timelocked proposals
+    SetGuardian {
+        /// The new guardian key (`Pubkey::default()` removes the guardian)
+        guardian: Pubkey,
+    },
+    /// Set the registry record of a validator
+    SetValidatorInfo {
+        /// The validator whose record is set
+        validator: Pubkey,
+        /// The new metadata of the validator
+        metadata: ValidatorMetadata,
+    },
+    /// Suspend (jail) or reinstate a validator
+    SetValidatorSuspension {
+        /// The validator whose suspension changes
+        validator: Pubkey,
+        /// `true` to suspend the validator, `false` to reinstate it
+        suspended: bool,
+    },
+    /// Change the voting weight of a validator
+    SetValidatorWeight {
+        /// The validator whose weight changes
+        validator: Pubkey,
+        /// The new voting weight of the validator
+        weight: u64,
+    },
+    /// Transfer lamports from the treasury
+    WithdrawTreasury {
+        /// The account receiving the lamports
+        recipient: Pubkey,
+        /// The number of lamports to transfer
+        amount: u64,
+    },
+    /// Apply the sealed validator delta assembled for the proposal's batch ID
+    ApplyValidatorDelta {
+        /// Hash of the sealed delta (see [`ValidatorDelta::delta_hash`])
+        delta_hash: [u8; 32],
+    },
+    /// Appoint the guardian council
+    SetGuardianCouncil {
+        /// The council members
+        #[max_len(MAX_GUARDIANS)]
+        members: Vec<Pubkey>,
+        /// Number of member approvals a guardian proposal needs (a majority of `members`)
+        threshold: u16,
+    },
+    /// Register a destination chain or replace its configuration
+    SetChain {
+        /// The configuration of the chain, identified by its chain ID
+        chain: ChainConfig,
+    },
+    /// Register a token or replace its configuration
+    SetToken {
+        /// The mint of the token
+        mint: Pubkey,
+        /// How the token is moved across the bridge
+        mode: TokenMode,
+        /// Whether the token can be bridged
+        enabled: bool,
+        /// The chains the token can be bridged to and from, with its foreign asset on each
+        #[max_len(MAX_TOKEN_CHAINS)]
+        chains: Vec<TokenChain>,
+    },
+    /// Transfer collected fees out of the fee vault
+    WithdrawFees {
+        /// The mint of the withdrawn token fees, or `None` to withdraw SOL fees
+        mint: Option<Pubkey>,
+        /// The account receiving the fees: a token account of the mint for token fees
+        recipient: Pubkey,
+        /// The amount to transfer, in the smallest unit of the mint or in lamports
+        amount: u64,
+    },
+    /// Set the rewards credited for executed bridge transactions
+    SetRewards {
+        /// Lamports credited to the payer of an executed bridge transaction
+        relayer_reward: u64,
+        /// Lamports credited to each approving validator of an executed bridge transaction
+        validator_reward: u64,
+    },
+    /// Upgrade the program from a buffer account
+    UpgradeProgram {
+        /// The loader buffer holding the new program, its authority must be the upgrade
+        /// authority PDA
+        buffer: Pubkey,
+        /// SHA-256 hash of the program bytes in the buffer (after the buffer metadata)
+        buffer_hash: [u8; 32],
+    },
+    /// Allow a key to initialize a bridge instance (see [`InitializerGrant`])
+    AuthorizeInitializer {
+        /// The bridge instance the key may initialize
+        instance_id: u64,
+        /// The key allowed to initialize the instance
+        initializer: Pubkey,
+    },
+}
+
+/// Represents a validator set update assembled across multiple transactions.
+///
+/// A `GovernanceAction::UpdateValidators` proposal has to fit in the arguments of a single
+/// instruction. Larger updates are appended to a `ValidatorDelta` in chunks by its proposer,
+/// and sealed once complete. The seal validates the whole update and commits to it with
+/// `delta_hash`, which an `ApplyValidatorDelta` proposal for the same batch ID carries; the
+/// delta cannot change after sealing, so one quorum applies the complete update.
+///
+/// Every added key proves possession of its key in the chunk that adds it.
 ///
 /// # Fields
 ///
-/// * `id` - Unique identifier for the validator set change (same as the account's key)
-/// * `added` - List of new validator public keys to add to the validator set
-/// * `removed` - List of validator indices to remove from the validator set
+/// * `version` - Layout version of the account, see [`ACCOUNT_VERSION`]
+/// * `proposer` - The validator assembling the delta, receives its rent when it is closed
+/// * `batch_id` - The batch ID of the validator set update
+/// * `added` - Validator keys added by the update
+/// * `removed` - Validator keys removed by the update
+/// * `weights` - Voting weights of the added validators, parallel to `added`
+/// * `sealed` - Whether the delta is complete and can be proposed
+/// * `delta_hash` - Hash of the sealed delta (all zeros until sealed)
 /// * `bump` - Bump seed for the PDA derivation
-/// * `batch_id` - The batch ID of this validator set change (must be greater than last_batch_id)
-/// * `signers` - List of validator public keys that have approved this change
-/// * `proposal_hash` - Hash of the proposal to ensure all validators approve the same change
 #[account]
 #[derive(InitSpace)]
 pub struct ValidatorDelta {
-    /// Unique identifier for the validator set change
-    pub id: Pubkey,
-    /// List of new validator public keys to add (max 10 per change)
-    #[max_len(MAX_VALIDATORS_CHANGE)]
+    /// Layout version of the account, [`ACCOUNT_VERSION`] once created or migrated
+    pub version: u8,
+    /// The validator assembling the delta
+    pub proposer: Pubkey,
+    /// The batch ID of the validator set update
+    pub batch_id: u64,
+    /// Validator keys added by the update, the account grows with every chunk
+    /// (see [`ValidatorDelta::space`])
+    #[max_len(0)]
     pub added: Vec<Pubkey>,
-    /// List of validator public keys to remove (max 10 per change)
-    #[max_len(MAX_VALIDATORS_CHANGE)]
+    /// Validator keys removed by the update
+    #[max_len(0)]
     pub removed: Vec<Pubkey>,
+    /// Voting weights of the added validators, `weights[i]` belongs to `added[i]`
+    #[max_len(0)]
+    pub weights: Vec<u64>,
+    /// Whether the delta is complete and can be proposed
+    pub sealed: bool,
+    /// Hash of the sealed delta (all zeros until sealed)
+    pub delta_hash: [u8; 32],
+    /// Bump seed for the Program Derived Address (PDA)
+    pub bump: u8,
+}
+
+impl ValidatorDelta {
+    /// Returns the account space (without the discriminator) needed for `num_added` added
+    /// and `num_removed` removed validators.
+    pub fn space(num_added: usize, num_removed: usize) -> usize {
+        Self::INIT_SPACE + num_added * (32 + 8) + num_removed * 32
+    }
+}
+
+/// Lifecycle state of a governance proposal.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum ProposalStatus {
+    /// The proposal is collecting validator approvals
+    Voting,
+    /// The proposal reached quorum and waits for its activation slot
+    Scheduled,
+}
+
+/// Represents a pending governance proposal that requires consensus.
+///
+/// The `GovernanceProposal` account tracks a proposed administrative action that
+/// requires approval from the current validators. Once enough validators have approved
+/// the proposal (meeting the threshold), the action is executed and the account is closed.
+///
+/// # Fields
+///
+/// * `version` - Layout version of the account, see [`ACCOUNT_VERSION`]
+/// * `id` - Unique identifier for the proposal (same as the account's key)
+/// * `action` - The typed action to execute once the proposal is approved
+/// * `bump` - Bump seed for the PDA derivation
+/// * `batch_id` - The batch ID of this proposal (must be greater than last_batch_id)
+/// * `signers` - List of validator public keys that have approved this proposal
+/// * `proposal_hash` - Hash of the action to ensure all validators approve the same change
+/// * `status` - Lifecycle state of the proposal
+/// * `activation_slot` - Slot from which a scheduled proposal can be activated
+/// * `vetoes` - List of validator public keys that have vetoed the scheduled proposal
+/// * `admitted` - List of new validator keys that have proven possession of their key
+#[account]
+#[derive(InitSpace)]
+pub struct GovernanceProposal {
+    /// Layout version of the account, [`ACCOUNT_VERSION`] once created or migrated
+    pub version: u8,
+    /// Unique identifier for the proposal
+    pub id: Pubkey,
+    /// The typed action to execute once the proposal is approved
+    pub action: GovernanceAction,
     /// Bump seed for the Program Derived Address (PDA)
     pub bump: u8,
-    /// The batch ID of this validator set change (must be greater than last_batch_id)
+    /// The batch ID of this proposal (must be greater than last_batch_id)
     pub batch_id: u64,
-    /// List of validator public keys that have approved this change
-    #[max_len(MAX_VALIDATORS)]
+    /// List of validator public keys that have approved this proposal, the account grows
+    /// with every approval (see [`GovernanceProposal::space`])
+    #[max_len(0)]
     pub signers: Vec<Pubkey>,
-    /// Hash of the proposal to ensure all validators approve the same change
+    /// Hash of the action to ensure all validators approve the same change
     pub proposal_hash: [u8; 32],
+    /// Lifecycle state of the proposal
+    pub status: ProposalStatus,
+    /// Slot from which a scheduled proposal can be activated
+    pub activation_slot: u64,
+    /// List of validator public keys that have vetoed the scheduled proposal, the account
+    /// grows with every veto (see [`GovernanceProposal::space`])
+    #[max_len(0)]
+    pub vetoes: Vec<Pubkey>,
+    /// List of new validator keys that have proven possession of their key
+    #[max_len(MAX_VALIDATORS_CHANGE)]
+    pub admitted: Vec<Pubkey>,
+}
+
+impl GovernanceProposal {
+    /// Returns the account space (without the discriminator) needed for `num_signers`
+    /// approvals and `num_vetoes` vetoes.
+    pub fn space(num_signers: usize, num_vetoes: usize) -> usize {
+        Self::INIT_SPACE + (num_signers + num_vetoes) * 32
+    }
+}
+
+/// Represents the guardian council of the bridge.
+///
+/// The guardian council is a second, independent group of keys with its own threshold. It
+/// is appointed by the validators through a `SetGuardianCouncil` governance proposal and
+/// acts through [`GuardianProposal`]s. Its powers are limited to emergencies and recovery:
+/// pausing bridging, vetoing timelocked proposals and key rotations, and replacing a
+/// validator set that has stopped reaching quorums (see [`GuardianAction`]).
+///
+/// # Fields
+///
+/// * `version` - Layout version of the account, see [`ACCOUNT_VERSION`]
+/// * `members` - Public keys of the council members (max `MAX_GUARDIANS`)
+/// * `threshold` - Number of member approvals required for a guardian proposal
+/// * `last_batch_id` - The last council batch ID consumed, to prevent replay attacks
+/// * `bump` - Bump seed for the PDA derivation
+#[account]
+#[derive(InitSpace)]
+pub struct GuardianCouncil {
+    /// Layout version of the account, [`ACCOUNT_VERSION`] once created or migrated
+    pub version: u8,
+    /// Public keys of the council members
+    #[max_len(MAX_GUARDIANS)]
+    pub members: Vec<Pubkey>,
+    /// Number of member approvals required for a guardian proposal
+    pub threshold: u16,
+    /// The last council batch ID consumed, sequenced independently of the validator batch IDs
+    pub last_batch_id: u64,
+    /// Bump seed for the Program Derived Address (PDA)
+    pub bump: u8,
+}
+
+/// Typed action carried by a guardian proposal.
+///
+/// Each variant describes one emergency or recovery power of the guardian council. All
+/// actions share the same approval accumulator ([`GuardianProposal`]) and are dispatched
+/// once the council threshold is reached.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub enum GuardianAction {
+    /// Pause or resume bridging
+    SetPaused {
+        /// `true` to pause `bridge_request` and `bridge_transaction`, `false` to resume them
+        paused: bool,
+    },
+    /// Cancel a scheduled governance proposal
+    VetoProposal {
+        /// The batch ID of the scheduled governance proposal
+        batch_id: u64,
+    },
+    /// Cancel a scheduled validator key rotation
+    VetoKeyRotation {
+        /// The validator key being rotated out
+        old_key: Pubkey,
+    },
+    /// Replace a stalled validator set
+    RecoverValidatorSet {
+        /// Public keys of the new validator set (max 10)
+        #[max_len(MAX_VALIDATORS_CHANGE)]
+        signers: Vec<Pubkey>,
+        /// Voting weights of the new validators, in the order of `signers`
+        /// (empty to give every validator `DEFAULT_VALIDATOR_WEIGHT`)
+        #[max_len(MAX_VALIDATORS_CHANGE)]
+        weights: Vec<u64>,
+    },
+}
+
+/// Represents a pending guardian proposal that requires the council threshold.
+///
+/// The `GuardianProposal` account mirrors [`GovernanceProposal`]: it is created on the first
+/// call, accumulates member approvals across transactions and is executed and closed once
+/// the council threshold is reached. A `RecoverValidatorSet` proposal is timelocked.
+///
+/// # Fields
+///
+/// * `version` - Layout version of the account, see [`ACCOUNT_VERSION`]
+/// * `id` - Unique identifier for the proposal (same as the account's key)
+/// * `action` - The typed action to execute once the proposal is approved
+/// * `bump` - Bump seed for the PDA derivation
+/// * `batch_id` - The council batch ID of this proposal (must be greater than the council's last_batch_id)
+/// * `signers` - List of council members that have approved this proposal
+/// * `proposal_hash` - Hash of the action to ensure all members approve the same action
+/// * `status` - Lifecycle state of the proposal
+/// * `activation_slot` - Slot from which a scheduled proposal can be activated
+/// * `admitted` - List of recovered validator keys that have proven possession of their key
+#[account]
+#[derive(InitSpace)]
+pub struct GuardianProposal {
+    /// Layout version of the account, [`ACCOUNT_VERSION`] once created or migrated
+    pub version: u8,
+    /// Unique identifier for the proposal
+    pub id: Pubkey,
+    /// The typed action to execute once the proposal is approved
+    pub action: GuardianAction,
+    /// Bump seed for the Program Derived Address (PDA)
+    pub bump: u8,
+    /// The council batch ID of this proposal (must be greater than the council's last_batch_id)
+    pub batch_id: u64,
+    /// List of council members that have approved this proposal, the account grows with
+    /// every approval (see [`GuardianProposal::space`])
+    #[max_len(0)]
+    pub signers: Vec<Pubkey>,
+    /// Hash of the action to ensure all members approve the same action
+    pub proposal_hash: [u8; 32],
+    /// Lifecycle state of the proposal
+    pub status: ProposalStatus,
+    /// Slot from which a scheduled proposal can be activated
+    pub activation_slot: u64,
+    /// List of recovered validator keys that have proven possession of their key
+    #[max_len(MAX_VALIDATORS_CHANGE)]
+    pub admitted: Vec<Pubkey>,
+}
+
+impl GuardianProposal {
+    /// Returns the account space (without the discriminator) needed for `num_signers` approvals.
+    pub fn space(num_signers: usize) -> usize {
+        Self::INIT_SPACE + num_signers * 32
+    }
+}
+
+/// Format of receiver addresses on a destination chain.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum AddressFormat {
+    /// Opaque bytes, only the length bounds apply
+    Raw,
+    /// EVM account address
+    Evm,
+    /// Cardano address on the network with the given network ID
+    Cardano {
+        /// Network ID carried in the address header (1 for mainnet, 0 for testnets)
+        network_id: u8,
+    },
+}
+
+/// Configuration of a single destination chain.
+///
+/// # Fields
+///
+/// * `chain_id` - Chain ID of the destination chain in the bridge network
+/// * `name` - Human-readable name of the chain (max 32 bytes)
+/// * `enabled` - Whether bridge requests to the chain are accepted
+/// * `address_format` - Format of receiver addresses on the chain
+/// * `min_receiver_len` - Minimum length in bytes of a receiver address
+/// * `max_receiver_len` - Maximum length in bytes of a receiver address
+/// * `min_amount` - Minimum amount of a bridge request to the chain
+/// * `max_amount` - Maximum amount of a bridge request to the chain
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct ChainConfig {
+    /// Chain ID of the destination chain, the `destination_chain` of bridge requests
+    pub chain_id: u8,
+    /// Human-readable name of the chain
+    #[max_len(MAX_CHAIN_NAME_LEN)]
+    pub name: String,
+    /// Whether bridge requests to the chain are accepted
+    pub enabled: bool,
+    /// Format of receiver addresses on the chain
+    pub address_format: AddressFormat,
+    /// Minimum length in bytes of a receiver address
+    pub min_receiver_len: u8,
+    /// Maximum length in bytes of a receiver address
+    pub max_receiver_len: u8,
+    /// Minimum amount of a bridge request to the chain, in the smallest unit of the
+    /// bridged token
+    pub min_amount: u64,
+    /// Maximum amount of a bridge request to the chain, in the smallest unit of the
+    /// bridged token
+    pub max_amount: u64,
+}
+
+/// Represents the registry of the destination chains the bridge serves.
+///
+/// The `ChainRegistry` account is created by the first `SetChain` governance proposal and
+/// holds one entry per registered chain. `bridge_request` only accepts destination chains
+/// that are registered and enabled. Chains are never removed, only disabled.
+///
+/// The account is allocated for the registered chains (see [`ChainRegistry::space`]) and
+/// grows as chains are added.
+///
+/// # Fields
+///
+/// * `version` - Layout version of the account, see [`ACCOUNT_VERSION`]
+/// * `chains` - Configuration of each registered chain (max `MAX_CHAINS`)
+/// * `bump` - Bump seed for the PDA derivation
+#[account]
+#[derive(InitSpace)]
+pub struct ChainRegistry {
+    /// Layout version of the account, [`ACCOUNT_VERSION`] once created or migrated
+    pub version: u8,
+    /// Configuration of each registered chain, the account is sized for the actual length
+    /// (see [`ChainRegistry::space`])
+    #[max_len(0)]
+    pub chains: Vec<ChainConfig>,
+    /// Bump seed for the Program Derived Address (PDA)
+    pub bump: u8,
+}
+
+impl ChainRegistry {
+    /// Returns the account space (without the discriminator) needed for `num_chains` chains.
+    pub fn space(num_chains: usize) -> usize {
+        Self::INIT_SPACE + num_chains * ChainConfig::INIT_SPACE
+    }
+
+    /// Returns the configuration of the chain with the given chain ID, if it is registered.
+    pub fn chain(&self, chain_id: u8) -> Option<&ChainConfig> {
+        self.chains.iter().find(|chain| chain.chain_id == chain_id)
+    }
+}
+
+/// How a token is moved across the bridge.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum TokenMode {
+    /// Outbound tokens are locked in the vault and released when they come back
+    Lock,
+    /// Outbound tokens are burned and minted when they come back, the vault is the mint
+    /// authority
+    Burn,
+}
+
+/// Currency a flat bridge fee is paid in.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum FeeCurrency {
+    /// The bridged token, in the smallest unit of the mint
+    Token,
+    /// SOL, in lamports
+    Sol,
+}
+
+/// Representation of a token on another chain.
+///
+/// # Fields
+///
+/// * `chain_id` - Chain ID of the chain in the bridge network
+/// * `foreign_asset` - Identifier of the asset on the chain
+/// * `decimals` - Decimals of the asset on the chain
+/// * `flat_fee` - Flat fee charged per bridge request to the chain
+/// * `fee_currency` - Currency the flat fee is paid in
+/// * `fee_bps` - Fee charged per bridge request to the chain, in basis points of the amount
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct TokenChain {
+    /// Chain ID of the chain in the bridge network
+    pub chain_id: u8,
+    /// Identifier of the asset on the chain: the policy ID followed by the asset name on
+    /// Cardano, the ERC-20 contract address on EVM chains
+    #[max_len(MAX_FOREIGN_ASSET_LEN)]
+    pub foreign_asset: Vec<u8>,
+    /// Decimals of the asset on the chain, amounts are converted between these and the
+    /// decimals of the mint when bridging
+    pub decimals: u8,
+    /// Flat fee charged per bridge request to the chain, in the smallest unit of
+    /// `fee_currency`
+    pub flat_fee: u64,
+    /// Currency the flat fee is paid in
+    pub fee_currency: FeeCurrency,
+    /// Fee charged per bridge request to the chain, in basis points of the amount. It is
+    /// always paid in the bridged token
+    pub fee_bps: u16,
+}
+
+impl TokenChain {
+    /// Returns the fees charged on a bridge request of `amount` tokens to the chain.
+    ///
+    /// The basis points fee is rounded down.
+    ///
+    /// # Returns
+    /// The fee taken from the bridged tokens and the fee paid in lamports
+    pub fn fees(&self, amount: u64) -> (u64, u64) {
+        let bps_fee = (amount as u128 * self.fee_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        match self.fee_currency {
+            FeeCurrency::Token => (bps_fee.saturating_add(self.flat_fee), 0),
+            FeeCurrency::Sol => (bps_fee, self.flat_fee),
+        }
+    }
+}
+
+/// Represents the registry entry of a token the bridge supports.
+///
+/// A `TokenConfig` account is created for a mint by the first `SetToken` governance
+/// proposal naming it. `bridge_request` and `bridge_transaction` only move tokens that
+/// are registered, enabled and allowed on the other chain, in the registered mode. Tokens
+/// are never removed, only disabled.
+///
+/// # Fields
+///
+/// * `version` - Layout version of the account, see [`ACCOUNT_VERSION`]
+/// * `mint` - The mint of the token
+/// * `mode` - How the token is moved across the bridge
+/// * `enabled` - Whether the token can be bridged
+/// * `chains` - The chains the token can be bridged to and from (max `MAX_TOKEN_CHAINS`)
+/// * `bump` - Bump seed for the PDA derivation
+#[account]
+#[derive(InitSpace)]
+pub struct TokenConfig {
+    /// Layout version of the account, [`ACCOUNT_VERSION`] once created or migrated
+    pub version: u8,
+    /// The mint of the token
+    pub mint: Pubkey,
+    /// How the token is moved across the bridge
+    pub mode: TokenMode,
+    /// Whether the token can be bridged
+    pub enabled: bool,
+    /// The chains the token can be bridged to and from, with its foreign asset on each
+    #[max_len(MAX_TOKEN_CHAINS)]
+    pub chains: Vec<TokenChain>,
+    /// Bump seed for the Program Derived Address (PDA)
+    pub bump: u8,
+}
+
+impl TokenConfig {
+    /// Loads the registry entry of a token from its `TokenConfig` account.
+    ///
+    /// The address of the account is checked by the caller.
+    ///
+    /// # Errors
+    /// * `TokenNotRegistered` - If the account does not exist
+    /// * `AccountOwnedByWrongProgram` - If the account is not owned by the program
+    /// * `AccountNotMigrated` - If the account is not at the current layout version
+    pub fn load(info: &AccountInfo) -> Result<Self> {
+        require!(!info.data_is_empty(), CustomError::TokenNotRegistered);
+        require_keys_eq!(
+            *info.owner,
+            crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+        let config = Self::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(
+            config.version == ACCOUNT_VERSION,
+            CustomError::AccountNotMigrated
+        );
+
+        Ok(config)
+    }
+
+    /// Returns the representation of the token on the given chain, if the token can be
+    /// bridged to and from it.
+    pub fn chain(&self, chain_id: u8) -> Option<&TokenChain> {
+        self.chains.iter().find(|chain| chain.chain_id == chain_id)
+    }
+
+    /// Checks that the token can be bridged to or from `chain_id` with the given mint.
+    ///
+    /// # Arguments
+    /// * `chain_id` - The chain the tokens are bridged to or from
+    /// * `vault_is_mint_authority` - Whether the vault is the mint authority of the mint
+    ///
+    /// # Errors
+    /// * `TokenDisabled` - If the token is disabled
+    /// * `TokenChainNotAllowed` - If the token is not registered for the chain
+    /// * `TokenModeMismatch` - If the mode does not match the mint authority
+    pub fn check(&self, chain_id: u8, vault_is_mint_authority: bool) -> Result<&TokenChain> {
+        require!(self.enabled, CustomError::TokenDisabled);
+        let chain = self
+            .chain(chain_id)
+            .ok_or(CustomError::TokenChainNotAllowed)?;
+        require!(
+            (self.mode == TokenMode::Burn) == vault_is_mint_authority,
+            CustomError::TokenModeMismatch
+        );
+
+        Ok(chain)
+    }
 }
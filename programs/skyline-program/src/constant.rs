@@ -9,10 +9,19 @@ use anchor_lang::constant;
 /// Maximum number of validators allowed in the validator set.
 ///
 /// This constant defines the upper limit for the number of validators that can be
-/// included in the validator set. The limit is set to 128 to balance security,
-/// decentralization, and practical constraints.
+/// included in the validator set. Accounts holding per-validator data are sized for the
+/// actual set, so the limit is bound by compute and heap usage rather than account space:
+/// transactions touching a set of more than 128 validators need a larger heap frame
+/// (see `MAX_HEAP_FRAME_BYTES`).
 #[constant]
-pub const MAX_VALIDATORS: u32 = 128;
+pub const MAX_VALIDATORS: u32 = 256;
+
+/// Largest heap frame, in bytes, the program can use.
+///
+/// The default heap frame is 32 KiB. Transactions touching large validator sets request
+/// a larger one with `ComputeBudgetInstruction::request_heap_frame`, up to this size.
+#[constant]
+pub const MAX_HEAP_FRAME_BYTES: u32 = 256 * 1024;
 
 /// Size of the account discriminator in bytes.
 ///
@@ -21,6 +30,15 @@ pub const MAX_VALIDATORS: u32 = 128;
 #[constant]
 pub const DISC: u32 = 8;
 
+/// Current layout version of the program accounts.
+///
+/// Every account stores the version it was written with right after the discriminator.
+/// Version 1 accounts were created before the field and bridge instances existed, at PDAs
+/// without an instance ID, and have to be moved into an instance with the `migrate`
+/// instruction before the program operates on them again.
+#[constant]
+pub const ACCOUNT_VERSION: u8 = 2;
+
 /// Seed string used to derive the ValidatorSet Program Derived Address (PDA).
 ///
 /// This seed is used in conjunction with the program ID to generate
@@ -62,8 +80,255 @@ pub const VAULT_SEED: &[u8] = b"vault";
 #[constant]
 pub const MAX_VALIDATORS_CHANGE: u32 = 10;
 
-/// Seed string used to derive ValidatorSetChange Program Derived Addresses (PDAs).
+/// Seed string used to derive GovernanceProposal Program Derived Addresses (PDAs).
+///
+/// This seed is combined with the proposal batch id to create a unique address for the governance proposal account.
+#[constant]
+pub const GOVERNANCE_PROPOSAL_SEED: &[u8] = b"governance_proposal";
+
+/// Seed string used to derive ValidatorSetSnapshot Program Derived Addresses (PDAs).
+///
+/// This seed is combined with the epoch of the archived validator set, so every validator set
+/// the bridge has had is kept in its own account.
+#[constant]
+pub const VALIDATOR_SET_SNAPSHOT_SEED: &[u8] = b"validator_set_snapshot";
+
+/// Seed string used to derive ValidatorDelta Program Derived Addresses (PDAs).
+///
+/// This seed is combined with the batch id of the validator set update the delta is assembled for.
+#[constant]
+pub const VALIDATOR_DELTA_SEED: &[u8] = b"validator_delta";
+
+/// Seed string used to derive KeyRotation Program Derived Addresses (PDAs).
+///
+/// This seed is combined with the rotated (old) validator key, so each validator can have
+/// at most one rotation scheduled at a time.
+#[constant]
+pub const KEY_ROTATION_SEED: &[u8] = b"key_rotation";
+
+/// Seed string used to derive ValidatorInfo Program Derived Addresses (PDAs).
+///
+/// This seed is combined with the validator key to create one metadata record per validator.
+#[constant]
+pub const VALIDATOR_INFO_SEED: &[u8] = b"validator_info";
+
+/// Seed string used to derive the ValidatorStats Program Derived Address (PDA).
+///
+/// This seed is combined with the instance id, there is a single statistics account for the
+/// whole set of an instance.
+#[constant]
+pub const VALIDATOR_STATS_SEED: &[u8] = b"validator_stats";
+
+/// Seed string used to derive ValidatorBond Program Derived Addresses (PDAs).
+///
+/// This seed is combined with the validator key to create one bond account per validator.
+#[constant]
+pub const VALIDATOR_BOND_SEED: &[u8] = b"validator_bond";
+
+/// Seed string used to derive the Treasury Program Derived Address (PDA).
+///
+/// The treasury receives the share of slashed bonds not paid to the reporter.
+#[constant]
+pub const TREASURY_SEED: &[u8] = b"treasury";
+
+/// Seed string used to derive the GuardianCouncil Program Derived Address (PDA).
+///
+/// This seed is combined with the instance id, there is a single guardian council per bridge
+/// instance.
+#[constant]
+pub const GUARDIAN_COUNCIL_SEED: &[u8] = b"guardian_council";
+
+/// Seed string used to derive GuardianProposal Program Derived Addresses (PDAs).
+///
+/// This seed is combined with the council batch id of the proposal, which is sequenced
+/// independently of the validator batch ids.
+#[constant]
+pub const GUARDIAN_PROPOSAL_SEED: &[u8] = b"guardian_proposal";
+
+/// Seed string used to derive the upgrade authority Program Derived Address (PDA).
+///
+/// This seed is used on its own: the program is shared by every bridge instance, so there is
+/// a single upgrade authority for the whole deployment. The PDA holds no data. It is set as
+/// the upgrade authority of the program and signs upgrades approved through `UpgradeProgram`
+/// governance proposals of the [`GOVERNING_INSTANCE_ID`] instance.
+#[constant]
+pub const UPGRADE_AUTHORITY_SEED: &[u8] = b"upgrade_authority";
+
+/// The bridge instance whose validators govern the deployment.
+///
+/// Only its validators can approve the deployment-wide `UpgradeProgram` and
+/// `AuthorizeInitializer` governance actions, since those affect every instance.
+#[constant]
+pub const GOVERNING_INSTANCE_ID: u64 = 0;
+
+/// Seed string used to derive the ChainRegistry Program Derived Address (PDA).
+///
+/// This seed is combined with the instance id, there is a single registry of destination
+/// chains per bridge instance.
+#[constant]
+pub const CHAIN_REGISTRY_SEED: &[u8] = b"chain_registry";
+
+/// Maximum number of destination chains in the chain registry.
+#[constant]
+pub const MAX_CHAINS: u32 = 32;
+
+/// Maximum length in bytes of a destination chain name.
+#[constant]
+pub const MAX_CHAIN_NAME_LEN: u32 = 32;
+
+/// Length in bytes of an EVM account address.
+#[constant]
+pub const EVM_ADDRESS_LEN: u32 = 20;
+
+/// Length in bytes of a Cardano base address (header, payment and stake credentials).
+#[constant]
+pub const CARDANO_BASE_ADDRESS_LEN: u32 = 57;
+
+/// Length in bytes of a Cardano enterprise address (header and payment credential).
+///
+/// Pointer addresses are longer, by the variable-length encoding of the stake pointer.
+#[constant]
+pub const CARDANO_ENTERPRISE_ADDRESS_LEN: u32 = 29;
+
+/// Seed string used to derive TokenConfig Program Derived Addresses (PDAs).
+///
+/// This seed is combined with the mint to create one registry entry per bridged token.
+#[constant]
+pub const TOKEN_CONFIG_SEED: &[u8] = b"token_config";
+
+/// Maximum number of chains a token can be bridged to and from.
+#[constant]
+pub const MAX_TOKEN_CHAINS: u32 = 16;
+
+/// Maximum length in bytes of a foreign asset identifier.
+///
+/// Large enough for a Cardano policy ID (28 bytes) followed by an asset name (up to 32
+/// bytes).
+#[constant]
+pub const MAX_FOREIGN_ASSET_LEN: u32 = 64;
+
+/// Seed string used to derive the FeeVault Program Derived Address (PDA).
+///
+/// This seed is combined with the instance id, there is a single fee vault per bridge
+/// instance. It holds SOL fees itself and token fees in its associated token accounts.
+#[constant]
+pub const FEE_VAULT_SEED: &[u8] = b"fee_vault";
+
+/// Seed string used to derive RewardAccount Program Derived Addresses (PDAs).
+///
+/// This seed is combined with the key of the participant to create one reward account per
+/// relayer or validator.
+#[constant]
+pub const REWARD_ACCOUNT_SEED: &[u8] = b"reward_account";
+
+/// Seed string used to derive InitializerGrant Program Derived Addresses (PDAs).
+///
+/// This seed is combined with the id of the bridge instance the grant allows initializing.
+#[constant]
+pub const INITIALIZER_GRANT_SEED: &[u8] = b"initializer_grant";
+
+/// Maximum number of members of the guardian council.
+#[constant]
+pub const MAX_GUARDIANS: u32 = 16;
+
+/// Maximum length in bytes of a validator display name.
+#[constant]
+pub const MAX_VALIDATOR_NAME_LEN: u32 = 32;
+
+/// Maximum length in bytes of a validator endpoint URL.
+#[constant]
+pub const MAX_VALIDATOR_ENDPOINT_LEN: u32 = 128;
+
+/// Default number of slots a timelocked governance proposal waits before activation.
+///
+/// At roughly 400ms per slot this is about one day, which gives the guardian and
+/// the validators enough time to veto a malicious validator set update.
+#[constant]
+pub const DEFAULT_TIMELOCK_SLOTS: u64 = 216_000;
+
+/// Minimum number of slots a governance proposal waits before activation.
+///
+/// About one hour, so `SetConfig` cannot remove the veto window of the guardian and
+/// a blocking minority.
+#[constant]
+pub const MIN_TIMELOCK_SLOTS: u64 = 9_000;
+
+/// Default number of slots a validator key rotation waits before activation.
+///
+/// A rotation keeps the operator of the seat unchanged, so it only needs to give the
+/// guardian enough time to react (about one hour).
+#[constant]
+pub const DEFAULT_ROTATION_TIMELOCK_SLOTS: u64 = 9_000;
+
+/// Default number of slots a validator set recovery by the guardian council waits before
+/// activation.
+///
+/// About seven days, so a validator set that is merely degraded has time to reach a quorum
+/// again, which voids the recovery.
+#[constant]
+pub const DEFAULT_RECOVERY_TIMELOCK_SLOTS: u64 = 1_512_000;
+
+/// Default number of slots without a validator quorum after which the guardian council can
+/// recover the validator set.
+///
+/// About seven days; any bridge transaction or governance proposal reaching quorum resets it.
+#[constant]
+pub const DEFAULT_RECOVERY_STALL_SLOTS: u64 = 1_512_000;
+
+/// Default number of slots unbonded lamports stay slashable before they can be withdrawn.
+///
+/// About three days, longer than the default proposal timelock, so equivocation evidence
+/// can still be reported after the validator starts leaving.
+#[constant]
+pub const DEFAULT_UNBONDING_SLOTS: u64 = 648_000;
+
+/// Default share of a slashed bond paid to the reporter, in basis points.
+#[constant]
+pub const DEFAULT_SLASH_REPORTER_BPS: u16 = 1_000;
+
+/// Default share of the total active validator weight required for consensus, in basis points.
+///
+/// While every validator has the default weight this gives the same threshold as
+/// `helpers::calculate_threshold`: more than two thirds of the active validators.
+#[constant]
+pub const DEFAULT_QUORUM_WEIGHT_BPS: u16 = 6_667;
+
+/// Voting weight of a validator whose weight was not set explicitly.
+#[constant]
+pub const DEFAULT_VALIDATOR_WEIGHT: u64 = 1;
+
+/// Maximum voting weight of a single validator.
+///
+/// This keeps the weight sums and the threshold calculation far from overflowing.
+#[constant]
+pub const MAX_VALIDATOR_WEIGHT: u64 = 1_000_000;
+
+/// Denominator for values expressed in basis points.
+#[constant]
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Default maximum share of the validator set that can change per churn window, in basis points.
+///
+/// Limiting churn to a third of the set per window keeps the quorums of consecutive
+/// validator sets intersecting in honest validators, even across several updates.
+#[constant]
+pub const DEFAULT_MAX_CHURN_BPS: u16 = 3_333;
+
+/// Default length of the churn window in slots.
+///
+/// This matches the length of a Solana epoch (roughly two days).
+#[constant]
+pub const DEFAULT_CHURN_WINDOW_SLOTS: u64 = 432_000;
+
+/// Domain separator of the approval attestation validators sign for every batch.
+///
+/// See `helpers::approval_message` for the full message layout. Two attestations of the
+/// same validator for the same batch ID with different operation hashes prove equivocation.
+#[constant]
+pub const APPROVAL_MESSAGE_DOMAIN: &[u8] = b"skyline:approval";
+
+/// Domain separator of the message a new validator signs to prove possession of its key.
 ///
-/// This seed is combined with the validator set change address to create a unique address for the validator set change account.
+/// See `helpers::admission_message` for the full message layout.
 #[constant]
-pub const VALIDATOR_SET_CHANGE_SEED: &[u8] = b"validator_set_change";
+pub const ADMISSION_MESSAGE_DOMAIN: &[u8] = b"skyline:validator-admission";
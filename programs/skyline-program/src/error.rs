@@ -15,7 +15,7 @@ use anchor_lang::prelude::*;
 pub enum CustomError {
     /// Maximum number of validators exceeded.
     ///
-    /// This error occurs when trying to set more than 128 validators in the validator set.
+    /// This error occurs when trying to set more than 256 validators in the validator set.
     /// The limit is defined by the `MAX_VALIDATORS` constant.
     #[msg("Maximum number of validators exceeded")]
     MaxValidatorsExceeded,
@@ -159,4 +159,344 @@ pub enum CustomError {
     /// This error occurs when the number of validators being removed exceeds the number of validators that will remain after additions during a validator set update.
     #[msg("Cannot remove more validators than will exist after additions")]
     TooManyValidatorsRemoved,
+
+    /// A validator set update is already pending.
+    ///
+    /// This error occurs when a validator set update is proposed or scheduled while another
+    /// one is waiting for its activation slot.
+    #[msg("A validator set update is already pending")]
+    ValidatorUpdatePending,
+
+    /// Proposal is not scheduled.
+    ///
+    /// This error occurs when activating or vetoing a proposal that has not reached quorum.
+    #[msg("Proposal is not scheduled")]
+    ProposalNotScheduled,
+
+    /// Timelock has not expired.
+    ///
+    /// This error occurs when activating a scheduled proposal or key rotation before its
+    /// activation slot.
+    #[msg("Timelock has not expired")]
+    TimelockNotExpired,
+
+    /// Validator churn limit exceeded.
+    ///
+    /// This error occurs when a validator set update would change more seats than the
+    /// configured `max_churn_bps` allows within the current churn window. Larger
+    /// rotations must be staged across several windows.
+    #[msg("Validator churn limit exceeded for the current window")]
+    ChurnLimitExceeded,
+
+    /// Invalid bridge configuration.
+    ///
+    /// This error occurs when a configuration value is out of its allowed range.
+    #[msg("Invalid bridge configuration")]
+    InvalidConfig,
+
+    /// Missing proof of possession.
+    ///
+    /// This error occurs when a new validator key has neither signed the transaction
+    /// nor provided an ed25519 signature over its admission message.
+    #[msg("Validator key has not proven possession")]
+    MissingAdmissionProof,
+
+    /// Invalid ed25519 instruction.
+    ///
+    /// This error occurs when an ed25519 precompile instruction in the transaction
+    /// cannot be parsed.
+    #[msg("Invalid ed25519 instruction")]
+    InvalidEd25519Instruction,
+
+    /// Invalid validator metadata.
+    ///
+    /// This error occurs when a validator name or endpoint exceeds its maximum length.
+    #[msg("Invalid validator metadata")]
+    InvalidValidatorMetadata,
+
+    /// Foreign keys do not match the added validators.
+    ///
+    /// This error occurs when a validator set update carries foreign keys, but not
+    /// exactly one entry per added validator.
+    #[msg("Foreign keys do not match the added validators")]
+    ForeignKeysMismatch,
+
+    /// Missing action account.
+    ///
+    /// This error occurs when executing a governance action that writes to an account
+    /// (such as a validator registry record) which was not passed in remaining accounts.
+    #[msg("Account required by the governance action is missing")]
+    MissingActionAccount,
+
+    /// Validator is suspended.
+    ///
+    /// This error occurs when a suspended validator tries to approve or veto an operation,
+    /// or tries to reinstate itself after being suspended through governance.
+    #[msg("Validator is suspended")]
+    ValidatorSuspended,
+
+    /// Validator is already suspended.
+    ///
+    /// This error occurs when suspending a validator that is already suspended in the
+    /// same way.
+    #[msg("Validator is already suspended")]
+    ValidatorAlreadySuspended,
+
+    /// Validator is not suspended.
+    ///
+    /// This error occurs when reinstating a validator that is not suspended.
+    #[msg("Validator is not suspended")]
+    ValidatorNotSuspended,
+
+    /// Insufficient bond.
+    ///
+    /// This error occurs when unbonding or withdrawing more lamports than are bonded or
+    /// unbonding.
+    #[msg("Insufficient bond")]
+    InsufficientBond,
+
+    /// Unbonding not complete.
+    ///
+    /// This error occurs when withdrawing unbonded lamports before `config.unbonding_slots`
+    /// have passed since the last unbonding.
+    #[msg("Unbonding period has not passed")]
+    UnbondingNotComplete,
+
+    /// Invalid equivocation proof.
+    ///
+    /// This error occurs when a reported equivocation is not backed by two ed25519
+    /// signatures of the validator over approval messages for the same batch ID with
+    /// different operation hashes.
+    #[msg("Invalid equivocation proof")]
+    InvalidEquivocationProof,
+
+    /// Nothing to slash.
+    ///
+    /// This error occurs when reporting an equivocation of a validator that has no bond left
+    /// and is not an active member of the validator set.
+    #[msg("Nothing to slash")]
+    NothingToSlash,
+
+    /// Insufficient treasury balance.
+    ///
+    /// This error occurs when a treasury withdrawal would take the treasury below its
+    /// rent-exempt minimum.
+    #[msg("Insufficient treasury balance")]
+    InsufficientTreasuryBalance,
+
+    /// Invalid validator weight.
+    ///
+    /// This error occurs when a validator weight is zero or above `MAX_VALIDATOR_WEIGHT`.
+    #[msg("Validator weight must be between 1 and MAX_VALIDATOR_WEIGHT")]
+    InvalidValidatorWeight,
+
+    /// Validator weights mismatch.
+    ///
+    /// This error occurs when validator weights are given, but not exactly one entry per
+    /// validator they apply to.
+    #[msg("Validator weights do not match the validators")]
+    ValidatorWeightsMismatch,
+
+    /// Validator delta is sealed.
+    ///
+    /// This error occurs when appending a chunk to a validator delta that has already been
+    /// sealed.
+    #[msg("Validator delta is already sealed")]
+    ValidatorDeltaSealed,
+
+    /// Validator delta is not sealed.
+    ///
+    /// This error occurs when proposing a validator delta that is still being assembled.
+    #[msg("Validator delta is not sealed")]
+    ValidatorDeltaNotSealed,
+
+    /// Validator delta mismatch.
+    ///
+    /// This error occurs when the sealed validator delta of the batch does not match the hash
+    /// carried by the `ApplyValidatorDelta` proposal.
+    #[msg("Validator delta does not match the proposal")]
+    ValidatorDeltaMismatch,
+
+    /// Bridge is paused.
+    ///
+    /// This error occurs when bridging while the guardian council has paused the bridge.
+    #[msg("Bridge is paused")]
+    BridgePaused,
+
+    /// Invalid guardian council.
+    ///
+    /// This error occurs when a guardian council has no members, more than `MAX_GUARDIANS`
+    /// members, duplicate members, or a threshold that is not a majority of its members.
+    #[msg("Invalid guardian council")]
+    InvalidGuardianCouncil,
+
+    /// Validator set is not stalled.
+    ///
+    /// This error occurs when the guardian council tries to recover a validator set that has
+    /// reached a quorum, or whose validators holding the threshold weight sent heartbeats or
+    /// approvals, within the last `recovery_stall_slots` slots.
+    #[msg("Validator set is not stalled")]
+    ValidatorSetNotStalled,
+
+    /// Unauthorized initializer.
+    ///
+    /// This error occurs when `initialize` is not signed by the upgrade authority of the
+    /// program, nor by the key of the initializer grant of the instance.
+    #[msg(
+        "Only the program upgrade authority or an authorized initializer can initialize the bridge"
+    )]
+    UnauthorizedInitializer,
+
+    /// Program buffer mismatch.
+    ///
+    /// This error occurs when the buffer of an `UpgradeProgram` proposal does not hold the
+    /// program bytes whose hash the validators approved.
+    #[msg("Program buffer does not match the approved hash")]
+    ProgramBufferMismatch,
+
+    /// Account not migrated.
+    ///
+    /// This error occurs when an instruction is given an account written with an earlier
+    /// layout version. The account has to be migrated with the `migrate` instruction first.
+    #[msg("Account has to be migrated to the current layout version")]
+    AccountNotMigrated,
+
+    /// Account cannot be migrated.
+    ///
+    /// This error occurs when `migrate` is given an account that is not a version 1 account
+    /// at its version 1 address, or a migrated account that is not the current PDA of the
+    /// account or already exists.
+    #[msg("Account cannot be migrated")]
+    AccountNotMigratable,
+
+    /// Chain not registered.
+    ///
+    /// This error occurs when bridging to a destination chain that is not in the chain
+    /// registry.
+    #[msg("Destination chain is not registered")]
+    ChainNotRegistered,
+
+    /// Chain disabled.
+    ///
+    /// This error occurs when bridging to a destination chain the validators have disabled.
+    #[msg("Destination chain is disabled")]
+    ChainDisabled,
+
+    /// Invalid chain configuration.
+    ///
+    /// This error occurs when a destination chain is registered with the chain ID of this
+    /// chain, a name longer than `MAX_CHAIN_NAME_LEN`, or inverted receiver length or amount
+    /// bounds.
+    #[msg("Invalid chain configuration")]
+    InvalidChainConfig,
+
+    /// Too many chains.
+    ///
+    /// This error occurs when registering a destination chain while the chain registry
+    /// already holds `MAX_CHAINS` chains.
+    #[msg("Chain registry is full")]
+    TooManyChains,
+
+    /// Amount out of range.
+    ///
+    /// This error occurs when the amount of a bridge request is below the minimum or above
+    /// the maximum amount of the destination chain.
+    #[msg("Amount is out of the range allowed for the destination chain")]
+    AmountOutOfRange,
+
+    /// Token not registered.
+    ///
+    /// This error occurs when bridging a mint that has no entry in the token registry.
+    #[msg("Token is not registered")]
+    TokenNotRegistered,
+
+    /// Token disabled.
+    ///
+    /// This error occurs when bridging a mint the validators have disabled.
+    #[msg("Token is disabled")]
+    TokenDisabled,
+
+    /// Token chain not allowed.
+    ///
+    /// This error occurs when bridging a token to or from a chain it is not registered for.
+    #[msg("Token cannot be bridged to or from this chain")]
+    TokenChainNotAllowed,
+
+    /// Token mode mismatch.
+    ///
+    /// This error occurs when the registered mode of a token does not match its mint
+    /// authority: burned and minted tokens need the vault as mint authority, locked and
+    /// released tokens must not have it.
+    #[msg("Token mode does not match the mint authority")]
+    TokenModeMismatch,
+
+    /// Invalid token configuration.
+    ///
+    /// This error occurs when a token is registered for more than `MAX_TOKEN_CHAINS` chains,
+    /// for the same chain twice or for the chain ID of this chain, with a foreign asset
+    /// identifier that is empty or longer than `MAX_FOREIGN_ASSET_LEN`, or with a fee above
+    /// 100%.
+    #[msg("Invalid token configuration")]
+    InvalidTokenConfig,
+
+    /// Amount too small.
+    ///
+    /// This error occurs when a bridged amount is zero once converted between the decimals
+    /// of the mint and of the token on the other chain.
+    #[msg("Amount is below the precision of the destination")]
+    AmountTooSmall,
+
+    /// Amount overflow.
+    ///
+    /// This error occurs when an amount does not fit its type once converted between the
+    /// decimals of the mint and of the token on the other chain.
+    #[msg("Converted amount overflows")]
+    AmountOverflow,
+
+    /// Invalid receiver address.
+    ///
+    /// This error occurs when the receiver of a bridge request is outside the receiver length
+    /// bounds of the destination chain, or is not an address in the chain's address format.
+    #[msg("Receiver is not a valid address on the destination chain")]
+    InvalidReceiverAddress,
+
+    /// Fee exceeds amount.
+    ///
+    /// This error occurs when the fee charged in the bridged token takes the whole amount of
+    /// a bridge request.
+    #[msg("Fee exceeds the bridged amount")]
+    FeeExceedsAmount,
+
+    /// Insufficient fee balance.
+    ///
+    /// This error occurs when a fee withdrawal exceeds the fees held by the fee vault, or would
+    /// take the fee vault below its rent-exempt minimum.
+    #[msg("Insufficient fee vault balance")]
+    InsufficientFeeBalance,
+
+    /// Nothing to claim.
+    ///
+    /// This error occurs when claiming rewards while none are accrued.
+    #[msg("No rewards to claim")]
+    NothingToClaim,
+
+    /// Rotated record exists.
+    ///
+    /// This error occurs when a key rotation is activated while the new key already holds a
+    /// validator registry record or bond, which the records of the old key would replace.
+    #[msg("The new key already holds a validator record")]
+    RotatedRecordExists,
+
+    /// Unauthorized migration.
+    ///
+    /// This error occurs when `migrate` is not signed by the upgrade authority of the program.
+    #[msg("Only the program upgrade authority can migrate accounts")]
+    UnauthorizedMigration,
+
+    /// Not the governing instance.
+    ///
+    /// This error occurs when an `UpgradeProgram` or `AuthorizeInitializer` governance
+    /// proposal is submitted in a bridge instance other than `GOVERNING_INSTANCE_ID`.
+    #[msg("Only the governing bridge instance can propose deployment-wide actions")]
+    NotGoverningInstance,
 }
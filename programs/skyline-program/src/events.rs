@@ -6,30 +6,405 @@
 
 use anchor_lang::prelude::*;
 
+use crate::{ChainConfig, TokenChain, TokenMode, ValidatorMetadata};
+
 /// Event emitted when a bridging transaction is successfully executed.
 ///
 /// This event is emitted after a bridging transaction has received sufficient
 /// validator approvals and the tokens have been transferred or minted to the recipient.
 #[event]
 pub struct TransactionExecutedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
     /// The unique identifier of the transaction that was executed
     pub transaction_id: Pubkey,
     /// The batch ID of the executed transaction
     pub batch_id: u64,
+    /// Number of active validators that approved the transaction
+    pub approvals: u32,
+    /// Sum of the weights of the active validators that approved the transaction
+    pub approved_weight: u64,
+    /// The part of the source amount, in the smallest unit of the token on the source chain,
+    /// below the precision of the mint; it is not delivered and stays locked on the source
+    /// chain
+    pub dust: u128,
 }
 
-/// Event emitted when the validator set is successfully updated.
+/// Event emitted when a validator set update is scheduled and when it is activated.
 ///
-/// This event is emitted after a validator set change proposal has received
-/// sufficient validator approvals and the changes have been applied to the validator set.
+/// This event is emitted once when a validator set change proposal has received
+/// sufficient validator approvals and enters its timelock (`activated == false`), and
+/// again when the changes are applied to the validator set (`activated == true`).
 #[event]
 pub struct ValidatorSetUpdatedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
     /// The new list of validator signers after the update
     pub new_signers: Vec<Pubkey>,
+    /// The voting weights of the new validator signers, in the order of `new_signers`
+    pub new_weights: Vec<u64>,
     /// The new consensus threshold for the validator set
-    pub new_threshold: u8,
+    pub new_threshold: u16,
+    /// The new consensus threshold weight for the validator set
+    pub new_threshold_weight: u64,
     /// The batch ID associated with the validator set update
     pub batch_id: u64,
+    /// The slot from which the update is (or was) in effect
+    pub activation_slot: u64,
+    /// `false` when the update is scheduled, `true` once it has been activated
+    pub activated: bool,
+}
+
+/// Event emitted when a governance proposal is successfully executed.
+///
+/// This event is emitted after a governance proposal has received sufficient
+/// validator approvals and its action has been applied. Action-specific events
+/// (such as `ValidatorSetUpdatedEvent`) are emitted alongside it.
+#[event]
+pub struct ProposalExecutedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// The unique identifier of the executed proposal
+    pub proposal_id: Pubkey,
+    /// The hash of the executed action
+    pub proposal_hash: [u8; 32],
+    /// The batch ID of the executed proposal
+    pub batch_id: u64,
+    /// Number of active validators that approved the proposal
+    pub approvals: u32,
+    /// Sum of the weights of the active validators that approved the proposal
+    pub approved_weight: u64,
+}
+
+/// Event emitted when a governance proposal reaches quorum and enters its timelock.
+#[event]
+pub struct ProposalScheduledEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// The unique identifier of the scheduled proposal
+    pub proposal_id: Pubkey,
+    /// The batch ID of the scheduled proposal
+    pub batch_id: u64,
+    /// The slot from which the proposal can be activated
+    pub activation_slot: u64,
+    /// Number of active validators that approved the proposal
+    pub approvals: u32,
+    /// Sum of the weights of the active validators that approved the proposal
+    pub approved_weight: u64,
+}
+
+/// Event emitted when a scheduled governance proposal is vetoed.
+#[event]
+pub struct ProposalVetoedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// The unique identifier of the vetoed proposal
+    pub proposal_id: Pubkey,
+    /// The batch ID of the vetoed proposal
+    pub batch_id: u64,
+    /// `true` if the guardian or the guardian council vetoed, `false` if a blocking
+    /// minority of validators did
+    pub by_guardian: bool,
+}
+
+/// Event emitted when a validator key rotation is scheduled and again when it is activated.
+///
+/// The rotated seat keeps its position in the validator set and the threshold is unchanged.
+#[event]
+pub struct ValidatorKeyRotatedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// The validator key being rotated out
+    pub old_key: Pubkey,
+    /// The key replacing `old_key`
+    pub new_key: Pubkey,
+    /// The slot from which the rotation is (or was) active
+    pub activation_slot: u64,
+    /// `false` when the rotation is scheduled, `true` once the new key is active
+    pub activated: bool,
+}
+
+/// Event emitted when the guardian vetoes a scheduled validator key rotation.
+#[event]
+pub struct KeyRotationVetoedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// The validator key that was to be rotated out
+    pub old_key: Pubkey,
+    /// The vetoed replacement key
+    pub new_key: Pubkey,
+}
+
+/// Event emitted when the registry record of a validator is created or updated.
+///
+/// Other chains can follow these events to mirror the validator set, including the
+/// keys each validator uses on their side.
+#[event]
+pub struct ValidatorInfoUpdatedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// The validator whose record changed
+    pub validator: Pubkey,
+    /// The new metadata of the validator
+    pub metadata: ValidatorMetadata,
+}
+
+/// Event emitted when a validator is suspended or reinstated.
+#[event]
+pub struct ValidatorSuspensionChangedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// The validator whose suspension changed
+    pub validator: Pubkey,
+    /// `true` if the validator is now suspended, `false` if it was reinstated
+    pub suspended: bool,
+    /// `true` if the change was made through governance, `false` if by the validator itself
+    pub by_consensus: bool,
+    /// The consensus threshold of the remaining active validators
+    pub new_threshold: u16,
+    /// The consensus threshold weight of the remaining active validators
+    pub new_threshold_weight: u64,
+}
+
+/// Event emitted when the voting weight of a validator changes.
+#[event]
+pub struct ValidatorWeightUpdatedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// The validator whose weight changed
+    pub validator: Pubkey,
+    /// The new voting weight of the validator
+    pub weight: u64,
+    /// The new consensus threshold weight for the validator set
+    pub new_threshold_weight: u64,
+}
+
+/// Event emitted when a validator set is activated and archived.
+///
+/// This event is emitted at initialization and whenever the validators or their weights
+/// change. The archived set is stored in the `ValidatorSetSnapshot` account of the epoch.
+#[event]
+pub struct ValidatorSetArchivedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// Number of the archived validator set
+    pub epoch: u64,
+    /// Hash committing to the archived set
+    pub set_hash: [u8; 32],
+    /// Last batch ID consumed when the set was activated
+    pub activation_batch_id: u64,
+    /// Slot at which the set was activated
+    pub activation_slot: u64,
+}
+
+/// Event emitted when a validator delta is sealed and can be proposed.
+#[event]
+pub struct ValidatorDeltaSealedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// The batch ID the delta was assembled for
+    pub batch_id: u64,
+    /// The hash committing to the complete delta, carried by `ApplyValidatorDelta`
+    pub delta_hash: [u8; 32],
+    /// Number of validators the delta adds
+    pub added: u32,
+    /// Number of validators the delta removes
+    pub removed: u32,
+}
+
+/// Event emitted when the bond of a validator changes.
+#[event]
+pub struct BondUpdatedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// The validator whose bond changed
+    pub validator: Pubkey,
+    /// Bonded lamports
+    pub amount: u64,
+    /// Lamports that are unbonding
+    pub unbonding_amount: u64,
+    /// Slot from which the unbonding lamports can be withdrawn
+    pub unbonding_slot: u64,
+}
+
+/// Event emitted when a validator is slashed for equivocation.
+///
+/// The validator is jailed as well, so governance should remove it with a validator set
+/// update.
+#[event]
+pub struct ValidatorSlashedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// The equivocating validator
+    pub validator: Pubkey,
+    /// The batch ID the validator signed conflicting approvals for
+    pub batch_id: u64,
+    /// The account that reported the equivocation
+    pub reporter: Pubkey,
+    /// Lamports paid to the reporter
+    pub reporter_amount: u64,
+    /// Lamports paid to the treasury
+    pub treasury_amount: u64,
+    /// Whether the validator was jailed by this report
+    pub jailed: bool,
+    /// Whether the validator was queued for removal by this report
+    pub removal_queued: bool,
+}
+
+/// Event emitted when a destination chain is registered or its configuration changes.
+#[event]
+pub struct ChainUpdatedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// The new configuration of the chain
+    pub chain: ChainConfig,
+}
+
+/// Event emitted when a token is registered or its configuration changes.
+#[event]
+pub struct TokenUpdatedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// The mint of the token
+    pub mint: Pubkey,
+    /// How the token is moved across the bridge
+    pub mode: TokenMode,
+    /// Whether the token can be bridged
+    pub enabled: bool,
+    /// The chains the token can be bridged to and from, with its foreign asset on each
+    pub chains: Vec<TokenChain>,
+}
+
+/// Event emitted when the validators appoint a new guardian council.
+#[event]
+pub struct GuardianCouncilUpdatedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// Public keys of the council members
+    pub members: Vec<Pubkey>,
+    /// Number of member approvals required for a guardian proposal
+    pub threshold: u16,
+}
+
+/// Event emitted when a guardian proposal reaches the council threshold and enters its timelock.
+#[event]
+pub struct GuardianProposalScheduledEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// The unique identifier of the scheduled proposal
+    pub proposal_id: Pubkey,
+    /// The council batch ID of the scheduled proposal
+    pub batch_id: u64,
+    /// The slot from which the proposal can be activated
+    pub activation_slot: u64,
+    /// Number of council members that approved the proposal
+    pub approvals: u32,
+}
+
+/// Event emitted when a guardian proposal is executed.
+#[event]
+pub struct GuardianProposalExecutedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// The unique identifier of the executed proposal
+    pub proposal_id: Pubkey,
+    /// Hash of the executed action
+    pub proposal_hash: [u8; 32],
+    /// The council batch ID of the executed proposal
+    pub batch_id: u64,
+    /// Number of council members that approved the proposal
+    pub approvals: u32,
+}
+
+/// Event emitted when the guardian council pauses or resumes bridging.
+#[event]
+pub struct BridgePausedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// `true` if bridging was paused, `false` if it was resumed
+    pub paused: bool,
+}
+
+/// Event emitted when the program is upgraded through an `UpgradeProgram` proposal.
+///
+/// The program data is not hashed on chain: the hash of the program before the upgrade is
+/// the `program_hash` of the previous event, or can be computed off chain.
+#[event]
+pub struct ProgramUpgradedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// The buffer the new program was loaded from
+    pub buffer: Pubkey,
+    /// SHA-256 hash of the new program bytes, the approved `buffer_hash`
+    pub program_hash: [u8; 32],
+}
+
+/// Event emitted when a key is authorized to initialize a bridge instance.
+#[event]
+pub struct InitializerAuthorizedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// The bridge instance the key may initialize
+    pub authorized_instance_id: u64,
+    /// The key allowed to initialize the instance
+    pub initializer: Pubkey,
+}
+
+/// Event emitted when a version 1 account is migrated into a bridge instance.
+#[event]
+pub struct AccountMigratedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// The version 1 account
+    pub account: Pubkey,
+    /// The current-layout account the data moved to
+    pub migrated: Pubkey,
+    /// The layout version the account was migrated to
+    pub version: u8,
+}
+
+/// Event emitted when an executed bridge transaction credits rewards.
+#[event]
+pub struct RewardsCreditedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// The batch ID of the executed bridge transaction
+    pub batch_id: u64,
+    /// The payer of the executing transaction
+    pub relayer: Pubkey,
+    /// Lamports credited to the relayer (0 if it was skipped)
+    pub relayer_reward: u64,
+    /// The credited approving validators
+    pub validators: Vec<Pubkey>,
+    /// Lamports credited to each credited validator
+    pub validator_reward: u64,
+    /// Lamports of the rewards owed to the participants that the fee vault could not cover,
+    /// credited rewards were scaled down pro rata
+    pub shortfall: u64,
+    /// Whether the relayer left its reward account out and forfeited its reward
+    pub relayer_skipped: bool,
+}
+
+/// Event emitted when a participant claims its rewards.
+#[event]
+pub struct RewardsClaimedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// The relayer or validator claiming the rewards
+    pub participant: Pubkey,
+    /// Lamports claimed
+    pub amount: u64,
+}
+
+/// Event emitted when the rewards for executed bridge transactions are set.
+#[event]
+pub struct RewardsUpdatedEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
+    /// Lamports credited to the payer of an executed bridge transaction
+    pub relayer_reward: u64,
+    /// Lamports credited to each approving validator of an executed bridge transaction
+    pub validator_reward: u64,
 }
 
 /// Event emitted when a bridge request is created.
@@ -39,9 +414,12 @@ pub struct ValidatorSetUpdatedEvent {
 /// the request and execute the corresponding transaction on the destination chain.
 #[event]
 pub struct BridgeRequestEvent {
+    /// The bridge instance the event belongs to (see `DeploymentConfig::instance_id`)
+    pub instance_id: u64,
     /// Public key of the user who initiated the bridge request
     pub sender: Pubkey,
-    /// Amount of tokens to be bridged to the destination chain
+    /// Amount of tokens taken from the sender including the fee, in the smallest unit of the
+    /// mint; dust below the precision of the destination chain stays with the sender
     pub amount: u64,
     /// Receiver's address on the destination chain (variable length byte vector)
     /// This format accommodates various address formats across different blockchains
@@ -52,4 +430,13 @@ pub struct BridgeRequestEvent {
     pub mint_token: Pubkey,
     /// The batch request ID associated with this bridge request
     pub batch_request_id: u64,
+    /// Amount of tokens to be bridged, in the smallest unit of the token on the destination
+    /// chain (see `TokenChain::decimals`)
+    pub normalized_amount: u128,
+    /// Fee paid in the bridged token and kept in the fee vault, in the smallest unit of the mint
+    pub fee: u64,
+    /// Amount of tokens bridged after the fee, in the smallest unit of the mint
+    pub net_amount: u64,
+    /// Fee paid in SOL and kept in the fee vault, in lamports
+    pub sol_fee: u64,
 }
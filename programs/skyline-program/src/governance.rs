@@ -0,0 +1,2100 @@
+//! Governance action validation and dispatch for the Skyline bridge program.
+//!
+//! This module contains the shared approval accumulator used by every governance
+//! instruction. A proposal is created on the first call, approved by validators on
+//! subsequent calls, and its typed action is executed once the threshold is reached.
+
+use crate::*;
+use anchor_lang::solana_program::{
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    program::invoke_signed,
+    sysvar::SysvarId,
+};
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    token::{self, transfer_checked, Mint, TokenAccount, TransferChecked},
+};
+
+/// Domain separator mixed into every governance proposal hash.
+const PROPOSAL_HASH_DOMAIN: &[u8] = b"skyline:governance-proposal";
+
+/// Domain separator mixed into every validator delta hash.
+const VALIDATOR_DELTA_HASH_DOMAIN: &[u8] = b"skyline:validator-delta";
+
+/// Domain separator mixed into every archived validator set hash.
+const VALIDATOR_SET_HASH_DOMAIN: &[u8] = b"skyline:validator-set";
+
+/// Accounts available to governance actions that touch state outside the validator set.
+///
+/// Such actions find the accounts they write to (for example validator registry records)
+/// by address among the remaining accounts of the instruction that activates them. Key
+/// rotations and guardian proposals use them as well.
+pub struct ActionAccounts<'a, 'info> {
+    /// The bridge instance the action belongs to, part of the seeds of every account it finds
+    pub instance_id: u64,
+    /// The account paying for accounts created by the action, receives the proposal rent
+    pub payer: &'a AccountInfo<'info>,
+    /// The system program, used to create accounts
+    pub system_program: &'a AccountInfo<'info>,
+    /// The remaining accounts of the instruction (validator signers and action accounts)
+    pub remaining_accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> ActionAccounts<'a, 'info> {
+    /// Returns the remaining account with the given address.
+    ///
+    /// # Errors
+    /// * `MissingActionAccount` - If no remaining account has the address
+    pub fn find(&self, address: &Pubkey) -> Result<&'a AccountInfo<'info>> {
+        self.remaining_accounts
+            .iter()
+            .find(|acc| acc.key == address)
+            .ok_or_else(|| error!(CustomError::MissingActionAccount))
+    }
+
+    /// Loads the sealed validator delta of `batch_id` from the remaining accounts.
+    ///
+    /// # Errors
+    /// * `MissingActionAccount` - If the delta was not passed in remaining accounts
+    /// * `ValidatorDeltaNotSealed` - If the delta is still being assembled
+    /// * `ValidatorDeltaMismatch` - If the delta does not hash to `delta_hash`
+    fn validator_delta(&self, batch_id: u64, delta_hash: &[u8; 32]) -> Result<ValidatorDelta> {
+        let (address, _) = Pubkey::find_program_address(
+            &[
+                VALIDATOR_DELTA_SEED,
+                self.instance_id.to_le_bytes().as_ref(),
+                batch_id.to_le_bytes().as_ref(),
+            ],
+            &crate::ID,
+        );
+        let info = self.find(&address)?;
+
+        require_keys_eq!(
+            *info.owner,
+            crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+        let delta = ValidatorDelta::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(
+            delta.version == ACCOUNT_VERSION,
+            CustomError::AccountNotMigrated
+        );
+        require!(delta.sealed, CustomError::ValidatorDeltaNotSealed);
+        require!(
+            delta.delta_hash == *delta_hash,
+            CustomError::ValidatorDeltaMismatch
+        );
+
+        Ok(delta)
+    }
+}
+
+impl ValidatorDelta {
+    /// Computes the hash that commits to the complete delta.
+    ///
+    /// The hash is taken over a domain separator, the batch ID and the Borsh serialization
+    /// of the added keys, the removed keys and the weights.
+    pub fn compute_hash(&self) -> Result<[u8; 32]> {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(VALIDATOR_DELTA_HASH_DOMAIN);
+        hasher.update(&self.batch_id.to_le_bytes());
+        hasher.update(&self.added.try_to_vec()?);
+        hasher.update(&self.removed.try_to_vec()?);
+        hasher.update(&self.weights.try_to_vec()?);
+        Ok(*hasher.finalize().as_bytes())
+    }
+}
+
+impl ValidatorSetSnapshot {
+    /// Captures the validator set as it is now, as the snapshot of `validator_set.epoch`.
+    ///
+    /// # Arguments
+    /// * `validator_set` - The validator set to archive
+    /// * `bump` - Bump seed of the snapshot PDA
+    pub fn capture(validator_set: &ValidatorSet, bump: u8) -> Result<Self> {
+        let mut snapshot = Self {
+            version: ACCOUNT_VERSION,
+            instance_id: validator_set.deployment.instance_id,
+            epoch: validator_set.epoch,
+            signers: validator_set.signers.clone(),
+            weights: validator_set.weights.clone(),
+            threshold: validator_set.threshold,
+            threshold_weight: validator_set.threshold_weight,
+            activation_batch_id: validator_set.last_batch_id,
+            activation_slot: Clock::get()?.slot,
+            set_hash: [0; 32],
+            bump,
+        };
+        snapshot.set_hash = snapshot.compute_hash()?;
+
+        Ok(snapshot)
+    }
+
+    /// Computes the hash that commits to the archived set.
+    ///
+    /// The hash is taken over a domain separator, the bridge instance, the epoch, the Borsh
+    /// serialization of the signers and the weights, and the threshold weight.
+    pub fn compute_hash(&self) -> Result<[u8; 32]> {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(VALIDATOR_SET_HASH_DOMAIN);
+        hasher.update(&self.instance_id.to_le_bytes());
+        hasher.update(&self.epoch.to_le_bytes());
+        hasher.update(&self.signers.try_to_vec()?);
+        hasher.update(&self.weights.try_to_vec()?);
+        hasher.update(&self.threshold_weight.to_le_bytes());
+        Ok(*hasher.finalize().as_bytes())
+    }
+
+    /// Emits the [`ValidatorSetArchivedEvent`] of the snapshot.
+    pub fn emit_archived(&self) {
+        emit!(ValidatorSetArchivedEvent {
+            instance_id: self.instance_id,
+            epoch: self.epoch,
+            set_hash: self.set_hash,
+            activation_batch_id: self.activation_batch_id,
+            activation_slot: self.activation_slot,
+        });
+    }
+}
+
+impl GovernanceAction {
+    /// Computes the hash that identifies this action.
+    ///
+    /// The hash is taken over a domain separator followed by the Borsh serialization
+    /// of the action, so two proposals hash equally only if their actions are identical.
+    pub fn hash(&self) -> Result<[u8; 32]> {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(PROPOSAL_HASH_DOMAIN);
+        hasher.update(&self.try_to_vec()?);
+        Ok(*hasher.finalize().as_bytes())
+    }
+
+    /// Returns the keys that must prove possession before the action can be scheduled.
+    ///
+    /// Every key joining the validator set has to sign its own admission, so a typo or
+    /// a key nobody controls cannot silently lower the liveness of the set. Keys added by a
+    /// validator delta are admitted while the delta is assembled.
+    pub fn admission_keys(&self) -> &[Pubkey] {
+        match self {
+            GovernanceAction::UpdateValidators { added, .. } => added,
+            GovernanceAction::SetConfig { .. }
+            | GovernanceAction::SetGuardian { .. }
+            | GovernanceAction::SetValidatorInfo { .. }
+            | GovernanceAction::SetValidatorSuspension { .. }
+            | GovernanceAction::SetValidatorWeight { .. }
+            | GovernanceAction::WithdrawTreasury { .. }
+            | GovernanceAction::ApplyValidatorDelta { .. }
+            | GovernanceAction::SetGuardianCouncil { .. }
+            | GovernanceAction::SetChain { .. }
+            | GovernanceAction::SetToken { .. }
+            | GovernanceAction::WithdrawFees { .. }
+            | GovernanceAction::SetRewards { .. }
+            | GovernanceAction::UpgradeProgram { .. }
+            | GovernanceAction::AuthorizeInitializer { .. } => &[],
+        }
+    }
+
+    /// Validates the action against the current validator set.
+    ///
+    /// This is run once, when the proposal is created. Later approvals are bound to
+    /// the same action through the proposal hash.
+    ///
+    /// # Arguments
+    /// * `validator_set` - The validator set account the action applies to
+    /// * `batch_id` - The batch ID of the proposal
+    /// * `accounts` - The accounts read by the action (the validator delta of the batch)
+    ///
+    /// # Errors
+    /// * `ForeignKeysMismatch` - If foreign keys are given, but not one per added validator
+    /// * `InvalidSigner` - If the validator whose weight changes is not in the validator set
+    /// * `InvalidAmount` - If a treasury or fee withdrawal is for a zero amount
+    /// * `NotGoverningInstance` - If a program upgrade or initializer grant is proposed outside
+    ///   the [`GOVERNING_INSTANCE_ID`] instance
+    /// * Any error of [`check_validator_update`], [`validate_config`], [`validate_metadata`],
+    ///   [`validate_suspension`], [`resolve_weights`], [`validate_weight`],
+    ///   [`validate_guardian_council`], [`validate_chain`], [`validate_token`] or of loading
+    ///   the validator delta
+    pub fn validate(
+        &self,
+        validator_set: &ValidatorSet,
+        batch_id: u64,
+        accounts: &ActionAccounts,
+    ) -> Result<()> {
+        match self {
+            GovernanceAction::UpdateValidators {
+                added,
+                removed,
+                foreign_keys,
+                weights,
+            } => {
+                require!(
+                    foreign_keys.is_empty() || foreign_keys.len() == added.len(),
+                    CustomError::ForeignKeysMismatch
+                );
+                resolve_weights(weights, added.len())?;
+                check_validator_update(validator_set, added, removed)?;
+            }
+            GovernanceAction::ApplyValidatorDelta { delta_hash } => {
+                // Approvals can only start once the delta is sealed
+                let delta = accounts.validator_delta(batch_id, delta_hash)?;
+                check_validator_update(validator_set, &delta.added, &delta.removed)?;
+            }
+            GovernanceAction::SetConfig { config } => {
+                validate_config(config)?;
+            }
+            GovernanceAction::SetGuardian { .. } | GovernanceAction::SetRewards { .. } => {}
+            GovernanceAction::UpgradeProgram { .. }
+            | GovernanceAction::AuthorizeInitializer { .. } => {
+                // Deployment-wide actions, the program is shared by every instance
+                require!(
+                    validator_set.deployment.instance_id == GOVERNING_INSTANCE_ID,
+                    CustomError::NotGoverningInstance
+                );
+            }
+            GovernanceAction::SetValidatorInfo { metadata, .. } => {
+                validate_metadata(metadata)?;
+            }
+            GovernanceAction::SetValidatorSuspension {
+                validator,
+                suspended,
+            } => {
+                validate_suspension(validator_set, validator, *suspended, true)?;
+            }
+            GovernanceAction::SetValidatorWeight { validator, weight } => {
+                require!(
+                    validator_set.signers.contains(validator),
+                    CustomError::InvalidSigner
+                );
+                validate_weight(*weight)?;
+            }
+            GovernanceAction::WithdrawTreasury { amount, .. }
+            | GovernanceAction::WithdrawFees { amount, .. } => {
+                require!(*amount > 0, CustomError::InvalidAmount);
+            }
+            GovernanceAction::SetGuardianCouncil { members, threshold } => {
+                validate_guardian_council(members, *threshold)?;
+            }
+            GovernanceAction::SetChain { chain } => {
+                validate_chain(validator_set, chain)?;
+            }
+            GovernanceAction::SetToken { chains, .. } => {
+                validate_token(validator_set, chains)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records that the action reached quorum and will activate at `activation_slot`.
+    ///
+    /// # Arguments
+    /// * `validator_set` - The validator set account the action applies to
+    /// * `batch_id` - The batch ID of the scheduled proposal
+    /// * `activation_slot` - The slot from which the proposal can be activated
+    /// * `accounts` - The accounts read by the action (the validator delta of the batch)
+    pub fn schedule(
+        &self,
+        validator_set: &mut ValidatorSet,
+        batch_id: u64,
+        activation_slot: u64,
+        accounts: &ActionAccounts,
+    ) -> Result<()> {
+        match self {
+            GovernanceAction::UpdateValidators {
+                added,
+                removed,
+                weights,
+                ..
+            } => schedule_validator_update(
+                validator_set,
+                added,
+                removed,
+                weights,
+                batch_id,
+                activation_slot,
+            ),
+            GovernanceAction::ApplyValidatorDelta { delta_hash } => {
+                let delta = accounts.validator_delta(batch_id, delta_hash)?;
+                schedule_validator_update(
+                    validator_set,
+                    &delta.added,
+                    &delta.removed,
+                    &delta.weights,
+                    batch_id,
+                    activation_slot,
+                )
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Releases any state reserved by [`GovernanceAction::schedule`] when the proposal is vetoed.
+    ///
+    /// # Arguments
+    /// * `validator_set` - The validator set account the action applies to
+    /// * `batch_id` - The batch ID of the vetoed proposal
+    /// * `accounts` - The accounts read by the action (the validator delta of the batch)
+    pub fn cancel(
+        &self,
+        validator_set: &mut ValidatorSet,
+        batch_id: u64,
+        accounts: &ActionAccounts,
+    ) -> Result<()> {
+        match self {
+            GovernanceAction::UpdateValidators { added, removed, .. } => {
+                cancel_validator_update(validator_set, added, removed, batch_id);
+            }
+            GovernanceAction::ApplyValidatorDelta { delta_hash } => {
+                let delta = accounts.validator_delta(batch_id, delta_hash)?;
+                cancel_validator_update(validator_set, &delta.added, &delta.removed, batch_id);
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Executes the action once the proposal is activated.
+    ///
+    /// # Arguments
+    /// * `validator_set` - The validator set account the action applies to
+    /// * `batch_id` - The batch ID of the executed proposal
+    /// * `accounts` - Accounts written by actions that touch state outside the validator set
+    pub fn execute<'info>(
+        &self,
+        validator_set: &mut ValidatorSet,
+        batch_id: u64,
+        accounts: &ActionAccounts<'_, 'info>,
+    ) -> Result<()> {
+        match self {
+            GovernanceAction::UpdateValidators {
+                added,
+                removed,
+                foreign_keys,
+                weights,
+            } => {
+                apply_validator_update(validator_set, added, removed, weights, batch_id, accounts)?;
+
+                // Record the foreign keys so other chains can mirror the new validators
+                for (validator, keys) in added.iter().zip(foreign_keys.iter()) {
+                    store_validator_info(accounts, validator, |metadata| {
+                        metadata.foreign_keys = keys.clone();
+                    })?;
+                }
+            }
+            GovernanceAction::SetConfig { config } => {
+                validator_set.config = config.clone();
+                // The quorum share may have changed
+                update_thresholds(validator_set);
+            }
+            GovernanceAction::SetGuardian { guardian } => {
+                validator_set.guardian = *guardian;
+            }
+            GovernanceAction::SetValidatorInfo {
+                validator,
+                metadata,
+            } => {
+                store_validator_info(accounts, validator, |current| {
+                    *current = metadata.clone();
+                })?;
+            }
+            GovernanceAction::SetValidatorSuspension {
+                validator,
+                suspended,
+            } => {
+                set_suspension(validator_set, validator, *suspended, true)?;
+            }
+            GovernanceAction::SetValidatorWeight { validator, weight } => {
+                // Re-validate, the validator may have left the set during the timelock
+                let index = validator_set
+                    .signers
+                    .iter()
+                    .position(|pk| pk == validator)
+                    .ok_or(CustomError::InvalidSigner)?;
+                validator_set.weights[index] = *weight;
+                update_thresholds(validator_set);
+
+                emit!(ValidatorWeightUpdatedEvent {
+                    instance_id: validator_set.deployment.instance_id,
+                    validator: *validator,
+                    weight: *weight,
+                    new_threshold_weight: validator_set.threshold_weight,
+                });
+
+                archive_validator_set(validator_set, accounts)?;
+            }
+            GovernanceAction::WithdrawTreasury { recipient, amount } => {
+                withdraw_treasury(accounts, recipient, *amount)?;
+            }
+            GovernanceAction::ApplyValidatorDelta { delta_hash } => {
+                let delta = accounts.validator_delta(batch_id, delta_hash)?;
+                apply_validator_update(
+                    validator_set,
+                    &delta.added,
+                    &delta.removed,
+                    &delta.weights,
+                    batch_id,
+                    accounts,
+                )?;
+            }
+            GovernanceAction::SetGuardianCouncil { members, threshold } => {
+                store_guardian_council(accounts, members, *threshold)?;
+            }
+            GovernanceAction::SetChain { chain } => {
+                store_chain(accounts, chain)?;
+            }
+            GovernanceAction::SetToken {
+                mint,
+                mode,
+                enabled,
+                chains,
+            } => {
+                store_token(accounts, mint, *mode, *enabled, chains)?;
+            }
+            GovernanceAction::WithdrawFees {
+                mint,
+                recipient,
+                amount,
+            } => {
+                withdraw_fees(accounts, mint.as_ref(), recipient, *amount)?;
+            }
+            GovernanceAction::SetRewards {
+                relayer_reward,
+                validator_reward,
+            } => {
+                store_rewards(accounts, *relayer_reward, *validator_reward)?;
+            }
+            GovernanceAction::UpgradeProgram {
+                buffer,
+                buffer_hash,
+            } => {
+                upgrade_program(accounts, buffer, buffer_hash)?;
+            }
+            GovernanceAction::AuthorizeInitializer {
+                instance_id,
+                initializer,
+            } => {
+                store_initializer_grant(accounts, *instance_id, initializer)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks that a validator set update can be proposed against the current validator set.
+///
+/// # Errors
+/// * `ValidatorUpdatePending` - If a validator set update is already scheduled
+/// * Any error of [`validate_validator_update`] or [`check_churn`]
+pub fn check_validator_update(
+    validator_set: &ValidatorSet,
+    added: &[Pubkey],
+    removed: &[Pubkey],
+) -> Result<()> {
+    require!(
+        validator_set.pending_update.is_none(),
+        CustomError::ValidatorUpdatePending
+    );
+    validate_validator_update(validator_set, added, removed)?;
+    check_churn(validator_set, added, removed, Clock::get()?.slot)
+}
+
+/// Reserves a validator set update that reached quorum and announces the incoming set.
+///
+/// # Errors
+/// * `ValidatorUpdatePending` - If a validator set update is already scheduled
+/// * Any error of [`record_churn`] or [`resolve_weights`]
+fn schedule_validator_update(
+    validator_set: &mut ValidatorSet,
+    added: &[Pubkey],
+    removed: &[Pubkey],
+    weights: &[u64],
+    batch_id: u64,
+    activation_slot: u64,
+) -> Result<()> {
+    require!(
+        validator_set.pending_update.is_none(),
+        CustomError::ValidatorUpdatePending
+    );
+    validator_set.pending_update = Some(batch_id);
+    record_churn(validator_set, added, removed, Clock::get()?.slot)?;
+
+    // Announce the incoming set so the outgoing set can react during the timelock
+    let removed = [removed, &queued_removals(validator_set, added, removed)].concat();
+    let (new_signers, new_weights) = updated_validators(validator_set, added, &removed, weights)?;
+    let (new_active, new_active_weight) = new_signers
+        .iter()
+        .zip(new_weights.iter())
+        .filter(|(pk, _)| !is_suspended(validator_set, pk))
+        .fold((0, 0), |(count, total), (_, weight)| {
+            (count + 1, total + weight)
+        });
+
+    emit!(ValidatorSetUpdatedEvent {
+        instance_id: validator_set.deployment.instance_id,
+        new_threshold: calculate_threshold(new_active),
+        new_threshold_weight: calculate_threshold_weight(
+            new_active_weight,
+            validator_set.config.quorum_weight_bps,
+        ),
+        new_signers,
+        new_weights,
+        batch_id,
+        activation_slot,
+        activated: false,
+    });
+
+    Ok(())
+}
+
+/// Releases the pending update and the churn reserved by a vetoed validator set update.
+fn cancel_validator_update(
+    validator_set: &mut ValidatorSet,
+    added: &[Pubkey],
+    removed: &[Pubkey],
+    batch_id: u64,
+) {
+    if validator_set.pending_update == Some(batch_id) {
+        validator_set.pending_update = None;
+        // Give the seats back; if the window rolled over the count is already reset
+        validator_set.churn_in_window = validator_set
+            .churn_in_window
+            .saturating_sub(churn_of(added, removed));
+    }
+}
+
+/// Applies a scheduled validator set update, recalculates the thresholds and archives the
+/// new set.
+///
+/// # Errors
+/// * Any error of [`validate_validator_update`], [`resolve_weights`] or [`archive_validator_set`]
+fn apply_validator_update<'info>(
+    validator_set: &mut ValidatorSet,
+    added: &[Pubkey],
+    removed: &[Pubkey],
+    weights: &[u64],
+    batch_id: u64,
+    accounts: &ActionAccounts<'_, 'info>,
+) -> Result<()> {
+    validator_set.pending_update = None;
+
+    // Re-validate against the set as it is now, it may have changed during the timelock
+    validate_validator_update(validator_set, added, removed)?;
+
+    // Validators queued for removal leave with the update
+    let removed = [removed, &queued_removals(validator_set, added, removed)].concat();
+
+    // Remove and add validators, keeping the weights aligned with the signers
+    let (new_signers, new_weights) = updated_validators(validator_set, added, &removed, weights)?;
+    validator_set.signers = new_signers;
+    validator_set.weights = new_weights;
+
+    // Removed validators are no longer suspended nor queued
+    let removed = sorted(&removed);
+    validator_set
+        .suspended
+        .retain(|s| removed.binary_search(&s.validator).is_err());
+    validator_set
+        .removal_queue
+        .retain(|pk| removed.binary_search(pk).is_err());
+
+    // Recalculate thresholds
+    update_thresholds(validator_set);
+
+    emit!(ValidatorSetUpdatedEvent {
+        instance_id: validator_set.deployment.instance_id,
+        new_signers: validator_set.signers.clone(),
+        new_weights: validator_set.weights.clone(),
+        new_threshold: validator_set.threshold,
+        new_threshold_weight: validator_set.threshold_weight,
+        batch_id,
+        activation_slot: Clock::get()?.slot,
+        activated: true,
+    });
+
+    archive_validator_set(validator_set, accounts)
+}
+
+/// Starts a new epoch and archives the validator set in the snapshot account of the epoch.
+///
+/// Must be called whenever the validators or their weights change. The snapshot must be
+/// passed among the remaining accounts; it is created, funded by the payer.
+///
+/// # Errors
+/// * `MissingActionAccount` - If the snapshot of the new epoch was not passed in remaining accounts
+pub fn archive_validator_set<'info>(
+    validator_set: &mut ValidatorSet,
+    accounts: &ActionAccounts<'_, 'info>,
+) -> Result<()> {
+    validator_set.epoch += 1;
+
+    let instance_id = validator_set.deployment.instance_id.to_le_bytes();
+    let epoch = validator_set.epoch.to_le_bytes();
+    let (address, bump) = Pubkey::find_program_address(
+        &[VALIDATOR_SET_SNAPSHOT_SEED, &instance_id, &epoch],
+        &crate::ID,
+    );
+    let info = accounts.find(&address)?;
+
+    let snapshot = ValidatorSetSnapshot::capture(validator_set, bump)?;
+    create_pda_account(
+        info,
+        accounts.payer,
+        accounts.system_program,
+        DISC as usize + ValidatorSetSnapshot::space(snapshot.signers.len()),
+        &[VALIDATOR_SET_SNAPSHOT_SEED, &instance_id, &epoch, &[bump]],
+    )?;
+    snapshot.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    snapshot.emit_archived();
+
+    Ok(())
+}
+
+/// Returns a sorted copy of `keys`, for membership checks by binary search.
+pub fn sorted(keys: &[Pubkey]) -> Vec<Pubkey> {
+    let mut keys = keys.to_vec();
+    keys.sort();
+    keys
+}
+
+/// Validates a validator set update against the current validator set.
+///
+/// # Errors
+/// * `DuplicateValidatorsInAdded` - If the added list contains duplicates
+/// * `DuplicateValidatorsInRemoved` - If the removed list contains duplicates
+/// * `AddingAndRemovingSameSigner` - If a key is both added and removed
+/// * `AddingExistingSigner` - If an added key is already a validator
+/// * `RemovingNonExistentSigner` - If a removed key is not a validator
+/// * `TooManyValidatorsRemoved` - If more keys are removed than would exist
+/// * `MaxValidatorsExceeded` - If the resulting set would exceed `MAX_VALIDATORS`
+/// * `MinValidatorsNotMet` - If the resulting set, or its active (not suspended) part,
+///   would fall below `MIN_VALIDATORS`
+pub fn validate_validator_update(
+    validator_set: &ValidatorSet,
+    added: &[Pubkey],
+    removed: &[Pubkey],
+) -> Result<()> {
+    // Validate no duplicates in added list
+    let mut added_sorted = sorted(added);
+    added_sorted.dedup();
+    require!(
+        added.len() == added_sorted.len(),
+        CustomError::DuplicateValidatorsInAdded
+    );
+    // Validate no duplicates in removed
+    let mut removed_sorted = sorted(removed);
+    removed_sorted.dedup();
+    require!(
+        removed.len() == removed_sorted.len(),
+        CustomError::DuplicateValidatorsInRemoved
+    );
+
+    // Membership is checked by binary search, updates may touch the whole set
+    let signers_sorted = sorted(&validator_set.signers);
+    let signers_len = validator_set.signers.len();
+    require!(
+        !added
+            .iter()
+            .any(|pk| removed_sorted.binary_search(pk).is_ok()),
+        CustomError::AddingAndRemovingSameSigner
+    );
+    // Validate that no added validator is already in the validator set
+    require!(
+        !added
+            .iter()
+            .any(|pk| signers_sorted.binary_search(pk).is_ok()),
+        CustomError::AddingExistingSigner
+    );
+    // Validate removed validators actually exist
+    require!(
+        removed
+            .iter()
+            .all(|pk| signers_sorted.binary_search(pk).is_ok()),
+        CustomError::RemovingNonExistentSigner
+    );
+    // Validate we won't underflow when calculating new validator count
+    require!(
+        removed.len() <= signers_len + added.len(),
+        CustomError::TooManyValidatorsRemoved
+    );
+
+    let new_signers_len = signers_len + added.len() - removed.len();
+
+    require!(
+        new_signers_len <= MAX_VALIDATORS as usize,
+        CustomError::MaxValidatorsExceeded
+    );
+    require!(
+        new_signers_len >= MIN_VALIDATORS as usize,
+        CustomError::MinValidatorsNotMet
+    );
+
+    // Suspended validators that stay in the set do not count towards the minimum
+    let staying_suspended = validator_set
+        .suspended
+        .iter()
+        .filter(|s| removed_sorted.binary_search(&s.validator).is_err())
+        .count();
+    require!(
+        new_signers_len - staying_suspended >= MIN_VALIDATORS as usize,
+        CustomError::MinValidatorsNotMet
+    );
+
+    Ok(())
+}
+
+/// Validates a change of a validator's suspension.
+///
+/// A validator suspended through governance is jailed: it can only be reinstated through
+/// governance. A validator that suspended itself can reinstate itself, and governance can
+/// turn its suspension into a jailing.
+///
+/// # Arguments
+/// * `validator_set` - The current validator set
+/// * `validator` - The validator whose suspension changes
+/// * `suspended` - `true` to suspend the validator, `false` to reinstate it
+/// * `by_consensus` - Whether the change is made through governance
+///
+/// # Errors
+/// * `InvalidSigner` - If the validator is not in the validator set
+/// * `ValidatorAlreadySuspended` - If the validator is already suspended
+/// * `MinValidatorsNotMet` - If the active set would fall below `MIN_VALIDATORS`
+/// * `ValidatorNotSuspended` - If a reinstated validator is not suspended
+/// * `ValidatorSuspended` - If a jailed validator tries to reinstate itself
+pub fn validate_suspension(
+    validator_set: &ValidatorSet,
+    validator: &Pubkey,
+    suspended: bool,
+    by_consensus: bool,
+) -> Result<()> {
+    require!(
+        validator_set.signers.contains(validator),
+        CustomError::InvalidSigner
+    );
+
+    let current = validator_set
+        .suspended
+        .iter()
+        .find(|s| s.validator == *validator);
+
+    match (current, suspended) {
+        (Some(current), true) => {
+            // Only a self-imposed suspension can be escalated to a jailing
+            require!(
+                by_consensus && !current.by_consensus,
+                CustomError::ValidatorAlreadySuspended
+            );
+        }
+        (None, true) => {
+            require!(
+                active_validator_count(validator_set) > MIN_VALIDATORS as usize,
+                CustomError::MinValidatorsNotMet
+            );
+        }
+        (Some(current), false) => {
+            require!(
+                by_consensus || !current.by_consensus,
+                CustomError::ValidatorSuspended
+            );
+        }
+        (None, false) => return err!(CustomError::ValidatorNotSuspended),
+    }
+
+    Ok(())
+}
+
+/// Suspends or reinstates a validator and recalculates the threshold.
+///
+/// The suspension is re-validated, the validator set may have changed since a governance
+/// proposal carrying it was created.
+///
+/// # Arguments
+/// * `validator_set` - The validator set account
+/// * `validator` - The validator whose suspension changes
+/// * `suspended` - `true` to suspend the validator, `false` to reinstate it
+/// * `by_consensus` - Whether the change is made through governance
+///
+/// # Errors
+/// * Any error of [`validate_suspension`]
+pub fn set_suspension(
+    validator_set: &mut ValidatorSet,
+    validator: &Pubkey,
+    suspended: bool,
+    by_consensus: bool,
+) -> Result<()> {
+    validate_suspension(validator_set, validator, suspended, by_consensus)?;
+    apply_suspension(validator_set, validator, suspended, by_consensus);
+
+    Ok(())
+}
+
+/// Queues a validator proven to have equivocated for removal and jails it.
+///
+/// The validator leaves the set with the next validator set update that activates (see
+/// [`queued_removals`]). It is jailed under the same `MIN_VALIDATORS` rule as a
+/// `SetValidatorSuspension` proposal: if jailing it would take the active set below the
+/// minimum, it keeps its vote until the update replaces it.
+///
+/// # Returns
+/// Whether the validator was jailed and whether it was queued for removal; both are
+/// `false` if it is not in the set, or already jailed and queued
+pub fn queue_removal(validator_set: &mut ValidatorSet, validator: &Pubkey) -> (bool, bool) {
+    if !validator_set.signers.contains(validator) {
+        return (false, false);
+    }
+
+    let queued = !validator_set.removal_queue.contains(validator);
+    if queued {
+        validator_set.removal_queue.push(*validator);
+    }
+
+    let jailed = validate_suspension(validator_set, validator, true, true).is_ok();
+    if jailed {
+        apply_suspension(validator_set, validator, true, true);
+    }
+
+    (jailed, queued)
+}
+
+/// Returns the queued validators a validator set update removes along with `removed`.
+///
+/// Validators in the removal queue that are still in the set are removed as long as the
+/// resulting set and its active (not suspended) part stay at or above `MIN_VALIDATORS`.
+/// These removals are not counted as churn, they only enforce earlier equivocation proofs.
+fn queued_removals(
+    validator_set: &ValidatorSet,
+    added: &[Pubkey],
+    removed: &[Pubkey],
+) -> Vec<Pubkey> {
+    let removed = sorted(removed);
+    let mut len = (validator_set.signers.len() + added.len()).saturating_sub(removed.len());
+    let staying_suspended = validator_set
+        .suspended
+        .iter()
+        .filter(|s| removed.binary_search(&s.validator).is_err())
+        .count();
+    let mut active = len.saturating_sub(staying_suspended);
+
+    let mut queued = Vec::new();
+    for validator in validator_set.removal_queue.iter() {
+        if removed.binary_search(validator).is_ok() || !validator_set.signers.contains(validator) {
+            continue;
+        }
+
+        let new_active = if is_suspended(validator_set, validator) {
+            active
+        } else {
+            active.saturating_sub(1)
+        };
+        if len <= MIN_VALIDATORS as usize || new_active < MIN_VALIDATORS as usize {
+            continue;
+        }
+
+        len -= 1;
+        active = new_active;
+        queued.push(*validator);
+    }
+
+    queued
+}
+
+/// Records a suspension change, recalculates the threshold and emits the event.
+fn apply_suspension(
+    validator_set: &mut ValidatorSet,
+    validator: &Pubkey,
+    suspended: bool,
+    by_consensus: bool,
+) {
+    validator_set
+        .suspended
+        .retain(|s| s.validator != *validator);
+    if suspended {
+        validator_set.suspended.push(Suspension {
+            validator: *validator,
+            by_consensus,
+        });
+    }
+    update_thresholds(validator_set);
+
+    emit!(ValidatorSuspensionChangedEvent {
+        instance_id: validator_set.deployment.instance_id,
+        validator: *validator,
+        suspended,
+        by_consensus,
+        new_threshold: validator_set.threshold,
+        new_threshold_weight: validator_set.threshold_weight,
+    });
+}
+
+/// Transfers lamports from the treasury through a governance action.
+///
+/// Both the treasury and the recipient must be passed among the remaining accounts.
+///
+/// # Errors
+/// * `MissingActionAccount` - If the treasury or the recipient was not passed
+/// * `InsufficientTreasuryBalance` - If the treasury would fall below its rent-exempt minimum
+fn withdraw_treasury(accounts: &ActionAccounts, recipient: &Pubkey, amount: u64) -> Result<()> {
+    let (address, _) = Pubkey::find_program_address(
+        &[TREASURY_SEED, accounts.instance_id.to_le_bytes().as_ref()],
+        &crate::ID,
+    );
+    let treasury = accounts.find(&address)?;
+    let recipient = accounts.find(recipient)?;
+
+    require_keys_eq!(
+        *treasury.owner,
+        crate::ID,
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+    let rent_exempt = Rent::get()?.minimum_balance(treasury.data_len());
+    require!(
+        treasury.lamports() >= amount.saturating_add(rent_exempt),
+        CustomError::InsufficientTreasuryBalance
+    );
+
+    treasury.sub_lamports(amount)?;
+    recipient.add_lamports(amount)?;
+
+    Ok(())
+}
+
+/// Transfers collected fees out of the fee vault through a governance action.
+///
+/// SOL fees are moved directly, keeping the fee vault rent-exempt and the rewards credited
+/// to participants (see [`FeeVault::reserved_rewards`]) claimable. Token fees are
+/// transferred from the fee vault's associated token account for the mint, signed by the
+/// fee vault PDA. The fee vault and the recipient must be passed among the remaining
+/// accounts, and for token fees also the fee vault token account, the mint and the token
+/// program.
+///
+/// # Errors
+/// * `MissingActionAccount` - If one of the accounts was not passed
+/// * `AccountOwnedByWrongProgram` - If the fee vault does not exist
+/// * `AccountNotMigrated` - If the fee vault is not at the current layout version
+/// * `InsufficientFeeBalance` - If the fee vault holds less than `amount`, or would fall
+///   below its rent-exempt minimum and the reserved rewards
+/// * Any deserialization error if the fee vault token account is not a token account
+/// * Any error of the token program, e.g. if the recipient is not a token account of the mint
+fn withdraw_fees<'info>(
+    accounts: &ActionAccounts<'_, 'info>,
+    mint: Option<&Pubkey>,
+    recipient: &Pubkey,
+    amount: u64,
+) -> Result<()> {
+    let instance_id = accounts.instance_id.to_le_bytes();
+    let (address, bump) = Pubkey::find_program_address(&[FEE_VAULT_SEED, &instance_id], &crate::ID);
+    let fee_vault = accounts.find(&address)?;
+    let recipient = accounts.find(recipient)?;
+
+    let Some(mint) = mint else {
+        require!(
+            FeeVault::load(fee_vault)?.available(fee_vault)? >= amount,
+            CustomError::InsufficientFeeBalance
+        );
+
+        fee_vault.sub_lamports(amount)?;
+        recipient.add_lamports(amount)?;
+
+        return Ok(());
+    };
+
+    FeeVault::load(fee_vault)?;
+    let fee_vault_ata = accounts.find(&get_associated_token_address(&address, mint))?;
+    let mint_info = accounts.find(mint)?;
+    let token_program = accounts.find(&token::ID)?;
+
+    let balance = TokenAccount::try_deserialize(&mut &fee_vault_ata.try_borrow_data()?[..])?.amount;
+    require!(balance >= amount, CustomError::InsufficientFeeBalance);
+    let decimals = Mint::try_deserialize(&mut &mint_info.try_borrow_data()?[..])?.decimals;
+
+    transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            TransferChecked {
+                from: fee_vault_ata.clone(),
+                to: recipient.clone(),
+                authority: fee_vault.clone(),
+                mint: mint_info.clone(),
+            },
+            &[&[FEE_VAULT_SEED, &instance_id, &[bump]]],
+        ),
+        amount,
+        decimals,
+    )
+}
+
+/// Sets the rewards credited for executed bridge transactions through a governance action.
+///
+/// The fee vault must be passed among the remaining accounts. If it doesn't exist yet it is
+/// created, funded by the payer.
+///
+/// # Errors
+/// * `MissingActionAccount` - If the fee vault was not passed in remaining accounts
+fn store_rewards<'info>(
+    accounts: &ActionAccounts<'_, 'info>,
+    relayer_reward: u64,
+    validator_reward: u64,
+) -> Result<()> {
+    let instance_id = accounts.instance_id.to_le_bytes();
+    let (address, bump) = Pubkey::find_program_address(&[FEE_VAULT_SEED, &instance_id], &crate::ID);
+    let info = accounts.find(&address)?;
+
+    let mut fee_vault = if info.data_is_empty() {
+        create_pda_account(
+            info,
+            accounts.payer,
+            accounts.system_program,
+            DISC as usize + FeeVault::INIT_SPACE,
+            &[FEE_VAULT_SEED, &instance_id, &[bump]],
+        )?;
+
+        FeeVault {
+            version: ACCOUNT_VERSION,
+            relayer_reward: 0,
+            validator_reward: 0,
+            reserved_rewards: 0,
+            bump,
+        }
+    } else {
+        FeeVault::load(info)?
+    };
+
+    fee_vault.relayer_reward = relayer_reward;
+    fee_vault.validator_reward = validator_reward;
+    fee_vault.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    emit!(RewardsUpdatedEvent {
+        instance_id: accounts.instance_id,
+        relayer_reward,
+        validator_reward,
+    });
+
+    Ok(())
+}
+
+/// Authorizes a key to initialize a bridge instance through a governance action.
+///
+/// The initializer grant of the instance must be passed among the remaining accounts. If it
+/// doesn't exist yet it is created, funded by the payer; otherwise its key is replaced.
+///
+/// # Errors
+/// * `MissingActionAccount` - If the initializer grant was not passed in remaining accounts
+fn store_initializer_grant<'info>(
+    accounts: &ActionAccounts<'_, 'info>,
+    instance_id: u64,
+    initializer: &Pubkey,
+) -> Result<()> {
+    let granted_instance = instance_id.to_le_bytes();
+    let (address, bump) =
+        Pubkey::find_program_address(&[INITIALIZER_GRANT_SEED, &granted_instance], &crate::ID);
+    let info = accounts.find(&address)?;
+
+    if info.data_is_empty() {
+        create_pda_account(
+            info,
+            accounts.payer,
+            accounts.system_program,
+            DISC as usize + InitializerGrant::INIT_SPACE,
+            &[INITIALIZER_GRANT_SEED, &granted_instance, &[bump]],
+        )?;
+    } else {
+        require_keys_eq!(
+            *info.owner,
+            crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+    }
+
+    let grant = InitializerGrant {
+        version: ACCOUNT_VERSION,
+        instance_id,
+        initializer: *initializer,
+        bump,
+    };
+    grant.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    emit!(InitializerAuthorizedEvent {
+        instance_id: accounts.instance_id,
+        authorized_instance_id: instance_id,
+        initializer: *initializer,
+    });
+
+    Ok(())
+}
+
+/// Upgrades the program from an approved buffer through the upgradeable loader.
+///
+/// The deployment-wide upgrade authority PDA signs the upgrade, and the buffer lamports are
+/// refunded to the payer. The program, its program data, the buffer, the upgrade authority, the rent and
+/// clock sysvars and the upgradeable loader must be passed among the remaining accounts.
+///
+/// # Errors
+/// * `MissingActionAccount` - If one of the upgrade accounts was not passed
+/// * `ProgramBufferMismatch` - If the buffer does not hash to `buffer_hash`
+/// * Any error of the upgradeable loader, e.g. if the upgrade authority of the program or of
+///   the buffer is not the upgrade authority PDA
+fn upgrade_program<'info>(
+    accounts: &ActionAccounts<'_, 'info>,
+    buffer: &Pubkey,
+    buffer_hash: &[u8; 32],
+) -> Result<()> {
+    let (authority_address, authority_bump) =
+        Pubkey::find_program_address(&[UPGRADE_AUTHORITY_SEED], &crate::ID);
+    let program_data_address = bpf_loader_upgradeable::get_program_data_address(&crate::ID);
+
+    let program = accounts.find(&crate::ID)?;
+    let program_data = accounts.find(&program_data_address)?;
+    let buffer_info = accounts.find(buffer)?;
+    let authority = accounts.find(&authority_address)?;
+    let rent = accounts.find(&Rent::id())?;
+    let clock = accounts.find(&Clock::id())?;
+    let loader = accounts.find(&bpf_loader_upgradeable::ID)?;
+
+    require!(
+        program_bytes_hash(
+            buffer_info,
+            UpgradeableLoaderState::size_of_buffer_metadata()
+        )? == *buffer_hash,
+        CustomError::ProgramBufferMismatch
+    );
+
+    invoke_signed(
+        &bpf_loader_upgradeable::upgrade(
+            &crate::ID,
+            buffer,
+            &authority_address,
+            accounts.payer.key,
+        ),
+        &[
+            program_data.clone(),
+            program.clone(),
+            buffer_info.clone(),
+            accounts.payer.clone(),
+            rent.clone(),
+            clock.clone(),
+            authority.clone(),
+            loader.clone(),
+        ],
+        &[&[UPGRADE_AUTHORITY_SEED, &[authority_bump]]],
+    )?;
+
+    emit!(ProgramUpgradedEvent {
+        instance_id: accounts.instance_id,
+        buffer: *buffer,
+        program_hash: *buffer_hash,
+    });
+
+    Ok(())
+}
+
+/// Returns the SHA-256 hash of the program bytes of a loader account, after its metadata.
+///
+/// SHA-256 runs as a syscall; hashing a whole program with BLAKE3 would exceed the compute
+/// budget. Only the buffer is hashed, the program data is never hashed on chain.
+///
+/// # Errors
+/// * `ProgramBufferMismatch` - If the account is shorter than its metadata
+fn program_bytes_hash(info: &AccountInfo, metadata_len: usize) -> Result<[u8; 32]> {
+    let data = info.try_borrow_data()?;
+    let bytes = data
+        .get(metadata_len..)
+        .ok_or(CustomError::ProgramBufferMismatch)?;
+
+    Ok(solana_sha256_hasher::hash(bytes).to_bytes())
+}
+
+/// Validates a bridge configuration.
+///
+/// # Errors
+/// * `InvalidConfig` - If `max_churn_bps` or `slash_reporter_bps` is above 100%,
+///   `quorum_weight_bps` is not above 50% or above 100%, `timelock_slots` is below
+///   [`MIN_TIMELOCK_SLOTS`], or `churn_window_slots` or `recovery_stall_slots` is zero
+pub fn validate_config(config: &BridgeConfig) -> Result<()> {
+    require!(
+        config.max_churn_bps as u64 <= BPS_DENOMINATOR
+            && config.slash_reporter_bps as u64 <= BPS_DENOMINATOR
+            && config.quorum_weight_bps as u64 * 2 > BPS_DENOMINATOR
+            && config.quorum_weight_bps as u64 <= BPS_DENOMINATOR
+            && config.timelock_slots >= MIN_TIMELOCK_SLOTS
+            && config.churn_window_slots > 0
+            && config.recovery_stall_slots > 0,
+        CustomError::InvalidConfig
+    );
+
+    Ok(())
+}
+
+/// Validates the members and the threshold of a guardian council.
+///
+/// The threshold must be a majority of the members, so two conflicting guardian proposals
+/// cannot both pass.
+///
+/// # Errors
+/// * `InvalidGuardianCouncil` - If there are no members, more than `MAX_GUARDIANS` members,
+///   duplicate members, or the threshold is not a majority of the members
+pub fn validate_guardian_council(members: &[Pubkey], threshold: u16) -> Result<()> {
+    let mut unique = sorted(members);
+    unique.dedup();
+    require!(
+        !members.is_empty()
+            && members.len() <= MAX_GUARDIANS as usize
+            && unique.len() == members.len()
+            && threshold as usize * 2 > members.len()
+            && threshold as usize <= members.len(),
+        CustomError::InvalidGuardianCouncil
+    );
+
+    Ok(())
+}
+
+/// Creates or replaces the guardian council through a governance action.
+///
+/// The council account must be passed among the remaining accounts. If it doesn't exist yet
+/// it is created, funded by the payer. A replaced council keeps its batch ID sequence.
+///
+/// # Errors
+/// * `MissingActionAccount` - If the council was not passed in remaining accounts
+fn store_guardian_council<'info>(
+    accounts: &ActionAccounts<'_, 'info>,
+    members: &[Pubkey],
+    threshold: u16,
+) -> Result<()> {
+    let instance_id = accounts.instance_id.to_le_bytes();
+    let (address, bump) =
+        Pubkey::find_program_address(&[GUARDIAN_COUNCIL_SEED, &instance_id], &crate::ID);
+    let info = accounts.find(&address)?;
+
+    let mut council = if info.data_is_empty() {
+        create_pda_account(
+            info,
+            accounts.payer,
+            accounts.system_program,
+            DISC as usize + GuardianCouncil::INIT_SPACE,
+            &[GUARDIAN_COUNCIL_SEED, &instance_id, &[bump]],
+        )?;
+
+        GuardianCouncil {
+            version: ACCOUNT_VERSION,
+            members: Vec::new(),
+            threshold: 0,
+            last_batch_id: 0,
+            bump,
+        }
+    } else {
+        require_keys_eq!(
+            *info.owner,
+            crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+        let council = GuardianCouncil::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(
+            council.version == ACCOUNT_VERSION,
+            CustomError::AccountNotMigrated
+        );
+        council
+    };
+
+    council.members = members.to_vec();
+    council.threshold = threshold;
+    council.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    emit!(GuardianCouncilUpdatedEvent {
+        instance_id: accounts.instance_id,
+        members: council.members,
+        threshold,
+    });
+
+    Ok(())
+}
+
+/// Validates the configuration of a destination chain.
+///
+/// # Errors
+/// * `InvalidChainConfig` - If the chain ID is the chain ID of this chain, the name exceeds
+///   `MAX_CHAIN_NAME_LEN`, or the minimum receiver length or amount is above the maximum
+pub fn validate_chain(validator_set: &ValidatorSet, chain: &ChainConfig) -> Result<()> {
+    require!(
+        chain.chain_id != validator_set.deployment.chain_id
+            && chain.name.len() <= MAX_CHAIN_NAME_LEN as usize
+            && chain.min_receiver_len <= chain.max_receiver_len
+            && chain.min_amount <= chain.max_amount,
+        CustomError::InvalidChainConfig
+    );
+
+    Ok(())
+}
+
+/// Registers a destination chain or replaces its configuration through a governance action.
+///
+/// The chain registry must be passed among the remaining accounts. If it doesn't exist yet
+/// it is created, and it grows with every new chain, funded by the payer.
+///
+/// # Errors
+/// * `MissingActionAccount` - If the registry was not passed in remaining accounts
+/// * `TooManyChains` - If a new chain is registered while the registry holds `MAX_CHAINS` chains
+fn store_chain<'info>(accounts: &ActionAccounts<'_, 'info>, chain: &ChainConfig) -> Result<()> {
+    let instance_id = accounts.instance_id.to_le_bytes();
+    let (address, bump) =
+        Pubkey::find_program_address(&[CHAIN_REGISTRY_SEED, &instance_id], &crate::ID);
+    let info = accounts.find(&address)?;
+
+    let mut registry = if info.data_is_empty() {
+        create_pda_account(
+            info,
+            accounts.payer,
+            accounts.system_program,
+            DISC as usize + ChainRegistry::space(0),
+            &[CHAIN_REGISTRY_SEED, &instance_id, &[bump]],
+        )?;
+
+        ChainRegistry {
+            version: ACCOUNT_VERSION,
+            chains: Vec::new(),
+            bump,
+        }
+    } else {
+        require_keys_eq!(
+            *info.owner,
+            crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+        let registry = ChainRegistry::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(
+            registry.version == ACCOUNT_VERSION,
+            CustomError::AccountNotMigrated
+        );
+        registry
+    };
+
+    match registry
+        .chains
+        .iter_mut()
+        .find(|current| current.chain_id == chain.chain_id)
+    {
+        Some(current) => *current = chain.clone(),
+        None => {
+            require!(
+                registry.chains.len() < MAX_CHAINS as usize,
+                CustomError::TooManyChains
+            );
+            registry.chains.push(chain.clone());
+        }
+    }
+
+    grow_account(
+        info,
+        accounts.payer,
+        accounts.system_program,
+        DISC as usize + ChainRegistry::space(registry.chains.len()),
+    )?;
+    registry.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    emit!(ChainUpdatedEvent {
+        instance_id: accounts.instance_id,
+        chain: chain.clone(),
+    });
+
+    Ok(())
+}
+
+/// Validates the chains a token is registered for.
+///
+/// # Errors
+/// * `InvalidTokenConfig` - If there are more than `MAX_TOKEN_CHAINS` chains, a chain is
+///   listed twice or is this chain, a foreign asset identifier is empty or longer than
+///   `MAX_FOREIGN_ASSET_LEN`, or a `fee_bps` is above 100%
+pub fn validate_token(validator_set: &ValidatorSet, chains: &[TokenChain]) -> Result<()> {
+    require!(
+        chains.len() <= MAX_TOKEN_CHAINS as usize,
+        CustomError::InvalidTokenConfig
+    );
+
+    for (i, chain) in chains.iter().enumerate() {
+        require!(
+            chain.chain_id != validator_set.deployment.chain_id
+                && !chains[..i].iter().any(|c| c.chain_id == chain.chain_id)
+                && !chain.foreign_asset.is_empty()
+                && chain.foreign_asset.len() <= MAX_FOREIGN_ASSET_LEN as usize
+                && chain.fee_bps as u64 <= BPS_DENOMINATOR,
+            CustomError::InvalidTokenConfig
+        );
+    }
+
+    Ok(())
+}
+
+/// Registers a token or replaces its configuration through a governance action.
+///
+/// The token config of the mint must be passed among the remaining accounts. If it doesn't
+/// exist yet it is created, funded by the payer.
+///
+/// # Errors
+/// * `MissingActionAccount` - If the token config was not passed in remaining accounts
+fn store_token<'info>(
+    accounts: &ActionAccounts<'_, 'info>,
+    mint: &Pubkey,
+    mode: TokenMode,
+    enabled: bool,
+    chains: &[TokenChain],
+) -> Result<()> {
+    let instance_id = accounts.instance_id.to_le_bytes();
+    let (address, bump) = Pubkey::find_program_address(
+        &[TOKEN_CONFIG_SEED, &instance_id, mint.as_ref()],
+        &crate::ID,
+    );
+    let info = accounts.find(&address)?;
+
+    if info.data_is_empty() {
+        create_pda_account(
+            info,
+            accounts.payer,
+            accounts.system_program,
+            DISC as usize + TokenConfig::INIT_SPACE,
+            &[TOKEN_CONFIG_SEED, &instance_id, mint.as_ref(), &[bump]],
+        )?;
+    } else {
+        TokenConfig::load(info)?;
+    }
+
+    TokenConfig {
+        version: ACCOUNT_VERSION,
+        mint: *mint,
+        mode,
+        enabled,
+        chains: chains.to_vec(),
+        bump,
+    }
+    .try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    emit!(TokenUpdatedEvent {
+        instance_id: accounts.instance_id,
+        mint: *mint,
+        mode,
+        enabled,
+        chains: chains.to_vec(),
+    });
+
+    Ok(())
+}
+
+/// Validates a validator voting weight.
+///
+/// # Errors
+/// * `InvalidValidatorWeight` - If the weight is zero or above `MAX_VALIDATOR_WEIGHT`
+pub fn validate_weight(weight: u64) -> Result<()> {
+    require!(
+        weight > 0 && weight <= MAX_VALIDATOR_WEIGHT,
+        CustomError::InvalidValidatorWeight
+    );
+
+    Ok(())
+}
+
+/// Validates the voting weights given for `count` validators and fills in the defaults.
+///
+/// # Arguments
+/// * `weights` - The given weights, either empty or one per validator
+/// * `count` - The number of validators the weights apply to
+///
+/// # Returns
+/// One weight per validator, `DEFAULT_VALIDATOR_WEIGHT` if `weights` is empty
+///
+/// # Errors
+/// * `ValidatorWeightsMismatch` - If weights are given, but not one per validator
+/// * `InvalidValidatorWeight` - If a weight is zero or above `MAX_VALIDATOR_WEIGHT`
+pub fn resolve_weights(weights: &[u64], count: usize) -> Result<Vec<u64>> {
+    if weights.is_empty() {
+        return Ok(vec![DEFAULT_VALIDATOR_WEIGHT; count]);
+    }
+
+    require!(
+        weights.len() == count,
+        CustomError::ValidatorWeightsMismatch
+    );
+    for weight in weights {
+        validate_weight(*weight)?;
+    }
+
+    Ok(weights.to_vec())
+}
+
+/// Returns the signers and weights of the validator set after a validator set update.
+///
+/// # Errors
+/// * Any error of [`resolve_weights`]
+fn updated_validators(
+    validator_set: &ValidatorSet,
+    added: &[Pubkey],
+    removed: &[Pubkey],
+    weights: &[u64],
+) -> Result<(Vec<Pubkey>, Vec<u64>)> {
+    let removed = sorted(removed);
+    let (mut signers, mut new_weights): (Vec<Pubkey>, Vec<u64>) = validator_set
+        .signers
+        .iter()
+        .zip(validator_set.weights.iter())
+        .filter(|(pk, _)| removed.binary_search(pk).is_err())
+        .map(|(pk, weight)| (*pk, *weight))
+        .unzip();
+
+    signers.extend(added.iter());
+    new_weights.extend(resolve_weights(weights, added.len())?);
+
+    Ok((signers, new_weights))
+}
+
+/// Validates validator metadata.
+///
+/// # Errors
+/// * `InvalidValidatorMetadata` - If the name or the endpoint exceeds its maximum length
+pub fn validate_metadata(metadata: &ValidatorMetadata) -> Result<()> {
+    require!(
+        metadata.name.len() <= MAX_VALIDATOR_NAME_LEN as usize
+            && metadata.endpoint.len() <= MAX_VALIDATOR_ENDPOINT_LEN as usize,
+        CustomError::InvalidValidatorMetadata
+    );
+
+    Ok(())
+}
+
+/// Creates or updates the registry record of `validator` through a governance action.
+///
+/// The record must be passed among the remaining accounts. If it doesn't exist yet it
+/// is created, funded by the payer.
+///
+/// # Errors
+/// * `MissingActionAccount` - If the record was not passed in remaining accounts
+fn store_validator_info<'info>(
+    accounts: &ActionAccounts<'_, 'info>,
+    validator: &Pubkey,
+    update: impl FnOnce(&mut ValidatorMetadata),
+) -> Result<()> {
+    let instance_id = accounts.instance_id.to_le_bytes();
+    let (address, bump) = Pubkey::find_program_address(
+        &[VALIDATOR_INFO_SEED, &instance_id, validator.as_ref()],
+        &crate::ID,
+    );
+    let info = accounts.find(&address)?;
+
+    let mut record = if info.data_is_empty() {
+        create_pda_account(
+            info,
+            accounts.payer,
+            accounts.system_program,
+            DISC as usize + ValidatorInfo::INIT_SPACE,
+            &[
+                VALIDATOR_INFO_SEED,
+                &instance_id,
+                validator.as_ref(),
+                &[bump],
+            ],
+        )?;
+
+        ValidatorInfo {
+            version: ACCOUNT_VERSION,
+            validator: *validator,
+            metadata: ValidatorMetadata::default(),
+            bump,
+        }
+    } else {
+        require_keys_eq!(
+            *info.owner,
+            crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+        let record = ValidatorInfo::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(
+            record.version == ACCOUNT_VERSION,
+            CustomError::AccountNotMigrated
+        );
+        record
+    };
+
+    update(&mut record.metadata);
+    record.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    emit!(ValidatorInfoUpdatedEvent {
+        instance_id: accounts.instance_id,
+        validator: *validator,
+        metadata: record.metadata,
+    });
+
+    Ok(())
+}
+
+/// Returns the number of validator seats a validator set update changes.
+///
+/// A rotation (one key removed, one added) changes a single seat.
+fn churn_of(added: &[Pubkey], removed: &[Pubkey]) -> u32 {
+    added.len().max(removed.len()) as u32
+}
+
+/// Returns the churn window in effect at `slot` as `(start, base, used)`.
+///
+/// If the stored window has elapsed, a fresh window starting at `slot` and measured
+/// against the current set size is returned.
+fn churn_window(validator_set: &ValidatorSet, slot: u64) -> (u64, u32, u32) {
+    let window_end = validator_set
+        .churn_window_start
+        .saturating_add(validator_set.config.churn_window_slots);
+
+    if slot >= window_end {
+        (slot, validator_set.signers.len() as u32, 0)
+    } else {
+        (
+            validator_set.churn_window_start,
+            validator_set.churn_window_base,
+            validator_set.churn_in_window,
+        )
+    }
+}
+
+/// Checks that a validator set update fits in the churn budget of the current window.
+///
+/// # Errors
+/// * `ChurnLimitExceeded` - If the update would change more seats than allowed
+fn check_churn(
+    validator_set: &ValidatorSet,
+    added: &[Pubkey],
+    removed: &[Pubkey],
+    slot: u64,
+) -> Result<()> {
+    let max_churn_bps = validator_set.config.max_churn_bps as u64;
+    if max_churn_bps >= BPS_DENOMINATOR {
+        return Ok(());
+    }
+
+    let (_, base, used) = churn_window(validator_set, slot);
+    // At least one seat can always change, otherwise small sets could never rotate
+    let allowed = (base as u64 * max_churn_bps / BPS_DENOMINATOR).max(1);
+
+    require!(
+        used as u64 + churn_of(added, removed) as u64 <= allowed,
+        CustomError::ChurnLimitExceeded
+    );
+
+    Ok(())
+}
+
+/// Checks the churn budget and charges the update against the current window.
+fn record_churn(
+    validator_set: &mut ValidatorSet,
+    added: &[Pubkey],
+    removed: &[Pubkey],
+    slot: u64,
+) -> Result<()> {
+    check_churn(validator_set, added, removed, slot)?;
+
+    let (start, base, used) = churn_window(validator_set, slot);
+    validator_set.churn_window_start = start;
+    validator_set.churn_window_base = base;
+    validator_set.churn_in_window = used + churn_of(added, removed);
+
+    Ok(())
+}
+
+/// Creates or approves a governance proposal and schedules it once the threshold is met.
+///
+/// This is the shared approval accumulator behind every governance instruction.
+/// Every action is scheduled for `timelock_slots` in the future, so the guardian or a
+/// blocking minority can veto it, and must be activated with `execute_proposal`.
+///
+/// # Arguments
+/// * `proposal` - The proposal account (freshly initialized on the first call)
+/// * `proposal_bump` - Bump seed of the proposal PDA
+/// * `validator_set` - The validator set account
+/// * `validator_stats` - The validator participation statistics account
+/// * `instructions_sysvar` - The instructions sysvar, holding the ed25519 admission proofs
+///   and approval attestations
+/// * `action` - The action being proposed or approved
+/// * `batch_id` - The batch ID of the proposal (must be greater than last_batch_id)
+/// * `accounts` - The payer, the system program and the remaining accounts holding the
+///   signatures of joining keys (and the accounts written by the action)
+///
+/// # Process Flow
+/// 1. Validates and stores the action if the proposal doesn't exist, or validates the hash matches
+/// 2. Records admission proofs of keys joining the validator set
+/// 3. Collects the validators whose approval attestations of the proposal hash are verified
+///    in the transaction (see [`attested_approvals`])
+/// 4. Adds the approvals to the approval list (growing the account) and records them in the
+///    validator statistics
+/// 5. If the approved weight meets the threshold weight and every new key is admitted, charges a missed batch to active
+///    validators that did not approve, schedules the action and updates last_batch_id
+#[allow(clippy::too_many_arguments)]
+pub fn process_proposal<'info>(
+    proposal: &mut Account<'info, GovernanceProposal>,
+    proposal_bump: u8,
+    validator_set: &mut Account<'info, ValidatorSet>,
+    validator_stats: &mut Account<'info, ValidatorStats>,
+    instructions_sysvar: &AccountInfo<'info>,
+    action: GovernanceAction,
+    batch_id: u64,
+    accounts: &ActionAccounts<'_, 'info>,
+) -> Result<()> {
+    let proposal_hash = action.hash()?;
+    let is_new = proposal.id == Pubkey::default();
+
+    if is_new {
+        action.validate(validator_set, batch_id, accounts)?;
+
+        proposal.version = ACCOUNT_VERSION;
+        proposal.id = proposal.key();
+        proposal.proposal_hash = proposal_hash;
+        proposal.action = action;
+        proposal.batch_id = batch_id;
+        proposal.bump = proposal_bump;
+        proposal.status = ProposalStatus::Voting;
+    } else {
+        require!(
+            proposal.version == ACCOUNT_VERSION,
+            CustomError::AccountNotMigrated
+        );
+        require!(
+            proposal.proposal_hash == proposal_hash,
+            CustomError::InvalidProposalHash
+        );
+    }
+
+    // Keys joining the set sign the transaction to prove possession
+    let admission_keys = proposal.action.admission_keys().to_vec();
+    let admission_signers: Vec<Pubkey> = signer_keys(accounts.remaining_accounts)
+        .into_iter()
+        .filter(|pk| admission_keys.contains(pk))
+        .collect();
+    // Validators approve through attestations of the proposal hash
+    let approval_signers = attested_approvals(
+        instructions_sysvar,
+        &validator_set.key(),
+        batch_id,
+        &proposal_hash,
+    )?;
+
+    let pending_admissions: Vec<Pubkey> = admission_keys
+        .iter()
+        .filter(|pk| !proposal.admitted.contains(pk))
+        .copied()
+        .collect();
+    let admissions = collect_admissions(
+        &pending_admissions,
+        &admission_signers,
+        instructions_sysvar,
+        &validator_set.key(),
+        batch_id,
+    )?;
+    proposal.admitted.extend(admissions.iter());
+
+    // Once a validator has created the proposal, a call may carry only admission proofs
+    if is_new || !approval_signers.is_empty() || admissions.is_empty() {
+        let signers = collect_approvals(&approval_signers, validator_set, &proposal.signers)?;
+        proposal.signers.extend(signers.iter());
+        grow_account(
+            &proposal.to_account_info(),
+            accounts.payer,
+            accounts.system_program,
+            DISC as usize
+                + GovernanceProposal::space(proposal.signers.len(), proposal.vetoes.len()),
+        )?;
+
+        ValidatorStats::ensure_space(
+            validator_stats,
+            validator_set,
+            accounts.payer,
+            accounts.system_program,
+        )?;
+        validator_stats.record_approvals(validator_set, &signers, Clock::get()?.slot);
+    }
+
+    // Check if the threshold weight is met and every new key proved possession
+    if !has_quorum(validator_set, &proposal.signers)
+        || proposal.admitted.len() < admission_keys.len()
+    {
+        return Ok(());
+    }
+
+    validator_stats.record_missed(validator_set, &proposal.signers);
+
+    let current_slot = Clock::get()?.slot;
+    validator_set.last_quorum_slot = current_slot;
+    let activation_slot = current_slot.saturating_add(validator_set.config.timelock_slots);
+
+    proposal.status = ProposalStatus::Scheduled;
+    proposal.activation_slot = activation_slot;
+    proposal
+        .action
+        .schedule(validator_set, batch_id, activation_slot, accounts)?;
+
+    // The batch is consumed once quorum is reached, even if activation is delayed
+    validator_set.last_batch_id = batch_id;
+
+    emit!(ProposalScheduledEvent {
+        instance_id: validator_set.deployment.instance_id,
+        proposal_id: proposal.id,
+        batch_id,
+        activation_slot,
+        approvals: count_approvals(validator_set, &proposal.signers) as u32,
+        approved_weight: approved_weight(validator_set, &proposal.signers),
+    });
+
+    Ok(())
+}
+
+/// Replaces a rotated validator key in place, archives the new set and closes the rotation
+/// account.
+///
+/// The seat keeps its position in `ValidatorSet.signers` and its weight, so the thresholds
+/// are unchanged.
+/// Approvals recorded under the old key stop counting (see [`count_approvals`]), and
+/// the new key can approve pending operations on its own. The registry record, bond and
+/// reward account of the old key move to the new key (see [`carry_over_records`]).
+///
+/// # Arguments
+/// * `key_rotation` - The scheduled key rotation account
+/// * `validator_set` - The validator set account
+/// * `accounts` - The payer (receives the rotation rent, pays for the snapshot and the moved
+///   records), the system program and the remaining accounts holding the snapshot of the new
+///   epoch and the old and new addresses of the moved records
+///
+/// # Errors
+/// * `TimelockNotExpired` - If the activation slot has not been reached
+/// * `RemovingNonExistentSigner` - If the old key has left the validator set meanwhile
+/// * `AddingExistingSigner` - If the new key has joined the validator set meanwhile
+/// * `MissingActionAccount` - If the snapshot of the new epoch or a record address was not
+///   passed in remaining accounts
+/// * `RotatedRecordExists` - If the new key already holds a registry record or bond
+pub fn activate_key_rotation<'info>(
+    key_rotation: &mut Account<'info, KeyRotation>,
+    validator_set: &mut Account<'info, ValidatorSet>,
+    accounts: &ActionAccounts<'_, 'info>,
+) -> Result<()> {
+    require!(
+        Clock::get()?.slot >= key_rotation.activation_slot,
+        CustomError::TimelockNotExpired
+    );
+
+    // Re-validate, a validator set update may have changed the set during the timelock
+    let index = validator_set
+        .signers
+        .iter()
+        .position(|pk| *pk == key_rotation.old_key)
+        .ok_or(CustomError::RemovingNonExistentSigner)?;
+    require!(
+        !validator_set.signers.contains(&key_rotation.new_key),
+        CustomError::AddingExistingSigner
+    );
+
+    validator_set.signers[index] = key_rotation.new_key;
+
+    // A suspension and a queued removal stay with the seat
+    if let Some(suspension) = validator_set
+        .suspended
+        .iter_mut()
+        .find(|s| s.validator == key_rotation.old_key)
+    {
+        suspension.validator = key_rotation.new_key;
+    }
+    if let Some(queued) = validator_set
+        .removal_queue
+        .iter_mut()
+        .find(|pk| **pk == key_rotation.old_key)
+    {
+        *queued = key_rotation.new_key;
+    }
+
+    carry_over_records(accounts, &key_rotation.old_key, &key_rotation.new_key)?;
+
+    emit!(ValidatorKeyRotatedEvent {
+        instance_id: validator_set.deployment.instance_id,
+        old_key: key_rotation.old_key,
+        new_key: key_rotation.new_key,
+        activation_slot: Clock::get()?.slot,
+        activated: true,
+    });
+
+    archive_validator_set(validator_set, accounts)?;
+
+    key_rotation.close(accounts.payer.clone())?;
+
+    Ok(())
+}
+
+/// Moves the records seeded by a rotated validator key to the new key.
+///
+/// The registry record, the bond and the reward account of the old key are recreated at the
+/// addresses seeded by the new key, with their lamports, and the old accounts are closed. The
+/// old and new addresses of all three records must be passed in remaining accounts, so an
+/// activation cannot leave a record behind. Rewards accrued by a new key that already
+/// relayed are merged.
+///
+/// # Errors
+/// * `MissingActionAccount` - If an old or new record address was not passed in remaining accounts
+/// * `RotatedRecordExists` - If the new key already holds a registry record or bond
+/// * `AccountNotMigrated` - If a record is not at the current layout version
+fn carry_over_records<'info>(
+    accounts: &ActionAccounts<'_, 'info>,
+    old_key: &Pubkey,
+    new_key: &Pubkey,
+) -> Result<()> {
+    carry_over_record::<ValidatorInfo>(
+        accounts,
+        VALIDATOR_INFO_SEED,
+        old_key,
+        new_key,
+        |info, existing, bump| {
+            require!(existing.is_none(), CustomError::RotatedRecordExists);
+            require!(
+                info.version == ACCOUNT_VERSION,
+                CustomError::AccountNotMigrated
+            );
+            info.validator = *new_key;
+            info.bump = bump;
+            Ok(())
+        },
+    )?;
+    carry_over_record::<ValidatorBond>(
+        accounts,
+        VALIDATOR_BOND_SEED,
+        old_key,
+        new_key,
+        |bond, existing, bump| {
+            require!(existing.is_none(), CustomError::RotatedRecordExists);
+            require!(
+                bond.version == ACCOUNT_VERSION,
+                CustomError::AccountNotMigrated
+            );
+            bond.validator = *new_key;
+            bond.bump = bump;
+            Ok(())
+        },
+    )?;
+    carry_over_record::<RewardAccount>(
+        accounts,
+        REWARD_ACCOUNT_SEED,
+        old_key,
+        new_key,
+        |rewards, existing, bump| {
+            require!(
+                rewards.version == ACCOUNT_VERSION,
+                CustomError::AccountNotMigrated
+            );
+            if let Some(existing) = existing {
+                rewards.accrued += existing.accrued;
+                rewards.claimed += existing.claimed;
+            }
+            rewards.participant = *new_key;
+            rewards.bump = bump;
+            Ok(())
+        },
+    )
+}
+
+/// Moves one record seeded by a rotated validator key to the address seeded by the new key.
+///
+/// Nothing is moved if the old key holds no record. Otherwise `rekey` updates the record for
+/// the new key, given the record the new key already holds (if any) and the bump of the new
+/// address. The new account is created if needed, funded by the payer, and receives the
+/// lamports the old one holds on top of its rent; the old account is closed to the payer.
+fn carry_over_record<'info, T>(
+    accounts: &ActionAccounts<'_, 'info>,
+    seed: &[u8],
+    old_key: &Pubkey,
+    new_key: &Pubkey,
+    rekey: impl FnOnce(&mut T, Option<T>, u8) -> Result<()>,
+) -> Result<()>
+where
+    T: AccountSerialize + AccountDeserialize,
+{
+    let instance_id = accounts.instance_id.to_le_bytes();
+    let (old_address, _) =
+        Pubkey::find_program_address(&[seed, instance_id.as_ref(), old_key.as_ref()], &crate::ID);
+    let (new_address, bump) =
+        Pubkey::find_program_address(&[seed, instance_id.as_ref(), new_key.as_ref()], &crate::ID);
+    let old = accounts.find(&old_address)?;
+    let new = accounts.find(&new_address)?;
+
+    if old.data_is_empty() {
+        return Ok(());
+    }
+    require_keys_eq!(*old.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+    let mut record = T::try_deserialize(&mut &old.try_borrow_data()?[..])?;
+
+    let existing = if new.data_is_empty() {
+        None
+    } else {
+        require_keys_eq!(*new.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        Some(T::try_deserialize(&mut &new.try_borrow_data()?[..])?)
+    };
+    let is_new = existing.is_none();
+    rekey(&mut record, existing, bump)?;
+
+    if is_new {
+        create_pda_account(
+            new,
+            accounts.payer,
+            accounts.system_program,
+            old.data_len(),
+            &[seed, instance_id.as_ref(), new_key.as_ref(), &[bump]],
+        )?;
+    }
+    record.try_serialize(&mut &mut new.try_borrow_mut_data()?[..])?;
+
+    // Bonded lamports are held on top of the rent and move with the record
+    let surplus = old
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(old.data_len()));
+    old.sub_lamports(surplus)?;
+    new.add_lamports(surplus)?;
+
+    close_account(old, accounts.payer)
+}
+
+/// Executes a scheduled proposal and closes its account.
+///
+/// # Arguments
+/// * `proposal` - The scheduled proposal account
+/// * `validator_set` - The validator set account
+/// * `accounts` - The payer (receives the proposal rent and pays for a larger validator set
+///   account) and the accounts written by the action
+pub fn activate_proposal<'info>(
+    proposal: &mut Account<'info, GovernanceProposal>,
+    validator_set: &mut Account<'info, ValidatorSet>,
+    accounts: &ActionAccounts<'_, 'info>,
+) -> Result<()> {
+    require!(
+        proposal.status == ProposalStatus::Scheduled,
+        CustomError::ProposalNotScheduled
+    );
+    require!(
+        Clock::get()?.slot >= proposal.activation_slot,
+        CustomError::TimelockNotExpired
+    );
+
+    // Count the approvals before the action changes the validator set
+    let approvals = count_approvals(validator_set, &proposal.signers) as u32;
+    let approved_weight = approved_weight(validator_set, &proposal.signers);
+
+    proposal
+        .action
+        .execute(validator_set, proposal.batch_id, accounts)?;
+
+    // The validator set account is sized for its validators and grows with them
+    grow_account(
+        &validator_set.to_account_info(),
+        accounts.payer,
+        accounts.system_program,
+        DISC as usize + ValidatorSet::space(validator_set.signers.len()),
+    )?;
+
+    emit!(ProposalExecutedEvent {
+        instance_id: validator_set.deployment.instance_id,
+        proposal_id: proposal.id,
+        proposal_hash: proposal.proposal_hash,
+        batch_id: proposal.batch_id,
+        approvals,
+        approved_weight,
+    });
+
+    proposal.close(accounts.payer.clone())?;
+
+    Ok(())
+}
@@ -0,0 +1,555 @@
+//! Guardian council action validation and dispatch for the Skyline bridge program.
+//!
+//! This module contains the approval accumulator of the guardian council. It follows the
+//! governance accumulator: a proposal is created on the first call, approved by council
+//! members on subsequent calls, and its typed action is executed once the council
+//! threshold is reached.
+
+use crate::*;
+
+/// Domain separator mixed into every guardian proposal hash.
+const GUARDIAN_PROPOSAL_HASH_DOMAIN: &[u8] = b"skyline:guardian-proposal";
+
+impl GuardianCouncil {
+    /// Returns the number of approvals that come from current council members.
+    ///
+    /// Approvals of members that were replaced by a later `SetGuardianCouncil` proposal
+    /// stop counting.
+    pub fn count_approvals(&self, approved: &[Pubkey]) -> usize {
+        approved
+            .iter()
+            .filter(|pk| self.members.contains(pk))
+            .count()
+    }
+
+    /// Validates the council members approving in the current call.
+    ///
+    /// # Arguments
+    /// * `signers` - The signers of the current call
+    /// * `approved` - The approvals already recorded for the proposal
+    ///
+    /// # Errors
+    /// * `NoSignersProvided` - If no signers are provided
+    /// * `DuplicateSignersProvided` - If duplicate signers are provided
+    /// * `InvalidSigner` - If a signer is not a council member
+    /// * `SignerAlreadyApproved` - If a signer has already approved the proposal
+    pub fn collect_approvals(
+        &self,
+        signers: &[Pubkey],
+        approved: &[Pubkey],
+    ) -> Result<Vec<Pubkey>> {
+        require!(!signers.is_empty(), CustomError::NoSignersProvided);
+
+        let mut unique = sorted(signers);
+        unique.dedup();
+        require!(
+            signers.len() == unique.len(),
+            CustomError::DuplicateSignersProvided
+        );
+
+        require!(
+            signers.iter().all(|k| self.members.contains(k)),
+            CustomError::InvalidSigner
+        );
+
+        require!(
+            !signers.iter().any(|s| approved.contains(s)),
+            CustomError::SignerAlreadyApproved
+        );
+
+        Ok(signers.to_vec())
+    }
+}
+
+impl GuardianAction {
+    /// Computes the hash that identifies this action.
+    ///
+    /// The hash is taken over a domain separator followed by the Borsh serialization
+    /// of the action, so it never collides with a governance proposal hash.
+    pub fn hash(&self) -> Result<[u8; 32]> {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(GUARDIAN_PROPOSAL_HASH_DOMAIN);
+        hasher.update(&self.try_to_vec()?);
+        Ok(*hasher.finalize().as_bytes())
+    }
+
+    /// Returns whether the action must wait out `config.recovery_timelock_slots` before
+    /// activation.
+    ///
+    /// Emergency actions only stop or cancel things and take effect immediately. Replacing
+    /// the validator set is the one action that hands over control of the bridge.
+    pub fn is_timelocked(&self) -> bool {
+        match self {
+            GuardianAction::RecoverValidatorSet { .. } => true,
+            GuardianAction::SetPaused { .. }
+            | GuardianAction::VetoProposal { .. }
+            | GuardianAction::VetoKeyRotation { .. } => false,
+        }
+    }
+
+    /// Returns the keys that must prove possession before the action can be scheduled.
+    ///
+    /// Every key of a recovered validator set signs its own admission, as for validators
+    /// joining through governance.
+    pub fn admission_keys(&self) -> &[Pubkey] {
+        match self {
+            GuardianAction::RecoverValidatorSet { signers, .. } => signers,
+            GuardianAction::SetPaused { .. }
+            | GuardianAction::VetoProposal { .. }
+            | GuardianAction::VetoKeyRotation { .. } => &[],
+        }
+    }
+
+    /// Validates the action against the current validator set.
+    ///
+    /// This is run once, when the proposal is created. Vetoes are checked against their
+    /// target when they are executed.
+    ///
+    /// # Arguments
+    /// * `validator_set` - The validator set account
+    /// * `validator_stats` - The validator participation statistics account
+    ///
+    /// # Errors
+    /// * Any error of [`validate_recovery`]
+    pub fn validate(
+        &self,
+        validator_set: &ValidatorSet,
+        validator_stats: &ValidatorStats,
+    ) -> Result<()> {
+        match self {
+            GuardianAction::RecoverValidatorSet { signers, weights } => {
+                validate_recovery(validator_set, validator_stats, signers, weights)?;
+            }
+            GuardianAction::SetPaused { .. }
+            | GuardianAction::VetoProposal { .. }
+            | GuardianAction::VetoKeyRotation { .. } => {}
+        }
+
+        Ok(())
+    }
+
+    /// Executes the action once the proposal is activated.
+    ///
+    /// # Arguments
+    /// * `validator_set` - The validator set account
+    /// * `validator_stats` - The validator participation statistics account
+    /// * `admitted` - The keys that proved possession for the proposal
+    /// * `accounts` - The payer (receives the rent of vetoed accounts), the system program and
+    ///   the remaining accounts holding the vetoed account or the snapshot of the recovered set
+    ///
+    /// # Errors
+    /// * `MissingActionAccount` - If the vetoed account or the snapshot is missing
+    /// * `MissingAdmissionProof` - If a key of a recovered set has not signed its admission
+    /// * `ProposalNotScheduled` - If the vetoed governance proposal is not waiting for activation
+    /// * Any error of [`validate_recovery`] or [`archive_validator_set`]
+    pub fn execute<'info>(
+        &self,
+        validator_set: &mut ValidatorSet,
+        validator_stats: &ValidatorStats,
+        admitted: &[Pubkey],
+        accounts: &ActionAccounts<'_, 'info>,
+    ) -> Result<()> {
+        match self {
+            GuardianAction::SetPaused { paused } => {
+                validator_set.paused = *paused;
+                emit!(BridgePausedEvent {
+                    instance_id: validator_set.deployment.instance_id,
+                    paused: *paused,
+                });
+            }
+            GuardianAction::VetoProposal { batch_id } => {
+                veto_proposal(validator_set, *batch_id, accounts)?;
+            }
+            GuardianAction::VetoKeyRotation { old_key } => {
+                veto_key_rotation(old_key, accounts)?;
+            }
+            GuardianAction::RecoverValidatorSet { signers, weights } => {
+                recover_validator_set(
+                    validator_set,
+                    validator_stats,
+                    signers,
+                    weights,
+                    admitted,
+                    accounts,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Validates a validator set recovery.
+///
+/// # Errors
+/// * `MaxValidatorsExceeded` - If more than `MAX_VALIDATORS_CHANGE` validators are given
+/// * `MinValidatorsNotMet` - If fewer than `MIN_VALIDATORS` validators are given
+/// * `ValidatorsNotUnique` - If a validator is given twice
+/// * `ValidatorSetNotStalled` - If the validators reached a quorum within the last
+///   `config.recovery_stall_slots` slots, or validators holding the threshold weight were
+///   seen (heartbeat or approval) within that window
+/// * Any error of [`resolve_weights`]
+pub fn validate_recovery(
+    validator_set: &ValidatorSet,
+    validator_stats: &ValidatorStats,
+    signers: &[Pubkey],
+    weights: &[u64],
+) -> Result<()> {
+    require!(
+        signers.len() <= MAX_VALIDATORS_CHANGE as usize,
+        CustomError::MaxValidatorsExceeded
+    );
+    require!(
+        signers.len() >= MIN_VALIDATORS as usize,
+        CustomError::MinValidatorsNotMet
+    );
+
+    let mut unique = sorted(signers);
+    unique.dedup();
+    require!(
+        unique.len() == signers.len(),
+        CustomError::ValidatorsNotUnique
+    );
+    resolve_weights(weights, signers.len())?;
+
+    // A set is stalled when it neither reached a quorum nor could have reached one: an idle
+    // bridge whose validators keep sending heartbeats is not recoverable
+    let slot = Clock::get()?.slot;
+    let stall_slots = validator_set.config.recovery_stall_slots;
+    require!(
+        slot >= validator_set.last_quorum_slot.saturating_add(stall_slots),
+        CustomError::ValidatorSetNotStalled
+    );
+    require!(
+        validator_stats.live_weight(validator_set, slot.saturating_sub(stall_slots))
+            < validator_set.threshold_weight,
+        CustomError::ValidatorSetNotStalled
+    );
+
+    Ok(())
+}
+
+/// Cancels a scheduled governance proposal on behalf of the guardian council.
+///
+/// The proposal (and the validator delta of an `ApplyValidatorDelta` proposal) must be
+/// passed among the remaining accounts.
+///
+/// # Errors
+/// * `MissingActionAccount` - If the proposal was not passed in remaining accounts
+/// * `ProposalNotScheduled` - If the proposal is not waiting for activation
+fn veto_proposal(
+    validator_set: &mut ValidatorSet,
+    batch_id: u64,
+    accounts: &ActionAccounts,
+) -> Result<()> {
+    let (address, _) = Pubkey::find_program_address(
+        &[
+            GOVERNANCE_PROPOSAL_SEED,
+            accounts.instance_id.to_le_bytes().as_ref(),
+            batch_id.to_le_bytes().as_ref(),
+        ],
+        &crate::ID,
+    );
+    let info = accounts.find(&address)?;
+
+    require_keys_eq!(
+        *info.owner,
+        crate::ID,
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+    let proposal = GovernanceProposal::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    require!(
+        proposal.version == ACCOUNT_VERSION,
+        CustomError::AccountNotMigrated
+    );
+    require!(
+        proposal.status == ProposalStatus::Scheduled,
+        CustomError::ProposalNotScheduled
+    );
+
+    proposal.action.cancel(validator_set, batch_id, accounts)?;
+
+    emit!(ProposalVetoedEvent {
+        instance_id: accounts.instance_id,
+        proposal_id: proposal.id,
+        batch_id,
+        by_guardian: true,
+    });
+
+    close_account(info, accounts.payer)
+}
+
+/// Cancels a scheduled validator key rotation on behalf of the guardian council.
+///
+/// # Errors
+/// * `MissingActionAccount` - If the rotation was not passed in remaining accounts
+fn veto_key_rotation(old_key: &Pubkey, accounts: &ActionAccounts) -> Result<()> {
+    let (address, _) = Pubkey::find_program_address(
+        &[
+            KEY_ROTATION_SEED,
+            accounts.instance_id.to_le_bytes().as_ref(),
+            old_key.as_ref(),
+        ],
+        &crate::ID,
+    );
+    let info = accounts.find(&address)?;
+
+    require_keys_eq!(
+        *info.owner,
+        crate::ID,
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+    let key_rotation = KeyRotation::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    require!(
+        key_rotation.version == ACCOUNT_VERSION,
+        CustomError::AccountNotMigrated
+    );
+
+    emit!(KeyRotationVetoedEvent {
+        instance_id: accounts.instance_id,
+        old_key: key_rotation.old_key,
+        new_key: key_rotation.new_key,
+    });
+
+    close_account(info, accounts.payer)
+}
+
+/// Replaces a stalled validator set and archives the recovered set.
+///
+/// The recovery is re-validated, the validators may have reached a quorum during the
+/// timelock. Suspensions, a pending validator set update and the churn accounting of the
+/// stalled set are dropped, and the recovered set starts a fresh stall period. Queued
+/// removals are kept for the validators that stay in the set. Every recovered key must
+/// have signed its admission, as for validators joining through governance.
+///
+/// # Errors
+/// * `MissingAdmissionProof` - If a recovered key has not signed its admission
+/// * Any error of [`validate_recovery`] or [`archive_validator_set`]
+fn recover_validator_set<'info>(
+    validator_set: &mut ValidatorSet,
+    validator_stats: &ValidatorStats,
+    signers: &[Pubkey],
+    weights: &[u64],
+    admitted: &[Pubkey],
+    accounts: &ActionAccounts<'_, 'info>,
+) -> Result<()> {
+    validate_recovery(validator_set, validator_stats, signers, weights)?;
+    require!(
+        signers.iter().all(|pk| admitted.contains(pk)),
+        CustomError::MissingAdmissionProof
+    );
+
+    let slot = Clock::get()?.slot;
+    validator_set.weights = resolve_weights(weights, signers.len())?;
+    validator_set.signers = signers.to_vec();
+    validator_set.suspended.clear();
+    validator_set
+        .removal_queue
+        .retain(|pk| signers.contains(pk));
+    validator_set.pending_update = None;
+    validator_set.churn_window_start = slot;
+    validator_set.churn_window_base = signers.len() as u32;
+    validator_set.churn_in_window = 0;
+    validator_set.last_quorum_slot = slot;
+    update_thresholds(validator_set);
+
+    emit!(ValidatorSetUpdatedEvent {
+        instance_id: validator_set.deployment.instance_id,
+        new_signers: validator_set.signers.clone(),
+        new_weights: validator_set.weights.clone(),
+        new_threshold: validator_set.threshold,
+        new_threshold_weight: validator_set.threshold_weight,
+        batch_id: validator_set.last_batch_id,
+        activation_slot: slot,
+        activated: true,
+    });
+
+    archive_validator_set(validator_set, accounts)
+}
+
+/// Creates or approves a guardian proposal and schedules it once the council threshold is met.
+///
+/// This is the approval accumulator behind `submit_guardian_proposal`. A
+/// `RecoverValidatorSet` proposal is scheduled for `config.recovery_timelock_slots` in the
+/// future and must be activated with `execute_guardian_proposal`; all other actions are
+/// activated immediately.
+///
+/// # Arguments
+/// * `proposal` - The proposal account (freshly initialized on the first call)
+/// * `proposal_bump` - Bump seed of the proposal PDA
+/// * `council` - The guardian council account
+/// * `validator_set` - The validator set account
+/// * `validator_stats` - The validator participation statistics account
+/// * `instructions_sysvar` - The instructions sysvar, holding ed25519 admission proofs
+/// * `action` - The action being proposed or approved
+/// * `batch_id` - The council batch ID of the proposal (must be greater than the council's
+///   last_batch_id)
+/// * `accounts` - The payer, the system program and the remaining accounts holding the
+///   member signatures (and the accounts written by the action)
+///
+/// # Process Flow
+/// 1. Validates and stores the action if the proposal doesn't exist, or validates the hash matches
+/// 2. Records admission proofs of the keys of a recovered validator set
+/// 3. Validates and collects council member signers from remaining accounts
+/// 4. Adds signers to the approval list (growing the account)
+/// 5. If the council threshold is met and every new key is admitted, schedules the action and
+///    updates the council's last_batch_id
+/// 6. If no timelock applies, activates the action and closes the proposal
+#[allow(clippy::too_many_arguments)]
+pub fn process_guardian_proposal<'info>(
+    proposal: &mut Account<'info, GuardianProposal>,
+    proposal_bump: u8,
+    council: &mut Account<'info, GuardianCouncil>,
+    validator_set: &mut Account<'info, ValidatorSet>,
+    validator_stats: &ValidatorStats,
+    instructions_sysvar: &AccountInfo<'info>,
+    action: GuardianAction,
+    batch_id: u64,
+    accounts: &ActionAccounts<'_, 'info>,
+) -> Result<()> {
+    let proposal_hash = action.hash()?;
+    let is_new = proposal.id == Pubkey::default();
+
+    if is_new {
+        action.validate(validator_set, validator_stats)?;
+
+        proposal.version = ACCOUNT_VERSION;
+        proposal.id = proposal.key();
+        proposal.proposal_hash = proposal_hash;
+        proposal.action = action;
+        proposal.batch_id = batch_id;
+        proposal.bump = proposal_bump;
+        proposal.status = ProposalStatus::Voting;
+    } else {
+        require!(
+            proposal.version == ACCOUNT_VERSION,
+            CustomError::AccountNotMigrated
+        );
+        require!(
+            proposal.proposal_hash == proposal_hash,
+            CustomError::InvalidProposalHash
+        );
+    }
+
+    // Signatures of keys of a recovered set count as admissions, not approvals
+    let admission_keys = proposal.action.admission_keys().to_vec();
+    let (admission_signers, approval_signers): (Vec<Pubkey>, Vec<Pubkey>) =
+        signer_keys(accounts.remaining_accounts)
+            .into_iter()
+            .partition(|pk| admission_keys.contains(pk));
+
+    let pending_admissions: Vec<Pubkey> = admission_keys
+        .iter()
+        .filter(|pk| !proposal.admitted.contains(pk))
+        .copied()
+        .collect();
+    // Admission proofs are bound to the council, so they cannot be replayed from governance
+    let admissions = collect_admissions(
+        &pending_admissions,
+        &admission_signers,
+        instructions_sysvar,
+        &council.key(),
+        batch_id,
+    )?;
+    proposal.admitted.extend(admissions.iter());
+
+    // Once a member has created the proposal, a call may carry only admission proofs
+    if is_new || !approval_signers.is_empty() || admissions.is_empty() {
+        let signers = council.collect_approvals(&approval_signers, &proposal.signers)?;
+        proposal.signers.extend(signers.iter());
+        grow_account(
+            &proposal.to_account_info(),
+            accounts.payer,
+            accounts.system_program,
+            DISC as usize + GuardianProposal::space(proposal.signers.len()),
+        )?;
+    }
+
+    if council.count_approvals(&proposal.signers) < council.threshold as usize
+        || proposal.admitted.len() < admission_keys.len()
+    {
+        return Ok(());
+    }
+
+    let current_slot = Clock::get()?.slot;
+    let timelock_slots = if proposal.action.is_timelocked() {
+        validator_set.config.recovery_timelock_slots
+    } else {
+        0
+    };
+    let activation_slot = current_slot.saturating_add(timelock_slots);
+
+    proposal.status = ProposalStatus::Scheduled;
+    proposal.activation_slot = activation_slot;
+
+    // The batch is consumed once the threshold is reached, even if activation is delayed
+    council.last_batch_id = batch_id;
+
+    if activation_slot > current_slot {
+        emit!(GuardianProposalScheduledEvent {
+            instance_id: validator_set.deployment.instance_id,
+            proposal_id: proposal.id,
+            batch_id,
+            activation_slot,
+            approvals: council.count_approvals(&proposal.signers) as u32,
+        });
+        return Ok(());
+    }
+
+    activate_guardian_proposal(proposal, council, validator_set, validator_stats, accounts)
+}
+
+/// Executes a scheduled guardian proposal and closes its account.
+///
+/// # Arguments
+/// * `proposal` - The scheduled proposal account
+/// * `council` - The guardian council account
+/// * `validator_set` - The validator set account
+/// * `validator_stats` - The validator participation statistics account
+/// * `accounts` - The payer (receives the proposal rent and pays for a larger validator set
+///   account) and the accounts written by the action
+///
+/// # Errors
+/// * `ProposalNotScheduled` - If the proposal has not reached the council threshold
+/// * `TimelockNotExpired` - If the activation slot has not been reached
+/// * Any error of [`GuardianAction::execute`]
+pub fn activate_guardian_proposal<'info>(
+    proposal: &mut Account<'info, GuardianProposal>,
+    council: &GuardianCouncil,
+    validator_set: &mut Account<'info, ValidatorSet>,
+    validator_stats: &ValidatorStats,
+    accounts: &ActionAccounts<'_, 'info>,
+) -> Result<()> {
+    require!(
+        proposal.status == ProposalStatus::Scheduled,
+        CustomError::ProposalNotScheduled
+    );
+    require!(
+        Clock::get()?.slot >= proposal.activation_slot,
+        CustomError::TimelockNotExpired
+    );
+
+    proposal
+        .action
+        .execute(validator_set, validator_stats, &proposal.admitted, accounts)?;
+
+    // A recovered set may be larger than the stalled one
+    grow_account(
+        &validator_set.to_account_info(),
+        accounts.payer,
+        accounts.system_program,
+        DISC as usize + ValidatorSet::space(validator_set.signers.len()),
+    )?;
+
+    emit!(GuardianProposalExecutedEvent {
+        instance_id: validator_set.deployment.instance_id,
+        proposal_id: proposal.id,
+        proposal_hash: proposal.proposal_hash,
+        batch_id: proposal.batch_id,
+        approvals: council.count_approvals(&proposal.signers) as u32,
+    });
+
+    proposal.close(accounts.payer.clone())?;
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+//! Heap allocator for the Skyline bridge program.
+//!
+//! The default Solana allocator hands out the fixed 32 KiB heap from the top down, so a
+//! larger heap frame requested through the compute budget program is never used. This
+//! allocator hands out the heap from the bottom up instead: transactions that fit the
+//! default heap work unchanged, while transactions touching large validator sets request a
+//! larger heap frame (up to `MAX_HEAP_FRAME_BYTES`). Like the default allocator it never
+//! frees memory.
+
+use std::{
+    alloc::{GlobalAlloc, Layout},
+    mem::size_of,
+    ptr::null_mut,
+};
+
+use anchor_lang::solana_program::entrypoint::HEAP_START_ADDRESS;
+
+use crate::MAX_HEAP_FRAME_BYTES;
+
+/// Bump allocator growing upwards from the start of the heap.
+///
+/// The first word of the heap holds the address of the next free byte. The runtime zeroes
+/// the heap, so a zero word means nothing has been allocated yet.
+struct BottomUpAllocator;
+
+unsafe impl GlobalAlloc for BottomUpAllocator {
+    #[inline]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let heap_start = HEAP_START_ADDRESS as usize;
+        let pos_ptr = heap_start as *mut usize;
+
+        let mut pos = *pos_ptr;
+        if pos == 0 {
+            pos = heap_start + size_of::<usize>();
+        }
+
+        let start = (pos + layout.align() - 1) & !(layout.align() - 1);
+        let end = start.saturating_add(layout.size());
+        if end > heap_start + MAX_HEAP_FRAME_BYTES as usize {
+            return null_mut();
+        }
+
+        *pos_ptr = end;
+        start as *mut u8
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {
+        // Memory is released when the instruction finishes
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: BottomUpAllocator = BottomUpAllocator;
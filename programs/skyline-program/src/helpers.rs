@@ -3,9 +3,21 @@
 //! This module contains utility functions used throughout the bridge program
 //! for common operations like threshold calculation and authority validation.
 
-use anchor_lang::{prelude::*, solana_program::program_option::COption};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program_option::COption, sysvar::instructions::load_instruction_at_checked},
+    system_program::{
+        allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+    },
+};
 use anchor_spl::token::Mint;
 
+use crate::{
+    AddressFormat, ChainConfig, CustomError, ValidatorSet, ADMISSION_MESSAGE_DOMAIN,
+    APPROVAL_MESSAGE_DOMAIN, BPS_DENOMINATOR, CARDANO_BASE_ADDRESS_LEN,
+    CARDANO_ENTERPRISE_ADDRESS_LEN, EVM_ADDRESS_LEN,
+};
+
 /// Calculates the consensus threshold for a given number of validators.
 ///
 /// Formula: `num_signers - floor((num_signers - 1) / 3)`
@@ -20,7 +32,7 @@ use anchor_spl::token::Mint;
 ///
 /// # Returns
 ///
-/// The minimum number of validator signatures required for consensus
+/// The minimum number of validator signatures required for consensus, zero for an empty set
 ///
 /// # Examples
 ///
@@ -30,9 +42,122 @@ use anchor_spl::token::Mint;
 /// // 7 validators: 7 - 2 = 5 (71%) - tolerates 2 Byzantine
 /// // 10 validators: 10 - 3 = 7 (70%) - tolerates 3 Byzantine
 /// ```
-pub fn calculate_threshold(num_signers: usize) -> u8 {
+pub fn calculate_threshold(num_signers: usize) -> u16 {
     // Integer division automatically floors in Rust
-    num_signers as u8 - ((num_signers - 1) / 3) as u8
+    (num_signers - num_signers.saturating_sub(1) / 3) as u16
+}
+
+/// Calculates the consensus threshold weight for a given total validator weight.
+///
+/// Formula: `ceil(total_weight * quorum_weight_bps / BPS_DENOMINATOR)`, at least 1
+///
+/// # Arguments
+///
+/// * `total_weight` - The sum of the weights of the active validators
+/// * `quorum_weight_bps` - The share of the total weight required, in basis points
+///
+/// # Returns
+///
+/// The minimum sum of approving validator weights required for consensus
+pub fn calculate_threshold_weight(total_weight: u64, quorum_weight_bps: u16) -> u64 {
+    (total_weight * quorum_weight_bps as u64)
+        .div_ceil(BPS_DENOMINATOR)
+        .max(1)
+}
+
+/// Recalculates both consensus thresholds from the active validators.
+///
+/// Must be called whenever the validators, their weights, their suspensions or the
+/// quorum configuration change.
+///
+/// # Arguments
+///
+/// * `validator_set` - The validator set to update
+pub fn update_thresholds(validator_set: &mut ValidatorSet) {
+    validator_set.threshold = calculate_threshold(active_validator_count(validator_set));
+    validator_set.threshold_weight = calculate_threshold_weight(
+        active_weight(validator_set),
+        validator_set.config.quorum_weight_bps,
+    );
+}
+
+/// Converts a token amount between two decimal precisions.
+///
+/// Scaling to fewer decimals truncates; the truncated remainder (dust) is returned in the
+/// units of `from_decimals`. Scaling to more decimals leaves no dust.
+///
+/// # Arguments
+///
+/// * `amount` - The amount in units of `from_decimals`
+/// * `from_decimals` - The decimals of the amount
+/// * `to_decimals` - The decimals to convert the amount to
+///
+/// # Returns
+///
+/// The converted amount and the dust, or `None` if the converted amount overflows
+///
+/// # Examples
+///
+/// ```
+/// // 1_234_567_891 at 9 decimals is 1_234_567 at 6 decimals with a dust of 891
+/// // 1_234_567 at 6 decimals is 1_234_567_000 at 9 decimals
+/// ```
+pub fn convert_decimals(amount: u128, from_decimals: u8, to_decimals: u8) -> Option<(u128, u128)> {
+    if to_decimals >= from_decimals {
+        let factor = 10u128.checked_pow((to_decimals - from_decimals) as u32)?;
+        Some((amount.checked_mul(factor)?, 0))
+    } else {
+        match 10u128.checked_pow((from_decimals - to_decimals) as u32) {
+            Some(factor) => Some((amount / factor, amount % factor)),
+            None => Some((0, amount)),
+        }
+    }
+}
+
+/// Validates the receiver of a bridge request against the destination chain.
+///
+/// The receiver must be within the receiver length bounds of the chain and an address in
+/// its format:
+/// - `Raw`: any bytes
+/// - `Evm`: a 20-byte account address
+/// - `Cardano`: a Shelley payment address (base, pointer or enterprise) in its binary form,
+///   whose header carries the network ID of the chain; Byron and reward addresses cannot
+///   receive bridged tokens
+///
+/// # Arguments
+///
+/// * `chain` - The configuration of the destination chain
+/// * `receiver` - The receiver address bytes
+///
+/// # Errors
+///
+/// * `InvalidReceiverAddress` - If the receiver is not a valid address on the chain
+pub fn validate_receiver(chain: &ChainConfig, receiver: &[u8]) -> Result<()> {
+    require!(
+        receiver.len() >= chain.min_receiver_len as usize
+            && receiver.len() <= chain.max_receiver_len as usize,
+        CustomError::InvalidReceiverAddress
+    );
+
+    let valid = match chain.address_format {
+        AddressFormat::Raw => true,
+        AddressFormat::Evm => receiver.len() == EVM_ADDRESS_LEN as usize,
+        AddressFormat::Cardano { network_id } => match receiver.first() {
+            Some(header) if header & 0x0f == network_id => match header >> 4 {
+                // Base addresses: payment and stake credentials
+                0..=3 => receiver.len() == CARDANO_BASE_ADDRESS_LEN as usize,
+                // Pointer addresses: payment credential and a stake pointer
+                4 | 5 => receiver.len() > CARDANO_ENTERPRISE_ADDRESS_LEN as usize,
+                // Enterprise addresses: payment credential only
+                6 | 7 => receiver.len() == CARDANO_ENTERPRISE_ADDRESS_LEN as usize,
+                _ => false,
+            },
+            _ => false,
+        },
+    };
+    require!(valid, CustomError::InvalidReceiverAddress);
+
+    Ok(())
 }
 
 /// Checks if the vault is the mint authority for a given token mint.
@@ -56,3 +181,535 @@ pub fn is_vault_mint_authority(mint: &Account<Mint>, vault: &AccountInfo) -> boo
         COption::None => false,
     }
 }
+
+/// Returns the keys of all remaining accounts flagged as signers.
+///
+/// # Arguments
+///
+/// * `remaining_accounts` - The instruction's remaining accounts
+pub fn signer_keys(remaining_accounts: &[AccountInfo]) -> Vec<Pubkey> {
+    remaining_accounts
+        .iter()
+        .filter(|acc| acc.is_signer)
+        .map(|acc| acc.key())
+        .collect()
+}
+
+/// Validates a batch of validator approvals.
+///
+/// The approvals of a single call must be unique, belong to the validator set and
+/// must not have been recorded already.
+///
+/// # Arguments
+///
+/// * `signers` - The keys approving in the current call (see [`attested_approvals`], or
+///   [`signer_keys`] for vetoes)
+/// * `validator_set` - The validator set the signers must belong to
+/// * `approved` - The approvals already recorded for the operation
+///
+/// # Returns
+///
+/// The public keys of the new approvals
+///
+/// # Errors
+///
+/// * `NoSignersProvided` - If no signers are provided
+/// * `DuplicateSignersProvided` - If the same signer is provided twice
+/// * `InvalidSigner` - If a signer is not in the validator set
+/// * `ValidatorSuspended` - If a signer is suspended
+/// * `SignerAlreadyApproved` - If a signer has already approved
+pub fn collect_approvals(
+    signers: &[Pubkey],
+    validator_set: &ValidatorSet,
+    approved: &[Pubkey],
+) -> Result<Vec<Pubkey>> {
+    require!(!signers.is_empty(), CustomError::NoSignersProvided);
+
+    let mut signers_copy = signers.to_vec();
+    signers_copy.sort();
+    signers_copy.dedup();
+    require!(
+        signers.len() == signers_copy.len(),
+        CustomError::DuplicateSignersProvided
+    );
+
+    require!(
+        signers.iter().all(|k| validator_set.signers.contains(k)),
+        CustomError::InvalidSigner
+    );
+
+    require!(
+        !signers.iter().any(|k| is_suspended(validator_set, k)),
+        CustomError::ValidatorSuspended
+    );
+
+    require!(
+        !signers.iter().any(|s| approved.contains(s)),
+        CustomError::SignerAlreadyApproved
+    );
+
+    Ok(signers.to_vec())
+}
+
+/// Counts the recorded approvals whose keys still belong to the active validator set.
+///
+/// Approvals recorded under keys that have since left the set (removed, or rotated
+/// out through `rotate_validator_key`) or have been suspended no longer count towards
+/// the threshold.
+///
+/// # Arguments
+///
+/// * `validator_set` - The current validator set
+/// * `approved` - The approvals recorded for the operation
+pub fn count_approvals(validator_set: &ValidatorSet, approved: &[Pubkey]) -> usize {
+    active_approvals(validator_set, approved).count()
+}
+
+/// Returns the `(key, weight)` pairs of the active validators that are among `approved`.
+///
+/// The approvals are sorted once, so the cost grows with `n log n` rather than with the
+/// product of the validator set size and the number of approvals.
+pub fn active_approvals<'a>(
+    validator_set: &'a ValidatorSet,
+    approved: &[Pubkey],
+) -> impl Iterator<Item = (&'a Pubkey, &'a u64)> {
+    let mut approved = approved.to_vec();
+    approved.sort_unstable();
+
+    validator_set
+        .signers
+        .iter()
+        .zip(validator_set.weights.iter())
+        .filter(move |(pk, _)| {
+            approved.binary_search(pk).is_ok() && !is_suspended(validator_set, pk)
+        })
+}
+
+/// Sums the weights of the recorded approvals whose keys still belong to the active
+/// validator set.
+///
+/// The same approvals are counted as in [`count_approvals`].
+///
+/// # Arguments
+///
+/// * `validator_set` - The current validator set
+/// * `approved` - The approvals recorded for the operation
+pub fn approved_weight(validator_set: &ValidatorSet, approved: &[Pubkey]) -> u64 {
+    active_approvals(validator_set, approved)
+        .map(|(_, weight)| weight)
+        .sum()
+}
+
+/// Returns whether the recorded approvals reach the consensus threshold weight.
+///
+/// # Arguments
+///
+/// * `validator_set` - The current validator set
+/// * `approved` - The approvals recorded for the operation
+pub fn has_quorum(validator_set: &ValidatorSet, approved: &[Pubkey]) -> bool {
+    approved_weight(validator_set, approved) >= validator_set.threshold_weight
+}
+
+/// Returns the voting weight of a validator, or zero if it is not in the validator set.
+///
+/// # Arguments
+///
+/// * `validator_set` - The current validator set
+/// * `validator` - The validator key to look up
+pub fn validator_weight(validator_set: &ValidatorSet, validator: &Pubkey) -> u64 {
+    validator_set
+        .signers
+        .iter()
+        .position(|pk| pk == validator)
+        .map_or(0, |index| validator_set.weights[index])
+}
+
+/// Returns the sum of the weights of the validators that are not suspended.
+///
+/// This is the total weight the consensus threshold weight is calculated from.
+///
+/// # Arguments
+///
+/// * `validator_set` - The current validator set
+pub fn active_weight(validator_set: &ValidatorSet) -> u64 {
+    validator_set
+        .signers
+        .iter()
+        .zip(validator_set.weights.iter())
+        .filter(|(pk, _)| !is_suspended(validator_set, pk))
+        .map(|(_, weight)| weight)
+        .sum()
+}
+
+/// Returns whether a validator is suspended.
+///
+/// # Arguments
+///
+/// * `validator_set` - The current validator set
+/// * `validator` - The validator key to check
+pub fn is_suspended(validator_set: &ValidatorSet, validator: &Pubkey) -> bool {
+    validator_set
+        .suspended
+        .iter()
+        .any(|s| s.validator == *validator)
+}
+
+/// Returns the number of validators that are not suspended.
+///
+/// This is the set size the consensus threshold is calculated from.
+///
+/// # Arguments
+///
+/// * `validator_set` - The current validator set
+pub fn active_validator_count(validator_set: &ValidatorSet) -> usize {
+    validator_set.signers.len() - validator_set.suspended.len()
+}
+
+/// Grows a program-owned account to `space` bytes, topping up its rent from `payer`.
+///
+/// Accounts holding per-validator data are allocated for what they hold and grow with it.
+/// The account is left unchanged if it is already large enough; it never shrinks.
+///
+/// # Arguments
+///
+/// * `account` - The account to grow
+/// * `payer` - The signer paying for the additional rent
+/// * `system_program` - The system program
+/// * `space` - The required account size in bytes, including the discriminator
+pub fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+) -> Result<()> {
+    if account.data_len() >= space {
+        return Ok(());
+    }
+
+    let rent_exempt = Rent::get()?.minimum_balance(space);
+    let missing = rent_exempt.saturating_sub(account.lamports());
+    if missing > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            missing,
+        )?;
+    }
+
+    account.resize(space)?;
+
+    Ok(())
+}
+
+/// Closes a program-owned account and sends its lamports to `destination`.
+///
+/// This is the equivalent of Anchor's `close` constraint for accounts that are found among
+/// the remaining accounts rather than declared in the accounts struct.
+///
+/// # Arguments
+///
+/// * `account` - The account to close
+/// * `destination` - The account receiving the rent
+pub fn close_account<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    destination.add_lamports(account.lamports())?;
+    account.sub_lamports(account.lamports())?;
+
+    account.assign(&anchor_lang::system_program::ID);
+    account.resize(0)?;
+
+    Ok(())
+}
+
+/// Creates a program-owned PDA of `space` bytes, funded by `payer`.
+///
+/// Lamports sent to the address beforehand do not prevent the creation: the rent is topped
+/// up and the account is allocated and assigned instead.
+///
+/// # Arguments
+///
+/// * `account` - The PDA to create
+/// * `payer` - The signer paying for the rent
+/// * `system_program` - The system program
+/// * `space` - The account size in bytes, including the discriminator
+/// * `seeds` - The seeds of the PDA, including the bump
+pub fn create_pda_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let rent_exempt = Rent::get()?.minimum_balance(space);
+
+    if account.lamports() == 0 {
+        return create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                &[seeds],
+            ),
+            rent_exempt,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let missing = rent_exempt.saturating_sub(account.lamports());
+    if missing > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            missing,
+        )?;
+    }
+    allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate {
+                account_to_allocate: account.clone(),
+            },
+            &[seeds],
+        ),
+        space as u64,
+    )?;
+    assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign {
+                account_to_assign: account.clone(),
+            },
+            &[seeds],
+        ),
+        &crate::ID,
+    )?;
+
+    Ok(())
+}
+
+/// Program ID of the native ed25519 signature verification precompile.
+const ED25519_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("Ed25519SigVerify111111111111111111111111111");
+
+/// Size of the ed25519 precompile instruction header (signature count and padding).
+const ED25519_HEADER_LEN: usize = 2;
+
+/// Size of one signature offsets entry in the ed25519 precompile instruction.
+const ED25519_OFFSETS_LEN: usize = 14;
+
+/// Instruction index used by the ed25519 precompile to reference its own data.
+const ED25519_SELF_INDEX: u16 = u16::MAX;
+
+/// Returns the `(public key, message)` pairs verified by ed25519 precompile instructions
+/// in the current transaction.
+///
+/// The runtime verifies every ed25519 precompile instruction before the program runs,
+/// so each returned pair is backed by a valid signature. Only self-contained entries
+/// (public key, signature and message stored in the precompile instruction itself)
+/// are returned.
+///
+/// # Arguments
+///
+/// * `instructions_sysvar` - The instructions sysvar account
+///
+/// # Errors
+///
+/// * `InvalidEd25519Instruction` - If an ed25519 instruction cannot be parsed
+pub fn ed25519_verified_messages(
+    instructions_sysvar: &AccountInfo,
+) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+    let mut verified = Vec::new();
+    let mut index = 0;
+
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions_sysvar) {
+        index += 1;
+
+        if instruction.program_id != ED25519_PROGRAM_ID {
+            continue;
+        }
+
+        let data = &instruction.data;
+        require!(
+            data.len() >= ED25519_HEADER_LEN,
+            CustomError::InvalidEd25519Instruction
+        );
+
+        let read_u16 = |offset: usize| -> Result<u16> {
+            data.get(offset..offset + 2)
+                .map(|b| u16::from_le_bytes([b[0], b[1]]))
+                .ok_or_else(|| error!(CustomError::InvalidEd25519Instruction))
+        };
+
+        for i in 0..data[0] as usize {
+            let start = ED25519_HEADER_LEN + i * ED25519_OFFSETS_LEN;
+            let signature_ix = read_u16(start + 2)?;
+            let public_key_offset = read_u16(start + 4)? as usize;
+            let public_key_ix = read_u16(start + 6)?;
+            let message_offset = read_u16(start + 8)? as usize;
+            let message_size = read_u16(start + 10)? as usize;
+            let message_ix = read_u16(start + 12)?;
+
+            if signature_ix != ED25519_SELF_INDEX
+                || public_key_ix != ED25519_SELF_INDEX
+                || message_ix != ED25519_SELF_INDEX
+            {
+                continue;
+            }
+
+            let public_key = data
+                .get(public_key_offset..public_key_offset + 32)
+                .ok_or_else(|| error!(CustomError::InvalidEd25519Instruction))?;
+            let message = data
+                .get(message_offset..message_offset + message_size)
+                .ok_or_else(|| error!(CustomError::InvalidEd25519Instruction))?;
+
+            verified.push((Pubkey::try_from(public_key).unwrap(), message.to_vec()));
+        }
+    }
+
+    Ok(verified)
+}
+
+/// Builds the domain-separated message a new validator signs to prove possession of its key.
+///
+/// Format: `ADMISSION_MESSAGE_DOMAIN || validator_set || batch_id (LE) || validator`
+///
+/// # Arguments
+///
+/// * `validator_set` - The address of the validator set the key is admitted to
+/// * `batch_id` - The batch ID of the admitting operation (`last_id` for initialization)
+/// * `validator` - The key being admitted
+pub fn admission_message(validator_set: &Pubkey, batch_id: u64, validator: &Pubkey) -> Vec<u8> {
+    [
+        ADMISSION_MESSAGE_DOMAIN,
+        validator_set.as_ref(),
+        &batch_id.to_le_bytes(),
+        validator.as_ref(),
+    ]
+    .concat()
+}
+
+/// Builds the domain-separated approval attestation a validator signs for a batch.
+///
+/// Format: `APPROVAL_MESSAGE_DOMAIN || validator_set || batch_id (LE) || operation_hash`
+///
+/// A batch ID is consumed by exactly one operation, so a validator signing this message
+/// for the same batch ID with two different operation hashes has equivocated.
+///
+/// # Arguments
+///
+/// * `validator_set` - The address of the validator set
+/// * `batch_id` - The batch ID of the approved operation
+/// * `operation_hash` - The hash of the approved operation (the proposal hash for
+///   governance proposals, [`BridgingTransaction::operation_hash`] for bridging transactions)
+///
+/// [`BridgingTransaction::operation_hash`]: crate::BridgingTransaction::operation_hash
+pub fn approval_message(
+    validator_set: &Pubkey,
+    batch_id: u64,
+    operation_hash: &[u8; 32],
+) -> Vec<u8> {
+    [
+        APPROVAL_MESSAGE_DOMAIN,
+        validator_set.as_ref(),
+        &batch_id.to_le_bytes(),
+        operation_hash,
+    ]
+    .concat()
+}
+
+/// Returns the keys that attested an operation in the current transaction.
+///
+/// Validators approve bridging transactions and governance proposals by signing the
+/// [`approval_message`] of the operation in an ed25519 precompile instruction, rather than
+/// by signing the transaction. Every recorded approval is therefore a signed statement that
+/// `report_equivocation` can hold against the validator. The keys are returned in the order
+/// of the precompile entries, duplicates included, for [`collect_approvals`] to validate.
+///
+/// # Arguments
+///
+/// * `instructions_sysvar` - The instructions sysvar account
+/// * `validator_set` - The address of the validator set
+/// * `batch_id` - The batch ID of the approved operation
+/// * `operation_hash` - The hash of the approved operation
+///
+/// # Errors
+///
+/// * `InvalidEd25519Instruction` - If an ed25519 instruction cannot be parsed
+pub fn attested_approvals(
+    instructions_sysvar: &AccountInfo,
+    validator_set: &Pubkey,
+    batch_id: u64,
+    operation_hash: &[u8; 32],
+) -> Result<Vec<Pubkey>> {
+    let message = approval_message(validator_set, batch_id, operation_hash);
+
+    Ok(ed25519_verified_messages(instructions_sysvar)?
+        .into_iter()
+        .filter(|(_, msg)| *msg == message)
+        .map(|(pk, _)| pk)
+        .collect())
+}
+
+/// Returns the candidate keys that proved possession in the current transaction.
+///
+/// A candidate proves possession either by signing the transaction (being a signer in
+/// the remaining accounts), or through an ed25519 precompile instruction over its
+/// [`admission_message`].
+///
+/// # Arguments
+///
+/// * `candidates` - The keys that need to prove possession
+/// * `signers` - The signer keys of the current call (see [`signer_keys`])
+/// * `instructions_sysvar` - The instructions sysvar account
+/// * `validator_set` - The address of the validator set the keys are admitted to
+/// * `batch_id` - The batch ID of the admitting operation
+pub fn collect_admissions(
+    candidates: &[Pubkey],
+    signers: &[Pubkey],
+    instructions_sysvar: &AccountInfo,
+    validator_set: &Pubkey,
+    batch_id: u64,
+) -> Result<Vec<Pubkey>> {
+    let mut admitted: Vec<Pubkey> = candidates
+        .iter()
+        .filter(|pk| signers.contains(pk))
+        .copied()
+        .collect();
+
+    // Only inspect the precompile instructions when some candidate did not sign
+    if admitted.len() < candidates.len() {
+        let verified = ed25519_verified_messages(instructions_sysvar)?;
+        for candidate in candidates
+            .iter()
+            .filter(|pk| !admitted.contains(pk))
+            .copied()
+            .collect::<Vec<_>>()
+        {
+            let message = admission_message(validator_set, batch_id, &candidate);
+            if verified
+                .iter()
+                .any(|(pk, msg)| *pk == candidate && *msg == message)
+            {
+                admitted.push(candidate);
+            }
+        }
+    }
+
+    Ok(admitted)
+}
@@ -0,0 +1,144 @@
+//! Append validator delta instruction for assembling large validator set updates.
+//!
+//! This module contains the logic for appending a chunk of added and removed validators
+//! to a validator delta. A delta collects a validator set update too large for the
+//! arguments of a single instruction, until its proposer seals it.
+
+use crate::*;
+
+/// Account structure for the append_validator_delta instruction.
+///
+/// This struct defines the accounts required to create a validator delta or append to it.
+#[derive(Accounts)]
+#[instruction(instance_id: u64, batch_id: u64)]
+pub struct AppendValidatorDelta<'info> {
+    /// The validator assembling the delta, pays for the delta account
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    /// The validator set the update applies to
+    #[account(
+        seeds = [VALIDATOR_SET_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_set.bump,
+        constraint = validator_set.last_batch_id < batch_id @ CustomError::InvalidBatchId,
+        constraint = validator_set.signers.contains(&proposer.key()) @ CustomError::InvalidSigner,
+        constraint = validator_set.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_set: Account<'info, ValidatorSet>,
+
+    /// The validator delta to be created or appended to
+    /// It grows with every chunk, so an existing account is accepted at its current size
+    #[account(
+        init_if_needed,
+        payer = proposer,
+        space = (DISC as usize + ValidatorDelta::space(0, 0)).max(validator_delta.data_len()),
+        seeds = [
+            VALIDATOR_DELTA_SEED,
+            instance_id.to_le_bytes().as_ref(),
+            batch_id.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub validator_delta: Account<'info, ValidatorDelta>,
+
+    /// The instructions sysvar, inspected for ed25519 admission proofs
+    /// CHECK: address is checked against the instructions sysvar ID
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// The system program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AppendValidatorDelta<'info> {
+    /// Process the append_validator_delta instruction.
+    ///
+    /// The first call creates the delta and makes the signer its proposer; only the proposer
+    /// can append further chunks. Every added key must prove possession of its key in the
+    /// chunk that adds it, by signing (passed in remaining accounts) or with an ed25519
+    /// signature over its admission message for `batch_id`. The complete update is only
+    /// validated when the delta is sealed.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `batch_id` - The batch ID of the validator set update (must be greater than last_batch_id)
+    /// * `added` - Validator keys to add
+    /// * `removed` - Validator keys to remove
+    /// * `weights` - Voting weights of the added validators, in the order of `added`
+    ///   (empty to give every added validator `DEFAULT_VALIDATOR_WEIGHT`)
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
+    ///
+    /// # Errors
+    /// * `InvalidBatchId` - If the batch_id is not greater than the last_batch_id
+    /// * `InvalidSigner` - If the signer is not in the validator set or not the proposer of the delta
+    /// * `ValidatorDeltaSealed` - If the delta has already been sealed
+    /// * `MaxValidatorsExceeded` - If the delta would add or remove more than `MAX_VALIDATORS` validators
+    /// * `ValidatorWeightsMismatch` - If weights are given, but not one per added validator
+    /// * `InvalidValidatorWeight` - If a weight is zero or above `MAX_VALIDATOR_WEIGHT`
+    /// * `MissingAdmissionProof` - If an added key has not proven possession of its key
+    pub fn process_instruction(
+        ctx: Context<Self>,
+        _instance_id: u64,
+        batch_id: u64,
+        added: Vec<Pubkey>,
+        removed: Vec<Pubkey>,
+        weights: Vec<u64>,
+    ) -> Result<()> {
+        let validator_delta = &mut ctx.accounts.validator_delta;
+        let proposer = &ctx.accounts.proposer;
+
+        if validator_delta.proposer == Pubkey::default() {
+            validator_delta.version = ACCOUNT_VERSION;
+            validator_delta.proposer = proposer.key();
+            validator_delta.batch_id = batch_id;
+            validator_delta.bump = ctx.bumps.validator_delta;
+        } else {
+            require!(
+                validator_delta.version == ACCOUNT_VERSION,
+                CustomError::AccountNotMigrated
+            );
+            require_keys_eq!(
+                validator_delta.proposer,
+                proposer.key(),
+                CustomError::InvalidSigner
+            );
+        }
+        require!(!validator_delta.sealed, CustomError::ValidatorDeltaSealed);
+
+        require!(
+            validator_delta.added.len() + added.len() <= MAX_VALIDATORS as usize
+                && validator_delta.removed.len() + removed.len() <= MAX_VALIDATORS as usize,
+            CustomError::MaxValidatorsExceeded
+        );
+        let weights = resolve_weights(&weights, added.len())?;
+
+        // Every added key must prove it controls its key
+        let admitted = collect_admissions(
+            &added,
+            &signer_keys(ctx.remaining_accounts),
+            &ctx.accounts.instructions,
+            &ctx.accounts.validator_set.key(),
+            batch_id,
+        )?;
+        require!(
+            admitted.len() == added.len(),
+            CustomError::MissingAdmissionProof
+        );
+
+        validator_delta.added.extend(added);
+        validator_delta.removed.extend(removed);
+        validator_delta.weights.extend(weights);
+        grow_account(
+            &validator_delta.to_account_info(),
+            &proposer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            DISC as usize
+                + ValidatorDelta::space(validator_delta.added.len(), validator_delta.removed.len()),
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+//! Bond instruction for validators to put SOL at stake.
+//!
+//! This module contains the logic for a validator to bond lamports into its program-owned
+//! `ValidatorBond` account. The bond can be slashed if the validator is proven to have
+//! signed conflicting approvals (see `report_equivocation`).
+
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::*;
+
+/// Account structure for the bond instruction.
+///
+/// This struct defines the accounts required for a validator to bond lamports.
+#[derive(Accounts)]
+#[instruction(instance_id: u64)]
+pub struct Bond<'info> {
+    /// The validator bonding lamports, pays for the bond account creation
+    #[account(mut)]
+    pub validator: Signer<'info>,
+
+    /// The bond account of the validator
+    #[account(
+        init_if_needed,
+        payer = validator,
+        space = DISC as usize + ValidatorBond::INIT_SPACE,
+        seeds = [VALIDATOR_BOND_SEED, instance_id.to_le_bytes().as_ref(), validator.key().as_ref()],
+        bump
+    )]
+    pub validator_bond: Account<'info, ValidatorBond>,
+
+    /// The system program for account creation and the transfer
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Bond<'info> {
+    /// Process the bond instruction.
+    ///
+    /// Keys outside the validator set can bond too, so candidates can put up their bond
+    /// before they are admitted.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `amount` - The number of lamports to bond
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If the amount is zero
+    /// * `AccountNotMigrated` - If the existing bond account has not been migrated
+    pub fn process_instruction(ctx: Context<Self>, instance_id: u64, amount: u64) -> Result<()> {
+        require!(amount > 0, CustomError::InvalidAmount);
+        require!(
+            ctx.accounts.validator_bond.validator == Pubkey::default()
+                || ctx.accounts.validator_bond.version == ACCOUNT_VERSION,
+            CustomError::AccountNotMigrated
+        );
+
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.validator.to_account_info(),
+                    to: ctx.accounts.validator_bond.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let validator_bond = &mut ctx.accounts.validator_bond;
+        validator_bond.version = ACCOUNT_VERSION;
+        validator_bond.validator = ctx.accounts.validator.key();
+        validator_bond.bump = ctx.bumps.validator_bond;
+        validator_bond.amount += amount;
+
+        emit!(BondUpdatedEvent {
+            instance_id,
+            validator: validator_bond.validator,
+            amount: validator_bond.amount,
+            unbonding_amount: validator_bond.unbonding_amount,
+            unbonding_slot: validator_bond.unbonding_slot,
+        });
+
+        Ok(())
+    }
+}
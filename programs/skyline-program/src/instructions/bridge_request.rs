@@ -3,9 +3,11 @@
 //! This module contains the logic for creating bridging requests that initiate
 //! cross-chain token transfers. Users transfer tokens to the vault (or burn them
 //! if the vault is the mint authority) and emit a request event that validators
-//! can process to mint/transfer equivalent tokens on the destination chain.
+//! can process to mint/transfer equivalent tokens on the destination chain. The
+//! fees of the token on the destination chain are collected into the fee vault.
 
 use crate::*;
+use anchor_lang::system_program::{transfer, Transfer};
 use anchor_spl::{
     associated_token::{create, get_associated_token_address, AssociatedToken, Create},
     token::{self, transfer_checked, Burn, Mint, TokenAccount, TransferChecked},
@@ -15,8 +17,10 @@ use anchor_spl::{
 ///
 /// This struct defines the accounts required to create a bridging request.
 /// It includes the user's token account, the vault account, the vault's associated
-/// token account (conditionally created), and the token mint for the tokens being bridged.
+/// token account (conditionally created), the fee vault and its associated token account
+/// (conditionally created), and the token mint for the tokens being bridged.
 #[derive(Accounts)]
+#[instruction(instance_id: u64)]
 pub struct BridgeRequest<'info> {
     /// The user initiating the bridge request
     #[account(mut)]
@@ -25,11 +29,21 @@ pub struct BridgeRequest<'info> {
     /// The validator set account
     #[account(
         mut,
-        seeds = [VALIDATOR_SET_SEED],
-        bump = validator_set.bump
+        seeds = [VALIDATOR_SET_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_set.bump,
+        constraint = !validator_set.paused @ CustomError::BridgePaused,
+        constraint = validator_set.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
     )]
     pub validator_set: Account<'info, ValidatorSet>,
 
+    /// The registry of the destination chains
+    #[account(
+        seeds = [CHAIN_REGISTRY_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = chain_registry.bump,
+        constraint = chain_registry.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub chain_registry: Account<'info, ChainRegistry>,
+
     /// The user's associated token account for the tokens being bridged
     #[account(
         mut,
@@ -39,7 +53,12 @@ pub struct BridgeRequest<'info> {
     pub signers_ata: Account<'info, TokenAccount>,
 
     /// The vault account
-    #[account(mut, seeds = [VAULT_SEED], bump = vault.bump)]
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+        constraint = vault.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
     pub vault: Account<'info, Vault>,
 
     /// The vault associated token account for the tokens being bridged.
@@ -56,10 +75,41 @@ pub struct BridgeRequest<'info> {
     )]
     pub vault_ata: UncheckedAccount<'info>,
 
+    /// The fee vault collecting the bridge fees, created by the first bridge request
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISC as usize + FeeVault::INIT_SPACE,
+        seeds = [FEE_VAULT_SEED, instance_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    /// The fee vault associated token account for the tokens being bridged.
+    /// Only created if a fee is paid in the bridged token.
+    /// CHECK: Address is validated via constraint to be the canonical ATA for (fee_vault, mint).
+    #[account(
+        mut,
+        constraint = fee_vault_ata.key() == get_associated_token_address(
+            &fee_vault.key(),
+            &mint.key()
+        ) @ CustomError::InvalidVault
+    )]
+    pub fee_vault_ata: UncheckedAccount<'info>,
+
     /// The token mint for the tokens being bridged
     #[account(mut)]
     pub mint: Account<'info, Mint>,
 
+    /// The registry entry of the token being bridged
+    /// CHECK: The address is validated by the seeds, the account is loaded with
+    /// `TokenConfig::load` so an unregistered token fails with `TokenNotRegistered`
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, instance_id.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub token_config: UncheckedAccount<'info>,
+
     /// The token program for token operations (burn/transfer)
     pub token_program: Program<'info, anchor_spl::token::Token>,
 
@@ -78,8 +128,15 @@ impl<'info> BridgeRequest<'info> {
     /// token account. It then emits a bridge request event that validators can process
     /// to mint/transfer equivalent tokens on the destination chain.
     ///
+    /// The fees of the token on the destination chain are taken on top: the fee in the
+    /// bridged token is transferred to the fee vault's associated token account and deducted
+    /// from the bridged amount, the fee in SOL is transferred to the fee vault. The net
+    /// amount is converted to the decimals of the token on the destination chain. Dust below
+    /// that precision is not taken from the user.
+    ///
     /// # Arguments
     /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
     /// * `amount` - The amount of tokens to bridge to the destination chain
     /// * `receiver` - The receiver's address on the destination chain (variable length byte vector)
     /// * `destination_chain` - The chain ID of the destination blockchain
@@ -89,20 +146,42 @@ impl<'info> BridgeRequest<'info> {
     ///
     /// # Errors
     /// * `InvalidAmount` - If amount is zero
+    /// * `BridgePaused` - If the guardian council has paused the bridge
+    /// * `ChainNotRegistered` - If the destination chain is not in the chain registry
+    /// * `ChainDisabled` - If the destination chain is disabled
+    /// * `AmountOutOfRange` - If the amount is outside the bounds of the destination chain
+    /// * `InvalidReceiverAddress` - If the receiver is not a valid address on the destination
+    ///   chain
+    /// * `TokenNotRegistered` - If the mint is not in the token registry
+    /// * `TokenDisabled` - If the token is disabled
+    /// * `TokenChainNotAllowed` - If the token cannot be bridged to the destination chain
+    /// * `TokenModeMismatch` - If the registered mode does not match the mint authority
+    /// * `FeeExceedsAmount` - If the fee in the bridged token is not below the amount
+    /// * `AmountTooSmall` - If the amount is zero once converted to the foreign decimals
+    /// * `AmountOverflow` - If the converted amount overflows
     /// * `InsufficientFunds` - If the user doesn't have enough tokens to bridge
-    /// * `InvalidVault` - If vault_ata address doesn't match the canonical ATA for (vault, mint)
+    /// * `InvalidVault` - If vault_ata or fee_vault_ata doesn't match the canonical ATA for
+    ///   (vault, mint) or (fee_vault, mint)
     ///
     /// # Process Flow
     /// 1. Validates that the amount is greater than zero
-    /// 2. Validates that the user has sufficient token balance
-    /// 3. If vault is mint authority: burns tokens from user's account
-    /// 4. If vault is not mint authority:
+    /// 2. Validates that the destination chain is registered and enabled, that the amount is
+    ///    within its bounds and that the receiver is an address on it
+    /// 3. Validates that the token is registered and enabled, can be bridged to the
+    ///    destination chain and is registered in the mode matching its mint authority
+    /// 4. Deducts the fee in the bridged token from the amount
+    /// 5. Converts the net amount to the foreign decimals, leaving the dust with the user
+    /// 6. Validates that the user has sufficient token balance
+    /// 7. If vault is mint authority: burns the net amount from user's account
+    /// 8. If vault is not mint authority:
     ///    a. Creates vault's ATA if it doesn't exist (manual creation)
-    ///    b. Transfers tokens to vault's ATA
-    /// 5. Emits a bridge request event with transfer details
-    /// 6. Increments the bridge request count
+    ///    b. Transfers the net amount to vault's ATA
+    /// 9. Transfers the fees to the fee vault, creating its ATA if it doesn't exist
+    /// 10. Emits a bridge request event with transfer details
+    /// 11. Increments the bridge request count
     pub fn process_instruction(
         ctx: Context<BridgeRequest>,
+        instance_id: u64,
         amount: u64,
         receiver: Vec<u8>,
         destination_chain: u8,
@@ -115,14 +194,30 @@ impl<'info> BridgeRequest<'info> {
         let vault_ata = &ctx.accounts.vault_ata;
         let validator_set = &mut ctx.accounts.validator_set;
 
-        // Validate amount
-        require!(amount > 0, CustomError::InvalidAmount);
+        // Validate the request and compute its fees and amounts
+        let BridgeQuote {
+            amount,
+            fee,
+            sol_fee,
+            net_amount,
+            normalized_amount,
+            ..
+        } = BridgeQuote::compute(
+            &ctx.accounts.chain_registry,
+            &ctx.accounts.token_config,
+            mint,
+            &vault.to_account_info(),
+            amount,
+            &receiver,
+            destination_chain,
+        )?;
+        let burn = is_vault_mint_authority(mint, &vault.to_account_info());
 
         // Validate that the user has sufficient tokens to bridge
         require!(from.amount >= amount, CustomError::InsufficientFunds);
 
         // Determine whether to burn or transfer based on vault's mint authority
-        if is_vault_mint_authority(mint, &vault.to_account_info()) {
+        if burn {
             // Burn branch: vault is mint authority
             // Tokens are burned from user's account, no vault ATA needed
             let cpi_accounts = Burn {
@@ -132,7 +227,7 @@ impl<'info> BridgeRequest<'info> {
             };
 
             let cpi_context = CpiContext::new(token_program.to_account_info(), cpi_accounts);
-            token::burn(cpi_context, amount)?;
+            token::burn(cpi_context, net_amount)?;
         } else {
             // Transfer branch: vault is not mint authority
             // Create vault ATA if it doesn't exist, then transfer tokens
@@ -162,17 +257,73 @@ impl<'info> BridgeRequest<'info> {
             };
 
             let cpi_context = CpiContext::new(token_program.to_account_info(), cpi_accounts);
-            transfer_checked(cpi_context, amount, mint.decimals)?;
+            transfer_checked(cpi_context, net_amount, mint.decimals)?;
+        }
+
+        // Collect the fees into the fee vault
+        let fee_vault = &mut ctx.accounts.fee_vault;
+        if fee_vault.version == 0 {
+            fee_vault.version = ACCOUNT_VERSION;
+            fee_vault.bump = ctx.bumps.fee_vault;
+        }
+        require!(
+            fee_vault.version == ACCOUNT_VERSION,
+            CustomError::AccountNotMigrated
+        );
+
+        if fee > 0 {
+            let fee_vault_ata = &ctx.accounts.fee_vault_ata;
+            if fee_vault_ata.data_is_empty() {
+                create(CpiContext::new(
+                    ctx.accounts.associated_token_program.to_account_info(),
+                    Create {
+                        payer: signer.to_account_info(),
+                        associated_token: fee_vault_ata.to_account_info(),
+                        authority: fee_vault.to_account_info(),
+                        mint: mint.to_account_info(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                        token_program: token_program.to_account_info(),
+                    },
+                ))?;
+            }
+
+            let cpi_accounts = TransferChecked {
+                from: from.to_account_info(),
+                to: fee_vault_ata.to_account_info(),
+                authority: signer.to_account_info(),
+                mint: mint.to_account_info(),
+            };
+
+            let cpi_context = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+            transfer_checked(cpi_context, fee, mint.decimals)?;
+        }
+
+        if sol_fee > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: signer.to_account_info(),
+                        to: fee_vault.to_account_info(),
+                    },
+                ),
+                sol_fee,
+            )?;
         }
 
         // Emit bridge request event for validators to process
         emit!(BridgeRequestEvent {
+            instance_id,
             sender: signer.key(),
             amount,
             receiver,
             destination_chain,
             mint_token: mint.key(),
             batch_request_id: validator_set.bridge_request_count,
+            normalized_amount,
+            fee,
+            net_amount,
+            sol_fee,
         });
 
         // Increment the bridge request count
@@ -16,7 +16,7 @@ use crate::*;
 /// This struct defines the accounts required to transfer tokens to a recipient.
 /// It includes the validator set for consensus validation and token accounts for minting/transferring.
 #[derive(Accounts)]
-#[instruction(amount: u64, batch_id: u64)]
+#[instruction(instance_id: u64, amount: u128, batch_id: u64)]
 pub struct BridgeTransaction<'info> {
     /// The payer for any associated token account creation
     #[account(mut)]
@@ -25,22 +25,39 @@ pub struct BridgeTransaction<'info> {
     /// The validator set account for consensus validation
     #[account(
         mut,
-        seeds = [VALIDATOR_SET_SEED],
+        seeds = [VALIDATOR_SET_SEED, instance_id.to_le_bytes().as_ref()],
         bump = validator_set.bump,
         constraint = validator_set.last_batch_id < batch_id @CustomError::InvalidBatchId,
+        constraint = !validator_set.paused @ CustomError::BridgePaused,
+        constraint = validator_set.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
     )]
     pub validator_set: Account<'info, ValidatorSet>,
 
     /// The bridging transaction account to be created
+    /// It grows with the recorded approvals, so an existing account is accepted at its current size
     #[account(
         init_if_needed,
         payer = payer,
-        space = DISC as usize + BridgingTransaction::INIT_SPACE,
-        seeds = [BRIDGING_TRANSACTION_SEED, batch_id.to_le_bytes().as_ref()],
+        space = (DISC as usize + BridgingTransaction::space(0)).max(bridging_transaction.data_len()),
+        seeds = [
+            BRIDGING_TRANSACTION_SEED,
+            instance_id.to_le_bytes().as_ref(),
+            batch_id.to_le_bytes().as_ref(),
+        ],
         bump
     )]
     pub bridging_transaction: Account<'info, BridgingTransaction>,
 
+    /// The validator participation statistics
+    #[account(
+        mut,
+        seeds = [VALIDATOR_STATS_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_stats.bump,
+        constraint = validator_stats.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_stats: Account<'info, ValidatorStats>,
+
+    /// The mint of the bridged tokens
     #[account(mut)]
     pub mint_token: Account<'info, Mint>,
 
@@ -62,7 +79,12 @@ pub struct BridgeTransaction<'info> {
     pub recipient_ata: UncheckedAccount<'info>,
 
     /// The vault account
-    #[account(mut, seeds = [VAULT_SEED], bump = vault.bump)]
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+        constraint = vault.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
     pub vault: Account<'info, Vault>,
 
     /// The vault associated token account for the mint
@@ -77,6 +99,34 @@ pub struct BridgeTransaction<'info> {
 )]
     pub vault_ata: UncheckedAccount<'info>,
 
+    /// The registry entry of the token being bridged
+    /// CHECK: The address is validated by the seeds, the account is loaded with
+    /// `TokenConfig::load` so an unregistered token fails with `TokenNotRegistered`
+    #[account(
+        seeds = [
+            TOKEN_CONFIG_SEED,
+            instance_id.to_le_bytes().as_ref(),
+            mint_token.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub token_config: UncheckedAccount<'info>,
+
+    /// The fee vault paying the rewards of the executed transaction
+    /// CHECK: The address is validated by the seeds, the account is loaded with
+    /// `FeeVault::load` if it exists; no rewards are credited before it is created
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, instance_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub fee_vault: UncheckedAccount<'info>,
+
+    /// The instructions sysvar, inspected for the ed25519 approval attestations
+    /// CHECK: address is checked against the instructions sysvar ID
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
     /// The token program for minting operations
     pub token_program: Program<'info, Token>,
 
@@ -92,38 +142,78 @@ impl<'info> BridgeTransaction<'info> {
     ///
     /// This function creates or approves a bridging transaction for transferring tokens
     /// to a recipient. The first call creates the transaction with the specified details,
-    /// and subsequent calls from validators approve it. Once the consensus threshold is met,
-    /// the tokens are automatically minted (if vault is mint authority) or transferred
-    /// from the vault to the recipient's associated token account, and the transaction
-    /// account is closed.
+    /// and every call records the approvals it carries. Validators approve by signing the
+    /// approval attestation of the transaction (see [`attested_approvals`] and
+    /// [`BridgingTransaction::operation_hash`]) in ed25519 precompile instructions of the
+    /// same transaction, so anyone can relay their approvals. Once the summed weight of the
+    /// approving validators meets the consensus threshold weight, the tokens are
+    /// automatically minted (if vault is mint authority) or transferred from the vault to
+    /// the recipient's associated token account, and the transaction account is closed.
+    ///
+    /// The amount is given in the decimals of the token on the source chain and converted
+    /// to the decimals of the mint, truncating any dust. The source chain, the source amount
+    /// and the converted amount are stored and approvals are compared against them. The dust
+    /// is not delivered, it stays locked on the source chain and is reported in the
+    /// `TransactionExecutedEvent`.
+    ///
+    /// Once executed, the payer and the approving validators are credited with the rewards
+    /// configured in the fee vault (see [`FeeVault::credit_rewards`]). Their reward accounts
+    /// are passed among the remaining accounts; the approving validators' accounts are
+    /// required, the relayer forfeits its reward if its own is missing.
     ///
     /// # Arguments
     /// * `ctx` - The instruction context containing all required accounts
-    /// * `amount` - The amount of tokens to transfer to the recipient
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `amount` - The amount of tokens to transfer to the recipient, in the smallest unit
+    ///   of the token on the source chain
     /// * `batch_id` - The batch ID of the transaction (must be greater than last_batch_id)
+    /// * `source_chain` - The chain ID of the chain the tokens are bridged from
     ///
     /// # Returns
     /// * `Result<()>` - Returns Ok(()) on success or an error on failure
     ///
     /// # Errors
     /// * `InvalidBatchId` - If the batch_id is not greater than the last_batch_id
+    /// * `BridgePaused` - If the guardian council has paused the bridge
+    /// * `TokenNotRegistered` - If the mint is not in the token registry
+    /// * `TokenDisabled` - If the token is disabled
+    /// * `TokenChainNotAllowed` - If the token cannot be bridged from the source chain
+    /// * `TokenModeMismatch` - If the registered mode does not match the mint authority
+    /// * `AmountTooSmall` - If the amount is zero once converted to the decimals of the mint
+    /// * `AmountOverflow` - If the converted amount does not fit in a `u64`
     /// * `BridgingTransactionMismatch` - If transaction details don't match on subsequent approvals
-    /// * `NoSignersProvided` - If no validator signers are provided
-    /// * `DuplicateSignersProvided` - If duplicate signers are provided
-    /// * `InvalidSigner` - If a signer is not in the validator set
-    /// * `SignerAlreadyApproved` - If a signer has already approved this transaction
+    /// * `NoSignersProvided` - If no approval attestation is provided
+    /// * `DuplicateSignersProvided` - If a validator attests twice
+    /// * `InvalidSigner` - If an attesting key is not in the validator set
+    /// * `ValidatorSuspended` - If an attesting validator is suspended
+    /// * `SignerAlreadyApproved` - If a validator has already approved this transaction
+    /// * `MissingActionAccount` - If the reward account of an approving validator is not passed
     ///
     /// # Process Flow
-    /// 1. Creates the transaction account if it doesn't exist, or validates details match
-    /// 2. Validates and collects validator signers from remaining accounts
-    /// 3. Checks for duplicate signers and ensures all are valid validators
-    /// 4. Adds signers to the approval list
-    /// 5. If threshold is met, creates recipient ATA if needed and transfers/mints tokens
-    /// 6. Updates last_batch_id and closes the transaction account
-    pub fn process_instruction(ctx: Context<Self>, amount: u64, batch_id: u64) -> Result<()> {
+    /// 1. Validates that the token is registered and enabled, can be bridged from the source
+    ///    chain and is registered in the mode matching its mint authority
+    /// 2. Converts the amount to the decimals of the mint
+    /// 3. Creates the transaction account if it doesn't exist, or validates details match
+    ///    (source chain, source amount, converted amount, recipient and mint)
+    /// 4. Collects the validators whose approval attestations are verified in the transaction
+    /// 5. Checks for duplicate approvals and ensures all are valid validators
+    /// 6. Adds the approvals to the approval list (growing the account) and records them in the
+    ///    validator statistics
+    /// 7. If the threshold weight is met, charges a missed batch to active validators that did not approve,
+    ///    creates recipient ATA if needed and transfers/mints tokens
+    /// 8. Credits the rewards of the payer and the approving validators
+    /// 9. Updates last_batch_id and closes the transaction account
+    pub fn process_instruction(
+        ctx: Context<'_, '_, '_, 'info, Self>,
+        instance_id: u64,
+        amount: u128,
+        batch_id: u64,
+        source_chain: u8,
+    ) -> Result<()> {
         let bridging_transaction = &mut ctx.accounts.bridging_transaction;
         let payer = &ctx.accounts.payer;
         let validator_set = &mut ctx.accounts.validator_set;
+        let validator_stats = &mut ctx.accounts.validator_stats;
         let recipient = &ctx.accounts.recipient;
         let recipient_ata = &ctx.accounts.recipient_ata;
         let vault = &ctx.accounts.vault;
@@ -135,59 +225,93 @@ impl<'info> BridgeTransaction<'info> {
         // Validate amount
         require!(amount > 0, CustomError::InvalidAmount);
 
+        // Validators attest to the transaction as observed on the source chain
+        let operation_hash = BridgingTransaction::operation_hash(
+            amount,
+            source_chain,
+            &recipient.key(),
+            &mint.key(),
+        );
+
+        // Validate the token
+        let mint_branch = is_vault_mint_authority(mint, &vault.to_account_info());
+        let token_config = TokenConfig::load(&ctx.accounts.token_config)?;
+        let token_chain = token_config.check(source_chain, mint_branch)?;
+
+        // Convert the amount to the decimals of the mint
+        let source_amount = amount;
+        let (amount, dust) = convert_decimals(source_amount, token_chain.decimals, mint.decimals)
+            .ok_or(CustomError::AmountOverflow)?;
+        let amount = u64::try_from(amount).map_err(|_| CustomError::AmountOverflow)?;
+        require!(amount > 0, CustomError::AmountTooSmall);
+
         // Store the transaction details
         if bridging_transaction.id == Pubkey::default() {
+            bridging_transaction.version = ACCOUNT_VERSION;
             bridging_transaction.id = bridging_transaction.key();
             bridging_transaction.amount = amount;
             bridging_transaction.receiver = recipient.key();
             bridging_transaction.mint_token = mint.key();
             bridging_transaction.batch_id = batch_id;
             bridging_transaction.bump = ctx.bumps.bridging_transaction;
+            bridging_transaction.source_chain = source_chain;
+            bridging_transaction.source_amount = source_amount;
         } else {
+            require!(
+                bridging_transaction.version == ACCOUNT_VERSION,
+                CustomError::AccountNotMigrated
+            );
+            // Transactions migrated from version 1 did not record the source details. The
+            // version 1 approvals did not attest to them, so they no longer count.
+            if bridging_transaction.source_amount == 0 {
+                bridging_transaction.source_chain = source_chain;
+                bridging_transaction.source_amount = source_amount;
+                bridging_transaction.signers.clear();
+            }
             require!(
                 bridging_transaction.amount == amount
+                    && bridging_transaction.source_chain == source_chain
+                    && bridging_transaction.source_amount == source_amount
                     && bridging_transaction.receiver == recipient.key()
                     && bridging_transaction.mint_token == mint.key(),
                 CustomError::BridgingTransactionMismatch
             );
         }
 
-        let signers = ctx
-            .remaining_accounts
-            .iter()
-            .filter(|acc| acc.is_signer)
-            .map(|acc| acc.key())
-            .collect::<Vec<Pubkey>>();
-
-        require!(!signers.is_empty(), CustomError::NoSignersProvided);
-
-        let mut signers_copy = signers.clone();
-        signers_copy.sort();
-        signers_copy.dedup();
-
-        require!(
-            signers.len() == signers_copy.len(),
-            CustomError::DuplicateSignersProvided
-        );
-
-        require!(
-            signers.iter().all(|k| validator_set.signers.contains(k)),
-            CustomError::InvalidSigner
-        );
-
-        require!(
-            !signers
-                .iter()
-                .any(|s| bridging_transaction.signers.contains(s)),
-            CustomError::SignerAlreadyApproved
-        );
+        let signers = collect_approvals(
+            &attested_approvals(
+                &ctx.accounts.instructions,
+                &validator_set.key(),
+                batch_id,
+                &operation_hash,
+            )?,
+            validator_set,
+            &bridging_transaction.signers,
+        )?;
 
         bridging_transaction.signers.extend(signers.iter());
-
-        if (bridging_transaction.signers.len() as u8) < validator_set.threshold {
+        grow_account(
+            &bridging_transaction.to_account_info(),
+            &payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            DISC as usize + BridgingTransaction::space(bridging_transaction.signers.len()),
+        )?;
+
+        ValidatorStats::ensure_space(
+            validator_stats,
+            validator_set,
+            &payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        validator_stats.record_approvals(validator_set, &signers, Clock::get()?.slot);
+
+        if !has_quorum(validator_set, &bridging_transaction.signers) {
             return Ok(());
         }
 
+        validator_stats.record_missed(validator_set, &bridging_transaction.signers);
+        validator_set.last_quorum_slot = Clock::get()?.slot;
+
         // Create recipient ATA if it doesn't exist (only after threshold met)
         if recipient_ata.data_is_empty() {
             let cpi_context = CpiContext::new(
@@ -204,10 +328,11 @@ impl<'info> BridgeTransaction<'info> {
             associated_token::create(cpi_context)?;
         }
 
-        let seeds = &[VAULT_SEED, &[vault.bump]];
+        let instance_seed = instance_id.to_le_bytes();
+        let seeds = &[VAULT_SEED, &instance_seed, &[vault.bump]];
         let signer_seeds = &[&seeds[..]];
 
-        if is_vault_mint_authority(mint, &vault.to_account_info()) {
+        if mint_branch {
             let cpi_accounts = token::MintTo {
                 mint: mint.to_account_info(),
                 to: recipient_ata.to_account_info(),
@@ -243,9 +368,31 @@ impl<'info> BridgeTransaction<'info> {
             )?;
         }
 
+        // Credit the relayer and the approving validators
+        let validators: Vec<Pubkey> =
+            active_approvals(validator_set, &bridging_transaction.signers)
+                .map(|(key, _)| *key)
+                .collect();
+        FeeVault::credit_rewards(
+            &ctx.accounts.fee_vault.to_account_info(),
+            &ActionAccounts {
+                instance_id,
+                payer: &payer.to_account_info(),
+                system_program: &ctx.accounts.system_program.to_account_info(),
+                remaining_accounts: ctx.remaining_accounts,
+            },
+            payer.key,
+            &validators,
+            bridging_transaction.batch_id,
+        )?;
+
         emit!(TransactionExecutedEvent {
+            instance_id,
             transaction_id: bridging_transaction.id,
             batch_id: bridging_transaction.batch_id,
+            approvals: count_approvals(validator_set, &bridging_transaction.signers) as u32,
+            approved_weight: approved_weight(validator_set, &bridging_transaction.signers),
+            dust,
         });
 
         validator_set.last_batch_id = bridging_transaction.batch_id;
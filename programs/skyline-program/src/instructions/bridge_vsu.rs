@@ -4,8 +4,6 @@
 //! bridge operations. This instruction requires consensus from the current validator
 //! set and maintains the same validation rules as initialization.
 
-use blake3;
-
 use crate::*;
 
 /// Account structure for the validator_set_change instruction.
@@ -13,7 +11,7 @@ use crate::*;
 /// This struct defines the accounts required to update the validator set.
 /// It includes validation constraints to ensure the new validator set meets security requirements.
 #[derive(Accounts)]
-#[instruction(added: Vec<Pubkey>, removed: Vec<Pubkey>, batch_id: u64)]
+#[instruction(instance_id: u64, added: Vec<Pubkey>, removed: Vec<Pubkey>, batch_id: u64)]
 pub struct BridgeVSU<'info> {
     /// The payer for any associated token account creation
     #[account(mut)]
@@ -22,174 +20,95 @@ pub struct BridgeVSU<'info> {
     /// The validator set account to be updated
     #[account(
         mut,
-        seeds = [VALIDATOR_SET_SEED],
+        seeds = [VALIDATOR_SET_SEED, instance_id.to_le_bytes().as_ref()],
         bump = validator_set.bump,
         constraint = validator_set.last_batch_id < batch_id @ CustomError::InvalidBatchId,
+        constraint = validator_set.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
     )]
     pub validator_set: Account<'info, ValidatorSet>,
 
-    /// The validator set change account to be created
+    /// The governance proposal account carrying the validator set change
+    /// It grows with the recorded approvals, so an existing account is accepted at its current size
     #[account(
         init_if_needed,
         payer = payer,
-        space = DISC as usize + ValidatorDelta::INIT_SPACE,
-        seeds = [VALIDATOR_SET_CHANGE_SEED, batch_id.to_le_bytes().as_ref()],
+        space = (DISC as usize + GovernanceProposal::space(0, 0)).max(validator_set_change.data_len()),
+        seeds = [
+            GOVERNANCE_PROPOSAL_SEED,
+            instance_id.to_le_bytes().as_ref(),
+            batch_id.to_le_bytes().as_ref(),
+        ],
         bump
     )]
-    pub validator_set_change: Account<'info, ValidatorDelta>,
+    pub validator_set_change: Account<'info, GovernanceProposal>,
+
+    /// The validator participation statistics
+    #[account(
+        mut,
+        seeds = [VALIDATOR_STATS_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_stats.bump,
+        constraint = validator_stats.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_stats: Account<'info, ValidatorStats>,
+
+    /// The instructions sysvar, inspected for ed25519 admission proofs and approval
+    /// attestations
+    /// CHECK: address is checked against the instructions sysvar ID
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
 
     /// The system program for account creation
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> BridgeVSU<'info> {
-    fn concat_pubkeys(pubkeys: &Vec<Pubkey>) -> Vec<u8> {
-        let mut bytes = Vec::with_capacity(pubkeys.len() * 32);
-        for pk in pubkeys {
-            bytes.extend_from_slice(pk.as_ref());
-        }
-
-        bytes
-    }
-
+    /// Process the bridge_vsu instruction.
+    ///
+    /// This is a convenience entry point for proposing an `UpdateValidators` governance
+    /// action. It shares the proposal account, hashing and approval accumulation with
+    /// `submit_proposal`, so approvals can be submitted through either instruction.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `added` - Vector of new validator public keys to add
+    /// * `removed` - Vector of validator public keys to remove
+    /// * `batch_id` - The batch ID of the validator set change (must be greater than last_batch_id)
+    /// * `foreign_keys` - Foreign-chain keys of the added validators, in the order of `added`
+    ///   (empty if the validators set them later themselves)
+    /// * `weights` - Voting weights of the added validators, in the order of `added`
+    ///   (empty to give every added validator `DEFAULT_VALIDATOR_WEIGHT`)
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
     pub fn process_instruction(
-        ctx: Context<Self>,
+        ctx: Context<'_, '_, '_, 'info, Self>,
+        instance_id: u64,
         added: Vec<Pubkey>,
         removed: Vec<Pubkey>,
         batch_id: u64,
+        foreign_keys: Vec<ForeignKeys>,
+        weights: Vec<u64>,
     ) -> Result<()> {
-        let validator_set = &mut ctx.accounts.validator_set;
-        let validator_set_change: &mut Account<'info, ValidatorDelta> =
-            &mut ctx.accounts.validator_set_change;
-        let payer = &ctx.accounts.payer;
-
-
-        // Validate no duplicates in added list
-        if !added.is_empty() {
-            let mut added_sorted = added.clone();
-            added_sorted.sort();
-            added_sorted.dedup();
-            require!(
-                added.len() == added_sorted.len(),
-                CustomError::DuplicateValidatorsInAdded
-            );
-        }
-        // Validate no duplicates in removed
-        if !removed.is_empty() {
-            let mut removed_sorted = removed.clone();
-            removed_sorted.sort();
-            removed_sorted.dedup();
-            require!(
-                removed.len() == removed_sorted.len(),
-                CustomError::DuplicateValidatorsInRemoved
-            );
-        }
-
-        let proposal_hash =
-            blake3::hash(&[Self::concat_pubkeys(&added), Self::concat_pubkeys(&removed)].concat());
-
-        if validator_set_change.id == Pubkey::default() {
-            let signers_len = validator_set.signers.len();
-            require!(
-                !added.iter().any(|pk| removed.contains(pk)),
-                CustomError::AddingAndRemovingSameSigner
-            );
-            // Validate that no added validator is already in the validator set
-            require!(
-                !added.iter().any(|pk| validator_set.signers.contains(pk)),
-                CustomError::AddingExistingSigner
-            );
-            // Validate removed validators actually exist
-            require!(
-                removed.iter().all(|pk| validator_set.signers.contains(pk)),
-                CustomError::RemovingNonExistentSigner
-            );
-            // Validate we won't underflow when calculating new validator count
-            require!(
-                removed.len() <= signers_len + added.len(),
-                CustomError::TooManyValidatorsRemoved
-            );
-
-            let new_signers_len = signers_len + added.len() - removed.len();
-
-            require!(
-                new_signers_len <= MAX_VALIDATORS as usize,
-                CustomError::MaxValidatorsExceeded
-            );
-            require!(
-                new_signers_len >= MIN_VALIDATORS as usize,
-                CustomError::MinValidatorsNotMet
-            );
-
-            validator_set_change.id = validator_set_change.key();
-            validator_set_change.proposal_hash = *proposal_hash.as_bytes();
-            validator_set_change.added = added;
-            validator_set_change.removed = removed;
-            validator_set_change.batch_id = batch_id;
-            validator_set_change.bump = ctx.bumps.validator_set_change;
-        } else {
-            require!(
-                validator_set_change.proposal_hash == *proposal_hash.as_bytes(),
-                CustomError::InvalidProposalHash
-            );
-        }
-
-        // Collect all signers from remaining accounts
-        let signers = ctx
-            .remaining_accounts
-            .iter()
-            .filter(|acc| acc.is_signer)
-            .map(|acc| acc.key())
-            .collect::<Vec<Pubkey>>();
-        // Validate no duplicate signers in current call
-        let mut signers_copy = signers.clone();
-        signers_copy.sort();
-        signers_copy.dedup();
-        require!(
-            signers.len() == signers_copy.len(),
-            CustomError::DuplicateSignersProvided
-        );
-        // Validate all signers are valid validators
-        require!(
-            signers.iter().all(|k| validator_set.signers.contains(k)),
-            CustomError::InvalidSigner
-        );
-        // Validate signers haven't already approved
-        require!(
-            signers
-                .iter()
-                .all(|s| !validator_set_change.signers.contains(s)),
-            CustomError::SignerAlreadyApproved
-        );
-        require!(!signers.is_empty(), CustomError::NoSignersProvided);
-
-        validator_set_change.signers.extend(signers.iter());
-        // Check if threshold is met
-        if (validator_set_change.signers.len() as u8) < validator_set.threshold {
-            return Ok(());
-        }
-
-        // Safe removal using retain (no index issues, no panics)
-        validator_set
-            .signers
-            .retain(|pk| !validator_set_change.removed.contains(pk));
-
-        // Add new validators
-        validator_set
-            .signers
-            .extend(validator_set_change.added.iter());
-        // Recalculate threshold
-        validator_set.threshold = helpers::calculate_threshold(validator_set.signers.len());
-
-        emit!(ValidatorSetUpdatedEvent {
-            new_signers: validator_set.signers.clone(),
-            new_threshold: validator_set.threshold,
+        process_proposal(
+            &mut ctx.accounts.validator_set_change,
+            ctx.bumps.validator_set_change,
+            &mut ctx.accounts.validator_set,
+            &mut ctx.accounts.validator_stats,
+            &ctx.accounts.instructions.to_account_info(),
+            GovernanceAction::UpdateValidators {
+                added,
+                removed,
+                foreign_keys,
+                weights,
+            },
             batch_id,
-        });
-
-        validator_set.last_batch_id = batch_id;
-        validator_set_change.close(payer.to_account_info())?;
-
-        Ok(())
+            &ActionAccounts {
+                instance_id,
+                payer: &ctx.accounts.payer.to_account_info(),
+                system_program: &ctx.accounts.system_program.to_account_info(),
+                remaining_accounts: ctx.remaining_accounts,
+            },
+        )
     }
 }
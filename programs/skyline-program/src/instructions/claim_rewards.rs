@@ -0,0 +1,73 @@
+//! Claim rewards instruction for relayers and validators.
+//!
+//! This module contains the logic for a participant to claim the rewards credited to it by
+//! executed bridge transactions, paid from the fee vault.
+
+use crate::*;
+
+/// Account structure for the claim_rewards instruction.
+///
+/// This struct defines the accounts required for a relayer or validator to claim its rewards.
+#[derive(Accounts)]
+#[instruction(instance_id: u64)]
+pub struct ClaimRewards<'info> {
+    /// The relayer or validator owning the rewards, receives the lamports
+    #[account(mut)]
+    pub participant: Signer<'info>,
+
+    /// The reward account of the participant
+    #[account(
+        mut,
+        seeds = [REWARD_ACCOUNT_SEED, instance_id.to_le_bytes().as_ref(), participant.key().as_ref()],
+        bump = reward_account.bump,
+        constraint = reward_account.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub reward_account: Account<'info, RewardAccount>,
+
+    /// The fee vault paying the rewards
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = fee_vault.bump,
+        constraint = fee_vault.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+}
+
+impl<'info> ClaimRewards<'info> {
+    /// Process the claim_rewards instruction.
+    ///
+    /// Transfers all accrued lamports from the fee vault to the participant and releases
+    /// them from the rewards reserved in the fee vault.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
+    ///
+    /// # Errors
+    /// * `NothingToClaim` - If no rewards are accrued
+    pub fn process_instruction(ctx: Context<Self>, instance_id: u64) -> Result<()> {
+        let reward_account = &mut ctx.accounts.reward_account;
+        let fee_vault = &mut ctx.accounts.fee_vault;
+        let amount = reward_account.accrued;
+
+        require!(amount > 0, CustomError::NothingToClaim);
+
+        reward_account.accrued = 0;
+        reward_account.claimed += amount;
+        fee_vault.reserved_rewards = fee_vault.reserved_rewards.saturating_sub(amount);
+        fee_vault.sub_lamports(amount)?;
+        ctx.accounts.participant.add_lamports(amount)?;
+
+        emit!(RewardsClaimedEvent {
+            instance_id,
+            participant: reward_account.participant,
+            amount,
+        });
+
+        Ok(())
+    }
+}
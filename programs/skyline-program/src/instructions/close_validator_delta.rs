@@ -0,0 +1,67 @@
+//! Close validator delta instruction for reclaiming the rent of a validator delta.
+//!
+//! This module contains the logic for closing a validator delta once it has been applied,
+//! or to abandon it.
+
+use crate::*;
+
+/// Account structure for the close_validator_delta instruction.
+///
+/// This struct defines the accounts required to close a validator delta.
+#[derive(Accounts)]
+#[instruction(instance_id: u64, batch_id: u64)]
+pub struct CloseValidatorDelta<'info> {
+    /// The validator that assembled the delta, receives its rent
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    /// The validator set, checked for a scheduled update applying the delta
+    #[account(
+        seeds = [VALIDATOR_SET_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_set.bump,
+        constraint = validator_set.pending_update != Some(batch_id) @ CustomError::ValidatorUpdatePending,
+        constraint = validator_set.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_set: Account<'info, ValidatorSet>,
+
+    /// The validator delta to be closed
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [
+            VALIDATOR_DELTA_SEED,
+            instance_id.to_le_bytes().as_ref(),
+            batch_id.to_le_bytes().as_ref(),
+        ],
+        bump = validator_delta.bump,
+        has_one = proposer @ CustomError::InvalidSigner,
+        constraint = validator_delta.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_delta: Account<'info, ValidatorDelta>,
+}
+
+impl<'info> CloseValidatorDelta<'info> {
+    /// Process the close_validator_delta instruction.
+    ///
+    /// A delta can be closed at any time, except while the update applying it is scheduled.
+    /// A proposal still collecting approvals for a closed delta can no longer reach quorum.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `batch_id` - The batch ID of the validator set update
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
+    ///
+    /// # Errors
+    /// * `InvalidSigner` - If the signer is not the proposer of the delta
+    /// * `ValidatorUpdatePending` - If the update applying the delta is scheduled
+    pub fn process_instruction(
+        _ctx: Context<Self>,
+        _instance_id: u64,
+        _batch_id: u64,
+    ) -> Result<()> {
+        Ok(())
+    }
+}
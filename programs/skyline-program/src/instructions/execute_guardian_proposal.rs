@@ -0,0 +1,99 @@
+//! Execute guardian proposal instruction for activating a timelocked validator set recovery.
+//!
+//! This module contains the logic for activating a guardian proposal that reached the
+//! council threshold and has waited out its timelock. Activation is permissionless, but
+//! fails if the validators have reached a quorum again in the meantime.
+
+use crate::*;
+
+/// Account structure for the execute_guardian_proposal instruction.
+///
+/// This struct defines the accounts required to activate a scheduled guardian proposal.
+#[derive(Accounts)]
+#[instruction(instance_id: u64, batch_id: u64)]
+pub struct ExecuteGuardianProposal<'info> {
+    /// The caller triggering the activation, receives the proposal rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The guardian council that approved the proposal
+    #[account(
+        seeds = [GUARDIAN_COUNCIL_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = guardian_council.bump,
+        constraint = guardian_council.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub guardian_council: Account<'info, GuardianCouncil>,
+
+    /// The validator set account the proposal applies to
+    #[account(
+        mut,
+        seeds = [VALIDATOR_SET_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_set.bump,
+        constraint = validator_set.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_set: Account<'info, ValidatorSet>,
+
+    /// The validator participation statistics, telling a stalled set from an idle one
+    #[account(
+        seeds = [VALIDATOR_STATS_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_stats.bump,
+        constraint = validator_stats.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_stats: Account<'info, ValidatorStats>,
+
+    /// The scheduled guardian proposal
+    #[account(
+        mut,
+        seeds = [
+            GUARDIAN_PROPOSAL_SEED,
+            instance_id.to_le_bytes().as_ref(),
+            batch_id.to_le_bytes().as_ref(),
+        ],
+        bump = proposal.bump,
+        constraint = proposal.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub proposal: Account<'info, GuardianProposal>,
+
+    /// The system program for accounts created by the action
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExecuteGuardianProposal<'info> {
+    /// Process the execute_guardian_proposal instruction.
+    ///
+    /// The snapshot of the recovered validator set is passed in remaining accounts.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `batch_id` - The council batch ID of the scheduled proposal
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
+    ///
+    /// # Errors
+    /// * `ProposalNotScheduled` - If the proposal has not reached the council threshold
+    /// * `TimelockNotExpired` - If the activation slot has not been reached
+    /// * `ValidatorSetNotStalled` - If the validators reached a quorum or were seen with the
+    ///   threshold weight during the timelock
+    /// * `MissingAdmissionProof` - If a recovered key has not signed its admission
+    /// * `MissingActionAccount` - If the snapshot of the recovered set is missing
+    pub fn process_instruction(
+        ctx: Context<'_, '_, '_, 'info, Self>,
+        instance_id: u64,
+        _batch_id: u64,
+    ) -> Result<()> {
+        activate_guardian_proposal(
+            &mut ctx.accounts.proposal,
+            &ctx.accounts.guardian_council,
+            &mut ctx.accounts.validator_set,
+            &ctx.accounts.validator_stats,
+            &ActionAccounts {
+                instance_id,
+                payer: &ctx.accounts.payer.to_account_info(),
+                system_program: &ctx.accounts.system_program.to_account_info(),
+                remaining_accounts: ctx.remaining_accounts,
+            },
+        )
+    }
+}
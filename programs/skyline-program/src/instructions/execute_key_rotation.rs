@@ -0,0 +1,80 @@
+//! Execute key rotation instruction for activating scheduled validator key rotations.
+//!
+//! This module contains the logic for activating a validator key rotation once its
+//! timelock has expired. Activation is permissionless: both keys already signed the
+//! rotation, so anyone can trigger it once the activation slot is reached.
+
+use crate::*;
+
+/// Account structure for the execute_key_rotation instruction.
+///
+/// This struct defines the accounts required to activate a scheduled key rotation.
+#[derive(Accounts)]
+#[instruction(instance_id: u64, old_key: Pubkey)]
+pub struct ExecuteKeyRotation<'info> {
+    /// The caller triggering the activation, receives the rotation rent and pays for the
+    /// snapshot of the new validator set and the records moved to the new key
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The validator set account the rotation applies to
+    #[account(
+        mut,
+        seeds = [VALIDATOR_SET_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_set.bump,
+        constraint = validator_set.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_set: Account<'info, ValidatorSet>,
+
+    /// The scheduled key rotation
+    #[account(
+        mut,
+        seeds = [KEY_ROTATION_SEED, instance_id.to_le_bytes().as_ref(), old_key.as_ref()],
+        bump = key_rotation.bump,
+        constraint = key_rotation.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub key_rotation: Account<'info, KeyRotation>,
+
+    /// The system program for the snapshot account creation
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExecuteKeyRotation<'info> {
+    /// Process the execute_key_rotation instruction.
+    ///
+    /// The snapshot of the new validator set epoch and the `ValidatorInfo`, `ValidatorBond`
+    /// and `RewardAccount` addresses of the old and the new key are passed in remaining
+    /// accounts; the records of the old key move to the new key.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `old_key` - The validator key being rotated out
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
+    ///
+    /// # Errors
+    /// * `TimelockNotExpired` - If the activation slot has not been reached
+    /// * `RemovingNonExistentSigner` - If the old key has left the validator set meanwhile
+    /// * `AddingExistingSigner` - If the new key has joined the validator set meanwhile
+    /// * `MissingActionAccount` - If the snapshot of the new epoch or a record address was not
+    ///   passed in remaining accounts
+    /// * `RotatedRecordExists` - If the new key already holds a registry record or bond
+    pub fn process_instruction(
+        ctx: Context<'_, '_, '_, 'info, Self>,
+        instance_id: u64,
+        _old_key: Pubkey,
+    ) -> Result<()> {
+        activate_key_rotation(
+            &mut ctx.accounts.key_rotation,
+            &mut ctx.accounts.validator_set,
+            &ActionAccounts {
+                instance_id,
+                payer: &ctx.accounts.payer.to_account_info(),
+                system_program: &ctx.accounts.system_program.to_account_info(),
+                remaining_accounts: ctx.remaining_accounts,
+            },
+        )
+    }
+}
@@ -0,0 +1,79 @@
+//! Execute proposal instruction for activating timelocked governance proposals.
+//!
+//! This module contains the logic for activating a governance proposal that reached
+//! quorum and has waited out its timelock. Activation is permissionless: the approvals
+//! were already collected, so anyone can trigger it once the activation slot is reached.
+
+use crate::*;
+
+/// Account structure for the execute_proposal instruction.
+///
+/// This struct defines the accounts required to activate a scheduled governance proposal.
+#[derive(Accounts)]
+#[instruction(instance_id: u64, batch_id: u64)]
+pub struct ExecuteProposal<'info> {
+    /// The caller triggering the activation, receives the proposal rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The validator set account the proposal applies to
+    #[account(
+        mut,
+        seeds = [VALIDATOR_SET_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_set.bump,
+        constraint = validator_set.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_set: Account<'info, ValidatorSet>,
+
+    /// The scheduled governance proposal
+    #[account(
+        mut,
+        seeds = [
+            GOVERNANCE_PROPOSAL_SEED,
+            instance_id.to_le_bytes().as_ref(),
+            batch_id.to_le_bytes().as_ref(),
+        ],
+        bump = proposal.bump,
+        constraint = proposal.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    /// The system program for accounts created by the action
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExecuteProposal<'info> {
+    /// Process the execute_proposal instruction.
+    ///
+    /// Accounts written by the action (such as validator registry records) are passed
+    /// in remaining accounts.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `batch_id` - The batch ID of the scheduled proposal
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
+    ///
+    /// # Errors
+    /// * `ProposalNotScheduled` - If the proposal has not reached quorum
+    /// * `TimelockNotExpired` - If the activation slot has not been reached
+    /// * `MissingActionAccount` - If an account written by the action is missing
+    pub fn process_instruction(
+        ctx: Context<'_, '_, '_, 'info, Self>,
+        instance_id: u64,
+        _batch_id: u64,
+    ) -> Result<()> {
+        activate_proposal(
+            &mut ctx.accounts.proposal,
+            &mut ctx.accounts.validator_set,
+            &ActionAccounts {
+                instance_id,
+                payer: &ctx.accounts.payer.to_account_info(),
+                system_program: &ctx.accounts.system_program.to_account_info(),
+                remaining_accounts: ctx.remaining_accounts,
+            },
+        )
+    }
+}
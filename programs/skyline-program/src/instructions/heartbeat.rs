@@ -0,0 +1,74 @@
+//! Heartbeat instruction for validator liveness reporting.
+//!
+//! This module contains the logic for a validator to report that it is online. Validators
+//! call it periodically; the slot of the latest heartbeat is kept in the validator
+//! participation statistics next to the approval counters.
+
+use crate::*;
+
+/// Account structure for the heartbeat instruction.
+///
+/// This struct defines the accounts required for a validator to record a heartbeat.
+#[derive(Accounts)]
+#[instruction(instance_id: u64)]
+pub struct Heartbeat<'info> {
+    /// The payer for growing the statistics account when the validator set has grown
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The validator reporting that it is online
+    pub validator: Signer<'info>,
+
+    /// The validator set the validator belongs to
+    #[account(
+        seeds = [VALIDATOR_SET_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_set.bump,
+        constraint = validator_set.signers.contains(&validator.key()) @ CustomError::InvalidSigner,
+        constraint = validator_set.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_set: Account<'info, ValidatorSet>,
+
+    /// The validator participation statistics
+    #[account(
+        mut,
+        seeds = [VALIDATOR_STATS_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_stats.bump,
+        constraint = validator_stats.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_stats: Account<'info, ValidatorStats>,
+
+    /// The system program, used to grow the statistics account
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Heartbeat<'info> {
+    /// Process the heartbeat instruction.
+    ///
+    /// Suspended validators can send heartbeats too, so governance can see they are back
+    /// online before reinstating them.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
+    ///
+    /// # Errors
+    /// * `InvalidSigner` - If the signer is not in the validator set
+    pub fn process_instruction(ctx: Context<Self>, _instance_id: u64) -> Result<()> {
+        ValidatorStats::ensure_space(
+            &ctx.accounts.validator_stats,
+            &ctx.accounts.validator_set,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        ctx.accounts.validator_stats.record_heartbeat(
+            &ctx.accounts.validator_set,
+            &ctx.accounts.validator.key(),
+            Clock::get()?.slot,
+        );
+
+        Ok(())
+    }
+}
@@ -2,7 +2,10 @@
 //!
 //! This module contains the logic for initializing the bridge system with an initial
 //! set of validators. The validators must meet certain requirements and will control
-//! all subsequent bridge operations.
+//! all subsequent bridge operations. Only the upgrade authority of the program, or a key
+//! the validators authorized through an `AuthorizeInitializer` governance action once the
+//! upgrade authority is the upgrade authority PDA, can initialize, so nobody can front-run
+//! the deployment with their own validator set.
 
 use crate::*;
 
@@ -11,18 +14,43 @@ use crate::*;
 /// This struct defines the accounts required to initialize the validator set.
 /// It includes validation constraints to ensure the validator set meets security requirements.
 #[derive(Accounts)]
-#[instruction(validators: Vec<Pubkey>)]
+#[instruction(
+    validators: Vec<Pubkey>,
+    last_id: Option<u64>,
+    config: Option<BridgeConfig>,
+    weights: Option<Vec<u64>>,
+    deployment: DeploymentConfig,
+)]
 pub struct Initialize<'info> {
-    /// The signer who is initializing the bridge system
+    /// The signer who is initializing the bridge system, must be the program upgrade authority
+    /// or the key of the initializer grant
     #[account(mut)]
     pub signer: Signer<'info>,
 
+    /// The program data account of this program, holding its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    /// The initializer grant of the instance, consumed when the signer initializes through it
+    #[account(
+        mut,
+        close = signer,
+        seeds = [INITIALIZER_GRANT_SEED, deployment.instance_id.to_le_bytes().as_ref()],
+        bump = initializer_grant.bump,
+        constraint = initializer_grant.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub initializer_grant: Option<Account<'info, InitializerGrant>>,
+
     /// The validator set account to be initialized
     #[account(
         init,
         payer = signer,
-        space = ValidatorSet::INIT_SPACE + DISC as usize,
-        seeds = [VALIDATOR_SET_SEED],
+        space = ValidatorSet::space(validators.len()) + DISC as usize,
+        seeds = [VALIDATOR_SET_SEED, deployment.instance_id.to_le_bytes().as_ref()],
         constraint = validators.len() <= MAX_VALIDATORS as usize @ CustomError::MaxValidatorsExceeded,
         constraint = validators.len() >= MIN_VALIDATORS as usize @ CustomError::MinValidatorsNotMet,
         bump
@@ -34,11 +62,50 @@ pub struct Initialize<'info> {
         init,
         payer = signer,
         space = Vault::INIT_SPACE + DISC as usize,
-        seeds = [VAULT_SEED],
+        seeds = [VAULT_SEED, deployment.instance_id.to_le_bytes().as_ref()],
         bump
     )]
     pub vault: Account<'info, Vault>,
 
+    /// The treasury account receiving slashed bonds
+    #[account(
+        init,
+        payer = signer,
+        space = Treasury::INIT_SPACE + DISC as usize,
+        seeds = [TREASURY_SEED, deployment.instance_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// The validator participation statistics account
+    #[account(
+        init,
+        payer = signer,
+        space = ValidatorStats::space(0) + DISC as usize,
+        seeds = [VALIDATOR_STATS_SEED, deployment.instance_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub validator_stats: Account<'info, ValidatorStats>,
+
+    /// The snapshot of the initial validator set (epoch 0)
+    #[account(
+        init,
+        payer = signer,
+        space = ValidatorSetSnapshot::space(validators.len()) + DISC as usize,
+        seeds = [
+            VALIDATOR_SET_SNAPSHOT_SEED,
+            deployment.instance_id.to_le_bytes().as_ref(),
+            0u64.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub validator_set_snapshot: Account<'info, ValidatorSetSnapshot>,
+
+    /// The instructions sysvar, inspected for ed25519 admission proofs
+    /// CHECK: address is checked against the instructions sysvar ID
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
     /// The system program for account creation
     pub system_program: Program<'info, System>,
 }
@@ -52,26 +119,61 @@ impl<'info> Initialize<'info> {
     /// # Arguments
     /// * `ctx` - The instruction context containing all required accounts
     /// * `validators` - Vector of validator public keys to initialize
+    /// * `last_id` - Initial batch ID
+    /// * `config` - Initial bridge configuration
+    /// * `weights` - Voting weights of the validators, in the order of `validators`
+    ///   (empty to give every validator `DEFAULT_VALIDATOR_WEIGHT`)
+    /// * `deployment` - Chain ID and bridge instance of this deployment
     ///
     /// # Returns
     /// * `Result<()>` - Returns Ok(()) on success or an error on failure
     ///
     /// # Errors
+    /// * `UnauthorizedInitializer` - If the signer is neither the program upgrade authority nor
+    ///   the key of the initializer grant
     /// * `ValidatorsNotUnique` - If duplicate validators are provided
+    /// * `MissingAdmissionProof` - If a validator has not proven possession of its key
+    /// * `InvalidConfig` - If the configuration is out of range
+    /// * `ValidatorWeightsMismatch` - If weights are given, but not one per validator
+    /// * `InvalidValidatorWeight` - If a weight is zero or above `MAX_VALIDATOR_WEIGHT`
     ///
     /// # Security Checks
+    /// * Validates that the signer is the upgrade authority recorded in the program data account,
+    ///   or the key the validators authorized through the initializer grant (which is closed)
     /// * Validates that all validators are unique (no duplicates)
+    /// * Validates that every validator signed the transaction or provided an ed25519
+    ///   signature over its admission message (with `last_id` as the batch ID)
     /// * Automatically calculates the consensus threshold using the formula: num_signers - floor((num_signers - 1) / 3)
+    ///   and the threshold weight as `config.quorum_weight_bps` of the total weight
     /// * Stores the bump seed for PDA derivation
-    /// * Initializes the vault account
+    /// * Initializes the vault, treasury and validator statistics accounts
+    /// * Records the initializer and the deployment configuration
+    /// * Archives the initial validator set as epoch 0
     pub fn process_instruction(
         ctx: Context<Self>,
         validators: Vec<Pubkey>,
         last_id: u64,
+        config: BridgeConfig,
+        weights: Vec<u64>,
+        deployment: DeploymentConfig,
     ) -> Result<()> {
+        let signer = ctx.accounts.signer.key();
+        require!(
+            ctx.accounts.program_data.upgrade_authority_address == Some(signer)
+                || ctx
+                    .accounts
+                    .initializer_grant
+                    .as_ref()
+                    .is_some_and(|grant| grant.initializer == signer),
+            CustomError::UnauthorizedInitializer
+        );
+
         let validator_set = &mut ctx.accounts.validator_set;
         let vault = &mut ctx.accounts.vault;
 
+        validate_config(&config)?;
+        let weights = resolve_weights(&weights, validators.len())?;
+
         // Check for duplicate validators by sorting and deduplicating
         let mut validators_copy = validators.clone();
         validators_copy.sort();
@@ -81,12 +183,23 @@ impl<'info> Initialize<'info> {
             CustomError::ValidatorsNotUnique
         );
 
-        // Set the validator list
-        validator_set.signers = validators;
+        // Every validator must prove it controls its key
+        let admitted = collect_admissions(
+            &validators,
+            &signer_keys(ctx.remaining_accounts),
+            &ctx.accounts.instructions,
+            &validator_set.key(),
+            last_id,
+        )?;
+        require!(
+            admitted.len() == validators.len(),
+            CustomError::MissingAdmissionProof
+        );
 
-        // Calculate consensus threshold as 2/3 of validators, rounded up
-        // This ensures that at least 2/3 of validators must approve critical operations
-        validator_set.threshold = helpers::calculate_threshold(validator_set.signers.len());
+        // Set the validator list and weights
+        validator_set.version = ACCOUNT_VERSION;
+        validator_set.signers = validators;
+        validator_set.weights = weights;
 
         // Store the bump seed for PDA derivation
         validator_set.bump = ctx.bumps.validator_set;
@@ -95,7 +208,46 @@ impl<'info> Initialize<'info> {
         validator_set.last_batch_id = last_id;
         validator_set.bridge_request_count = 0;
 
+        // No guardian until one is set through governance
+        validator_set.guardian = Pubkey::default();
+        validator_set.config = config;
+        validator_set.pending_update = None;
+        validator_set.suspended = Vec::new();
+        validator_set.removal_queue = Vec::new();
+
+        // Calculate consensus thresholds as 2/3 of validators and of their weight, rounded up
+        // This ensures that at least 2/3 of the stake must approve critical operations
+        update_thresholds(validator_set);
+
+        // Open the first churn window
+        validator_set.churn_window_start = Clock::get()?.slot;
+        validator_set.churn_window_base = validator_set.signers.len() as u32;
+        validator_set.churn_in_window = 0;
+
+        // Bridging starts unpaused, and the stall period of the initial set starts now
+        validator_set.paused = false;
+        validator_set.last_quorum_slot = validator_set.churn_window_start;
+
+        // Record who deployed the bridge and for which chain and instance
+        validator_set.initializer = ctx.accounts.signer.key();
+        validator_set.deployment = deployment;
+
+        // Archive the initial set as epoch 0
+        validator_set.epoch = 0;
+        let snapshot =
+            ValidatorSetSnapshot::capture(validator_set, ctx.bumps.validator_set_snapshot)?;
+        snapshot.emit_archived();
+        ctx.accounts.validator_set_snapshot.set_inner(snapshot);
+
+        vault.version = ACCOUNT_VERSION;
         vault.bump = ctx.bumps.vault;
+        ctx.accounts.treasury.version = ACCOUNT_VERSION;
+        ctx.accounts.treasury.bump = ctx.bumps.treasury;
+
+        // Statistics entries are added as validators participate
+        ctx.accounts.validator_stats.version = ACCOUNT_VERSION;
+        ctx.accounts.validator_stats.entries = Vec::new();
+        ctx.accounts.validator_stats.bump = ctx.bumps.validator_stats;
         Ok(())
     }
 }
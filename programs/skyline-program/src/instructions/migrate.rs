@@ -0,0 +1,562 @@
+//! Migrate instruction for moving version 1 accounts into the current layout.
+//!
+//! This module contains the logic for migrating the accounts of the bridge as it was before
+//! bridge instances and layout versions existed (version 1). Those accounts live at PDAs
+//! derived without an instance ID, so they cannot be upgraded in place: each is copied field
+//! by field into the current-layout PDA of the instance the legacy bridge becomes, new fields
+//! take their defaults, and the version 1 account is closed. Instructions refuse to operate
+//! on accounts that have not been migrated.
+
+use anchor_lang::Discriminator;
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    token::{
+        self, set_authority, spl_token::instruction::AuthorityType, transfer_checked, Mint,
+        SetAuthority, TokenAccount, TransferChecked,
+    },
+};
+
+use crate::*;
+
+/// Seed of the version 1 validator set change (the pending validator set update) PDA.
+const VALIDATOR_SET_CHANGE_SEED_V1: &[u8] = b"validator_set_change";
+
+/// Layout of a version 1 `ValidatorSet`, stored at `[VALIDATOR_SET_SEED]`.
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct ValidatorSetV1 {
+    /// List of validator public keys that can sign bridge operations
+    pub signers: Vec<Pubkey>,
+    /// Consensus threshold - number of validator signatures required
+    pub threshold: u8,
+    /// Bump seed for the Program Derived Address (PDA)
+    pub bump: u8,
+    /// Last batch ID processed
+    pub last_batch_id: u64,
+    /// Total count of bridge requests processed since initialization
+    pub bridge_request_count: u64,
+}
+
+/// Layout of a version 1 `Vault`, stored at `[VAULT_SEED]`.
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct VaultV1 {
+    /// Bump seed for the Program Derived Address (PDA)
+    pub bump: u8,
+}
+
+/// Layout of a version 1 `BridgingTransaction`, stored at
+/// `[BRIDGING_TRANSACTION_SEED, batch_id.to_le_bytes()]`.
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct BridgingTransactionV1 {
+    /// Unique identifier for the transaction (the account's key)
+    pub id: Pubkey,
+    /// The amount of tokens to transfer to the recipient
+    pub amount: u64,
+    /// The recipient of the bridged tokens
+    pub receiver: Pubkey,
+    /// The mint of the bridged tokens
+    pub mint_token: Pubkey,
+    /// List of validator public keys that have approved this transaction
+    pub signers: Vec<Pubkey>,
+    /// Bump seed for the Program Derived Address (PDA)
+    pub bump: u8,
+    /// The batch ID of this transaction
+    pub batch_id: u64,
+}
+
+/// Layout of a version 1 `ValidatorDelta`, a pending validator set update stored at
+/// `[b"validator_set_change", batch_id.to_le_bytes()]`.
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct ValidatorDeltaV1 {
+    /// Unique identifier for the validator set change (the account's key)
+    pub id: Pubkey,
+    /// List of new validator public keys to add
+    pub added: Vec<Pubkey>,
+    /// List of validator public keys to remove
+    pub removed: Vec<Pubkey>,
+    /// Bump seed for the Program Derived Address (PDA)
+    pub bump: u8,
+    /// The batch ID of this validator set change
+    pub batch_id: u64,
+    /// List of validator public keys that have approved this change
+    pub signers: Vec<Pubkey>,
+    /// Hash of the proposal the validators approved
+    pub proposal_hash: [u8; 32],
+}
+
+/// A version 1 account, identified by its discriminator and its address.
+enum LegacyAccount {
+    ValidatorSet(ValidatorSetV1),
+    Vault(VaultV1),
+    BridgingTransaction(BridgingTransactionV1),
+    ValidatorDelta(ValidatorDeltaV1),
+}
+
+impl LegacyAccount {
+    /// Parses version 1 account data, including the discriminator.
+    ///
+    /// `None` if the type is unknown, the data does not deserialize, or the seeds and the
+    /// stored bump do not derive `address`. Current accounts are seeded with an instance ID,
+    /// so they never match the address of a version 1 account.
+    fn parse(data: &[u8], address: &Pubkey) -> Option<Self> {
+        let discriminator = data.get(..DISC as usize)?;
+        let mut body = &data[DISC as usize..];
+
+        let (account, derived) = if discriminator == ValidatorSet::DISCRIMINATOR {
+            let v1 = ValidatorSetV1::deserialize(&mut body).ok()?;
+            let derived = pda(&[VALIDATOR_SET_SEED, &[v1.bump]]);
+            (Self::ValidatorSet(v1), derived)
+        } else if discriminator == Vault::DISCRIMINATOR {
+            let v1 = VaultV1::deserialize(&mut body).ok()?;
+            let derived = pda(&[VAULT_SEED, &[v1.bump]]);
+            (Self::Vault(v1), derived)
+        } else if discriminator == BridgingTransaction::DISCRIMINATOR {
+            let v1 = BridgingTransactionV1::deserialize(&mut body).ok()?;
+            let derived = pda(&[
+                BRIDGING_TRANSACTION_SEED,
+                &v1.batch_id.to_le_bytes(),
+                &[v1.bump],
+            ]);
+            (Self::BridgingTransaction(v1), derived)
+        } else if discriminator == ValidatorDelta::DISCRIMINATOR {
+            let v1 = ValidatorDeltaV1::deserialize(&mut body).ok()?;
+            let derived = pda(&[
+                VALIDATOR_SET_CHANGE_SEED_V1,
+                &v1.batch_id.to_le_bytes(),
+                &[v1.bump],
+            ]);
+            (Self::ValidatorDelta(v1), derived)
+        } else {
+            return None;
+        };
+
+        derived
+            .filter(|derived| derived == address)
+            .map(|_| account)
+    }
+}
+
+/// Account structure for the migrate instruction.
+///
+/// This struct defines the accounts required to migrate a version 1 account. The type of
+/// the account is identified by its discriminator.
+#[derive(Accounts)]
+pub struct Migrate<'info> {
+    /// The program upgrade authority, pays for the migrated accounts and receives the rent
+    /// of the version 1 account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The program data account of this program, holding its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(payer.key())
+            @ CustomError::UnauthorizedMigration,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    /// The version 1 account to migrate
+    /// CHECK: The type is identified by the discriminator and the layout is validated against
+    /// the version 1 PDA the account data describes
+    #[account(mut, owner = crate::ID @ CustomError::AccountNotMigratable)]
+    pub account: UncheckedAccount<'info>,
+
+    /// The current-layout account the data moves to
+    /// CHECK: The address is validated against the current PDA of the migrated account
+    #[account(mut)]
+    pub migrated: UncheckedAccount<'info>,
+
+    /// The system program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Migrate<'info> {
+    /// Process the migrate instruction.
+    ///
+    /// Version 1 accounts belong to the single bridge the program hosted before bridge
+    /// instances existed. The upgrade authority names the instance that bridge becomes, and
+    /// each account moves to its PDA in that instance:
+    ///
+    /// * `ValidatorSet` - becomes the validator set of the instance, with default weights,
+    ///   configuration and thresholds; the treasury, the validator statistics and the epoch 0
+    ///   snapshot of the instance are created alongside and must be passed in remaining
+    ///   accounts
+    /// * `Vault` - becomes the vault of the instance; for every mint passed in remaining
+    ///   accounts, the mint authority and the balance of the associated token account of the
+    ///   version 1 vault are handed to the new vault. The version 1 vault is left in place, so
+    ///   it can hand over further mints in later calls
+    /// * `BridgingTransaction` - keeps its approvals at the same batch ID; the source chain and
+    ///   source amount, which version 1 did not record, are recorded by the next approval
+    /// * `ValidatorDelta` - becomes a `GovernanceProposal` voting on the same
+    ///   `UpdateValidators` action; the added keys still have to prove possession
+    ///
+    /// Every version 1 account other than the vault is closed.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance the version 1 accounts migrate to
+    /// * `chain_id` - Chain ID of this deployment, recorded in a migrated validator set
+    ///   (see `DeploymentConfig::chain_id`)
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
+    ///
+    /// # Errors
+    /// * `UnauthorizedMigration` - If the payer is not the program upgrade authority
+    /// * `AccountNotMigratable` - If the account is not a version 1 account, or the migrated
+    ///   account is not its current PDA or already exists
+    /// * `MissingActionAccount` - If an account created alongside a validator set, or a
+    ///   token account of a handed over mint, is missing
+    pub fn process_instruction(
+        ctx: Context<'_, '_, '_, 'info, Self>,
+        instance_id: u64,
+        chain_id: u8,
+    ) -> Result<()> {
+        let info = ctx.accounts.account.to_account_info();
+        let migrated = ctx.accounts.migrated.to_account_info();
+        let legacy = LegacyAccount::parse(&info.try_borrow_data()?, info.key)
+            .ok_or(CustomError::AccountNotMigratable)?;
+        let accounts = ActionAccounts {
+            instance_id,
+            payer: &ctx.accounts.payer.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            remaining_accounts: ctx.remaining_accounts,
+        };
+        let instance_seed = instance_id.to_le_bytes();
+        let close = !matches!(legacy, LegacyAccount::Vault(_));
+
+        match legacy {
+            LegacyAccount::ValidatorSet(v1) => {
+                migrate_validator_set(&accounts, &migrated, v1, chain_id)?;
+            }
+            LegacyAccount::Vault(v1) => {
+                let vault_seeds: &[&[u8]] = &[VAULT_SEED, &instance_seed];
+                if migrated.data_is_empty() {
+                    store(&accounts, &migrated, vault_seeds, |bump| {
+                        Ok((
+                            Vault::INIT_SPACE,
+                            Vault {
+                                version: ACCOUNT_VERSION,
+                                bump,
+                            },
+                        ))
+                    })?;
+                } else {
+                    // The vault of the instance exists when a handover is repeated
+                    require_keys_eq!(
+                        migrated.key(),
+                        pda_address(vault_seeds),
+                        CustomError::AccountNotMigratable
+                    );
+                    let vault = Vault::try_deserialize(&mut &migrated.try_borrow_data()?[..])?;
+                    require!(
+                        vault.version == ACCOUNT_VERSION,
+                        CustomError::AccountNotMigratable
+                    );
+                }
+                hand_over_vault(&accounts, &info, v1.bump, &migrated)?;
+            }
+            LegacyAccount::BridgingTransaction(v1) => {
+                let batch_id = v1.batch_id.to_le_bytes();
+                store(
+                    &accounts,
+                    &migrated,
+                    &[BRIDGING_TRANSACTION_SEED, &instance_seed, &batch_id],
+                    |bump| {
+                        Ok((
+                            BridgingTransaction::space(v1.signers.len()),
+                            BridgingTransaction {
+                                version: ACCOUNT_VERSION,
+                                id: migrated.key(),
+                                amount: v1.amount,
+                                receiver: v1.receiver,
+                                mint_token: v1.mint_token,
+                                signers: v1.signers,
+                                bump,
+                                batch_id: v1.batch_id,
+                                // Recorded by the next approval, which clears `signers`
+                                source_chain: 0,
+                                source_amount: 0,
+                            },
+                        ))
+                    },
+                )?;
+            }
+            LegacyAccount::ValidatorDelta(v1) => {
+                let action = GovernanceAction::UpdateValidators {
+                    added: v1.added,
+                    removed: v1.removed,
+                    foreign_keys: Vec::new(),
+                    weights: Vec::new(),
+                };
+                let proposal_hash = action.hash()?;
+                let batch_id = v1.batch_id.to_le_bytes();
+                store(
+                    &accounts,
+                    &migrated,
+                    &[GOVERNANCE_PROPOSAL_SEED, &instance_seed, &batch_id],
+                    |bump| {
+                        Ok((
+                            GovernanceProposal::space(v1.signers.len(), 0),
+                            GovernanceProposal {
+                                version: ACCOUNT_VERSION,
+                                id: migrated.key(),
+                                action,
+                                bump,
+                                batch_id: v1.batch_id,
+                                signers: v1.signers,
+                                proposal_hash,
+                                status: ProposalStatus::Voting,
+                                activation_slot: 0,
+                                vetoes: Vec::new(),
+                                admitted: Vec::new(),
+                            },
+                        ))
+                    },
+                )?;
+            }
+        }
+
+        if close {
+            close_account(&info, accounts.payer)?;
+        }
+
+        emit!(AccountMigratedEvent {
+            instance_id,
+            account: info.key(),
+            migrated: migrated.key(),
+            version: ACCOUNT_VERSION,
+        });
+
+        Ok(())
+    }
+}
+
+/// Migrates a version 1 validator set into the validator set of the instance.
+///
+/// The signers, the last batch ID and the bridge request count are kept. Every validator
+/// gets `DEFAULT_VALIDATOR_WEIGHT`, the configuration is `BridgeConfig::default()` and the
+/// thresholds are recalculated; the churn window and the stall period start now. The
+/// treasury, the validator statistics and the epoch 0 snapshot of the instance, which
+/// `initialize` creates for new instances, are created from the remaining accounts.
+///
+/// # Errors
+/// * `AccountNotMigratable` - If `migrated` is not the validator set PDA of the instance, or
+///   one of the accounts already exists
+/// * `MissingActionAccount` - If the treasury, statistics or snapshot PDA is missing
+fn migrate_validator_set<'info>(
+    accounts: &ActionAccounts<'_, 'info>,
+    migrated: &AccountInfo<'info>,
+    v1: ValidatorSetV1,
+    chain_id: u8,
+) -> Result<()> {
+    let instance_seed = accounts.instance_id.to_le_bytes();
+    let slot = Clock::get()?.slot;
+    let num_validators = v1.signers.len();
+
+    let mut validator_set = ValidatorSet {
+        version: ACCOUNT_VERSION,
+        signers: v1.signers,
+        weights: vec![DEFAULT_VALIDATOR_WEIGHT; num_validators],
+        threshold: 0,
+        threshold_weight: 0,
+        bump: 0,
+        last_batch_id: v1.last_batch_id,
+        bridge_request_count: v1.bridge_request_count,
+        guardian: Pubkey::default(),
+        config: BridgeConfig::default(),
+        pending_update: None,
+        churn_window_start: slot,
+        churn_window_base: num_validators as u32,
+        churn_in_window: 0,
+        suspended: Vec::new(),
+        removal_queue: Vec::new(),
+        epoch: 0,
+        paused: false,
+        last_quorum_slot: slot,
+        initializer: accounts.payer.key(),
+        deployment: DeploymentConfig {
+            chain_id,
+            instance_id: accounts.instance_id,
+        },
+    };
+    update_thresholds(&mut validator_set);
+
+    store(
+        accounts,
+        migrated,
+        &[VALIDATOR_SET_SEED, &instance_seed],
+        |bump| {
+            validator_set.bump = bump;
+            Ok((ValidatorSet::space(num_validators), validator_set.clone()))
+        },
+    )?;
+
+    let treasury = accounts.find(&pda_address(&[TREASURY_SEED, &instance_seed]))?;
+    store(
+        accounts,
+        treasury,
+        &[TREASURY_SEED, &instance_seed],
+        |bump| {
+            Ok((
+                Treasury::INIT_SPACE,
+                Treasury {
+                    version: ACCOUNT_VERSION,
+                    bump,
+                },
+            ))
+        },
+    )?;
+
+    let stats = accounts.find(&pda_address(&[VALIDATOR_STATS_SEED, &instance_seed]))?;
+    store(
+        accounts,
+        stats,
+        &[VALIDATOR_STATS_SEED, &instance_seed],
+        |bump| {
+            Ok((
+                ValidatorStats::space(0),
+                ValidatorStats {
+                    version: ACCOUNT_VERSION,
+                    entries: Vec::new(),
+                    bump,
+                },
+            ))
+        },
+    )?;
+
+    let epoch = 0u64.to_le_bytes();
+    let snapshot_seeds: &[&[u8]] = &[VALIDATOR_SET_SNAPSHOT_SEED, &instance_seed, &epoch];
+    let snapshot_info = accounts.find(&pda_address(snapshot_seeds))?;
+    let mut snapshot = None;
+    store(accounts, snapshot_info, snapshot_seeds, |bump| {
+        let captured = ValidatorSetSnapshot::capture(&validator_set, bump)?;
+        snapshot = Some(captured.clone());
+        Ok((ValidatorSetSnapshot::space(num_validators), captured))
+    })?;
+    if let Some(snapshot) = snapshot {
+        snapshot.emit_archived();
+    }
+
+    Ok(())
+}
+
+/// Hands the mints and token balances of the version 1 vault over to the vault of the
+/// instance.
+///
+/// Every mint among the remaining accounts whose mint authority is the version 1 vault gets
+/// the new vault as mint authority. If the associated token account of the version 1 vault
+/// for a mint is passed, its balance moves to the associated token account of the new
+/// vault, which must be passed and exist.
+///
+/// # Errors
+/// * `MissingActionAccount` - If the token program, or the new vault's token account of a
+///   mint with a balance to move, is missing
+fn hand_over_vault<'info>(
+    accounts: &ActionAccounts<'_, 'info>,
+    legacy_vault: &AccountInfo<'info>,
+    legacy_bump: u8,
+    vault: &AccountInfo<'info>,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SEED, &[legacy_bump]]];
+    let mints = accounts
+        .remaining_accounts
+        .iter()
+        .filter(|info| *info.owner == token::ID)
+        .filter_map(|info| {
+            Mint::try_deserialize(&mut &info.try_borrow_data().ok()?[..])
+                .ok()
+                .map(|mint| (info, mint))
+        })
+        .collect::<Vec<_>>();
+    if mints.is_empty() {
+        return Ok(());
+    }
+    let token_program = accounts.find(&token::ID)?;
+
+    for (mint_info, mint) in mints {
+        if mint.mint_authority == Some(legacy_vault.key()).into() {
+            set_authority(
+                CpiContext::new_with_signer(
+                    token_program.clone(),
+                    SetAuthority {
+                        current_authority: legacy_vault.clone(),
+                        account_or_mint: mint_info.clone(),
+                    },
+                    signer_seeds,
+                ),
+                AuthorityType::MintTokens,
+                Some(vault.key()),
+            )?;
+        }
+
+        let legacy_ata = get_associated_token_address(legacy_vault.key, mint_info.key);
+        let Ok(from) = accounts.find(&legacy_ata) else {
+            continue;
+        };
+        let balance = TokenAccount::try_deserialize(&mut &from.try_borrow_data()?[..])?.amount;
+        if balance == 0 {
+            continue;
+        }
+        let to = accounts.find(&get_associated_token_address(vault.key, mint_info.key))?;
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                TransferChecked {
+                    from: from.clone(),
+                    to: to.clone(),
+                    authority: legacy_vault.clone(),
+                    mint: mint_info.clone(),
+                },
+                signer_seeds,
+            ),
+            balance,
+            mint.decimals,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Creates the current-layout PDA of `seeds` (without the bump) and writes an account to it.
+///
+/// `account` is given the bump and returns the account space (without the discriminator)
+/// and the account.
+///
+/// # Errors
+/// * `AccountNotMigratable` - If `info` is not the PDA of `seeds` or already exists
+fn store<'info, T: AccountSerialize>(
+    accounts: &ActionAccounts<'_, 'info>,
+    info: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    account: impl FnOnce(u8) -> Result<(usize, T)>,
+) -> Result<()> {
+    let (address, bump) = Pubkey::find_program_address(seeds, &crate::ID);
+    require_keys_eq!(*info.key, address, CustomError::AccountNotMigratable);
+    require!(info.data_is_empty(), CustomError::AccountNotMigratable);
+
+    let (space, account) = account(bump)?;
+    let bump_seed = [bump];
+    let signer_seeds = [seeds, &[&bump_seed[..]]].concat();
+    create_pda_account(
+        info,
+        accounts.payer,
+        accounts.system_program,
+        DISC as usize + space,
+        &signer_seeds,
+    )?;
+
+    account.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])
+}
+
+/// Derives the program address of `seeds`, which include the bump.
+fn pda(seeds: &[&[u8]]) -> Option<Pubkey> {
+    Pubkey::create_program_address(seeds, &crate::ID).ok()
+}
+
+/// Finds the program address of `seeds`, which do not include the bump.
+fn pda_address(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &crate::ID).0
+}
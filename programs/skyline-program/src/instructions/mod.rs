@@ -12,6 +12,10 @@ pub use initialize::*;
 pub mod bridge_request;
 pub use bridge_request::*;
 
+/// Quote a bridge request without moving any tokens.
+pub mod quote_bridge_request;
+pub use quote_bridge_request::*;
+
 /// Update the validator set with proper consensus.
 pub mod bridge_vsu;
 pub use bridge_vsu::*;
@@ -19,3 +23,83 @@ pub use bridge_vsu::*;
 /// Create or approve a bridging transaction.
 pub mod bridge_transaction;
 pub use bridge_transaction::*;
+
+/// Create or approve a typed governance proposal.
+pub mod submit_proposal;
+pub use submit_proposal::*;
+
+/// Append a chunk to a validator set update assembled across transactions.
+pub mod append_validator_delta;
+pub use append_validator_delta::*;
+
+/// Seal a validator delta so it can be proposed.
+pub mod seal_validator_delta;
+pub use seal_validator_delta::*;
+
+/// Close a validator delta and reclaim its rent.
+pub mod close_validator_delta;
+pub use close_validator_delta::*;
+
+/// Activate a governance proposal once its timelock has expired.
+pub mod execute_proposal;
+pub use execute_proposal::*;
+
+/// Veto a governance proposal during its timelock.
+pub mod veto_proposal;
+pub use veto_proposal::*;
+
+/// Rotate the key of a single validator.
+pub mod rotate_validator_key;
+pub use rotate_validator_key::*;
+
+/// Activate a validator key rotation once its timelock has expired.
+pub mod execute_key_rotation;
+pub use execute_key_rotation::*;
+
+/// Veto a validator key rotation during its timelock.
+pub mod veto_key_rotation;
+pub use veto_key_rotation::*;
+
+/// Update the registry record of a validator.
+pub mod update_validator_info;
+pub use update_validator_info::*;
+
+/// Suspend or reinstate the signing validator.
+pub mod set_validator_suspension;
+pub use set_validator_suspension::*;
+
+/// Record a liveness heartbeat of the signing validator.
+pub mod heartbeat;
+pub use heartbeat::*;
+
+/// Bond lamports as stake of the signing validator.
+pub mod bond;
+pub use bond::*;
+
+/// Start unbonding lamports of the signing validator.
+pub mod unbond;
+pub use unbond::*;
+
+/// Withdraw unbonded lamports of the signing validator.
+pub mod withdraw_bond;
+pub use withdraw_bond::*;
+
+/// Claim the rewards credited to a relayer or validator.
+pub mod claim_rewards;
+pub use claim_rewards::*;
+
+/// Slash and jail a validator that signed conflicting approvals.
+pub mod report_equivocation;
+pub use report_equivocation::*;
+
+/// Create or approve a guardian council proposal.
+pub mod submit_guardian_proposal;
+pub use submit_guardian_proposal::*;
+
+/// Activate a guardian council proposal once its timelock has expired.
+pub mod execute_guardian_proposal;
+pub use execute_guardian_proposal::*;
+
+/// Migrate a program account to the current layout version.
+pub mod migrate;
+pub use migrate::*;
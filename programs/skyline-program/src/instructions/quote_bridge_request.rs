@@ -0,0 +1,222 @@
+//! Quote bridge request instruction for pricing cross-chain transfers ahead of time.
+//!
+//! This module contains the read-only counterpart of `bridge_request`: it runs the same
+//! validation and fee computation without moving any tokens and returns the result as
+//! return data, so wallets and dApps can simulate a bridge request and show its quote.
+
+use crate::*;
+use anchor_spl::token::Mint;
+
+/// The quote of a bridge request, returned by `quote_bridge_request`.
+///
+/// The amounts are the ones a `bridge_request` with the same arguments would take and emit
+/// (see `BridgeRequestEvent`). A rejected request only reports the request headroom.
+///
+/// # Fields
+///
+/// * `amount` - The amount taken from the user, including the fee
+/// * `fee` - The fee taken from the bridged tokens
+/// * `sol_fee` - The fee paid in lamports
+/// * `net_amount` - The amount bridged to the destination chain
+/// * `normalized_amount` - The net amount in the decimals of the token on the destination chain
+/// * `request_headroom` - How much more this single request could bridge to the destination
+///   chain (not a rate limit)
+/// * `rejection` - The code of the error `bridge_request` would fail with, if any
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, PartialEq, Eq, Debug)]
+pub struct BridgeQuote {
+    /// The amount taken from the user, including the fee; dust below the precision of the
+    /// destination chain is not taken
+    pub amount: u64,
+    /// The fee taken from the bridged tokens
+    pub fee: u64,
+    /// The fee paid in lamports
+    pub sol_fee: u64,
+    /// The amount bridged to the destination chain, in the smallest unit of the mint
+    pub net_amount: u64,
+    /// The net amount in the smallest unit of the token on the destination chain
+    pub normalized_amount: u128,
+    /// Per-request headroom: how far the requested amount is below `max_amount`, the maximum
+    /// amount of a single request to the destination chain; zero if the chain is not
+    /// registered or the maximum is exceeded. The bridge has no rate limit, so this does not
+    /// bound the amount of further requests
+    pub request_headroom: u64,
+    /// The code of the error `bridge_request` would fail with, `None` if it would succeed:
+    /// the Anchor or custom error code, or for built-in program errors their index (see
+    /// [`rejection_code`])
+    pub rejection: Option<u32>,
+}
+
+impl BridgeQuote {
+    /// Validates a bridge request and computes its fees and amounts.
+    ///
+    /// The balance of the user is not checked.
+    ///
+    /// # Arguments
+    /// * `chain_registry` - The registry of the destination chains
+    /// * `token_config` - The `TokenConfig` account of the mint, address checked by the caller
+    /// * `mint` - The mint of the tokens being bridged
+    /// * `vault` - The vault account
+    /// * `amount` - The amount of tokens to bridge to the destination chain
+    /// * `receiver` - The receiver's address on the destination chain
+    /// * `destination_chain` - The chain ID of the destination blockchain
+    ///
+    /// # Errors
+    /// The validation errors of `bridge_request`, see [`BridgeRequest::process_instruction`]
+    pub fn compute(
+        chain_registry: &ChainRegistry,
+        token_config: &AccountInfo,
+        mint: &Account<Mint>,
+        vault: &AccountInfo,
+        amount: u64,
+        receiver: &[u8],
+        destination_chain: u8,
+    ) -> Result<Self> {
+        // Validate amount
+        require!(amount > 0, CustomError::InvalidAmount);
+
+        // Validate the destination chain
+        let chain = chain_registry
+            .chain(destination_chain)
+            .ok_or(CustomError::ChainNotRegistered)?;
+        require!(chain.enabled, CustomError::ChainDisabled);
+        require!(
+            amount >= chain.min_amount && amount <= chain.max_amount,
+            CustomError::AmountOutOfRange
+        );
+        validate_receiver(chain, receiver)?;
+
+        // Validate the token
+        let burn = is_vault_mint_authority(mint, vault);
+        let token_config = TokenConfig::load(token_config)?;
+        let token_chain = token_config.check(destination_chain, burn)?;
+
+        // Deduct the fee in the bridged token
+        let (fee, sol_fee) = token_chain.fees(amount);
+        require!(fee < amount, CustomError::FeeExceedsAmount);
+
+        // Convert the net amount to the decimals of the token on the destination chain
+        let (normalized_amount, dust) =
+            convert_decimals((amount - fee) as u128, mint.decimals, token_chain.decimals)
+                .ok_or(CustomError::AmountOverflow)?;
+        require!(normalized_amount > 0, CustomError::AmountTooSmall);
+        let net_amount = amount - fee - dust as u64;
+
+        Ok(Self {
+            amount: net_amount + fee,
+            fee,
+            sol_fee,
+            net_amount,
+            normalized_amount,
+            request_headroom: chain.max_amount - amount,
+            rejection: None,
+        })
+    }
+}
+
+/// Account structure for the quote_bridge_request instruction.
+///
+/// This struct defines the accounts `bridge_request` validates against. None of them is
+/// written to.
+#[derive(Accounts)]
+#[instruction(instance_id: u64)]
+pub struct QuoteBridgeRequest<'info> {
+    /// The validator set account
+    #[account(
+        seeds = [VALIDATOR_SET_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_set.bump,
+        constraint = validator_set.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_set: Account<'info, ValidatorSet>,
+
+    /// The registry of the destination chains
+    #[account(
+        seeds = [CHAIN_REGISTRY_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = chain_registry.bump,
+        constraint = chain_registry.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub chain_registry: Account<'info, ChainRegistry>,
+
+    /// The vault account
+    #[account(
+        seeds = [VAULT_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+        constraint = vault.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// The token mint for the tokens being bridged
+    pub mint: Account<'info, Mint>,
+
+    /// The registry entry of the token being bridged
+    /// CHECK: The address is validated by the seeds, the account is loaded with
+    /// `TokenConfig::load` so an unregistered token is rejected with `TokenNotRegistered`
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, instance_id.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub token_config: UncheckedAccount<'info>,
+}
+
+impl<'info> QuoteBridgeRequest<'info> {
+    /// Process the quote_bridge_request instruction.
+    ///
+    /// Runs the validation and fee computation of `bridge_request` and returns the quote.
+    /// A request `bridge_request` would reject does not fail the instruction, whatever the
+    /// error; the quote carries the code of the error instead.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `amount` - The amount of tokens to bridge to the destination chain
+    /// * `receiver` - The receiver's address on the destination chain
+    /// * `destination_chain` - The chain ID of the destination blockchain
+    ///
+    /// # Returns
+    /// * `Result<BridgeQuote>` - The quote, set as return data of the instruction
+    pub fn process_instruction(
+        ctx: Context<Self>,
+        _instance_id: u64,
+        amount: u64,
+        receiver: Vec<u8>,
+        destination_chain: u8,
+    ) -> Result<BridgeQuote> {
+        let chain_registry = &ctx.accounts.chain_registry;
+
+        let quote = if ctx.accounts.validator_set.paused {
+            Err(error!(CustomError::BridgePaused))
+        } else {
+            BridgeQuote::compute(
+                chain_registry,
+                &ctx.accounts.token_config,
+                &ctx.accounts.mint,
+                &ctx.accounts.vault.to_account_info(),
+                amount,
+                &receiver,
+                destination_chain,
+            )
+        };
+
+        Ok(quote.unwrap_or_else(|err| BridgeQuote {
+            request_headroom: chain_registry
+                .chain(destination_chain)
+                .map_or(0, |chain| chain.max_amount.saturating_sub(amount)),
+            rejection: Some(rejection_code(&err)),
+            ..Default::default()
+        }))
+    }
+}
+
+/// Returns the code a quote reports for an error `bridge_request` would fail with.
+///
+/// Anchor and custom errors keep their code. Built-in program errors (for example from
+/// loading an account) are reported by their index, the upper half of their `u64` code, which
+/// is below the Anchor error codes.
+fn rejection_code(err: &Error) -> u32 {
+    match err {
+        Error::AnchorError(err) => err.error_code_number,
+        Error::ProgramError(err) => match &err.program_error {
+            ProgramError::Custom(code) => *code,
+            program_error => (u64::from(program_error.clone()) >> 32) as u32,
+        },
+    }
+}
@@ -0,0 +1,142 @@
+//! Report equivocation instruction for slashing validators that signed conflicting approvals.
+//!
+//! This module contains the logic for proving that a validator signed approval attestations
+//! for two different operations under the same batch ID. Approvals of bridging transactions
+//! and governance proposals are recorded from these attestations, so any approval a
+//! validator submitted can be used as evidence. Anyone can report: the proof is two ed25519
+//! precompile instructions in the same transaction. The validator's bond is slashed between
+//! the reporter and the treasury, and the validator is queued for removal by the next
+//! validator set update and jailed meanwhile, as far as `MIN_VALIDATORS` allows.
+
+use crate::*;
+
+/// Account structure for the report_equivocation instruction.
+///
+/// This struct defines the accounts required to report an equivocation.
+#[derive(Accounts)]
+#[instruction(instance_id: u64, validator: Pubkey)]
+pub struct ReportEquivocation<'info> {
+    /// The account reporting the equivocation, receives the reporter share of the bond
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+
+    /// The validator set account
+    #[account(
+        mut,
+        seeds = [VALIDATOR_SET_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_set.bump,
+        constraint = validator_set.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_set: Account<'info, ValidatorSet>,
+
+    /// The bond account of the validator, if it has ever bonded
+    #[account(
+        mut,
+        seeds = [VALIDATOR_BOND_SEED, instance_id.to_le_bytes().as_ref(), validator.as_ref()],
+        bump = validator_bond.bump,
+        constraint = validator_bond.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_bond: Option<Account<'info, ValidatorBond>>,
+
+    /// The treasury receiving the rest of the slashed bond
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = treasury.bump,
+        constraint = treasury.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// The instructions sysvar, inspected for the ed25519 signatures of the approvals
+    /// CHECK: address is checked against the instructions sysvar ID
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+impl<'info> ReportEquivocation<'info> {
+    /// Process the report_equivocation instruction.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `validator` - The equivocating validator
+    /// * `batch_id` - The batch ID both approvals were signed for
+    /// * `first_hash` - The operation hash of the first approval
+    /// * `second_hash` - The operation hash of the second approval
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
+    ///
+    /// # Errors
+    /// * `InvalidEquivocationProof` - If the hashes are equal, or either approval is not
+    ///   signed by the validator in an ed25519 instruction of the transaction
+    /// * `NothingToSlash` - If the validator has no bond left and is already queued for
+    ///   removal and jailed, or not in the validator set
+    pub fn process_instruction(
+        ctx: Context<Self>,
+        instance_id: u64,
+        validator: Pubkey,
+        batch_id: u64,
+        first_hash: [u8; 32],
+        second_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(
+            first_hash != second_hash,
+            CustomError::InvalidEquivocationProof
+        );
+
+        let validator_set = &mut ctx.accounts.validator_set;
+        let verified = ed25519_verified_messages(&ctx.accounts.instructions)?;
+        let validator_set_key = validator_set.key();
+        let signed = |hash: &[u8; 32]| {
+            let message = approval_message(&validator_set_key, batch_id, hash);
+            verified
+                .iter()
+                .any(|(pk, msg)| *pk == validator && *msg == message)
+        };
+        require!(
+            signed(&first_hash) && signed(&second_hash),
+            CustomError::InvalidEquivocationProof
+        );
+
+        // Slash the whole bond, including lamports that are unbonding
+        let (reporter_amount, treasury_amount) = match ctx.accounts.validator_bond.as_mut() {
+            Some(validator_bond) => {
+                let slashed = validator_bond.amount + validator_bond.unbonding_amount;
+                let reporter_amount = (slashed as u128
+                    * validator_set.config.slash_reporter_bps as u128
+                    / BPS_DENOMINATOR as u128) as u64;
+
+                validator_bond.amount = 0;
+                validator_bond.unbonding_amount = 0;
+                validator_bond.sub_lamports(slashed)?;
+                ctx.accounts.reporter.add_lamports(reporter_amount)?;
+                ctx.accounts
+                    .treasury
+                    .add_lamports(slashed - reporter_amount)?;
+
+                (reporter_amount, slashed - reporter_amount)
+            }
+            None => (0, 0),
+        };
+
+        let (jailed, removal_queued) = queue_removal(validator_set, &validator);
+        require!(
+            jailed || removal_queued || reporter_amount + treasury_amount > 0,
+            CustomError::NothingToSlash
+        );
+
+        emit!(ValidatorSlashedEvent {
+            instance_id,
+            validator,
+            batch_id,
+            reporter: ctx.accounts.reporter.key(),
+            reporter_amount,
+            treasury_amount,
+            jailed,
+            removal_queued,
+        });
+
+        Ok(())
+    }
+}
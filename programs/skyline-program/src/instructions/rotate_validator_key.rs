@@ -0,0 +1,112 @@
+//! Validator key rotation instruction for replacing a single validator key.
+//!
+//! This module contains the logic for rotating the key of one validator without a full
+//! validator set update round. The rotation is authorized by the validator itself (old
+//! key) and the new key, and waits out a short timelock during which the guardian can
+//! veto it.
+
+use crate::*;
+
+/// Account structure for the rotate_validator_key instruction.
+///
+/// This struct defines the accounts required to schedule a validator key rotation.
+#[derive(Accounts)]
+#[instruction(instance_id: u64)]
+pub struct RotateValidatorKey<'info> {
+    /// The payer for the key rotation account creation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The validator key being rotated out
+    pub old_key: Signer<'info>,
+
+    /// The key replacing the old key, signing proves possession
+    pub new_key: Signer<'info>,
+
+    /// The validator set account the rotated key belongs to
+    #[account(
+        mut,
+        seeds = [VALIDATOR_SET_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_set.bump,
+        constraint = validator_set.signers.contains(&old_key.key()) @ CustomError::InvalidSigner,
+        constraint = !validator_set.signers.contains(&new_key.key()) @ CustomError::AddingExistingSigner,
+        constraint = validator_set.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_set: Account<'info, ValidatorSet>,
+
+    /// The key rotation account, one per rotated key
+    #[account(
+        init,
+        payer = payer,
+        space = DISC as usize + KeyRotation::INIT_SPACE,
+        seeds = [KEY_ROTATION_SEED, instance_id.to_le_bytes().as_ref(), old_key.key().as_ref()],
+        bump
+    )]
+    pub key_rotation: Account<'info, KeyRotation>,
+
+    /// The system program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RotateValidatorKey<'info> {
+    /// Process the rotate_validator_key instruction.
+    ///
+    /// Schedules the rotation for `config.rotation_timelock_slots` in the future. If the
+    /// rotation timelock is zero, the rotation is activated immediately, and the snapshot of
+    /// the new validator set epoch and the record addresses of both keys (see
+    /// `execute_key_rotation`) are passed in remaining accounts.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
+    ///
+    /// # Errors
+    /// * `InvalidSigner` - If the old key is not in the validator set
+    /// * `AddingExistingSigner` - If the new key is already in the validator set
+    /// * `MissingActionAccount` - If the rotation is activated and the snapshot of the new epoch
+    ///   or a record address was not passed in remaining accounts
+    /// * `RotatedRecordExists` - If the rotation is activated and the new key already holds a
+    ///   registry record or bond
+    pub fn process_instruction(
+        ctx: Context<'_, '_, '_, 'info, Self>,
+        instance_id: u64,
+    ) -> Result<()> {
+        let key_rotation = &mut ctx.accounts.key_rotation;
+        let validator_set = &mut ctx.accounts.validator_set;
+
+        let current_slot = Clock::get()?.slot;
+        let activation_slot =
+            current_slot.saturating_add(validator_set.config.rotation_timelock_slots);
+
+        key_rotation.version = ACCOUNT_VERSION;
+        key_rotation.old_key = ctx.accounts.old_key.key();
+        key_rotation.new_key = ctx.accounts.new_key.key();
+        key_rotation.activation_slot = activation_slot;
+        key_rotation.bump = ctx.bumps.key_rotation;
+
+        if activation_slot > current_slot {
+            emit!(ValidatorKeyRotatedEvent {
+                instance_id,
+                old_key: key_rotation.old_key,
+                new_key: key_rotation.new_key,
+                activation_slot,
+                activated: false,
+            });
+            return Ok(());
+        }
+
+        activate_key_rotation(
+            key_rotation,
+            validator_set,
+            &ActionAccounts {
+                instance_id,
+                payer: &ctx.accounts.payer.to_account_info(),
+                system_program: &ctx.accounts.system_program.to_account_info(),
+                remaining_accounts: ctx.remaining_accounts,
+            },
+        )
+    }
+}
@@ -0,0 +1,85 @@
+//! Seal validator delta instruction for completing a large validator set update.
+//!
+//! This module contains the logic for sealing a validator delta. Sealing validates the
+//! complete update and commits to it with a hash, so validators can approve it through an
+//! `ApplyValidatorDelta` governance proposal.
+
+use crate::*;
+
+/// Account structure for the seal_validator_delta instruction.
+///
+/// This struct defines the accounts required to seal a validator delta.
+#[derive(Accounts)]
+#[instruction(instance_id: u64, batch_id: u64)]
+pub struct SealValidatorDelta<'info> {
+    /// The validator that assembled the delta
+    pub proposer: Signer<'info>,
+
+    /// The validator set the update applies to
+    #[account(
+        seeds = [VALIDATOR_SET_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_set.bump,
+        constraint = validator_set.last_batch_id < batch_id @ CustomError::InvalidBatchId,
+        constraint = validator_set.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_set: Account<'info, ValidatorSet>,
+
+    /// The validator delta to be sealed
+    #[account(
+        mut,
+        seeds = [
+            VALIDATOR_DELTA_SEED,
+            instance_id.to_le_bytes().as_ref(),
+            batch_id.to_le_bytes().as_ref(),
+        ],
+        bump = validator_delta.bump,
+        has_one = proposer @ CustomError::InvalidSigner,
+        constraint = validator_delta.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_delta: Account<'info, ValidatorDelta>,
+}
+
+impl<'info> SealValidatorDelta<'info> {
+    /// Process the seal_validator_delta instruction.
+    ///
+    /// The complete update is validated against the current validator set, and the delta
+    /// hash is stored and emitted. Once sealed, the delta can no longer change; it can only
+    /// be proposed (`ApplyValidatorDelta` with the emitted hash) or closed.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `batch_id` - The batch ID of the validator set update
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
+    ///
+    /// # Errors
+    /// * `InvalidBatchId` - If the batch_id is not greater than the last_batch_id
+    /// * `InvalidSigner` - If the signer is not the proposer of the delta
+    /// * `ValidatorDeltaSealed` - If the delta has already been sealed
+    /// * Any error of `validate_validator_update` (e.g. `AddingExistingSigner`)
+    pub fn process_instruction(ctx: Context<Self>, instance_id: u64, batch_id: u64) -> Result<()> {
+        let validator_delta = &mut ctx.accounts.validator_delta;
+
+        require!(!validator_delta.sealed, CustomError::ValidatorDeltaSealed);
+        validate_validator_update(
+            &ctx.accounts.validator_set,
+            &validator_delta.added,
+            &validator_delta.removed,
+        )?;
+
+        validator_delta.delta_hash = validator_delta.compute_hash()?;
+        validator_delta.sealed = true;
+
+        emit!(ValidatorDeltaSealedEvent {
+            instance_id,
+            batch_id,
+            delta_hash: validator_delta.delta_hash,
+            added: validator_delta.added.len() as u32,
+            removed: validator_delta.removed.len() as u32,
+        });
+
+        Ok(())
+    }
+}
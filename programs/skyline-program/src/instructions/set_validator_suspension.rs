@@ -0,0 +1,59 @@
+//! Validator suspension instruction for taking a validator out of consensus temporarily.
+//!
+//! This module contains the logic for a validator to suspend itself (for example during
+//! maintenance) and to reinstate itself afterwards. Suspended validators keep their seat,
+//! but cannot approve operations and do not count towards the threshold. The validator
+//! set can also suspend (jail) or reinstate any validator through a
+//! `SetValidatorSuspension` governance proposal.
+
+use crate::*;
+
+/// Account structure for the set_validator_suspension instruction.
+///
+/// This struct defines the accounts required for a validator to change its own suspension.
+#[derive(Accounts)]
+#[instruction(instance_id: u64)]
+pub struct SetValidatorSuspension<'info> {
+    /// The validator suspending or reinstating itself
+    pub validator: Signer<'info>,
+
+    /// The validator set the validator belongs to
+    #[account(
+        mut,
+        seeds = [VALIDATOR_SET_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_set.bump,
+        constraint = validator_set.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_set: Account<'info, ValidatorSet>,
+}
+
+impl<'info> SetValidatorSuspension<'info> {
+    /// Process the set_validator_suspension instruction.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `suspended` - `true` to suspend the validator, `false` to reinstate it
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
+    ///
+    /// # Errors
+    /// * `InvalidSigner` - If the signer is not in the validator set
+    /// * `ValidatorAlreadySuspended` - If the validator is already suspended
+    /// * `MinValidatorsNotMet` - If the active set would fall below `MIN_VALIDATORS`
+    /// * `ValidatorNotSuspended` - If the validator is not suspended
+    /// * `ValidatorSuspended` - If the validator was suspended through governance
+    pub fn process_instruction(
+        ctx: Context<Self>,
+        _instance_id: u64,
+        suspended: bool,
+    ) -> Result<()> {
+        set_suspension(
+            &mut ctx.accounts.validator_set,
+            &ctx.accounts.validator.key(),
+            suspended,
+            false,
+        )
+    }
+}
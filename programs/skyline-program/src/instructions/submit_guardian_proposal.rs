@@ -0,0 +1,119 @@
+//! Guardian proposal instruction for emergency and recovery actions.
+//!
+//! This module contains the entry point for creating and approving guardian proposals.
+//! The guardian council votes on its limited set of actions through the same approval
+//! accumulator pattern as the validators, with its own members, threshold and batch IDs.
+
+use crate::*;
+
+/// Account structure for the submit_guardian_proposal instruction.
+///
+/// This struct defines the accounts required to create or approve a guardian proposal.
+#[derive(Accounts)]
+#[instruction(instance_id: u64, action: GuardianAction, batch_id: u64)]
+pub struct SubmitGuardianProposal<'info> {
+    /// The payer for the proposal account creation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The guardian council the proposal is voted on by
+    #[account(
+        mut,
+        seeds = [GUARDIAN_COUNCIL_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = guardian_council.bump,
+        constraint = guardian_council.last_batch_id < batch_id @ CustomError::InvalidBatchId,
+        constraint = guardian_council.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub guardian_council: Account<'info, GuardianCouncil>,
+
+    /// The validator set account the actions apply to
+    #[account(
+        mut,
+        seeds = [VALIDATOR_SET_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_set.bump,
+        constraint = validator_set.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_set: Account<'info, ValidatorSet>,
+
+    /// The validator participation statistics, telling a stalled set from an idle one
+    #[account(
+        seeds = [VALIDATOR_STATS_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_stats.bump,
+        constraint = validator_stats.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_stats: Account<'info, ValidatorStats>,
+
+    /// The guardian proposal account to be created or approved
+    /// It grows with the recorded approvals, so an existing account is accepted at its current size
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = (DISC as usize + GuardianProposal::space(0)).max(proposal.data_len()),
+        seeds = [
+            GUARDIAN_PROPOSAL_SEED,
+            instance_id.to_le_bytes().as_ref(),
+            batch_id.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, GuardianProposal>,
+
+    /// The instructions sysvar, inspected for ed25519 admission proofs
+    /// CHECK: address is checked against the instructions sysvar ID
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// The system program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SubmitGuardianProposal<'info> {
+    /// Process the submit_guardian_proposal instruction.
+    ///
+    /// The first call validates the action and stores it, and subsequent calls from council
+    /// members approve it. Once the council threshold is met, the action is executed (or
+    /// scheduled, for a validator set recovery) and the proposal account is closed.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `action` - The typed guardian action to propose or approve
+    /// * `batch_id` - The council batch ID of the proposal (must be greater than the council's
+    ///   last_batch_id)
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
+    ///
+    /// # Errors
+    /// * `InvalidBatchId` - If the batch_id is not greater than the council's last_batch_id
+    /// * `InvalidProposalHash` - If approving a proposal with a different action than the original
+    /// * `NoSignersProvided` - If no council members are provided
+    /// * `DuplicateSignersProvided` - If duplicate signers are provided
+    /// * `InvalidSigner` - If a signer is not a council member
+    /// * `SignerAlreadyApproved` - If a signer has already approved this proposal
+    /// * `ValidatorSetNotStalled` - If a recovery is proposed while the validators still reach
+    ///   quorums or are seen with the threshold weight
+    pub fn process_instruction(
+        ctx: Context<'_, '_, '_, 'info, Self>,
+        instance_id: u64,
+        action: GuardianAction,
+        batch_id: u64,
+    ) -> Result<()> {
+        process_guardian_proposal(
+            &mut ctx.accounts.proposal,
+            ctx.bumps.proposal,
+            &mut ctx.accounts.guardian_council,
+            &mut ctx.accounts.validator_set,
+            &ctx.accounts.validator_stats,
+            &ctx.accounts.instructions.to_account_info(),
+            action,
+            batch_id,
+            &ActionAccounts {
+                instance_id,
+                payer: &ctx.accounts.payer.to_account_info(),
+                system_program: &ctx.accounts.system_program.to_account_info(),
+                remaining_accounts: ctx.remaining_accounts,
+            },
+        )
+    }
+}
@@ -0,0 +1,110 @@
+//! Governance proposal instruction for administrative changes.
+//!
+//! This module contains the entry point for creating and approving typed governance
+//! proposals. Every administrative change goes through the same approval accumulator
+//! and is scheduled once the validator consensus threshold is reached.
+
+use crate::*;
+
+/// Account structure for the submit_proposal instruction.
+///
+/// This struct defines the accounts required to create or approve a governance proposal.
+#[derive(Accounts)]
+#[instruction(instance_id: u64, action: GovernanceAction, batch_id: u64)]
+pub struct SubmitProposal<'info> {
+    /// The payer for the proposal account creation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The validator set account the proposal is voted on by
+    #[account(
+        mut,
+        seeds = [VALIDATOR_SET_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_set.bump,
+        constraint = validator_set.last_batch_id < batch_id @ CustomError::InvalidBatchId,
+        constraint = validator_set.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_set: Account<'info, ValidatorSet>,
+
+    /// The governance proposal account to be created or approved
+    /// It grows with the recorded approvals, so an existing account is accepted at its current size
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = (DISC as usize + GovernanceProposal::space(0, 0)).max(proposal.data_len()),
+        seeds = [
+            GOVERNANCE_PROPOSAL_SEED,
+            instance_id.to_le_bytes().as_ref(),
+            batch_id.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    /// The validator participation statistics
+    #[account(
+        mut,
+        seeds = [VALIDATOR_STATS_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_stats.bump,
+        constraint = validator_stats.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_stats: Account<'info, ValidatorStats>,
+
+    /// The instructions sysvar, inspected for ed25519 admission proofs and approval
+    /// attestations
+    /// CHECK: address is checked against the instructions sysvar ID
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// The system program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SubmitProposal<'info> {
+    /// Process the submit_proposal instruction.
+    ///
+    /// The first call validates the action against the current validator set and
+    /// stores it, and every call records the approvals it carries. Validators approve by
+    /// signing the approval attestation of the proposal hash (see [`attested_approvals`])
+    /// in ed25519 precompile instructions of the same transaction. Once the consensus
+    /// threshold is met, the action is scheduled (see [`process_proposal`]).
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `action` - The typed governance action to propose or approve
+    /// * `batch_id` - The batch ID of the proposal (must be greater than last_batch_id)
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
+    ///
+    /// # Errors
+    /// * `InvalidBatchId` - If the batch_id is not greater than the last_batch_id
+    /// * `InvalidProposalHash` - If approving a proposal with a different action than the original
+    /// * `NoSignersProvided` - If no approval attestation is provided
+    /// * `DuplicateSignersProvided` - If a validator attests twice
+    /// * `InvalidSigner` - If an attesting key is not in the validator set
+    /// * `SignerAlreadyApproved` - If a validator has already approved this proposal
+    pub fn process_instruction(
+        ctx: Context<'_, '_, '_, 'info, Self>,
+        instance_id: u64,
+        action: GovernanceAction,
+        batch_id: u64,
+    ) -> Result<()> {
+        process_proposal(
+            &mut ctx.accounts.proposal,
+            ctx.bumps.proposal,
+            &mut ctx.accounts.validator_set,
+            &mut ctx.accounts.validator_stats,
+            &ctx.accounts.instructions.to_account_info(),
+            action,
+            batch_id,
+            &ActionAccounts {
+                instance_id,
+                payer: &ctx.accounts.payer.to_account_info(),
+                system_program: &ctx.accounts.system_program.to_account_info(),
+                remaining_accounts: ctx.remaining_accounts,
+            },
+        )
+    }
+}
@@ -0,0 +1,78 @@
+//! Unbond instruction for validators to start withdrawing their bond.
+//!
+//! This module contains the logic for a validator to move bonded lamports into unbonding.
+//! Unbonding lamports stay slashable for `config.unbonding_slots` before they can be
+//! withdrawn with `withdraw_bond`.
+
+use crate::*;
+
+/// Account structure for the unbond instruction.
+///
+/// This struct defines the accounts required for a validator to start unbonding.
+#[derive(Accounts)]
+#[instruction(instance_id: u64)]
+pub struct Unbond<'info> {
+    /// The validator owning the bond
+    pub validator: Signer<'info>,
+
+    /// The validator set account holding the unbonding period
+    #[account(
+        seeds = [VALIDATOR_SET_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_set.bump,
+        constraint = validator_set.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_set: Account<'info, ValidatorSet>,
+
+    /// The bond account of the validator
+    #[account(
+        mut,
+        seeds = [VALIDATOR_BOND_SEED, instance_id.to_le_bytes().as_ref(), validator.key().as_ref()],
+        bump = validator_bond.bump,
+        constraint = validator_bond.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_bond: Account<'info, ValidatorBond>,
+}
+
+impl<'info> Unbond<'info> {
+    /// Process the unbond instruction.
+    ///
+    /// The unbonding period restarts for all unbonding lamports, including lamports
+    /// unbonded earlier.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `amount` - The number of lamports to unbond
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If the amount is zero
+    /// * `InsufficientBond` - If the amount exceeds the bonded lamports
+    pub fn process_instruction(ctx: Context<Self>, instance_id: u64, amount: u64) -> Result<()> {
+        require!(amount > 0, CustomError::InvalidAmount);
+
+        let validator_bond = &mut ctx.accounts.validator_bond;
+        require!(
+            validator_bond.amount >= amount,
+            CustomError::InsufficientBond
+        );
+
+        validator_bond.amount -= amount;
+        validator_bond.unbonding_amount += amount;
+        validator_bond.unbonding_slot = Clock::get()?
+            .slot
+            .saturating_add(ctx.accounts.validator_set.config.unbonding_slots);
+
+        emit!(BondUpdatedEvent {
+            instance_id,
+            validator: validator_bond.validator,
+            amount: validator_bond.amount,
+            unbonding_amount: validator_bond.unbonding_amount,
+            unbonding_slot: validator_bond.unbonding_slot,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+//! Validator info instruction for maintaining a validator's registry record.
+//!
+//! This module contains the logic for a validator to set its own display name, endpoint
+//! URL and foreign-chain keys. The same record can also be set by the validator set
+//! through a `SetValidatorInfo` governance proposal.
+
+use crate::*;
+
+/// Account structure for the update_validator_info instruction.
+///
+/// This struct defines the accounts required for a validator to update its own record.
+#[derive(Accounts)]
+#[instruction(instance_id: u64)]
+pub struct UpdateValidatorInfo<'info> {
+    /// The payer for the record account creation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The validator updating its own record
+    pub validator: Signer<'info>,
+
+    /// The validator set the validator belongs to
+    #[account(
+        seeds = [VALIDATOR_SET_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_set.bump,
+        constraint = validator_set.signers.contains(&validator.key()) @ CustomError::InvalidSigner,
+        constraint = validator_set.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_set: Account<'info, ValidatorSet>,
+
+    /// The registry record of the validator
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = DISC as usize + ValidatorInfo::INIT_SPACE,
+        seeds = [VALIDATOR_INFO_SEED, instance_id.to_le_bytes().as_ref(), validator.key().as_ref()],
+        bump
+    )]
+    pub validator_info: Account<'info, ValidatorInfo>,
+
+    /// The system program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateValidatorInfo<'info> {
+    /// Process the update_validator_info instruction.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `metadata` - The new metadata of the validator
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
+    ///
+    /// # Errors
+    /// * `InvalidSigner` - If the signer is not in the validator set
+    /// * `InvalidValidatorMetadata` - If the name or the endpoint is too long
+    pub fn process_instruction(
+        ctx: Context<Self>,
+        instance_id: u64,
+        metadata: ValidatorMetadata,
+    ) -> Result<()> {
+        validate_metadata(&metadata)?;
+
+        let validator_info = &mut ctx.accounts.validator_info;
+        require!(
+            validator_info.validator == Pubkey::default()
+                || validator_info.version == ACCOUNT_VERSION,
+            CustomError::AccountNotMigrated
+        );
+        validator_info.version = ACCOUNT_VERSION;
+        validator_info.validator = ctx.accounts.validator.key();
+        validator_info.metadata = metadata;
+        validator_info.bump = ctx.bumps.validator_info;
+
+        emit!(ValidatorInfoUpdatedEvent {
+            instance_id,
+            validator: validator_info.validator,
+            metadata: validator_info.metadata.clone(),
+        });
+
+        Ok(())
+    }
+}
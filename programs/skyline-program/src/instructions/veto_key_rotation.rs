@@ -0,0 +1,72 @@
+//! Veto key rotation instruction for cancelling scheduled validator key rotations.
+//!
+//! This module contains the logic for the guardian to cancel a validator key rotation
+//! during its timelock, for example when the old key was compromised and used to
+//! rotate the seat to an attacker-controlled key.
+
+use crate::*;
+
+/// Account structure for the veto_key_rotation instruction.
+///
+/// This struct defines the accounts required to veto a scheduled key rotation.
+#[derive(Accounts)]
+#[instruction(instance_id: u64, old_key: Pubkey)]
+pub struct VetoKeyRotation<'info> {
+    /// The payer of the transaction, receives the rotation rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The guardian of the validator set
+    #[account(
+        constraint = guardian.key() == validator_set.guardian @ CustomError::InvalidSigner,
+    )]
+    pub guardian: Signer<'info>,
+
+    /// The validator set account
+    #[account(
+        seeds = [VALIDATOR_SET_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_set.bump,
+        constraint = validator_set.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_set: Account<'info, ValidatorSet>,
+
+    /// The scheduled key rotation to veto
+    #[account(
+        mut,
+        close = payer,
+        seeds = [KEY_ROTATION_SEED, instance_id.to_le_bytes().as_ref(), old_key.as_ref()],
+        bump = key_rotation.bump,
+        constraint = key_rotation.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub key_rotation: Account<'info, KeyRotation>,
+}
+
+impl<'info> VetoKeyRotation<'info> {
+    /// Process the veto_key_rotation instruction.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `old_key` - The validator key of the scheduled rotation
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
+    ///
+    /// # Errors
+    /// * `InvalidSigner` - If the signer is not the guardian
+    pub fn process_instruction(
+        ctx: Context<Self>,
+        instance_id: u64,
+        _old_key: Pubkey,
+    ) -> Result<()> {
+        let key_rotation = &ctx.accounts.key_rotation;
+
+        emit!(KeyRotationVetoedEvent {
+            instance_id,
+            old_key: key_rotation.old_key,
+            new_key: key_rotation.new_key,
+        });
+
+        Ok(())
+    }
+}
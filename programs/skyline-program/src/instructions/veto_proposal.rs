@@ -0,0 +1,129 @@
+//! Veto proposal instruction for cancelling timelocked governance proposals.
+//!
+//! This module contains the logic for vetoing a governance proposal during its timelock.
+//! A scheduled proposal is cancelled either by the guardian alone, or once a blocking
+//! minority of validators (enough to have prevented the quorum) has vetoed it.
+
+use crate::*;
+
+/// Account structure for the veto_proposal instruction.
+///
+/// This struct defines the accounts required to veto a scheduled governance proposal.
+#[derive(Accounts)]
+#[instruction(instance_id: u64, batch_id: u64)]
+pub struct VetoProposal<'info> {
+    /// The payer of the transaction, receives the proposal rent if it is cancelled
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The validator set account
+    #[account(
+        mut,
+        seeds = [VALIDATOR_SET_SEED, instance_id.to_le_bytes().as_ref()],
+        bump = validator_set.bump,
+        constraint = validator_set.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_set: Account<'info, ValidatorSet>,
+
+    /// The scheduled governance proposal to veto
+    #[account(
+        mut,
+        seeds = [
+            GOVERNANCE_PROPOSAL_SEED,
+            instance_id.to_le_bytes().as_ref(),
+            batch_id.to_le_bytes().as_ref(),
+        ],
+        bump = proposal.bump,
+        constraint = proposal.status == ProposalStatus::Scheduled @ CustomError::ProposalNotScheduled,
+        constraint = proposal.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    /// The system program, used to grow the proposal account with the recorded vetoes
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> VetoProposal<'info> {
+    /// Process the veto_proposal instruction.
+    ///
+    /// The validator delta applied by an `ApplyValidatorDelta` proposal is passed in
+    /// remaining accounts alongside the signers.
+    ///
+    /// If the guardian is among the signers in remaining accounts, the proposal is
+    /// cancelled immediately. Otherwise the validator signers are recorded as vetoes and
+    /// the proposal is cancelled once validators holding more than `active - threshold_weight`
+    /// weight have vetoed it, where `active` is the total weight of the validators that are
+    /// not suspended.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `batch_id` - The batch ID of the scheduled proposal
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
+    ///
+    /// # Errors
+    /// * `ProposalNotScheduled` - If the proposal is not waiting for activation
+    /// * `NoSignersProvided` - If no signers are provided
+    /// * `DuplicateSignersProvided` - If duplicate signers are provided
+    /// * `InvalidSigner` - If a signer is neither the guardian nor a validator
+    /// * `ValidatorSuspended` - If a validator signer is suspended
+    /// * `SignerAlreadyApproved` - If a validator has already vetoed this proposal
+    /// * `MissingActionAccount` - If the validator delta of an `ApplyValidatorDelta` proposal
+    ///   is not passed in remaining accounts
+    pub fn process_instruction(
+        ctx: Context<'_, '_, '_, 'info, Self>,
+        instance_id: u64,
+        batch_id: u64,
+    ) -> Result<()> {
+        let validator_set = &mut ctx.accounts.validator_set;
+        let proposal = &mut ctx.accounts.proposal;
+        let payer = &ctx.accounts.payer;
+
+        let signer_keys = signer_keys(ctx.remaining_accounts);
+        let guardian = validator_set.guardian;
+        let by_guardian = guardian != Pubkey::default() && signer_keys.contains(&guardian);
+
+        if !by_guardian {
+            let signers = collect_approvals(&signer_keys, validator_set, &proposal.vetoes)?;
+            proposal.vetoes.extend(signers.iter());
+            grow_account(
+                &proposal.to_account_info(),
+                &payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                DISC as usize
+                    + GovernanceProposal::space(proposal.signers.len(), proposal.vetoes.len()),
+            )?;
+
+            // A blocking minority is the smallest weight without which no quorum can form
+            let blocking_minority =
+                active_weight(validator_set) - validator_set.threshold_weight + 1;
+            if approved_weight(validator_set, &proposal.vetoes) < blocking_minority {
+                return Ok(());
+            }
+        }
+
+        proposal.action.cancel(
+            validator_set,
+            batch_id,
+            &ActionAccounts {
+                instance_id,
+                payer: &payer.to_account_info(),
+                system_program: &ctx.accounts.system_program.to_account_info(),
+                remaining_accounts: ctx.remaining_accounts,
+            },
+        )?;
+
+        emit!(ProposalVetoedEvent {
+            instance_id,
+            proposal_id: proposal.id,
+            batch_id,
+            by_guardian,
+        });
+
+        proposal.close(payer.to_account_info())?;
+
+        Ok(())
+    }
+}
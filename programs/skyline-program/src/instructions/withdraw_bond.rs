@@ -0,0 +1,65 @@
+//! Withdraw bond instruction for validators to take back unbonded lamports.
+//!
+//! This module contains the logic for a validator to withdraw its unbonding lamports once
+//! the unbonding period has passed without the bond being slashed.
+
+use crate::*;
+
+/// Account structure for the withdraw_bond instruction.
+///
+/// This struct defines the accounts required for a validator to withdraw unbonded lamports.
+#[derive(Accounts)]
+#[instruction(instance_id: u64)]
+pub struct WithdrawBond<'info> {
+    /// The validator owning the bond, receives the lamports
+    #[account(mut)]
+    pub validator: Signer<'info>,
+
+    /// The bond account of the validator
+    #[account(
+        mut,
+        seeds = [VALIDATOR_BOND_SEED, instance_id.to_le_bytes().as_ref(), validator.key().as_ref()],
+        bump = validator_bond.bump,
+        constraint = validator_bond.version == ACCOUNT_VERSION @ CustomError::AccountNotMigrated,
+    )]
+    pub validator_bond: Account<'info, ValidatorBond>,
+}
+
+impl<'info> WithdrawBond<'info> {
+    /// Process the withdraw_bond instruction.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context containing all required accounts
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    ///
+    /// # Returns
+    /// * `Result<()>` - Returns Ok(()) on success or an error on failure
+    ///
+    /// # Errors
+    /// * `InsufficientBond` - If no lamports are unbonding
+    /// * `UnbondingNotComplete` - If the unbonding period has not passed
+    pub fn process_instruction(ctx: Context<Self>, instance_id: u64) -> Result<()> {
+        let validator_bond = &mut ctx.accounts.validator_bond;
+        let amount = validator_bond.unbonding_amount;
+
+        require!(amount > 0, CustomError::InsufficientBond);
+        require!(
+            Clock::get()?.slot >= validator_bond.unbonding_slot,
+            CustomError::UnbondingNotComplete
+        );
+
+        validator_bond.unbonding_amount = 0;
+        validator_bond.sub_lamports(amount)?;
+        ctx.accounts.validator.add_lamports(amount)?;
+
+        emit!(BondUpdatedEvent {
+            instance_id,
+            validator: validator_bond.validator,
+            amount: validator_bond.amount,
+            unbonding_amount: 0,
+            unbonding_slot: validator_bond.unbonding_slot,
+        });
+
+        Ok(())
+    }
+}
@@ -11,7 +11,8 @@
 //! - **Validator Management**: Initialize and manage a set of validators that control bridge operations
 //! - **Token Bridging**: Transfer tokens to vault or burn tokens on source chain and mint/transfer equivalent tokens on destination chain
 //! - **Bridge Requests**: Create and manage cross-chain transfer requests
-//! - **Consensus Mechanism**: Require threshold validator approval for critical operations (calculated as ceil(2/3) of validators)
+//! - **Consensus Mechanism**: Require validator approvals for critical operations whose weight meets the
+//!   threshold weight (`quorum_weight_bps` of the active validator weight, rounded up)
 //!
 //! ## Architecture
 //!
@@ -19,23 +20,71 @@
 //! - `ValidatorSet`: Stores the list of validators, consensus threshold, last batch ID, and bridge request count
 //! - `Vault`: Represents the vault account that holds bridged tokens
 //! - `BridgingTransaction`: Represents validator-approved transactions for minting/transferring tokens to recipients
-//! - `ValidatorDelta`: Represents pending validator set updates that require consensus
+//! - `GovernanceProposal`: Represents pending governance actions (such as validator set updates) that require consensus
+//! - `KeyRotation`: Represents a scheduled rotation of a single validator key
+//! - `ValidatorInfo`: Registry record of a validator (display name, endpoint, foreign-chain keys)
+//! - `ValidatorStats`: Participation statistics of the validators (approvals, missed batches, heartbeats)
+//! - `ValidatorBond`: SOL bond of a validator, slashable on proven equivocation
+//! - `Treasury`: Receives the treasury share of slashed bonds, spent through governance
+//! - `GuardianCouncil`: Members and threshold of the guardian council appointed by the validators
+//! - `GuardianProposal`: Represents pending guardian council actions (pause, vetoes, recovery)
+//! - `ChainRegistry`: Destination chains the bridge serves, registered through governance
+//! - `TokenConfig`: Registry entry of a bridged token (mode, chains, foreign assets and fees), one per mint
+//! - `FeeVault`: Collects the bridge request fees, spent through governance and on rewards
+//! - `RewardAccount`: Rewards credited to a relayer or validator for executed bridge transactions
+//! - `InitializerGrant`: Authorizes a key to initialize a bridge instance, granted through governance
+//!
+//! Every PDA is seeded with the bridge instance ID (`DeploymentConfig::instance_id`), so one
+//! deployment can host several independent bridges; instructions take the instance ID as their
+//! first argument and every event carries it.
 //!
 //! ## Security Model
 //!
-//! - Validator set requires minimum 4 and maximum 128 validators
-//! - Consensus threshold is automatically calculated using the formula: num_signers - floor((num_signers - 1) / 3)
-//! - All critical operations require validator signatures meeting the threshold
+//! - Only the program upgrade authority, or a key authorized by a validator quorum, can initialize the bridge
+//! - Program upgrades are signed by a program PDA and require a timelocked validator quorum
+//! - Validator set requires minimum 4 and maximum 256 validators
+//! - Consensus threshold is automatically calculated using the formula: num_active - floor((num_active - 1) / 3)
+//! - Suspended validators keep their seat, but cannot approve and do not count towards the threshold
+//! - All critical operations require validator approvals meeting the threshold, signed as
+//!   ed25519 attestations that double as evidence of equivocation
 //! - Validator set changes require approval from current validator set
+//! - New validator keys must prove possession (co-sign or ed25519 signature) before admission
+//! - Validator set changes are timelocked and can be vetoed by the guardian or a blocking minority
+//! - Validator churn per time window is capped so consecutive quorums keep intersecting
+//! - Validators bond SOL; signing conflicting approvals for one batch ID gets the bond slashed
+//! - Every account stores its layout version; instructions reject accounts that have not been migrated
+//! - Bridge requests are only accepted to registered and enabled destination chains
+//! - Only registered and enabled tokens are bridged, in their registered mode and to and from
+//!   the chains they are registered for
+//! - Bridge fees are kept in the fee vault and only leave it through a timelocked governance action
 //! - Batch IDs ensure operations are processed in order and prevent replay attacks
+//! - A separate guardian council can pause bridging, veto timelocked actions and, after a long
+//!   timelock, replace a validator set that has stopped reaching quorums
 //!
 //! ## Instructions
 //!
 //! - `initialize`: Initialize the validator set and vault for the bridge system
 //! - `bridge_request`: Create a cross-chain transfer request and transfer source tokens to vault
-//! - `create_or_approve_vsu`: Create or approve a validator set update (requires current validator approval)
+//! - `quote_bridge_request`: Quote the fees and amounts of a bridge request, or why it would be rejected
+//! - `bridge_vsu`: Create or approve a validator set update (requires current validator approval)
+//! - `submit_proposal`: Create or approve a typed governance proposal (requires current validator approval)
+//! - `execute_proposal`: Activate a scheduled governance proposal once its timelock has expired
+//! - `veto_proposal`: Cancel a scheduled governance proposal (guardian or blocking minority of validators)
+//! - `append_validator_delta` / `seal_validator_delta` / `close_validator_delta`: Assemble a validator
+//!   set update too large for a single instruction, applied by an `ApplyValidatorDelta` proposal
+//! - `rotate_validator_key`: Schedule the rotation of a single validator key (old and new key sign)
+//! - `execute_key_rotation`: Activate a scheduled key rotation once its timelock has expired
+//! - `veto_key_rotation`: Cancel a scheduled key rotation (guardian)
+//! - `update_validator_info`: Update the registry record of the signing validator
+//! - `set_validator_suspension`: Suspend or reinstate the signing validator
+//! - `heartbeat`: Record a liveness heartbeat of the signing validator
+//! - `bond` / `unbond` / `withdraw_bond`: Manage the SOL bond of the signing validator
+//! - `claim_rewards`: Claim the rewards credited to the signing relayer or validator
+//! - `report_equivocation`: Slash a validator that signed conflicting approvals and queue its removal (permissionless)
 //! - `bridge_transaction`: Create or approve a bridging transaction to transfer tokens to recipients (requires validator approval)
-//! - `close_request`: Close a bridging request account (requires validator approval)
+//! - `submit_guardian_proposal`: Create or approve a guardian council proposal (requires council approval)
+//! - `execute_guardian_proposal`: Activate a scheduled validator set recovery once its timelock has expired
+//! - `migrate`: Upgrade an account written before the layout version field to the current layout (permissionless)
 
 use anchor_lang::prelude::*;
 
@@ -57,6 +106,23 @@ pub use events::*;
 pub mod helpers;
 pub use helpers::*;
 
+pub mod governance;
+pub use governance::*;
+
+pub mod guardian;
+pub use guardian::*;
+
+pub mod stats;
+
+pub mod rewards;
+
+#[cfg(all(
+    feature = "custom-heap",
+    not(feature = "no-entrypoint"),
+    target_os = "solana"
+))]
+mod heap;
+
 declare_id!("CkTNcuk9EELmuR65eCfzKfz8XpDvJ27FPFHauGHVD1E9");
 
 #[program]
@@ -67,24 +133,45 @@ pub mod skyline_program {
     ///
     /// This instruction sets up the initial validator set that will control all bridge operations
     /// and creates the vault account that will hold bridged tokens. The validators must be unique
-    /// and meet the minimum/maximum requirements. The consensus threshold is automatically calculated
-    /// as 2/3 of the validator count (rounded up).
+    /// and meet the minimum/maximum requirements. The threshold weight is automatically calculated
+    /// as `quorum_weight_bps` of the validator weight (rounded up). Only the program upgrade
+    /// authority, or the key of the instance's initializer grant (see `AuthorizeInitializer`),
+    /// can initialize the bridge.
     ///
     /// # Arguments
     /// * `ctx` - The context containing accounts for initialization
-    /// * `validators` - Vector of validator public keys (4-128 validators required)
+    /// * `validators` - Vector of validator public keys (4-256 validators required)
     /// * `last_id` - Optional initial batch ID (defaults to 0 if not provided)
+    /// * `config` - Optional bridge configuration (defaults to `BridgeConfig::default()`)
+    /// * `weights` - Optional voting weights, one per validator (defaults to `DEFAULT_VALIDATOR_WEIGHT`)
+    /// * `deployment` - Chain ID and bridge instance of this deployment
     ///
     /// # Errors
-    /// * `MaxValidatorsExceeded` - If more than 10 validators are provided
-    /// * `MinValidatorsNotMet` - If fewer than 4 validators are provided
+    /// * `UnauthorizedInitializer` - If the signer is neither the program upgrade authority nor
+    ///   the key of the initializer grant
+    /// * `MaxValidatorsExceeded` - If more than `MAX_VALIDATORS` (256) validators are provided
+    /// * `MinValidatorsNotMet` - If fewer than `MIN_VALIDATORS` (4) validators are provided
     /// * `ValidatorsNotUnique` - If duplicate validators are provided
+    /// * `MissingAdmissionProof` - If a validator has not proven possession of its key
+    /// * `InvalidConfig` - If the configuration is out of range
+    /// * `ValidatorWeightsMismatch` - If weights are given, but not one per validator
+    /// * `InvalidValidatorWeight` - If a weight is zero or above `MAX_VALIDATOR_WEIGHT`
     pub fn initialize(
         ctx: Context<Initialize>,
         validators: Vec<Pubkey>,
         last_id: Option<u64>,
+        config: Option<BridgeConfig>,
+        weights: Option<Vec<u64>>,
+        deployment: DeploymentConfig,
     ) -> Result<()> {
-        Initialize::process_instruction(ctx, validators, last_id.unwrap_or(0))
+        Initialize::process_instruction(
+            ctx,
+            validators,
+            last_id.unwrap_or(0),
+            config.unwrap_or_default(),
+            weights.unwrap_or_default(),
+            deployment,
+        )
     }
 
     /// Create a cross-chain bridging request and transfer source tokens to vault.
@@ -92,80 +179,632 @@ pub mod skyline_program {
     /// This instruction creates a bridging request for transferring tokens to another chain.
     /// The source tokens are either burned (if the vault is the mint authority) or transferred
     /// to the vault account, and a request event is emitted that can be processed by validators
-    /// to mint/transfer equivalent tokens on the destination chain.
+    /// to mint/transfer equivalent tokens on the destination chain. The fees of the token on the
+    /// destination chain are collected into the fee vault.
     ///
     /// # Arguments
     /// * `ctx` - The context containing accounts for the bridge request
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
     /// * `amount` - The amount of tokens to bridge
     /// * `receiver` - The receiver's address on the destination chain (variable length byte vector)
     /// * `destination_chain` - The chain ID of the destination blockchain
     ///
     /// # Errors
     /// * `InsufficientFunds` - If the sender doesn't have enough tokens
+    /// * `BridgePaused` - If the guardian council has paused the bridge
+    /// * `ChainNotRegistered` - If the destination chain is not in the chain registry
+    /// * `ChainDisabled` - If the destination chain is disabled
+    /// * `AmountOutOfRange` - If the amount is outside the bounds of the destination chain
+    /// * `InvalidReceiverAddress` - If the receiver is not a valid address on the destination
+    ///   chain
+    /// * `TokenNotRegistered` - If the mint is not in the token registry
+    /// * `TokenDisabled` - If the token is disabled
+    /// * `TokenChainNotAllowed` - If the token cannot be bridged to the destination chain
+    /// * `TokenModeMismatch` - If the registered mode does not match the mint authority
+    /// * `FeeExceedsAmount` - If the fee in the bridged token is not below the amount
+    /// * `AmountTooSmall` - If the amount is zero once converted to the foreign decimals
+    /// * `AmountOverflow` - If the converted amount overflows
     pub fn bridge_request(
         ctx: Context<BridgeRequest>,
+        instance_id: u64,
         amount: u64,
         receiver: Vec<u8>,
         destination_chain: u8,
     ) -> Result<()> {
-        BridgeRequest::process_instruction(ctx, amount, receiver, destination_chain)
+        BridgeRequest::process_instruction(ctx, instance_id, amount, receiver, destination_chain)
+    }
+
+    /// Quote a cross-chain bridging request without moving any tokens.
+    ///
+    /// Read-only, meant to be simulated. Runs the validation and fee computation of
+    /// `bridge_request` and returns a `BridgeQuote` as return data: the fees, the net and
+    /// normalized amounts, the per-request headroom below the maximum amount of a request to
+    /// the destination chain (not a rate limit) and,
+    /// if `bridge_request` would reject the request, the code of its error. The balance of the
+    /// sender is not checked.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for the quote
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `amount` - The amount of tokens to bridge
+    /// * `receiver` - The receiver's address on the destination chain (variable length byte vector)
+    /// * `destination_chain` - The chain ID of the destination blockchain
+    pub fn quote_bridge_request(
+        ctx: Context<QuoteBridgeRequest>,
+        instance_id: u64,
+        amount: u64,
+        receiver: Vec<u8>,
+        destination_chain: u8,
+    ) -> Result<BridgeQuote> {
+        QuoteBridgeRequest::process_instruction(
+            ctx,
+            instance_id,
+            amount,
+            receiver,
+            destination_chain,
+        )
     }
 
     /// Create or approve a validator set update (VSU) for the bridge.
     ///
     /// This instruction allows changing the set of validators that control bridge operations.
-    /// The first call creates a validator set change proposal, and subsequent calls from validators
-    /// approve the proposal. Requires approval from the current validator set meeting the consensus
-    /// threshold and maintains the same validation rules as initialization (unique validators, 4-10 count).
+    /// The first call creates a validator set change proposal, and every call records the approval
+    /// attestations of validators it carries (ed25519 signatures of `helpers::approval_message` over the
+    /// proposal hash). Requires approval from the current validator set meeting the consensus
+    /// threshold and maintains the same validation rules as initialization (unique validators,
+    /// `MIN_VALIDATORS..=MAX_VALIDATORS` count).
+    /// Every added key must prove possession before the update is scheduled.
     ///
     /// # Arguments
     /// * `ctx` - The context containing accounts for creating or approving the validator set change
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
     /// * `added` - Vector of new validator public keys to add
-    /// * `removed` - Vector of validator indexes to remove
+    /// * `removed` - Vector of validator public keys to remove
     /// * `batch_id` - The batch ID of the validator set change (must be greater than last_batch_id)
+    /// * `foreign_keys` - Foreign-chain keys of the added validators (empty or one per added validator)
+    /// * `weights` - Voting weights of the added validators (empty or one per added validator)
     ///
     /// # Errors
-    /// * `MaxValidatorsExceeded` - If more than 10 validators would result from the change
-    /// * `MinValidatorsNotMet` - If fewer than 4 validators would result from the change
+    /// * `ForeignKeysMismatch` - If foreign keys are given, but not one per added validator
+    /// * `ValidatorWeightsMismatch` - If weights are given, but not one per added validator
+    /// * `InvalidValidatorWeight` - If a weight is zero or above `MAX_VALIDATOR_WEIGHT`
+    /// * `MaxValidatorsExceeded` - If more than `MAX_VALIDATORS` validators would result from the change
+    /// * `MinValidatorsNotMet` - If fewer than `MIN_VALIDATORS` validators would result from the change
+    /// * `RemovingNonExistentSigner` - If a removed key is not in the current validator set
+    /// * `ChurnLimitExceeded` - If the change exceeds the churn budget of the current window
     /// * `AddingExistingSigner` - If attempting to add a validator that already exists
     /// * `InvalidBatchId` - If the batch_id is not greater than the last_batch_id
     /// * `InvalidProposalHash` - If approving a proposal with a different hash than the original
-    /// * `NoSignersProvided` - If no validator signers are provided
-    /// * `NotEnoughSigners` - If insufficient current validators have signed (checked when threshold is met)
-    /// * `InvalidSigner` - If a signer is not in the current validator set
-    pub fn bridge_vsu(
-        ctx: Context<BridgeVSU>,
+    /// * `NoSignersProvided` - If no approval attestation is provided
+    /// * `InvalidSigner` - If an attesting key is not in the current validator set
+    pub fn bridge_vsu<'info>(
+        ctx: Context<'_, '_, '_, 'info, BridgeVSU<'info>>,
+        instance_id: u64,
         added: Vec<Pubkey>,
         removed: Vec<Pubkey>,
         batch_id: u64,
+        foreign_keys: Vec<ForeignKeys>,
+        weights: Vec<u64>,
     ) -> Result<()> {
-        BridgeVSU::process_instruction(ctx, added, removed, batch_id)
+        BridgeVSU::process_instruction(
+            ctx,
+            instance_id,
+            added,
+            removed,
+            batch_id,
+            foreign_keys,
+            weights,
+        )
     }
 
     /// Create or approve a bridging transaction.
     ///
     /// This instruction creates or approves a bridging transaction for transferring tokens from the vault
-    /// to a recipient. The first call creates the transaction, and subsequent calls from validators approve it.
+    /// to a recipient. The first call creates the transaction, and every call records the approval attestations
+    /// of validators it carries (ed25519 signatures of `helpers::approval_message` over
+    /// `BridgingTransaction::operation_hash`), so anyone can relay them.
     /// Once the consensus threshold is met, the tokens are automatically minted (if vault is mint authority)
     /// or transferred from the vault to the recipient's associated token account, and the transaction account is closed.
+    /// The executing payer and the approving validators are credited with the configured rewards, their reward
+    /// accounts are passed via remaining accounts.
     ///
     /// # Arguments
     /// * `ctx` - The context containing accounts for the bridging transaction
-    /// * `amount` - The amount of tokens to transfer to the recipient
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `amount` - The amount of tokens to transfer to the recipient, in the smallest unit of
+    ///   the token on the source chain
     /// * `batch_id` - The batch ID of the transaction (must be greater than last_batch_id)
+    /// * `source_chain` - The chain ID of the chain the tokens are bridged from
     ///
     /// # Errors
     /// * `InvalidBatchId` - If the batch_id is not greater than the last_batch_id
+    /// * `BridgePaused` - If the guardian council has paused the bridge
+    /// * `TokenNotRegistered` - If the mint is not in the token registry
+    /// * `TokenDisabled` - If the token is disabled
+    /// * `TokenChainNotAllowed` - If the token cannot be bridged from the source chain
+    /// * `TokenModeMismatch` - If the registered mode does not match the mint authority
+    /// * `AmountTooSmall` - If the amount is zero once converted to the decimals of the mint
+    /// * `AmountOverflow` - If the converted amount does not fit in a `u64`
     /// * `InvalidReceiver` - If the receiver is the same as the payer
-    /// * `NoSignersProvided` - If no validator signers are provided
-    /// * `SignerAlreadyApproved` - If a signer has already approved this transaction
-    /// * `NotEnoughSigners` - If insufficient validators have signed (checked when threshold is met)
-    /// * `InvalidSigner` - If a signer is not in the validator set
-    pub fn bridge_transaction(
-        ctx: Context<BridgeTransaction>,
-        amount: u64,
+    /// * `NoSignersProvided` - If no approval attestation is provided
+    /// * `SignerAlreadyApproved` - If a validator has already approved this transaction
+    /// * `InvalidSigner` - If an attesting key is not in the validator set
+    pub fn bridge_transaction<'info>(
+        ctx: Context<'_, '_, '_, 'info, BridgeTransaction<'info>>,
+        instance_id: u64,
+        amount: u128,
+        batch_id: u64,
+        source_chain: u8,
+    ) -> Result<()> {
+        BridgeTransaction::process_instruction(ctx, instance_id, amount, batch_id, source_chain)
+    }
+
+    /// Create or approve a governance proposal.
+    ///
+    /// This instruction is the generic entry point for administrative changes voted on by the
+    /// validator set. The first call validates and stores the typed action, and every call records
+    /// the approval attestations of validators it carries (ed25519 signatures of
+    /// `helpers::approval_message` over the proposal hash). Once the consensus threshold is met, `last_batch_id` is updated
+    /// and the action is scheduled for `config.timelock_slots` in the future (see `execute_proposal`
+    /// and `veto_proposal`).
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for creating or approving the proposal
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `action` - The typed governance action (see `GovernanceAction`)
+    /// * `batch_id` - The batch ID of the proposal (must be greater than last_batch_id)
+    ///
+    /// # Errors
+    /// * `InvalidBatchId` - If the batch_id is not greater than the last_batch_id
+    /// * `InvalidProposalHash` - If approving a proposal with a different action than the original
+    /// * `NoSignersProvided` - If no approval attestation is provided
+    /// * `SignerAlreadyApproved` - If a validator has already approved this proposal
+    /// * `InvalidSigner` - If an attesting key is not in the validator set
+    /// * Any action-specific validation error (e.g. `AddingExistingSigner`)
+    pub fn submit_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, SubmitProposal<'info>>,
+        instance_id: u64,
+        action: GovernanceAction,
+        batch_id: u64,
+    ) -> Result<()> {
+        SubmitProposal::process_instruction(ctx, instance_id, action, batch_id)
+    }
+
+    /// Activate a scheduled governance proposal.
+    ///
+    /// Timelocked proposals (such as validator set updates) are scheduled when they reach quorum
+    /// and can be activated by anyone once their activation slot is reached. Until then the
+    /// outgoing validator set keeps operating. Accounts written by the action (such as validator
+    /// registry records) are passed via remaining accounts.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for activating the proposal
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `batch_id` - The batch ID of the scheduled proposal
+    ///
+    /// # Errors
+    /// * `ProposalNotScheduled` - If the proposal has not reached quorum
+    /// * `TimelockNotExpired` - If the activation slot has not been reached
+    /// * `MissingActionAccount` - If an account written by the action is missing
+    pub fn execute_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>,
+        instance_id: u64,
+        batch_id: u64,
+    ) -> Result<()> {
+        ExecuteProposal::process_instruction(ctx, instance_id, batch_id)
+    }
+
+    /// Veto a scheduled governance proposal.
+    ///
+    /// The guardian can cancel a scheduled proposal on its own. Validators can cancel it once a
+    /// blocking minority (`active - threshold + 1`) has vetoed it. Signers are passed via
+    /// remaining accounts.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for vetoing the proposal
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `batch_id` - The batch ID of the scheduled proposal
+    ///
+    /// # Errors
+    /// * `ProposalNotScheduled` - If the proposal is not waiting for activation
+    /// * `InvalidSigner` - If a signer is neither the guardian nor a validator
+    /// * `SignerAlreadyApproved` - If a validator has already vetoed this proposal
+    pub fn veto_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, VetoProposal<'info>>,
+        instance_id: u64,
+        batch_id: u64,
+    ) -> Result<()> {
+        VetoProposal::process_instruction(ctx, instance_id, batch_id)
+    }
+
+    /// Append a chunk of added and removed validators to a validator delta.
+    ///
+    /// Validator set updates larger than `MAX_VALIDATORS_CHANGE` are assembled in a
+    /// `ValidatorDelta` across multiple transactions. The first call creates the delta for
+    /// `batch_id` and makes the signing validator its proposer. Added keys prove possession of
+    /// their key in the chunk that adds them (signers in remaining accounts or ed25519 proofs).
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for appending to the delta
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `batch_id` - The batch ID of the validator set update (must be greater than last_batch_id)
+    /// * `added` - Validator keys to add
+    /// * `removed` - Validator keys to remove
+    /// * `weights` - Voting weights of the added validators (empty for the default weight)
+    ///
+    /// # Errors
+    /// * `InvalidSigner` - If the signer is not a validator or not the proposer of the delta
+    /// * `ValidatorDeltaSealed` - If the delta has already been sealed
+    /// * `MaxValidatorsExceeded` - If the delta would add or remove more than `MAX_VALIDATORS` validators
+    /// * `MissingAdmissionProof` - If an added key has not proven possession of its key
+    pub fn append_validator_delta(
+        ctx: Context<AppendValidatorDelta>,
+        instance_id: u64,
+        batch_id: u64,
+        added: Vec<Pubkey>,
+        removed: Vec<Pubkey>,
+        weights: Vec<u64>,
+    ) -> Result<()> {
+        AppendValidatorDelta::process_instruction(
+            ctx,
+            instance_id,
+            batch_id,
+            added,
+            removed,
+            weights,
+        )
+    }
+
+    /// Seal a validator delta.
+    ///
+    /// Validates the complete update against the validator set and commits to it with a hash
+    /// (emitted in `ValidatorDeltaSealedEvent`). Validators then approve the update through an
+    /// `ApplyValidatorDelta` proposal for the same batch ID, passing the delta account in
+    /// remaining accounts.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for sealing the delta
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `batch_id` - The batch ID of the validator set update
+    ///
+    /// # Errors
+    /// * `InvalidSigner` - If the signer is not the proposer of the delta
+    /// * `ValidatorDeltaSealed` - If the delta has already been sealed
+    /// * Any validator set update validation error (e.g. `AddingExistingSigner`)
+    pub fn seal_validator_delta(
+        ctx: Context<SealValidatorDelta>,
+        instance_id: u64,
+        batch_id: u64,
+    ) -> Result<()> {
+        SealValidatorDelta::process_instruction(ctx, instance_id, batch_id)
+    }
+
+    /// Close a validator delta and return its rent to the proposer.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for closing the delta
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `batch_id` - The batch ID of the validator set update
+    ///
+    /// # Errors
+    /// * `InvalidSigner` - If the signer is not the proposer of the delta
+    /// * `ValidatorUpdatePending` - If the update applying the delta is scheduled
+    pub fn close_validator_delta(
+        ctx: Context<CloseValidatorDelta>,
+        instance_id: u64,
+        batch_id: u64,
+    ) -> Result<()> {
+        CloseValidatorDelta::process_instruction(ctx, instance_id, batch_id)
+    }
+
+    /// Rotate the key of a single validator.
+    ///
+    /// Both the validator's current key and its new key must sign. The rotation waits out
+    /// `config.rotation_timelock_slots`, during which the guardian can veto it, and then
+    /// replaces the old key in place in the validator set. Approvals recorded under the old
+    /// key stop counting towards the threshold once the rotation is activated. Activation
+    /// archives the new set, whose `ValidatorSetSnapshot` is passed via remaining accounts.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for scheduling the rotation
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    ///
+    /// # Errors
+    /// * `InvalidSigner` - If the old key is not in the validator set
+    /// * `AddingExistingSigner` - If the new key is already in the validator set
+    pub fn rotate_validator_key<'info>(
+        ctx: Context<'_, '_, '_, 'info, RotateValidatorKey<'info>>,
+        instance_id: u64,
+    ) -> Result<()> {
+        RotateValidatorKey::process_instruction(ctx, instance_id)
+    }
+
+    /// Activate a scheduled validator key rotation once its timelock has expired.
+    ///
+    /// The registry record, bond and reward account of the old key move to the new key.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for activating the rotation
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `old_key` - The validator key being rotated out
+    ///
+    /// # Errors
+    /// * `TimelockNotExpired` - If the activation slot has not been reached
+    /// * `RemovingNonExistentSigner` - If the old key has left the validator set meanwhile
+    /// * `AddingExistingSigner` - If the new key has joined the validator set meanwhile
+    /// * `MissingActionAccount` - If the snapshot of the new epoch or a record address is missing
+    /// * `RotatedRecordExists` - If the new key already holds a registry record or bond
+    pub fn execute_key_rotation<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteKeyRotation<'info>>,
+        instance_id: u64,
+        old_key: Pubkey,
+    ) -> Result<()> {
+        ExecuteKeyRotation::process_instruction(ctx, instance_id, old_key)
+    }
+
+    /// Veto a scheduled validator key rotation (guardian only).
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for vetoing the rotation
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `old_key` - The validator key of the scheduled rotation
+    ///
+    /// # Errors
+    /// * `InvalidSigner` - If the signer is not the guardian
+    pub fn veto_key_rotation(
+        ctx: Context<VetoKeyRotation>,
+        instance_id: u64,
+        old_key: Pubkey,
+    ) -> Result<()> {
+        VetoKeyRotation::process_instruction(ctx, instance_id, old_key)
+    }
+
+    /// Update the registry record of the signing validator.
+    ///
+    /// A validator maintains its own display name, endpoint URL and foreign-chain keys. The
+    /// validator set can also set any record through a `SetValidatorInfo` governance proposal.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for updating the record
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `metadata` - The new metadata of the validator
+    ///
+    /// # Errors
+    /// * `InvalidSigner` - If the signer is not in the validator set
+    /// * `InvalidValidatorMetadata` - If the name or the endpoint is too long
+    pub fn update_validator_info(
+        ctx: Context<UpdateValidatorInfo>,
+        instance_id: u64,
+        metadata: ValidatorMetadata,
+    ) -> Result<()> {
+        UpdateValidatorInfo::process_instruction(ctx, instance_id, metadata)
+    }
+
+    /// Suspend or reinstate the signing validator.
+    ///
+    /// A suspended validator keeps its seat, but cannot approve operations and does not count
+    /// towards the active set size the threshold is calculated from. A validator can reinstate
+    /// itself only if it suspended itself; validators suspended through a
+    /// `SetValidatorSuspension` governance proposal must be reinstated through governance.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for changing the suspension
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `suspended` - `true` to suspend the validator, `false` to reinstate it
+    ///
+    /// # Errors
+    /// * `InvalidSigner` - If the signer is not in the validator set
+    /// * `ValidatorAlreadySuspended` - If the validator is already suspended
+    /// * `MinValidatorsNotMet` - If the active set would fall below `MIN_VALIDATORS`
+    /// * `ValidatorNotSuspended` - If the validator is not suspended
+    /// * `ValidatorSuspended` - If the validator was suspended through governance
+    pub fn set_validator_suspension(
+        ctx: Context<SetValidatorSuspension>,
+        instance_id: u64,
+        suspended: bool,
+    ) -> Result<()> {
+        SetValidatorSuspension::process_instruction(ctx, instance_id, suspended)
+    }
+
+    /// Record a liveness heartbeat of the signing validator.
+    ///
+    /// Validators call this periodically. The slot is stored in the `ValidatorStats` account,
+    /// next to the approval counters, last approval slot and missed batches of each validator.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for recording the heartbeat
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    ///
+    /// # Errors
+    /// * `InvalidSigner` - If the signer is not in the validator set
+    pub fn heartbeat(ctx: Context<Heartbeat>, instance_id: u64) -> Result<()> {
+        Heartbeat::process_instruction(ctx, instance_id)
+    }
+
+    /// Bond lamports as stake of the signing validator.
+    ///
+    /// The lamports are held by the validator's `ValidatorBond` account and can be slashed
+    /// through `report_equivocation`.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for bonding
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `amount` - The number of lamports to bond
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If the amount is zero
+    pub fn bond(ctx: Context<Bond>, instance_id: u64, amount: u64) -> Result<()> {
+        Bond::process_instruction(ctx, instance_id, amount)
+    }
+
+    /// Start unbonding lamports of the signing validator.
+    ///
+    /// Unbonding lamports stay slashable and can be withdrawn with `withdraw_bond` once
+    /// `config.unbonding_slots` have passed.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for unbonding
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `amount` - The number of lamports to unbond
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If the amount is zero
+    /// * `InsufficientBond` - If the amount exceeds the bonded lamports
+    pub fn unbond(ctx: Context<Unbond>, instance_id: u64, amount: u64) -> Result<()> {
+        Unbond::process_instruction(ctx, instance_id, amount)
+    }
+
+    /// Withdraw the unbonded lamports of the signing validator.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for the withdrawal
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    ///
+    /// # Errors
+    /// * `InsufficientBond` - If no lamports are unbonding
+    /// * `UnbondingNotComplete` - If the unbonding period has not passed
+    pub fn withdraw_bond(ctx: Context<WithdrawBond>, instance_id: u64) -> Result<()> {
+        WithdrawBond::process_instruction(ctx, instance_id)
+    }
+
+    /// Claim the rewards credited to the signing relayer or validator.
+    ///
+    /// Executed bridge transactions credit their payer and approving validators with the
+    /// rewards set through a `SetRewards` governance proposal. The accrued lamports are paid
+    /// from the fee vault.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for the claim
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    ///
+    /// # Errors
+    /// * `NothingToClaim` - If no rewards are accrued
+    pub fn claim_rewards(ctx: Context<ClaimRewards>, instance_id: u64) -> Result<()> {
+        ClaimRewards::process_instruction(ctx, instance_id)
+    }
+
+    /// Report a validator that signed approvals for two different operations under the same batch ID.
+    ///
+    /// Permissionless. Both approval attestations (see `helpers::approval_message`) must be
+    /// verified by ed25519 precompile instructions in the same transaction. The whole bond of
+    /// the validator is slashed, `config.slash_reporter_bps` of it to the reporter and the rest
+    /// to the treasury. The validator is queued for removal by the next validator set update and
+    /// jailed meanwhile, unless that would take the active set below `MIN_VALIDATORS`.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for the report
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `validator` - The equivocating validator
+    /// * `batch_id` - The batch ID both approvals were signed for
+    /// * `first_hash` - The operation hash of the first approval
+    /// * `second_hash` - The operation hash of the second approval
+    ///
+    /// # Errors
+    /// * `InvalidEquivocationProof` - If the approvals are not conflicting signed attestations
+    /// * `NothingToSlash` - If the validator has no bond left and is already queued and jailed, or not in the set
+    pub fn report_equivocation(
+        ctx: Context<ReportEquivocation>,
+        instance_id: u64,
+        validator: Pubkey,
         batch_id: u64,
+        first_hash: [u8; 32],
+        second_hash: [u8; 32],
+    ) -> Result<()> {
+        ReportEquivocation::process_instruction(
+            ctx,
+            instance_id,
+            validator,
+            batch_id,
+            first_hash,
+            second_hash,
+        )
+    }
+
+    /// Create or approve a guardian council proposal.
+    ///
+    /// The guardian council, appointed by the validators through a `SetGuardianCouncil`
+    /// proposal, votes on emergency and recovery actions (see `GuardianAction`) with its own
+    /// members, threshold and batch IDs. Council members approve by signing in remaining
+    /// accounts; accounts written by the action are passed there too. Pausing and vetoes take
+    /// effect once the threshold is met, a validator set recovery is scheduled for
+    /// `config.recovery_timelock_slots` in the future (see `execute_guardian_proposal`).
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for creating or approving the proposal
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `action` - The typed guardian action
+    /// * `batch_id` - The council batch ID of the proposal (must be greater than the council's last_batch_id)
+    ///
+    /// # Errors
+    /// * `InvalidBatchId` - If the batch_id is not greater than the council's last_batch_id
+    /// * `InvalidProposalHash` - If approving a proposal with a different action than the original
+    /// * `InvalidSigner` - If a signer is not a council member
+    /// * `SignerAlreadyApproved` - If a signer has already approved this proposal
+    /// * `ValidatorSetNotStalled` - If a recovery is proposed while the validators still reach
+    ///   quorums or are seen with the threshold weight
+    /// * `MissingActionAccount` - If an account written by the action is missing
+    pub fn submit_guardian_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, SubmitGuardianProposal<'info>>,
+        instance_id: u64,
+        action: GuardianAction,
+        batch_id: u64,
+    ) -> Result<()> {
+        SubmitGuardianProposal::process_instruction(ctx, instance_id, action, batch_id)
+    }
+
+    /// Activate a scheduled validator set recovery of the guardian council.
+    ///
+    /// Permissionless once the timelock has expired. The recovery is voided if the validators
+    /// reached a quorum during the timelock. The snapshot of the recovered set is passed via
+    /// remaining accounts.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for activating the proposal
+    /// * `instance_id` - The bridge instance (see `DeploymentConfig::instance_id`)
+    /// * `batch_id` - The council batch ID of the scheduled proposal
+    ///
+    /// # Errors
+    /// * `ProposalNotScheduled` - If the proposal has not reached the council threshold
+    /// * `TimelockNotExpired` - If the activation slot has not been reached
+    /// * `ValidatorSetNotStalled` - If the validators reached a quorum or were seen with the
+    ///   threshold weight during the timelock
+    /// * `MissingAdmissionProof` - If a recovered key has not signed its admission
+    /// * `MissingActionAccount` - If the snapshot of the recovered set is missing
+    pub fn execute_guardian_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteGuardianProposal<'info>>,
+        instance_id: u64,
+        batch_id: u64,
+    ) -> Result<()> {
+        ExecuteGuardianProposal::process_instruction(ctx, instance_id, batch_id)
+    }
+
+    /// Migrate a version 1 account into a bridge instance.
+    ///
+    /// Accounts written before bridge instances and the layout version field existed
+    /// (version 1) live at PDAs derived without an instance ID. Each is copied field by field
+    /// into its current-layout PDA in `instance_id`, new fields take their defaults, and the
+    /// version 1 account is closed (the version 1 vault is kept and hands its mint authority
+    /// and token balances over to the new vault). Migrating the validator set also creates
+    /// the treasury, validator statistics and epoch 0 snapshot of the instance. Signed by the
+    /// program upgrade authority. Instructions reject accounts that have not been migrated
+    /// with `AccountNotMigrated`.
+    ///
+    /// # Arguments
+    /// * `ctx` - The context containing accounts for the migration
+    /// * `instance_id` - The bridge instance the account migrates to
+    /// * `chain_id` - Chain ID of this deployment (see `DeploymentConfig::chain_id`)
+    ///
+    /// # Errors
+    /// * `UnauthorizedMigration` - If the payer is not the program upgrade authority
+    /// * `AccountNotMigratable` - If the account is not a version 1 account, or the migrated
+    ///   account is not its current PDA or already exists
+    /// * `MissingActionAccount` - If an account created alongside the validator set, or a
+    ///   token account of a handed over mint, is missing
+    pub fn migrate<'info>(
+        ctx: Context<'_, '_, '_, 'info, Migrate<'info>>,
+        instance_id: u64,
+        chain_id: u8,
     ) -> Result<()> {
-        BridgeTransaction::process_instruction(ctx, amount, batch_id)
+        Migrate::process_instruction(ctx, instance_id, chain_id)
     }
 }
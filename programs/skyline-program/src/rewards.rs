@@ -0,0 +1,180 @@
+//! Relayer and validator rewards for the Skyline bridge program.
+//!
+//! This module credits the rewards configured in the `FeeVault` account when a bridge
+//! transaction is executed. Rewards are paid from the SOL fees and accrue in one
+//! `RewardAccount` per participant until it claims them with `claim_rewards`.
+
+use crate::*;
+
+impl FeeVault {
+    /// Credits the rewards of an executed bridge transaction.
+    ///
+    /// The relayer and every approving validator are credited in their reward account,
+    /// which is created if it doesn't exist yet, funded by the payer. The reward account of
+    /// every approving validator must be passed among the remaining accounts, the relayer
+    /// chooses them and could otherwise withhold the validators' rewards. Only the relayer
+    /// may leave its own reward account out, it then forfeits its reward for the transaction.
+    /// When the fee vault holds fewer unreserved lamports than the rewards owed to all the
+    /// participants, each is credited pro rata and the shortfall is reported; the transaction
+    /// is executed all the same.
+    ///
+    /// # Arguments
+    /// * `info` - The fee vault account, nothing is credited if it doesn't exist yet
+    /// * `accounts` - The remaining accounts holding the reward accounts
+    /// * `relayer` - The payer of the executing transaction
+    /// * `validators` - The approving validators
+    /// * `batch_id` - The batch ID of the executed transaction
+    ///
+    /// # Errors
+    /// * `MissingActionAccount` - If the reward account of an approving validator is not passed
+    /// * `AccountOwnedByWrongProgram` - If a passed reward account is not owned by the program
+    /// * `AccountNotMigrated` - If a passed reward account is not at the current layout version
+    pub fn credit_rewards<'info>(
+        info: &AccountInfo<'info>,
+        accounts: &ActionAccounts<'_, 'info>,
+        relayer: &Pubkey,
+        validators: &[Pubkey],
+        batch_id: u64,
+    ) -> Result<()> {
+        if info.data_is_empty() {
+            return Ok(());
+        }
+
+        let mut fee_vault = Self::load(info)?;
+        let rewards = std::iter::once((relayer, fee_vault.relayer_reward, true))
+            .chain(
+                validators
+                    .iter()
+                    .map(|key| (key, fee_vault.validator_reward, false)),
+            )
+            .filter(|(_, reward, _)| *reward > 0);
+
+        let mut owed = Vec::new();
+        let mut relayer_skipped = false;
+        for (participant, reward, is_relayer) in rewards {
+            let (address, bump) = reward_account_address(accounts.instance_id, participant);
+            let reward_info = match accounts.find(&address) {
+                Ok(reward_info) => Some(reward_info),
+                Err(_) if is_relayer => {
+                    relayer_skipped = true;
+                    None
+                }
+                Err(err) => return Err(err),
+            };
+            owed.push((participant, reward_info, bump, reward, is_relayer));
+        }
+        // The skipped relayer's reward counts, so leaving it out never raises the other shares
+        let total = owed
+            .iter()
+            .fold(0u64, |total, owed| total.saturating_add(owed.3));
+        if total == 0 {
+            return Ok(());
+        }
+
+        // Pro rata share of the rewards the fee vault can cover
+        let funded = total.min(fee_vault.available(info)?);
+        let mut credited = 0;
+        let mut relayer_reward = 0;
+        let mut validator_reward = 0;
+        let mut credited_validators = Vec::new();
+        for (participant, reward_info, bump, reward, is_relayer) in owed {
+            let Some(reward_info) = reward_info else {
+                continue;
+            };
+            let amount = (reward as u128 * funded as u128 / total as u128) as u64;
+            if amount == 0 {
+                continue;
+            }
+            credit(accounts, reward_info, participant, bump, amount)?;
+            credited += amount;
+            if is_relayer {
+                relayer_reward = amount;
+            } else {
+                validator_reward = amount;
+                credited_validators.push(*participant);
+            }
+        }
+
+        fee_vault.reserved_rewards += credited;
+        fee_vault.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        emit!(RewardsCreditedEvent {
+            instance_id: accounts.instance_id,
+            batch_id,
+            relayer: *relayer,
+            relayer_reward,
+            validators: credited_validators,
+            validator_reward,
+            shortfall: total - funded,
+            relayer_skipped,
+        });
+
+        Ok(())
+    }
+}
+
+/// Returns the address and bump of the reward account of `participant` in `instance_id`.
+fn reward_account_address(instance_id: u64, participant: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            REWARD_ACCOUNT_SEED,
+            &instance_id.to_le_bytes(),
+            participant.as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+/// Credits `amount` lamports to the reward account `info` of `participant` (with the PDA
+/// bump `bump`), creating it if needed.
+///
+/// # Errors
+/// * `AccountOwnedByWrongProgram` - If the reward account is not owned by the program
+/// * `AccountNotMigrated` - If the reward account is not at the current layout version
+fn credit<'info>(
+    accounts: &ActionAccounts<'_, 'info>,
+    info: &AccountInfo<'info>,
+    participant: &Pubkey,
+    bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let instance_id = accounts.instance_id.to_le_bytes();
+
+    let mut reward_account = if info.data_is_empty() {
+        create_pda_account(
+            info,
+            accounts.payer,
+            accounts.system_program,
+            DISC as usize + RewardAccount::INIT_SPACE,
+            &[
+                REWARD_ACCOUNT_SEED,
+                &instance_id,
+                participant.as_ref(),
+                &[bump],
+            ],
+        )?;
+
+        RewardAccount {
+            version: ACCOUNT_VERSION,
+            participant: *participant,
+            accrued: 0,
+            claimed: 0,
+            bump,
+        }
+    } else {
+        require_keys_eq!(
+            *info.owner,
+            crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+        let reward_account = RewardAccount::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(
+            reward_account.version == ACCOUNT_VERSION,
+            CustomError::AccountNotMigrated
+        );
+        reward_account
+    };
+
+    reward_account.accrued += amount;
+    reward_account.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])
+}
@@ -0,0 +1,133 @@
+//! Validator participation statistics for the Skyline bridge program.
+//!
+//! This module keeps the `ValidatorStats` account up to date. Approvals are recorded as
+//! validators submit them, missed batches are charged when a batch reaches quorum, and
+//! heartbeats are recorded by the `heartbeat` instruction.
+
+use crate::*;
+
+impl ValidatorStats {
+    /// Returns the entry of `validator`, adding a fresh one if it has none yet.
+    ///
+    /// The account is sized for one entry per current validator (see [`Self::ensure_space`]).
+    /// When it holds that many entries, entries of keys that have left the validator set are
+    /// dropped first, which always makes room for a current validator.
+    fn entry(
+        &mut self,
+        validator_set: &ValidatorSet,
+        validator: &Pubkey,
+    ) -> &mut ValidatorActivity {
+        if let Some(index) = self.entries.iter().position(|e| e.validator == *validator) {
+            return &mut self.entries[index];
+        }
+
+        if self.entries.len() >= validator_set.signers.len() {
+            self.entries
+                .retain(|e| validator_set.signers.contains(&e.validator));
+        }
+
+        self.entries.push(ValidatorActivity {
+            validator: *validator,
+            approvals: 0,
+            last_approval_slot: 0,
+            missed_batches: 0,
+            last_heartbeat_slot: 0,
+        });
+        self.entries.last_mut().unwrap()
+    }
+
+    /// Records the approvals submitted by `signers` at `slot`.
+    ///
+    /// # Arguments
+    /// * `validator_set` - The current validator set
+    /// * `signers` - The validators that approved in the current call
+    /// * `slot` - The current slot
+    pub fn record_approvals(
+        &mut self,
+        validator_set: &ValidatorSet,
+        signers: &[Pubkey],
+        slot: u64,
+    ) {
+        for signer in signers {
+            let entry = self.entry(validator_set, signer);
+            entry.approvals += 1;
+            entry.last_approval_slot = slot;
+        }
+    }
+
+    /// Charges a missed batch to every active validator that did not approve it.
+    ///
+    /// This is called once, when the batch reaches quorum. Suspended validators are
+    /// excused.
+    ///
+    /// # Arguments
+    /// * `validator_set` - The current validator set
+    /// * `approved` - The approvals recorded for the batch
+    pub fn record_missed(&mut self, validator_set: &ValidatorSet, approved: &[Pubkey]) {
+        let mut approved = approved.to_vec();
+        approved.sort_unstable();
+
+        for validator in validator_set.signers.iter() {
+            if approved.binary_search(validator).is_ok() || is_suspended(validator_set, validator) {
+                continue;
+            }
+            self.entry(validator_set, validator).missed_batches += 1;
+        }
+    }
+
+    /// Records a heartbeat of `validator` at `slot`.
+    ///
+    /// # Arguments
+    /// * `validator_set` - The current validator set
+    /// * `validator` - The validator sending the heartbeat
+    /// * `slot` - The current slot
+    pub fn record_heartbeat(
+        &mut self,
+        validator_set: &ValidatorSet,
+        validator: &Pubkey,
+        slot: u64,
+    ) {
+        self.entry(validator_set, validator).last_heartbeat_slot = slot;
+    }
+
+    /// Returns the weight of the active validators seen after `since_slot`.
+    ///
+    /// A validator is seen when it sends a heartbeat or submits an approval, so an idle
+    /// bridge whose validators keep sending heartbeats stays live. Suspended validators
+    /// are not counted, they cannot approve.
+    ///
+    /// # Arguments
+    /// * `validator_set` - The current validator set
+    /// * `since_slot` - The slot before the liveness window
+    pub fn live_weight(&self, validator_set: &ValidatorSet, since_slot: u64) -> u64 {
+        self.entries
+            .iter()
+            .filter(|e| e.last_heartbeat_slot.max(e.last_approval_slot) > since_slot)
+            .filter(|e| !is_suspended(validator_set, &e.validator))
+            .map(|e| validator_weight(validator_set, &e.validator))
+            .sum()
+    }
+
+    /// Grows the account to hold one entry per current validator.
+    ///
+    /// Must be called before recording, so entries added for new validators fit.
+    ///
+    /// # Arguments
+    /// * `validator_stats` - The validator statistics account
+    /// * `validator_set` - The current validator set
+    /// * `payer` - The signer paying for the additional rent
+    /// * `system_program` - The system program
+    pub fn ensure_space<'info>(
+        validator_stats: &Account<'info, ValidatorStats>,
+        validator_set: &ValidatorSet,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<()> {
+        grow_account(
+            &validator_stats.to_account_info(),
+            payer,
+            system_program,
+            DISC as usize + ValidatorStats::space(validator_set.signers.len()),
+        )
+    }
+}
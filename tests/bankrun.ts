@@ -0,0 +1,374 @@
+// tests/bankrun.ts
+import { web3, utils } from "@coral-xyz/anchor";
+import { AccountLayout, MintLayout } from "@solana/spl-token";
+import { AddedAccount, ProgramTestContext, start } from "solana-bankrun";
+import { randomBytes } from "crypto";
+import { readFileSync, readdirSync } from "fs";
+import { BPF_LOADER_UPGRADEABLE_ID } from "./fixtures";
+
+/**
+ * Program binary built by `anchor build`
+ */
+const PROGRAM_PATH = "target/deploy/skyline_program.so";
+
+/**
+ * Version 1 accounts written with the layouts of the bridge before bridge
+ * instances existed (see LEGACY_ACCOUNTS)
+ */
+const LEGACY_ACCOUNTS_DIR = "tests/accounts";
+
+/**
+ * Size of the `UpgradeableLoaderState::ProgramData` metadata
+ */
+export const PROGRAM_DATA_METADATA_SIZE = 45;
+
+/**
+ * Lamports the program authority starts with
+ */
+const AUTHORITY_LAMPORTS = 1_000_000 * web3.LAMPORTS_PER_SOL;
+
+interface ProcessedTransaction {
+  slot: number;
+  err: string | null;
+  logs: string[];
+}
+
+/**
+ * Connection served by an in-process bank instead of a validator, so the test
+ * suite can warp past the governance timelock. It implements the part of the
+ * RPC API used by the tests, Anchor and spl-token.
+ */
+export class BankrunConnection extends web3.Connection {
+  private context: ProgramTestContext | null = null;
+  private transactions = new Map<string, ProcessedTransaction>();
+
+  constructor() {
+    // Never contacted, the bank serves every request
+    super("http://127.0.0.1:8899", "confirmed");
+    // Anchor simulates views through the raw RPC request
+    (this as any)._rpcRequest = (method: string, args: any[]) =>
+      this.rpcRequest(method, args);
+  }
+
+  /**
+   * Start the bank with the program deployed as upgradeable by `authority`,
+   * the version 1 accounts and a funded `authority`
+   */
+  async start(
+    programId: web3.PublicKey,
+    authority: web3.PublicKey
+  ): Promise<void> {
+    const [programData] = web3.PublicKey.findProgramAddressSync(
+      [programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE_ID
+    );
+    const elf = readFileSync(PROGRAM_PATH);
+
+    const program = Buffer.alloc(36);
+    program.writeUInt32LE(2, 0); // Program
+    programData.toBuffer().copy(program, 4);
+
+    const data = Buffer.alloc(PROGRAM_DATA_METADATA_SIZE + elf.length);
+    data.writeUInt32LE(3, 0); // ProgramData, deployed in slot 0
+    data.writeUInt8(1, 12); // Some(upgrade authority)
+    authority.toBuffer().copy(data, 13);
+    elf.copy(data, PROGRAM_DATA_METADATA_SIZE);
+
+    this.context = await start(
+      [],
+      [
+        {
+          address: programId,
+          info: {
+            lamports: web3.LAMPORTS_PER_SOL,
+            data: program,
+            owner: BPF_LOADER_UPGRADEABLE_ID,
+            executable: true,
+          },
+        },
+        {
+          address: programData,
+          info: {
+            lamports: 100 * web3.LAMPORTS_PER_SOL,
+            data,
+            owner: BPF_LOADER_UPGRADEABLE_ID,
+            executable: false,
+          },
+        },
+        {
+          address: authority,
+          info: {
+            lamports: AUTHORITY_LAMPORTS,
+            data: Buffer.alloc(0),
+            owner: web3.SystemProgram.programId,
+            executable: false,
+          },
+        },
+        ...legacyAccounts(),
+      ]
+    );
+
+    // A program becomes callable in the slot after its deployment
+    await this.warpToSlot((await this.getSlot()) + 1);
+  }
+
+  /**
+   * Move the bank forward to `slot`, if it has not reached it yet
+   */
+  async warpToSlot(slot: number): Promise<void> {
+    if (slot > (await this.getSlot())) {
+      this.bank.warpToSlot(BigInt(slot));
+    }
+  }
+
+  /**
+   * Process a transaction. Like a validator running the preflight simulation,
+   * it rejects a failing transaction unless `skipPreflight` is set. Every
+   * transaction gets a slot of its own, so repeated calls are not deduplicated.
+   */
+  async sendRawTransaction(
+    rawTransaction: Buffer | Uint8Array | Array<number>,
+    options?: web3.SendOptions
+  ): Promise<string> {
+    const transaction = web3.VersionedTransaction.deserialize(
+      Uint8Array.from(rawTransaction)
+    );
+    const signature = utils.bytes.bs58.encode(transaction.signatures[0]);
+    const slot = await this.getSlot();
+
+    if (!options?.skipPreflight) {
+      const simulation = await this.bank.banksClient.simulateTransaction(
+        transaction
+      );
+      if (simulation.result) {
+        throw new web3.SendTransactionError({
+          action: "simulate",
+          signature,
+          transactionMessage: simulation.result,
+          logs: simulation.meta?.logMessages ?? [],
+        });
+      }
+    }
+
+    const { result, meta } = await this.bank.banksClient.tryProcessTransaction(
+      transaction
+    );
+    this.transactions.set(signature, {
+      slot,
+      err: result,
+      logs: meta?.logMessages ?? [],
+    });
+    await this.warpToSlot(slot + 1);
+
+    return signature;
+  }
+
+  async sendTransaction(
+    transaction: any,
+    signers?: any,
+    options?: any
+  ): Promise<string> {
+    if (transaction instanceof web3.VersionedTransaction) {
+      return await this.sendRawTransaction(transaction.serialize(), signers);
+    }
+    transaction.recentBlockhash = (await this.getLatestBlockhash()).blockhash;
+    transaction.sign(...signers);
+    return await this.sendRawTransaction(transaction.serialize(), options);
+  }
+
+  async confirmTransaction(strategy: any, _commitment?: any): Promise<any> {
+    const signature =
+      typeof strategy === "string" ? strategy : strategy.signature;
+    return {
+      context: { slot: await this.getSlot() },
+      value: { err: this.transactions.get(signature)?.err ?? null },
+    };
+  }
+
+  async getTransaction(signature: string, _config?: any): Promise<any> {
+    const transaction = this.transactions.get(signature);
+    if (!transaction) {
+      return null;
+    }
+    return {
+      slot: transaction.slot,
+      blockTime: null,
+      meta: { err: transaction.err, logMessages: transaction.logs },
+    };
+  }
+
+  async requestAirdrop(to: web3.PublicKey, lamports: number): Promise<string> {
+    const account = await this.bank.banksClient.getAccount(to);
+    this.bank.setAccount(to, {
+      lamports: Number(account?.lamports ?? 0) + lamports,
+      data: account?.data ?? Buffer.alloc(0),
+      owner: account?.owner ?? web3.SystemProgram.programId,
+      executable: account?.executable ?? false,
+    });
+
+    const signature = utils.bytes.bs58.encode(randomBytes(64));
+    this.transactions.set(signature, {
+      slot: await this.getSlot(),
+      err: null,
+      logs: [],
+    });
+    return signature;
+  }
+
+  async getLatestBlockhash(
+    _commitment?: any
+  ): Promise<web3.BlockhashWithExpiryBlockHeight> {
+    const [blockhash, lastValidBlockHeight] =
+      await this.bank.banksClient.getLatestBlockhash();
+    return { blockhash, lastValidBlockHeight: Number(lastValidBlockHeight) };
+  }
+
+  async getSlot(_commitment?: any): Promise<number> {
+    return Number(await this.bank.banksClient.getSlot());
+  }
+
+  async getAccountInfo(
+    publicKey: web3.PublicKey,
+    _commitment?: any
+  ): Promise<web3.AccountInfo<Buffer> | null> {
+    const account = await this.bank.banksClient.getAccount(publicKey);
+    if (!account) {
+      return null;
+    }
+    return {
+      data: Buffer.from(account.data),
+      executable: account.executable,
+      lamports: Number(account.lamports),
+      owner: account.owner,
+      rentEpoch: Number(account.rentEpoch ?? 0),
+    };
+  }
+
+  async getAccountInfoAndContext(
+    publicKey: web3.PublicKey,
+    _commitment?: any
+  ): Promise<web3.RpcResponseAndContext<web3.AccountInfo<Buffer> | null>> {
+    return {
+      context: { slot: await this.getSlot() },
+      value: await this.getAccountInfo(publicKey),
+    };
+  }
+
+  async getMultipleAccountsInfo(
+    publicKeys: web3.PublicKey[],
+    _commitment?: any
+  ): Promise<(web3.AccountInfo<Buffer> | null)[]> {
+    return await Promise.all(publicKeys.map((key) => this.getAccountInfo(key)));
+  }
+
+  async getMultipleAccountsInfoAndContext(
+    publicKeys: web3.PublicKey[],
+    _commitment?: any
+  ): Promise<web3.RpcResponseAndContext<(web3.AccountInfo<Buffer> | null)[]>> {
+    return {
+      context: { slot: await this.getSlot() },
+      value: await this.getMultipleAccountsInfo(publicKeys),
+    };
+  }
+
+  async getBalance(
+    publicKey: web3.PublicKey,
+    _commitment?: any
+  ): Promise<number> {
+    return Number(await this.bank.banksClient.getBalance(publicKey));
+  }
+
+  async getMinimumBalanceForRentExemption(
+    dataLength: number,
+    _commitment?: any
+  ): Promise<number> {
+    const rent = await this.bank.banksClient.getRent();
+    return Number(rent.minimumBalance(BigInt(dataLength)));
+  }
+
+  async getTokenAccountBalance(
+    tokenAddress: web3.PublicKey,
+    _commitment?: any
+  ): Promise<web3.RpcResponseAndContext<web3.TokenAmount>> {
+    const account = await this.getAccountInfo(tokenAddress);
+    if (!account) {
+      throw new Error(`Token account ${tokenAddress.toBase58()} not found`);
+    }
+    const { mint, amount } = AccountLayout.decode(account.data);
+    const { decimals } = MintLayout.decode(
+      (await this.getAccountInfo(mint)).data
+    );
+    const uiAmount = Number(amount) / 10 ** decimals;
+
+    return {
+      context: { slot: await this.getSlot() },
+      value: {
+        amount: amount.toString(),
+        decimals,
+        uiAmount,
+        uiAmountString: uiAmount.toString(),
+      },
+    };
+  }
+
+  private get bank(): ProgramTestContext {
+    if (!this.context) {
+      throw new Error("The bank has not been started");
+    }
+    return this.context;
+  }
+
+  private async rpcRequest(method: string, args: any[]): Promise<any> {
+    if (method !== "simulateTransaction") {
+      throw new Error(`${method} is not served by the bank`);
+    }
+    const transaction = web3.VersionedTransaction.deserialize(
+      Buffer.from(args[0], "base64")
+    );
+    const { result, meta } = await this.bank.banksClient.simulateTransaction(
+      transaction
+    );
+
+    return {
+      jsonrpc: "2.0",
+      id: "0",
+      result: {
+        context: { slot: await this.getSlot() },
+        value: {
+          err: result,
+          logs: meta?.logMessages ?? [],
+          accounts: null,
+          unitsConsumed: Number(meta?.computeUnitsConsumed ?? 0),
+          returnData: meta?.returnData && {
+            programId: meta.returnData.programId.toBase58(),
+            data: [
+              Buffer.from(meta.returnData.data).toString("base64"),
+              "base64",
+            ],
+          },
+        },
+      },
+    };
+  }
+}
+
+/**
+ * The version 1 accounts of LEGACY_ACCOUNTS_DIR, in the JSON format of
+ * `solana account --output json`
+ */
+function legacyAccounts(): AddedAccount[] {
+  return readdirSync(LEGACY_ACCOUNTS_DIR).map((file) => {
+    const { pubkey, account } = JSON.parse(
+      readFileSync(`${LEGACY_ACCOUNTS_DIR}/${file}`, "utf8")
+    );
+    return {
+      address: new web3.PublicKey(pubkey),
+      info: {
+        lamports: account.lamports,
+        data: Buffer.from(account.data[0], "base64"),
+        owner: new web3.PublicKey(account.owner),
+        executable: account.executable,
+      },
+    };
+  });
+}
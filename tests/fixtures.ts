@@ -3,12 +3,17 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program, web3, BN } from "@coral-xyz/anchor";
 import { SkylineProgram } from "../target/types/skyline_program";
 import { expect } from "chai";
+import { createHash } from "crypto";
+import { ed25519 } from "@noble/curves/ed25519";
+import { blake3 } from "@noble/hashes/blake3";
 import {
   createMint,
   getOrCreateAssociatedTokenAccount,
   mintTo,
   getAssociatedTokenAddressSync,
+  getMint,
 } from "@solana/spl-token";
+import type { BankrunConnection } from "./bankrun";
 
 // ============================================================================
 // CONSTANTS
@@ -18,14 +23,96 @@ export const SEEDS = {
   VALIDATOR_SET: "validator-set",
   VAULT: "vault",
   BRIDGING_TRANSACTION: "bridging_transaction",
-  VALIDATOR_SET_CHANGE: "validator_set_change",
+  GOVERNANCE_PROPOSAL: "governance_proposal",
+  VALIDATOR_DELTA: "validator_delta",
+  VALIDATOR_SET_SNAPSHOT: "validator_set_snapshot",
+  KEY_ROTATION: "key_rotation",
+  VALIDATOR_INFO: "validator_info",
+  VALIDATOR_STATS: "validator_stats",
+  VALIDATOR_BOND: "validator_bond",
+  TREASURY: "treasury",
+  GUARDIAN_COUNCIL: "guardian_council",
+  GUARDIAN_PROPOSAL: "guardian_proposal",
+  UPGRADE_AUTHORITY: "upgrade_authority",
+  CHAIN_REGISTRY: "chain_registry",
+  TOKEN_CONFIG: "token_config",
+  FEE_VAULT: "fee_vault",
+  REWARD_ACCOUNT: "reward_account",
+  INITIALIZER_GRANT: "initializer_grant",
 } as const;
 
+/**
+ * The BPF upgradeable loader, owner of program data and buffer accounts
+ */
+export const BPF_LOADER_UPGRADEABLE_ID = new web3.PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+
+/**
+ * Domain separator of the proof-of-possession message signed by new validators
+ */
+export const ADMISSION_MESSAGE_DOMAIN = "skyline:validator-admission";
+
+/**
+ * Domain separator of the approval attestation validators sign for every batch
+ */
+export const APPROVAL_MESSAGE_DOMAIN = "skyline:approval";
+
+/**
+ * Domain separator of the governance proposal hash validators attest to
+ */
+export const PROPOSAL_HASH_DOMAIN = "skyline:governance-proposal";
+
+/**
+ * Domain separator of the bridging transaction hash validators attest to
+ */
+export const BRIDGING_TRANSACTION_HASH_DOMAIN = "skyline:bridging-transaction";
+
+/**
+ * Bridge configuration used by the test suite.
+ * The timelock is the program's `MIN_TIMELOCK_SLOTS`, which the fixtures warp
+ * past, and a 100% churn limit disables the churn check; feature specific tests
+ * switch it on explicitly through governance.
+ */
+export const TEST_CONFIG = {
+  timelockSlots: new BN(9_000),
+  maxChurnBps: 10_000,
+  churnWindowSlots: new BN(432_000),
+  rotationTimelockSlots: new BN(0),
+  unbondingSlots: new BN(0),
+  slashReporterBps: 1_000,
+  quorumWeightBps: 6_667,
+  recoveryTimelockSlots: new BN(0),
+  recoveryStallSlots: new BN(432_000),
+};
+
+/**
+ * Deployment configuration the test suite initializes the bridge with
+ */
+export const TEST_DEPLOYMENT = {
+  chainId: 4,
+  instanceId: new BN(0),
+};
+
+/**
+ * Chain the bridge transactions of the test suite come from
+ */
+export const TEST_SOURCE_CHAIN = 1;
+
+/**
+ * Layout version stored in every program account (mirrors ACCOUNT_VERSION)
+ */
+export const ACCOUNT_VERSION = 2;
+
 export const LIMITS = {
   MIN_VALIDATORS: 4,
-  MAX_VALIDATORS: 128,
+  MAX_VALIDATORS: 256,
   MAX_VALIDATORS_CHANGE: 10,
   MAX_TX_VALIDATORS: 29, // Solana transaction size limit
+  MAX_TX_ATTESTATIONS: 3, // Approval attestations relayed per transaction
+  BPS_DENOMINATOR: 10_000,
+  MAX_VALIDATOR_NAME_LEN: 32,
+  MAX_VALIDATOR_WEIGHT: 1_000_000,
 } as const;
 
 // ============================================================================
@@ -36,22 +123,49 @@ export interface TestContext {
   provider: anchor.AnchorProvider;
   program: Program<SkylineProgram>;
   owner: anchor.Wallet;
-  connection: anchor.web3.Connection;
+  connection: BankrunConnection;
 }
 
 export interface ValidatorSetData {
+  version: number;
   signers: web3.PublicKey[];
+  weights: BN[];
   threshold: number;
+  thresholdWeight: BN;
   bump: number;
   lastBatchId: BN;
   bridgeRequestCount: BN;
+  guardian: web3.PublicKey;
+  config: {
+    timelockSlots: BN;
+    maxChurnBps: number;
+    churnWindowSlots: BN;
+    rotationTimelockSlots: BN;
+    unbondingSlots: BN;
+    slashReporterBps: number;
+    quorumWeightBps: number;
+    recoveryTimelockSlots: BN;
+    recoveryStallSlots: BN;
+  };
+  pendingUpdate: BN | null;
+  churnWindowStart: BN;
+  churnWindowBase: number;
+  churnInWindow: number;
+  suspended: { validator: web3.PublicKey; byConsensus: boolean }[];
+  epoch: BN;
+  paused: boolean;
+  lastQuorumSlot: BN;
+  initializer: web3.PublicKey;
+  deployment: { chainId: number; instanceId: BN };
 }
 
 export interface VaultData {
+  version: number;
   bump: number;
 }
 
 export interface BridgingTransactionData {
+  version: number;
   id: web3.PublicKey;
   amount: BN;
   receiver: web3.PublicKey;
@@ -81,7 +195,222 @@ export function generateValidators(count: number): web3.Keypair[] {
  * Formula: num_signers - floor((num_signers - 1) / 3)
  */
 export function calculateExpectedThreshold(validatorCount: number): number {
-  return validatorCount - Math.floor((validatorCount - 1) / 3);
+  return validatorCount - Math.floor(Math.max(validatorCount - 1, 0) / 3);
+}
+
+/**
+ * Calculate expected threshold weight for a given total active weight
+ * Formula: ceil(total_weight * quorum_weight_bps / BPS_DENOMINATOR), at least 1
+ */
+export function calculateExpectedThresholdWeight(
+  totalWeight: number,
+  quorumWeightBps: number = TEST_CONFIG.quorumWeightBps
+): number {
+  return Math.max(
+    1,
+    Math.ceil((totalWeight * quorumWeightBps) / LIMITS.BPS_DENOMINATOR)
+  );
+}
+
+/**
+ * Build the ed25519 precompile instruction proving possession of a new validator key.
+ * Message: domain || validator_set || batch_id (LE u64) || validator
+ */
+export function admissionProof(
+  validatorSet: web3.PublicKey,
+  batchId: number | BN,
+  validator: web3.Keypair
+): web3.TransactionInstruction {
+  const message = Buffer.concat([
+    Buffer.from(ADMISSION_MESSAGE_DOMAIN),
+    validatorSet.toBuffer(),
+    new BN(batchId).toArrayLike(Buffer, "le", 8),
+    validator.publicKey.toBuffer(),
+  ]);
+
+  return web3.Ed25519Program.createInstructionWithPrivateKey({
+    privateKey: validator.secretKey,
+    message,
+  });
+}
+
+/**
+ * Build the approval attestation message of an operation.
+ * Message: domain || validator_set || batch_id (LE u64) || operation_hash
+ */
+export function approvalMessage(
+  validatorSet: web3.PublicKey,
+  batchId: number | BN,
+  operationHash: number[] | Uint8Array
+): Buffer {
+  return Buffer.concat([
+    Buffer.from(APPROVAL_MESSAGE_DOMAIN),
+    validatorSet.toBuffer(),
+    new BN(batchId).toArrayLike(Buffer, "le", 8),
+    Buffer.from(operationHash),
+  ]);
+}
+
+/**
+ * Build the ed25519 precompile instruction carrying a validator's approval attestation.
+ */
+export function approvalAttestation(
+  validatorSet: web3.PublicKey,
+  batchId: number | BN,
+  operationHash: number[] | Uint8Array,
+  validator: web3.Keypair
+): web3.TransactionInstruction {
+  return web3.Ed25519Program.createInstructionWithPrivateKey({
+    privateKey: validator.secretKey,
+    message: approvalMessage(validatorSet, batchId, operationHash),
+  });
+}
+
+/**
+ * Build the ed25519 precompile instructions carrying the approval attestations of
+ * several validators for one operation. All signatures share a single copy of the
+ * message, so they fit in one instruction. Returns no instruction without validators.
+ */
+export function approvalAttestations(
+  validatorSet: web3.PublicKey,
+  batchId: number | BN,
+  operationHash: number[] | Uint8Array,
+  validators: web3.Keypair[]
+): web3.TransactionInstruction[] {
+  if (validators.length === 0) {
+    return [];
+  }
+
+  const message = approvalMessage(validatorSet, batchId, operationHash);
+  const offsetsEnd = 2 + validators.length * 14;
+  const messageOffset = offsetsEnd + validators.length * (32 + 64);
+  const data = Buffer.alloc(messageOffset + message.length);
+  data.writeUInt8(validators.length, 0);
+  message.copy(data, messageOffset);
+
+  validators.forEach((validator, i) => {
+    const publicKeyOffset = offsetsEnd + i * (32 + 64);
+    const signatureOffset = publicKeyOffset + 32;
+    const offsets = [
+      signatureOffset,
+      0xffff,
+      publicKeyOffset,
+      0xffff,
+      messageOffset,
+      message.length,
+      0xffff,
+    ];
+    offsets.forEach((value, j) =>
+      data.writeUInt16LE(value, 2 + i * 14 + j * 2)
+    );
+
+    const signature = ed25519.sign(message, validator.secretKey.slice(0, 32));
+    validator.publicKey.toBuffer().copy(data, publicKeyOffset);
+    Buffer.from(signature).copy(data, signatureOffset);
+  });
+
+  return [
+    new web3.TransactionInstruction({
+      programId: web3.Ed25519Program.programId,
+      keys: [],
+      data,
+    }),
+  ];
+}
+
+/**
+ * Compute the operation hash validators attest to for a governance proposal.
+ * Hash: blake3(domain || borsh(action))
+ */
+export function proposalHash(
+  program: Program<SkylineProgram>,
+  action: any
+): Uint8Array {
+  return blake3(
+    Buffer.concat([
+      Buffer.from(PROPOSAL_HASH_DOMAIN),
+      program.coder.types.encode("GovernanceAction", action),
+    ])
+  );
+}
+
+/**
+ * Compute the operation hash validators attest to for a bridging transaction.
+ * Hash: blake3(domain || amount (LE u128) || source_chain || recipient || mint)
+ */
+export function bridgingTransactionHash(
+  amount: number | BN,
+  sourceChain: number,
+  recipient: web3.PublicKey,
+  mint: web3.PublicKey
+): Uint8Array {
+  return blake3(
+    Buffer.concat([
+      Buffer.from(BRIDGING_TRANSACTION_HASH_DOMAIN),
+      new BN(amount).toArrayLike(Buffer, "le", 16),
+      Buffer.from([sourceChain]),
+      recipient.toBuffer(),
+      mint.toBuffer(),
+    ])
+  );
+}
+
+/**
+ * Split approvals into the groups relayed per transaction; no approvals still make one
+ * (empty) group, so the call is sent
+ */
+export function approvalChunks(
+  validators: web3.Keypair[],
+  size: number = LIMITS.MAX_TX_ATTESTATIONS
+): web3.Keypair[][] {
+  const chunks: web3.Keypair[][] = [];
+  for (let i = 0; i < validators.length; i += size) {
+    chunks.push(validators.slice(i, i + size));
+  }
+  return chunks.length > 0 ? chunks : [[]];
+}
+
+/**
+ * Governance actions that replace the validator set and archive it on execution
+ */
+export const SET_CHANGING_ACTIONS = [
+  "updateValidators",
+  "applyValidatorDelta",
+  "setValidatorWeight",
+];
+
+/**
+ * Build writable remaining account metas for accounts written by a governance action
+ */
+export function actionAccountMetas(
+  accounts: web3.PublicKey[] = []
+): web3.AccountMeta[] {
+  return accounts.map((pubkey) => ({
+    pubkey,
+    isWritable: true,
+    isSigner: false,
+  }));
+}
+
+/**
+ * Snapshot PDA the next validator set change archives into (current epoch + 1)
+ */
+export async function nextSnapshot(
+  program: Program<SkylineProgram>
+): Promise<web3.PublicKey> {
+  const pdas = new PDAs(program.programId);
+  const vs = await program.account.validatorSet.fetch(pdas.validatorSet());
+  return pdas.validatorSetSnapshot(vs.epoch.addn(1));
+}
+
+/**
+ * Warp the bank to the given slot
+ */
+export async function warpToSlot(
+  connection: BankrunConnection,
+  slot: number
+): Promise<void> {
+  await connection.warpToSlot(slot);
 }
 
 // ============================================================================
@@ -90,20 +419,32 @@ export function calculateExpectedThreshold(validatorCount: number): number {
 
 export class PDAs {
   private programId: web3.PublicKey;
-  constructor(programId: web3.PublicKey) {
+  private instanceId: BN;
+  constructor(
+    programId: web3.PublicKey,
+    instanceId: BN = TEST_DEPLOYMENT.instanceId
+  ) {
     this.programId = programId;
+    this.instanceId = instanceId;
+  }
+
+  /**
+   * Little-endian bridge instance ID, part of the seeds of every PDA
+   */
+  instanceSeed(): Buffer {
+    return this.instanceId.toArrayLike(Buffer, "le", 8);
   }
 
   validatorSet(): web3.PublicKey {
     return web3.PublicKey.findProgramAddressSync(
-      [Buffer.from(SEEDS.VALIDATOR_SET)],
+      [Buffer.from(SEEDS.VALIDATOR_SET), this.instanceSeed()],
       this.programId
     )[0];
   }
 
   vault(): web3.PublicKey {
     return web3.PublicKey.findProgramAddressSync(
-      [Buffer.from(SEEDS.VAULT)],
+      [Buffer.from(SEEDS.VAULT), this.instanceSeed()],
       this.programId
     )[0];
   }
@@ -112,16 +453,141 @@ export class PDAs {
     const batchBN = typeof batchId === "number" ? new BN(batchId) : batchId;
     const batchLe = batchBN.toArrayLike(Buffer, "le", 8);
     return web3.PublicKey.findProgramAddressSync(
-      [Buffer.from(SEEDS.BRIDGING_TRANSACTION), batchLe],
+      [Buffer.from(SEEDS.BRIDGING_TRANSACTION), this.instanceSeed(), batchLe],
+      this.programId
+    )[0];
+  }
+
+  governanceProposal(batchId: number | BN): web3.PublicKey {
+    const batchBN = typeof batchId === "number" ? new BN(batchId) : batchId;
+    const batchLe = batchBN.toArrayLike(Buffer, "le", 8);
+    return web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(SEEDS.GOVERNANCE_PROPOSAL), this.instanceSeed(), batchLe],
+      this.programId
+    )[0];
+  }
+
+  validatorDelta(batchId: number | BN): web3.PublicKey {
+    const batchBN = typeof batchId === "number" ? new BN(batchId) : batchId;
+    const batchLe = batchBN.toArrayLike(Buffer, "le", 8);
+    return web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(SEEDS.VALIDATOR_DELTA), this.instanceSeed(), batchLe],
+      this.programId
+    )[0];
+  }
+
+  validatorSetSnapshot(epoch: number | BN): web3.PublicKey {
+    const epochBN = typeof epoch === "number" ? new BN(epoch) : epoch;
+    const epochLe = epochBN.toArrayLike(Buffer, "le", 8);
+    return web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(SEEDS.VALIDATOR_SET_SNAPSHOT), this.instanceSeed(), epochLe],
+      this.programId
+    )[0];
+  }
+
+  validatorInfo(validator: web3.PublicKey): web3.PublicKey {
+    return web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(SEEDS.VALIDATOR_INFO), this.instanceSeed(), validator.toBuffer()],
+      this.programId
+    )[0];
+  }
+
+  validatorStats(): web3.PublicKey {
+    return web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(SEEDS.VALIDATOR_STATS), this.instanceSeed()],
+      this.programId
+    )[0];
+  }
+
+  validatorBond(validator: web3.PublicKey): web3.PublicKey {
+    return web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(SEEDS.VALIDATOR_BOND), this.instanceSeed(), validator.toBuffer()],
+      this.programId
+    )[0];
+  }
+
+  treasury(): web3.PublicKey {
+    return web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(SEEDS.TREASURY), this.instanceSeed()],
+      this.programId
+    )[0];
+  }
+
+  chainRegistry(): web3.PublicKey {
+    return web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(SEEDS.CHAIN_REGISTRY), this.instanceSeed()],
+      this.programId
+    )[0];
+  }
+
+  tokenConfig(mint: web3.PublicKey): web3.PublicKey {
+    return web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(SEEDS.TOKEN_CONFIG), this.instanceSeed(), mint.toBuffer()],
+      this.programId
+    )[0];
+  }
+
+  feeVault(): web3.PublicKey {
+    return web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(SEEDS.FEE_VAULT), this.instanceSeed()],
+      this.programId
+    )[0];
+  }
+
+  rewardAccount(participant: web3.PublicKey): web3.PublicKey {
+    return web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(SEEDS.REWARD_ACCOUNT),
+        this.instanceSeed(),
+        participant.toBuffer(),
+      ],
+      this.programId
+    )[0];
+  }
+
+  initializerGrant(): web3.PublicKey {
+    return web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(SEEDS.INITIALIZER_GRANT), this.instanceSeed()],
+      this.programId
+    )[0];
+  }
+
+  guardianCouncil(): web3.PublicKey {
+    return web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(SEEDS.GUARDIAN_COUNCIL), this.instanceSeed()],
       this.programId
     )[0];
   }
 
-  validatorSetChange(batchId: number | BN): web3.PublicKey {
+  guardianProposal(batchId: number | BN): web3.PublicKey {
     const batchBN = typeof batchId === "number" ? new BN(batchId) : batchId;
     const batchLe = batchBN.toArrayLike(Buffer, "le", 8);
     return web3.PublicKey.findProgramAddressSync(
-      [Buffer.from(SEEDS.VALIDATOR_SET_CHANGE), batchLe],
+      [Buffer.from(SEEDS.GUARDIAN_PROPOSAL), this.instanceSeed(), batchLe],
+      this.programId
+    )[0];
+  }
+
+  programData(): web3.PublicKey {
+    return web3.PublicKey.findProgramAddressSync(
+      [this.programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE_ID
+    )[0];
+  }
+
+  /**
+   * Deployment-wide upgrade authority, shared by every instance
+   */
+  upgradeAuthority(): web3.PublicKey {
+    return web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(SEEDS.UPGRADE_AUTHORITY)],
+      this.programId
+    )[0];
+  }
+
+  keyRotation(oldKey: web3.PublicKey): web3.PublicKey {
+    return web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(SEEDS.KEY_ROTATION), this.instanceSeed(), oldKey.toBuffer()],
       this.programId
     )[0];
   }
@@ -147,6 +613,12 @@ export class AccountFetchers {
     return await this.program.account.validatorSet.fetchNullable(pda);
   }
 
+  async getValidatorSetSnapshotNullable(
+    pda: web3.PublicKey
+  ): Promise<any | null> {
+    return await this.program.account.validatorSetSnapshot.fetchNullable(pda);
+  }
+
   async getVault(pda: web3.PublicKey): Promise<VaultData> {
     return await this.program.account.vault.fetch(pda);
   }
@@ -408,20 +880,89 @@ export class InitializeHelper {
     this.owner = owner;
   }
 
+  /**
+   * Build the initialize call; `signers` prove possession of the validator keys.
+   * The owner (the program upgrade authority) initializes unless `initializer` is given.
+   */
+  private build(
+    validators: web3.PublicKey[],
+    lastId: number | BN,
+    signers: web3.Keypair[],
+    weights: number[] | null,
+    initializer: web3.Keypair | null = null
+  ) {
+    const lastIdBN = typeof lastId === "number" ? new BN(lastId) : lastId;
+    const pdas = new PDAs(this.program.programId);
+
+    return this.program.methods
+      .initialize(
+        validators,
+        lastIdBN,
+        TEST_CONFIG,
+        weights && weights.map((w) => new BN(w)),
+        TEST_DEPLOYMENT
+      )
+      .accountsPartial({
+        signer: initializer?.publicKey ?? this.owner.publicKey,
+        programData: pdas.programData(),
+        initializerGrant: null,
+        validatorSetSnapshot: pdas.validatorSetSnapshot(0),
+      })
+      .remainingAccounts(
+        signers.map((s) => ({
+          pubkey: s.publicKey,
+          isWritable: false,
+          isSigner: true,
+        }))
+      )
+      .signers(initializer ? [initializer, ...signers] : signers);
+  }
+
   /**
    * Call initialize instruction
    */
   async call(
     validators: web3.PublicKey[],
-    lastId: number | BN = 0
+    lastId: number | BN = 0,
+    signers: web3.Keypair[] = [],
+    weights: number[] | null = null
   ): Promise<string> {
-    const lastIdBN = typeof lastId === "number" ? new BN(lastId) : lastId;
+    return await this.build(validators, lastId, signers, weights).rpc();
+  }
+
+  /**
+   * Call initialize for another bridge instance as the key of its initializer
+   * grant; the validators co-sign their admission
+   */
+  async callWithGrant(
+    instanceId: BN,
+    validators: web3.Keypair[],
+    initializer: web3.Keypair
+  ): Promise<string> {
+    const pdas = new PDAs(this.program.programId, instanceId);
 
     return await this.program.methods
-      .initialize(validators, lastIdBN)
-      .accounts({
-        signer: this.owner.publicKey,
+      .initialize(
+        validators.map((v) => v.publicKey),
+        new BN(0),
+        TEST_CONFIG,
+        null,
+        { ...TEST_DEPLOYMENT, instanceId }
+      )
+      .accountsPartial({
+        signer: initializer.publicKey,
+        programData: pdas.programData(),
+        initializerGrant: pdas.initializerGrant(),
+        validatorSetSnapshot: pdas.validatorSetSnapshot(0),
       })
+      .remainingAccounts(
+        validators.map((v) => ({
+          pubkey: v.publicKey,
+          isWritable: false,
+          isSigner: true,
+        }))
+      )
+      .signers([initializer, ...validators])
       .rpc();
   }
 
@@ -431,18 +972,14 @@ export class InitializeHelper {
   async expectError(
     validators: web3.PublicKey[],
     expectedErrorCode: string,
-    lastId: number | BN = 0
+    lastId: number | BN = 0,
+    signers: web3.Keypair[] = [],
+    weights: number[] | null = null,
+    initializer: web3.Keypair | null = null
   ): Promise<void> {
-    const lastIdBN = typeof lastId === "number" ? new BN(lastId) : lastId;
-
     let thrown = false;
     try {
-      await this.program.methods
-        .initialize(validators, lastIdBN)
-        .accounts({
-          signer: this.owner.publicKey,
-        })
-        .rpc();
+      await this.build(validators, lastId, signers, weights, initializer).rpc();
     } catch (e: any) {
       thrown = true;
       expect(e.error?.errorCode?.code).to.equal(expectedErrorCode);
@@ -460,18 +997,12 @@ export class InitializeHelper {
    */
   async expectFailure(
     validators: web3.PublicKey[],
-    lastId: number | BN = 0
+    lastId: number | BN = 0,
+    signers: web3.Keypair[] = []
   ): Promise<void> {
-    const lastIdBN = typeof lastId === "number" ? new BN(lastId) : lastId;
-
     let thrown = false;
     try {
-      await this.program.methods
-        .initialize(validators, lastIdBN)
-        .accounts({
-          signer: this.owner.publicKey,
-        })
-        .rpc();
+      await this.build(validators, lastId, signers).rpc();
     } catch (e: any) {
       thrown = true;
     }
@@ -487,25 +1018,31 @@ export class InitializeHelper {
 // ============================================================================
 
 export interface BridgeTransactionParams {
-  amount: number | BN;
+  amount: number | BN; // In the decimals of the token on the source chain
   batchId: number | BN;
   recipient: web3.PublicKey;
   mint: web3.PublicKey;
   validators: web3.Keypair[];
   vaultPDA: web3.PublicKey;
+  sourceChain?: number; // Optional, defaults to TEST_SOURCE_CHAIN
+  rewardAccounts?: web3.PublicKey[]; // Reward accounts credited on execution
 }
 
 export class BridgeTransactionHelper {
   private program: Program<SkylineProgram>;
   private owner: anchor.Wallet;
+  private validatorSet: web3.PublicKey;
 
   constructor(program: Program<SkylineProgram>, owner: anchor.Wallet) {
     this.program = program;
     this.owner = owner;
+    this.validatorSet = new PDAs(program.programId).validatorSet();
   }
 
   /**
-   * Call bridgeTransaction instruction
+   * Call bridgeTransaction instruction, relaying the approval attestations of
+   * `validators` in groups of `MAX_TX_ATTESTATIONS` (two next to reward accounts).
+   * Returns the signature of the last transaction.
    */
   async call(params: BridgeTransactionParams): Promise<string> {
     const amountBN =
@@ -514,32 +1051,53 @@ export class BridgeTransactionHelper {
       typeof params.batchId === "number"
         ? new BN(params.batchId)
         : params.batchId;
+    const sourceChain = params.sourceChain ?? TEST_SOURCE_CHAIN;
+    const operationHash = bridgingTransactionHash(
+      amountBN,
+      sourceChain,
+      params.recipient,
+      params.mint
+    );
 
-    const remainingAccounts = params.validators.map((v) => ({
-      pubkey: v.publicKey,
-      isSigner: true,
-      isWritable: false,
-    }));
+    let tx = "";
+    for (const approvals of approvalChunks(
+      params.validators,
+      params.rewardAccounts ? 2 : LIMITS.MAX_TX_ATTESTATIONS
+    )) {
+      tx = await this.program.methods
+        .bridgeTransaction(
+          TEST_DEPLOYMENT.instanceId,
+          amountBN,
+          batchIdBN,
+          sourceChain
+        )
+        .accounts({
+          payer: this.owner.publicKey,
+          recipient: params.recipient,
+          mintToken: params.mint,
+          recipientAta: getAssociatedTokenAddressSync(
+            params.mint,
+            params.recipient
+          ),
+          vaultAta: getAssociatedTokenAddressSync(
+            params.mint,
+            params.vaultPDA,
+            true
+          ),
+        })
+        .remainingAccounts(actionAccountMetas(params.rewardAccounts))
+        .preInstructions(
+          approvalAttestations(
+            this.validatorSet,
+            batchIdBN,
+            operationHash,
+            approvals
+          )
+        )
+        .rpc();
+    }
 
-    return await this.program.methods
-      .bridgeTransaction(amountBN, batchIdBN)
-      .accounts({
-        payer: this.owner.publicKey,
-        recipient: params.recipient,
-        mintToken: params.mint,
-        recipientAta: getAssociatedTokenAddressSync(
-          params.mint,
-          params.recipient
-        ),
-        vaultAta: getAssociatedTokenAddressSync(
-          params.mint,
-          params.vaultPDA,
-          true
-        ),
-      })
-      .signers(params.validators)
-      .remainingAccounts(remainingAccounts)
-      .rpc();
+    return tx;
   }
 
   /**
@@ -558,22 +1116,38 @@ export class BridgeTransactionHelper {
   ): Promise<string> {
     const amountBN = typeof amount === "number" ? new BN(amount) : amount;
     const batchIdBN = typeof batchId === "number" ? new BN(batchId) : batchId;
+    const operationHash = bridgingTransactionHash(
+      amountBN,
+      TEST_SOURCE_CHAIN,
+      accounts.recipient,
+      accounts.mintToken
+    );
 
-    const remainingAccounts = validators.map((v) => ({
-      pubkey: v.publicKey,
-      isSigner: true,
-      isWritable: false,
-    }));
+    let tx = "";
+    for (const approvals of approvalChunks(validators)) {
+      tx = await this.program.methods
+        .bridgeTransaction(
+          TEST_DEPLOYMENT.instanceId,
+          amountBN,
+          batchIdBN,
+          TEST_SOURCE_CHAIN
+        )
+        .accounts({
+          payer: this.owner.publicKey,
+          ...accounts,
+        })
+        .preInstructions(
+          approvalAttestations(
+            this.validatorSet,
+            batchIdBN,
+            operationHash,
+            approvals
+          )
+        )
+        .rpc();
+    }
 
-    return await this.program.methods
-      .bridgeTransaction(amountBN, batchIdBN)
-      .accounts({
-        payer: this.owner.publicKey,
-        ...accounts,
-      })
-      .signers(validators)
-      .remainingAccounts(remainingAccounts)
-      .rpc();
+    return tx;
   }
 
   /**
@@ -600,7 +1174,7 @@ export class BridgeTransactionHelper {
   }
 
   /**
-   * Call bridgeTransaction with no remaining accounts (for NoSignersProvided test)
+   * Call bridgeTransaction without approval attestations (for NoSignersProvided test)
    */
   async callWithNoSigners(
     amount: number | BN,
@@ -613,7 +1187,12 @@ export class BridgeTransactionHelper {
     const batchIdBN = typeof batchId === "number" ? new BN(batchId) : batchId;
 
     return await this.program.methods
-      .bridgeTransaction(amountBN, batchIdBN)
+      .bridgeTransaction(
+        TEST_DEPLOYMENT.instanceId,
+        amountBN,
+        batchIdBN,
+        TEST_SOURCE_CHAIN
+      )
       .accounts({
         payer: this.owner.publicKey,
         recipient: recipient,
@@ -768,15 +1347,25 @@ export class BridgeRequestHelper {
   private program: Program<SkylineProgram>;
   private owner: anchor.Wallet;
   private vaultPDA: web3.PublicKey;
+  private feeVaultPDA: web3.PublicKey;
 
   constructor(
     program: Program<SkylineProgram>,
     owner: anchor.Wallet,
-    vaultPDA: web3.PublicKey
+    vaultPDA: web3.PublicKey,
+    feeVaultPDA: web3.PublicKey
   ) {
     this.program = program;
     this.owner = owner;
     this.vaultPDA = vaultPDA;
+    this.feeVaultPDA = feeVaultPDA;
+  }
+
+  /**
+   * Fee vault ATA collecting the token fees of a mint
+   */
+  feeVaultAta(mint: web3.PublicKey): web3.PublicKey {
+    return getAssociatedTokenAddressSync(mint, this.feeVaultPDA, true);
   }
 
   /**
@@ -799,6 +1388,7 @@ export class BridgeRequestHelper {
 
     return await this.program.methods
       .bridgeRequest(
+        TEST_DEPLOYMENT.instanceId,
         amountBN,
         Buffer.from(params.receiver),
         params.destinationChain
@@ -807,6 +1397,7 @@ export class BridgeRequestHelper {
         signer: signer.publicKey,
         signersAta: signerAta,
         vaultAta: vaultAta,
+        feeVaultAta: this.feeVaultAta(params.mint),
         mint: params.mint,
       })
       .signers(signer === this.owner.payer ? [] : [signer])
@@ -814,16 +1405,35 @@ export class BridgeRequestHelper {
   }
 
   /**
-   * Call with custom accounts (for error testing)
+   * Simulate quoteBridgeRequest instruction and return the quote
    */
-  async callWithCustomAccounts(
-    amount: number | BN,
-    receiver: Buffer | Uint8Array,
+  async quote(params: Omit<BridgeRequestParams, "signer">): Promise<any> {
+    const amountBN =
+      typeof params.amount === "number" ? new BN(params.amount) : params.amount;
+
+    return await this.program.methods
+      .quoteBridgeRequest(
+        TEST_DEPLOYMENT.instanceId,
+        amountBN,
+        Buffer.from(params.receiver),
+        params.destinationChain
+      )
+      .accounts({ mint: params.mint })
+      .view();
+  }
+
+  /**
+   * Call with custom accounts (for error testing)
+   */
+  async callWithCustomAccounts(
+    amount: number | BN,
+    receiver: Buffer | Uint8Array,
     destinationChain: number,
     accounts: {
       signer: web3.PublicKey;
       signersAta: web3.PublicKey;
       vaultAta: web3.PublicKey;
+      feeVaultAta?: web3.PublicKey;
       mint: web3.PublicKey;
     },
     signers: web3.Keypair[]
@@ -831,8 +1441,16 @@ export class BridgeRequestHelper {
     const amountBN = typeof amount === "number" ? new BN(amount) : amount;
 
     return await this.program.methods
-      .bridgeRequest(amountBN, Buffer.from(receiver), destinationChain)
-      .accounts(accounts)
+      .bridgeRequest(
+        TEST_DEPLOYMENT.instanceId,
+        amountBN,
+        Buffer.from(receiver),
+        destinationChain
+      )
+      .accounts({
+        feeVaultAta: this.feeVaultAta(accounts.mint),
+        ...accounts,
+      })
       .signers(signers)
       .rpc();
   }
@@ -866,17 +1484,25 @@ export class BridgeRequestHelper {
 // ============================================================================
 
 export interface BridgeRequestEventData {
+  instanceId: BN;
   sender: web3.PublicKey;
   amount: BN;
   receiver: Buffer;
   destinationChain: number;
   mintToken: web3.PublicKey;
   batchRequestId: BN;
+  normalizedAmount: BN;
+  fee: BN;
+  netAmount: BN;
+  solFee: BN;
 }
 
 export interface ValidatorSetUpdatedEventData {
+  instanceId: BN;
   newSigners: web3.PublicKey[];
+  newWeights: BN[];
   newThreshold: number;
+  newThresholdWeight: BN;
   batchId: BN;
 }
 
@@ -953,41 +1579,112 @@ export class EventParser {
   private decodeBridgeRequestEvent(data: Buffer): BridgeRequestEventData {
     let offset = 0;
 
-    // Field 1: sender (Pubkey - 32 bytes)
+    // Field 1: instance_id (u64 - 8 bytes, little-endian)
+    const instanceId = new BN(data.slice(offset, offset + 8), "le");
+    offset += 8;
+
+    // Field 2: sender (Pubkey - 32 bytes)
     const sender = new web3.PublicKey(data.slice(offset, offset + 32));
     offset += 32;
 
-    // Field 2: amount (u64 - 8 bytes, little-endian)
+    // Field 3: amount (u64 - 8 bytes, little-endian)
     const amount = new BN(data.slice(offset, offset + 8), "le");
     offset += 8;
 
-    // Field 3: receiver (Vec<u8> - 4 bytes length prefix + data)
+    // Field 4: receiver (Vec<u8> - 4 bytes length prefix + data)
     const receiverLength = data.readUInt32LE(offset);
     offset += 4;
     const receiver = Buffer.from(data.slice(offset, offset + receiverLength));
     offset += receiverLength;
 
-    // Field 4: destination_chain (u8 - 1 byte)
+    // Field 5: destination_chain (u8 - 1 byte)
     const destinationChain = data.readUInt8(offset);
     offset += 1;
 
-    // Field 5: mint_token (Pubkey - 32 bytes)
+    // Field 6: mint_token (Pubkey - 32 bytes)
     const mintToken = new web3.PublicKey(data.slice(offset, offset + 32));
     offset += 32;
 
-    // Field 6: batch_request_id (u64 - 8 bytes, little-endian)
+    // Field 7: batch_request_id (u64 - 8 bytes, little-endian)
     const batchRequestId = new BN(data.slice(offset, offset + 8), "le");
+    offset += 8;
+
+    // Field 8: normalized_amount (u128 - 16 bytes, little-endian)
+    const normalizedAmount = new BN(data.slice(offset, offset + 16), "le");
+    offset += 16;
+
+    // Field 9: fee (u64 - 8 bytes, little-endian)
+    const fee = new BN(data.slice(offset, offset + 8), "le");
+    offset += 8;
+
+    // Field 10: net_amount (u64 - 8 bytes, little-endian)
+    const netAmount = new BN(data.slice(offset, offset + 8), "le");
+    offset += 8;
+
+    // Field 11: sol_fee (u64 - 8 bytes, little-endian)
+    const solFee = new BN(data.slice(offset, offset + 8), "le");
 
     return {
+      instanceId,
       sender,
       amount,
       receiver,
       destinationChain,
       mintToken,
       batchRequestId,
+      normalizedAmount,
+      fee,
+      netAmount,
+      solFee,
     };
   }
 
+  /**
+   * Parse TransactionExecutedEvent from transaction signature, decoded with the
+   * program's event coder (the execution emits several events)
+   */
+  async parseTransactionExecutedEvent(signature: string): Promise<any | null> {
+    return await this.parseEvent(signature, "TransactionExecutedEvent");
+  }
+
+  /**
+   * Parse ProgramUpgradedEvent from transaction signature
+   */
+  async parseProgramUpgradedEvent(signature: string): Promise<any | null> {
+    return await this.parseEvent(signature, "ProgramUpgradedEvent");
+  }
+
+  /**
+   * Parse the first event called `name` from transaction signature, decoded with
+   * the program's event coder
+   */
+  private async parseEvent(
+    signature: string,
+    name: string
+  ): Promise<any | null> {
+    await new Promise((resolve) => setTimeout(resolve, 500));
+
+    const tx = await this.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+
+    for (const log of tx?.meta?.logMessages ?? []) {
+      if (!log.startsWith("Program data: ")) {
+        continue;
+      }
+      const event = this.program.coder.events.decode(
+        log.slice("Program data: ".length)
+      );
+      if (event?.name === name) {
+        return event.data;
+      }
+    }
+
+    console.error(`No ${name} found`);
+    return null;
+  }
+
   // Event discriminator for ValidatorSetUpdatedEvent
   private static readonly VALIDATOR_SET_UPDATED_DISCRIMINATOR = Buffer.from([
     92, 126, 111, 2, 195, 25, 244, 136,
@@ -1049,6 +1746,10 @@ export class EventParser {
   ): ValidatorSetUpdatedEventData {
     let offset = 0;
 
+    // instance_id: u64 (8 bytes, little-endian)
+    const instanceId = new BN(data.slice(offset, offset + 8), "le");
+    offset += 8;
+
     // new_signers: Vec<Pubkey> (4 bytes length + data)
     const signersLength = data.readUInt32LE(offset);
     offset += 4;
@@ -1058,16 +1759,32 @@ export class EventParser {
       offset += 32;
     }
 
-    // new_threshold: u8 (1 byte)
-    const newThreshold = data.readUInt8(offset);
-    offset += 1;
+    // new_weights: Vec<u64> (4 bytes length + data)
+    const weightsLength = data.readUInt32LE(offset);
+    offset += 4;
+    const newWeights: BN[] = [];
+    for (let i = 0; i < weightsLength; i++) {
+      newWeights.push(new BN(data.slice(offset, offset + 8), "le"));
+      offset += 8;
+    }
+
+    // new_threshold: u16 (2 bytes, little-endian)
+    const newThreshold = data.readUInt16LE(offset);
+    offset += 2;
+
+    // new_threshold_weight: u64 (8 bytes, little-endian)
+    const newThresholdWeight = new BN(data.slice(offset, offset + 8), "le");
+    offset += 8;
 
     // batch_id: u64 (8 bytes, little-endian)
     const batchId = new BN(data.slice(offset, offset + 8), "le");
 
     return {
+      instanceId,
       newSigners,
+      newWeights,
       newThreshold,
+      newThresholdWeight,
       batchId,
     };
   }
@@ -1082,29 +1799,30 @@ export class EventParser {
  */
 export class BridgeVSUFixture {
   private program: Program<SkylineProgram>;
-  private connection: web3.Connection;
+  private governance: GovernanceFixture;
   private validatorSetPDA: web3.PublicKey;
   private defaultPayer: web3.Keypair;
 
   constructor(
     program: Program<SkylineProgram>,
-    connection: web3.Connection,
+    governance: GovernanceFixture,
     validatorSetPDA: web3.PublicKey,
     defaultPayer: web3.Keypair
   ) {
     this.program = program;
-    this.connection = connection;
+    this.governance = governance;
     this.validatorSetPDA = validatorSetPDA;
     this.defaultPayer = defaultPayer;
   }
 
   /**
-   * Get the ValidatorSetChange PDA for a given batch_id
+   * Get the GovernanceProposal PDA carrying the validator set change for a given batch_id
    */
   getValidatorSetChangePDA(batchId: number): [web3.PublicKey, number] {
     return web3.PublicKey.findProgramAddressSync(
       [
-        Buffer.from("validator_set_change"),
+        Buffer.from(SEEDS.GOVERNANCE_PROPOSAL),
+        TEST_DEPLOYMENT.instanceId.toArrayLike(Buffer, "le", 8),
         new BN(batchId).toArrayLike(Buffer, "le", 8),
       ],
       this.program.programId
@@ -1112,7 +1830,11 @@ export class BridgeVSUFixture {
   }
 
   /**
-   * Call bridge_vsu instruction
+   * Call bridge_vsu instruction. Signers among `added` sign their admission
+   * (with the first transaction), the others relay approval attestations in groups
+   * of `MAX_TX_ATTESTATIONS`. Once the update is scheduled, it is activated after
+   * its timelock unless `activate` is false.
+   * Returns the signature of the last transaction.
    */
   async call(params: {
     added: web3.PublicKey[];
@@ -1120,29 +1842,76 @@ export class BridgeVSUFixture {
     batchId: number;
     payer?: web3.Keypair;
     signers: web3.Keypair[];
+    foreignKeys?: any[];
+    weights?: BN[];
+    actionAccounts?: web3.PublicKey[];
+    preInstructions?: web3.TransactionInstruction[];
+    activate?: boolean;
   }): Promise<string> {
     const { added, removed, batchId, signers } = params;
     const payer = params.payer || this.defaultPayer;
     const [validatorSetChangePDA] = this.getValidatorSetChangePDA(batchId);
+    const snapshot = await nextSnapshot(this.program);
+    const foreignKeys = params.foreignKeys ?? [];
+    const weights = params.weights ?? [];
+
+    const isAdmission = (signer: web3.Keypair) =>
+      added.some((key) => key.equals(signer.publicKey));
+    const admissions = signers.filter(isAdmission);
+    const operationHash = proposalHash(this.program, {
+      updateValidators: { added, removed, foreignKeys, weights },
+    });
 
-    const remainingAccounts = signers.map((signer) => ({
-      pubkey: signer.publicKey,
-      isWritable: false,
-      isSigner: true,
-    }));
-
-    const tx = await this.program.methods
-      .bridgeVsu(added, removed, new BN(batchId))
-      .accountsPartial({
-        payer: payer.publicKey,
-        validatorSet: this.validatorSetPDA,
-        validatorSetChange: validatorSetChangePDA,
-        systemProgram: web3.SystemProgram.programId,
-      })
-      .remainingAccounts(remainingAccounts)
-      .signers([payer, ...signers])
-      .rpc();
+    let tx = "";
+    for (const [i, approvals] of approvalChunks(
+      signers.filter((signer) => !isAdmission(signer))
+    ).entries()) {
+      const txAdmissions = i === 0 ? admissions : [];
+      tx = await this.program.methods
+        .bridgeVsu(
+          TEST_DEPLOYMENT.instanceId,
+          added,
+          removed,
+          new BN(batchId),
+          foreignKeys,
+          weights
+        )
+        .accountsPartial({
+          payer: payer.publicKey,
+          validatorSet: this.validatorSetPDA,
+          validatorSetChange: validatorSetChangePDA,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .remainingAccounts([
+          ...txAdmissions.map((signer) => ({
+            pubkey: signer.publicKey,
+            isWritable: false,
+            isSigner: true,
+          })),
+          ...actionAccountMetas([...(params.actionAccounts ?? []), snapshot]),
+        ])
+        .preInstructions([
+          ...(i === 0 ? params.preInstructions ?? [] : []),
+          ...approvalAttestations(
+            this.validatorSetPDA,
+            batchId,
+            operationHash,
+            approvals
+          ),
+        ])
+        .signers([payer, ...txAdmissions])
+        .rpc();
+    }
 
+    if (params.activate ?? true) {
+      return (
+        (await this.governance.activateScheduled(
+          batchId,
+          payer,
+          params.actionAccounts
+        )) ?? tx
+      );
+    }
     return tx;
   }
 
@@ -1152,7 +1921,7 @@ export class BridgeVSUFixture {
   async fetchValidatorSetChange(batchId: number): Promise<any | null> {
     try {
       const [pda] = this.getValidatorSetChangePDA(batchId);
-      const account = await this.program.account.validatorDelta.fetch(pda);
+      const account = await this.program.account.governanceProposal.fetch(pda);
       return account;
     } catch {
       return null;
@@ -1169,62 +1938,1439 @@ export class BridgeVSUFixture {
 }
 
 // ============================================================================
-// MAIN TEST FIXTURE CLASS
+// GOVERNANCE PROPOSAL HELPERS
 // ============================================================================
 
-export class SkylineTestFixture {
-  public pdas: PDAs;
-  public accounts: AccountFetchers;
-  public initialize: InitializeHelper;
-  public bridgeTransaction: BridgeTransactionHelper;
-  public bridgeRequest: BridgeRequestHelper;
-  public mints: MintHelper;
-  public batchIds: BatchIdManager;
-  public tokenBalances: TokenBalanceHelper;
-  public events: EventParser;
-  public bridgeVSU: BridgeVSUFixture;
+/**
+ * Fixture for generic governance proposals
+ */
+export class GovernanceFixture {
+  private program: Program<SkylineProgram>;
+  private connection: BankrunConnection;
+  private pdas: PDAs;
+  private defaultPayer: web3.Keypair;
 
-  constructor(ctx: TestContext) {
-    this.pdas = new PDAs(ctx.program.programId);
-    this.accounts = new AccountFetchers(ctx.program);
-    this.initialize = new InitializeHelper(ctx.program, ctx.owner);
-    this.bridgeTransaction = new BridgeTransactionHelper(
-      ctx.program,
-      ctx.owner
-    );
-    this.bridgeRequest = new BridgeRequestHelper(
-      ctx.program,
-      ctx.owner,
-      this.pdas.vault()
+  constructor(
+    program: Program<SkylineProgram>,
+    connection: BankrunConnection,
+    pdas: PDAs,
+    defaultPayer: web3.Keypair
+  ) {
+    this.program = program;
+    this.connection = connection;
+    this.pdas = pdas;
+    this.defaultPayer = defaultPayer;
+  }
+
+  /**
+   * Call submit_proposal instruction. Signers joining the validator set sign their
+   * admission (with the first transaction), the others relay approval attestations
+   * in groups of `MAX_TX_ATTESTATIONS`. Once the proposal is scheduled, it is
+   * activated after its timelock unless `activate` is false.
+   * Returns the signature of the last transaction.
+   */
+  async submit(params: {
+    action: any;
+    batchId: number;
+    payer?: web3.Keypair;
+    signers: web3.Keypair[];
+    actionAccounts?: web3.PublicKey[];
+    activate?: boolean;
+  }): Promise<string> {
+    const { action, batchId, signers } = params;
+    const payer = params.payer || this.defaultPayer;
+    const actionAccounts = [...(params.actionAccounts ?? [])];
+    if (SET_CHANGING_ACTIONS.some((kind) => kind in action)) {
+      actionAccounts.push(await nextSnapshot(this.program));
+    }
+
+    const added: web3.PublicKey[] = action.updateValidators?.added ?? [];
+    const isAdmission = (signer: web3.Keypair) =>
+      added.some((key) => key.equals(signer.publicKey));
+    const admissions = signers.filter(isAdmission);
+    const operationHash = proposalHash(this.program, action);
+
+    let tx = "";
+    for (const [i, approvals] of approvalChunks(
+      signers.filter((signer) => !isAdmission(signer))
+    ).entries()) {
+      const txAdmissions = i === 0 ? admissions : [];
+      tx = await this.program.methods
+        .submitProposal(TEST_DEPLOYMENT.instanceId, action, new BN(batchId))
+        .accountsPartial({
+          payer: payer.publicKey,
+          validatorSet: this.pdas.validatorSet(),
+          proposal: this.pdas.governanceProposal(batchId),
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .remainingAccounts([
+          ...txAdmissions.map((signer) => ({
+            pubkey: signer.publicKey,
+            isWritable: false,
+            isSigner: true,
+          })),
+          ...actionAccountMetas(actionAccounts),
+        ])
+        .preInstructions(
+          approvalAttestations(
+            this.pdas.validatorSet(),
+            batchId,
+            operationHash,
+            approvals
+          )
+        )
+        .signers([payer, ...txAdmissions])
+        .rpc();
+    }
+
+    if (params.activate ?? true) {
+      return (
+        (await this.activateScheduled(
+          batchId,
+          payer,
+          params.actionAccounts
+        )) ?? tx
+      );
+    }
+    return tx;
+  }
+
+  /**
+   * Warp past the timelock of a scheduled proposal and activate it. Returns the
+   * signature of the activation, or null if the proposal is not scheduled.
+   */
+  async activateScheduled(
+    batchId: number,
+    payer?: web3.Keypair,
+    actionAccounts?: web3.PublicKey[]
+  ): Promise<string | null> {
+    const proposal = await this.fetchProposal(batchId);
+    if (!proposal || !("scheduled" in proposal.status)) {
+      return null;
+    }
+
+    await warpToSlot(this.connection, proposal.activationSlot.toNumber());
+    return await this.execute(batchId, payer, actionAccounts);
+  }
+
+  /**
+   * Call execute_proposal instruction, with the maximum compute unit limit an
+   * `UpgradeProgram` activation needs to hash the buffer
+   */
+  async execute(
+    batchId: number,
+    payer?: web3.Keypair,
+    actionAccounts?: web3.PublicKey[]
+  ): Promise<string> {
+    const signer = payer || this.defaultPayer;
+
+    return await this.program.methods
+      .executeProposal(TEST_DEPLOYMENT.instanceId, new BN(batchId))
+      .accountsPartial({
+        payer: signer.publicKey,
+        validatorSet: this.pdas.validatorSet(),
+        proposal: this.pdas.governanceProposal(batchId),
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .remainingAccounts(
+        actionAccountMetas([
+          ...(actionAccounts ?? []),
+          await nextSnapshot(this.program),
+        ])
+      )
+      .preInstructions([
+        web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 }),
+      ])
+      .signers([signer])
+      .rpc();
+  }
+
+  /**
+   * Call veto_proposal instruction
+   */
+  async veto(params: {
+    batchId: number;
+    payer?: web3.Keypair;
+    signers: web3.Keypair[];
+    actionAccounts?: web3.PublicKey[];
+  }): Promise<string> {
+    const payer = params.payer || this.defaultPayer;
+
+    const remainingAccounts = [
+      ...params.signers.map((signer) => ({
+        pubkey: signer.publicKey,
+        isWritable: false,
+        isSigner: true,
+      })),
+      ...actionAccountMetas(params.actionAccounts),
+    ];
+
+    return await this.program.methods
+      .vetoProposal(TEST_DEPLOYMENT.instanceId, new BN(params.batchId))
+      .accountsPartial({
+        payer: payer.publicKey,
+        validatorSet: this.pdas.validatorSet(),
+        proposal: this.pdas.governanceProposal(params.batchId),
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([payer, ...params.signers])
+      .rpc();
+  }
+
+  /**
+   * Submit an action with exactly `threshold` validator approvals and activate it
+   * after the timelock.
+   * Keys joining the validator set sign their admission alongside the first validator.
+   */
+  async pass(
+    action: any,
+    batchId: number,
+    validators: web3.Keypair[],
+    admissions: web3.Keypair[] = []
+  ): Promise<string> {
+    const tx = await this.schedule(action, batchId, validators, admissions);
+    return (await this.activateScheduled(batchId)) ?? tx;
+  }
+
+  /**
+   * Submit an action with exactly `threshold` validator approvals, leaving it
+   * scheduled for activation after the timelock.
+   * Keys joining the validator set sign their admission alongside the first validator.
+   */
+  async schedule(
+    action: any,
+    batchId: number,
+    validators: web3.Keypair[],
+    admissions: web3.Keypair[] = []
+  ): Promise<string> {
+    const vs = await this.program.account.validatorSet.fetch(
+      this.pdas.validatorSet()
     );
-    this.mints = new MintHelper(ctx.connection, ctx.owner.payer);
-    this.batchIds = new BatchIdManager(this.accounts, this.pdas.validatorSet());
-    this.tokenBalances = new TokenBalanceHelper(ctx.connection);
-    this.events = new EventParser(ctx.program, ctx.connection);
+    if (admissions.length === 0) {
+      return await this.submit({
+        action,
+        batchId,
+        signers: validators.slice(0, vs.threshold),
+        activate: false,
+      });
+    }
 
-    this.bridgeVSU = new BridgeVSUFixture(
-      ctx.program,
-      ctx.connection,
-      this.pdas.validatorSet(),
-      ctx.owner.payer
+    await this.submit({
+      action,
+      batchId,
+      signers: [validators[0], ...admissions],
+      activate: false,
+    });
+    return await this.submit({
+      action,
+      batchId,
+      signers: validators.slice(1, vs.threshold),
+      activate: false,
+    });
+  }
+
+  /**
+   * Fetch GovernanceProposal account or null if it doesn't exist
+   */
+  async fetchProposal(batchId: number): Promise<any | null> {
+    return await this.program.account.governanceProposal.fetchNullable(
+      this.pdas.governanceProposal(batchId)
     );
   }
+}
+
+// ============================================================================
+// VALIDATOR DELTA HELPERS
+// ============================================================================
+
+/**
+ * Fixture for validator set updates assembled across multiple transactions
+ */
+export class ValidatorDeltaFixture {
+  private program: Program<SkylineProgram>;
+  private pdas: PDAs;
+
+  constructor(program: Program<SkylineProgram>, pdas: PDAs) {
+    this.program = program;
+    this.pdas = pdas;
+  }
 
   /**
-   * Check if validator set is already initialized
+   * Call append_validator_delta instruction (the proposer signs and pays).
+   * Added keys passed in `admissions` sign their admission alongside.
    */
-  async isInitialized(): Promise<boolean> {
-    const vsPDA = this.pdas.validatorSet();
-    const vs = await this.accounts.getValidatorSetNullable(vsPDA);
-    return vs !== null;
+  async append(params: {
+    batchId: number;
+    proposer: web3.Keypair;
+    added?: web3.PublicKey[];
+    removed?: web3.PublicKey[];
+    weights?: BN[];
+    admissions?: web3.Keypair[];
+  }): Promise<string> {
+    const admissions = params.admissions ?? [];
+
+    return await this.program.methods
+      .appendValidatorDelta(
+        TEST_DEPLOYMENT.instanceId,
+new BN(params.batchId),
+        params.added ?? [],
+        params.removed ?? [],
+        params.weights ?? []
+      )
+      .accountsPartial({
+        proposer: params.proposer.publicKey,
+        validatorSet: this.pdas.validatorSet(),
+        validatorDelta: this.pdas.validatorDelta(params.batchId),
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .remainingAccounts(
+        admissions.map((admission) => ({
+          pubkey: admission.publicKey,
+          isWritable: false,
+          isSigner: true,
+        }))
+      )
+      .signers([params.proposer, ...admissions])
+      .rpc();
   }
 
   /**
-   * Get current validator set or throw if not initialized
+   * Call seal_validator_delta instruction
    */
-  async getValidatorSet(): Promise<ValidatorSetData> {
-    const vsPDA = this.pdas.validatorSet();
-    return await this.accounts.getValidatorSet(vsPDA);
+  async seal(batchId: number, proposer: web3.Keypair): Promise<string> {
+    return await this.program.methods
+      .sealValidatorDelta(TEST_DEPLOYMENT.instanceId, new BN(batchId))
+      .accountsPartial({
+        proposer: proposer.publicKey,
+        validatorSet: this.pdas.validatorSet(),
+        validatorDelta: this.pdas.validatorDelta(batchId),
+      })
+      .signers([proposer])
+      .rpc();
+  }
+
+  /**
+   * Call close_validator_delta instruction
+   */
+  async close(batchId: number, proposer: web3.Keypair): Promise<string> {
+    return await this.program.methods
+      .closeValidatorDelta(TEST_DEPLOYMENT.instanceId, new BN(batchId))
+      .accountsPartial({
+        proposer: proposer.publicKey,
+        validatorSet: this.pdas.validatorSet(),
+        validatorDelta: this.pdas.validatorDelta(batchId),
+      })
+      .signers([proposer])
+      .rpc();
+  }
+
+  /**
+   * Fetch ValidatorDelta account or null if it doesn't exist
+   */
+  async fetch(batchId: number): Promise<any | null> {
+    return await this.program.account.validatorDelta.fetchNullable(
+      this.pdas.validatorDelta(batchId)
+    );
+  }
+}
+
+// ============================================================================
+// KEY ROTATION HELPERS
+// ============================================================================
+
+/**
+ * Fixture for single validator key rotations
+ */
+export class KeyRotationFixture {
+  private program: Program<SkylineProgram>;
+  private pdas: PDAs;
+  private defaultPayer: web3.Keypair;
+
+  constructor(
+    program: Program<SkylineProgram>,
+    pdas: PDAs,
+    defaultPayer: web3.Keypair
+  ) {
+    this.program = program;
+    this.pdas = pdas;
+    this.defaultPayer = defaultPayer;
+  }
+
+  /**
+   * Call rotate_validator_key instruction (old and new key both sign)
+   */
+  async rotate(
+    oldKey: web3.Keypair,
+    newKey: web3.Keypair,
+    payer?: web3.Keypair
+  ): Promise<string> {
+    const signer = payer || this.defaultPayer;
+
+    return await this.program.methods
+      .rotateValidatorKey(TEST_DEPLOYMENT.instanceId)
+      .accountsPartial({
+        payer: signer.publicKey,
+        oldKey: oldKey.publicKey,
+        newKey: newKey.publicKey,
+        validatorSet: this.pdas.validatorSet(),
+        keyRotation: this.pdas.keyRotation(oldKey.publicKey),
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .remainingAccounts(
+        actionAccountMetas([
+          await nextSnapshot(this.program),
+          ...this.records(oldKey.publicKey),
+          ...this.records(newKey.publicKey),
+        ])
+      )
+      .signers([signer, oldKey, newKey])
+      .rpc();
+  }
+
+  /**
+   * Records of a validator key moved by a key rotation
+   */
+  records(key: web3.PublicKey): web3.PublicKey[] {
+    return [
+      this.pdas.validatorInfo(key),
+      this.pdas.validatorBond(key),
+      this.pdas.rewardAccount(key),
+    ];
+  }
+
+  /**
+   * Call execute_key_rotation instruction, passing the records of both keys
+   */
+  async execute(oldKey: web3.PublicKey, payer?: web3.Keypair): Promise<string> {
+    const signer = payer || this.defaultPayer;
+    const rotation = await this.program.account.keyRotation.fetch(
+      this.pdas.keyRotation(oldKey)
+    );
+
+    return await this.program.methods
+      .executeKeyRotation(TEST_DEPLOYMENT.instanceId, oldKey)
+      .accountsPartial({
+        payer: signer.publicKey,
+        validatorSet: this.pdas.validatorSet(),
+        keyRotation: this.pdas.keyRotation(oldKey),
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .remainingAccounts(
+        actionAccountMetas([
+          await nextSnapshot(this.program),
+          ...this.records(oldKey),
+          ...this.records(rotation.newKey),
+        ])
+      )
+      .signers([signer])
+      .rpc();
+  }
+
+  /**
+   * Call veto_key_rotation instruction
+   */
+  async veto(
+    oldKey: web3.PublicKey,
+    guardian: web3.Keypair,
+    payer?: web3.Keypair
+  ): Promise<string> {
+    const signer = payer || this.defaultPayer;
+
+    return await this.program.methods
+      .vetoKeyRotation(TEST_DEPLOYMENT.instanceId, oldKey)
+      .accountsPartial({
+        payer: signer.publicKey,
+        guardian: guardian.publicKey,
+        validatorSet: this.pdas.validatorSet(),
+        keyRotation: this.pdas.keyRotation(oldKey),
+      })
+      .signers([signer, guardian])
+      .rpc();
+  }
+
+  /**
+   * Fetch KeyRotation account or null if it doesn't exist
+   */
+  async fetch(oldKey: web3.PublicKey): Promise<any | null> {
+    return await this.program.account.keyRotation.fetchNullable(
+      this.pdas.keyRotation(oldKey)
+    );
+  }
+}
+
+// ============================================================================
+// VALIDATOR INFO HELPERS
+// ============================================================================
+
+/**
+ * Build validator metadata; unset foreign keys are all zeros
+ */
+export function validatorMetadata(
+  name: string,
+  endpoint: string,
+  foreignKeys?: { cardano?: number[]; evm?: number[] }
+): any {
+  return {
+    name,
+    endpoint,
+    foreignKeys: {
+      cardano: foreignKeys?.cardano ?? Array(32).fill(0),
+      evm: foreignKeys?.evm ?? Array(33).fill(0),
+    },
+  };
+}
+
+/**
+ * Fixture for validator registry records
+ */
+export class ValidatorInfoFixture {
+  private program: Program<SkylineProgram>;
+  private pdas: PDAs;
+  private defaultPayer: web3.Keypair;
+
+  constructor(
+    program: Program<SkylineProgram>,
+    pdas: PDAs,
+    defaultPayer: web3.Keypair
+  ) {
+    this.program = program;
+    this.pdas = pdas;
+    this.defaultPayer = defaultPayer;
+  }
+
+  /**
+   * Call update_validator_info instruction (the validator signs for itself)
+   */
+  async update(
+    validator: web3.Keypair,
+    metadata: any,
+    payer?: web3.Keypair
+  ): Promise<string> {
+    const signer = payer || this.defaultPayer;
+
+    return await this.program.methods
+      .updateValidatorInfo(TEST_DEPLOYMENT.instanceId, metadata)
+      .accountsPartial({
+        payer: signer.publicKey,
+        validator: validator.publicKey,
+        validatorSet: this.pdas.validatorSet(),
+        validatorInfo: this.pdas.validatorInfo(validator.publicKey),
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .signers([signer, validator])
+      .rpc();
+  }
+
+  /**
+   * Fetch ValidatorInfo account or null if it doesn't exist
+   */
+  async fetch(validator: web3.PublicKey): Promise<any | null> {
+    return await this.program.account.validatorInfo.fetchNullable(
+      this.pdas.validatorInfo(validator)
+    );
+  }
+}
+
+/**
+ * Fixture for validator suspensions
+ */
+export class SuspensionFixture {
+  private program: Program<SkylineProgram>;
+  private pdas: PDAs;
+
+  constructor(program: Program<SkylineProgram>, pdas: PDAs) {
+    this.program = program;
+    this.pdas = pdas;
+  }
+
+  /**
+   * Call set_validator_suspension instruction (the validator signs for itself)
+   */
+  async set(validator: web3.Keypair, suspended: boolean): Promise<string> {
+    return await this.program.methods
+      .setValidatorSuspension(TEST_DEPLOYMENT.instanceId, suspended)
+      .accountsPartial({
+        validator: validator.publicKey,
+        validatorSet: this.pdas.validatorSet(),
+      })
+      .signers([validator])
+      .rpc();
+  }
+}
+
+/**
+ * Fixture for validator participation statistics
+ */
+export class StatsFixture {
+  private program: Program<SkylineProgram>;
+  private pdas: PDAs;
+
+  constructor(program: Program<SkylineProgram>, pdas: PDAs) {
+    this.program = program;
+    this.pdas = pdas;
+  }
+
+  /**
+   * Call heartbeat instruction (the validator signs for itself)
+   */
+  async heartbeat(validator: web3.Keypair): Promise<string> {
+    return await this.program.methods
+      .heartbeat(TEST_DEPLOYMENT.instanceId)
+      .accountsPartial({
+        payer: this.program.provider.publicKey,
+        validator: validator.publicKey,
+        validatorSet: this.pdas.validatorSet(),
+        validatorStats: this.pdas.validatorStats(),
+      })
+      .signers([validator])
+      .rpc();
+  }
+
+  /**
+   * Fetch the statistics entry of a validator or null if it has none
+   */
+  async fetch(validator: web3.PublicKey): Promise<any | null> {
+    const stats = await this.program.account.validatorStats.fetch(
+      this.pdas.validatorStats()
+    );
+    return (
+      stats.entries.find((e: any) => e.validator.equals(validator)) ?? null
+    );
+  }
+}
+
+/**
+ * Fixture for validator bonds and equivocation reports
+ */
+export class BondFixture {
+  private program: Program<SkylineProgram>;
+  private pdas: PDAs;
+  private defaultPayer: web3.Keypair;
+
+  constructor(
+    program: Program<SkylineProgram>,
+    pdas: PDAs,
+    defaultPayer: web3.Keypair
+  ) {
+    this.program = program;
+    this.pdas = pdas;
+    this.defaultPayer = defaultPayer;
+  }
+
+  /**
+   * Call bond instruction (the validator signs and pays)
+   */
+  async bond(validator: web3.Keypair, amount: number | BN): Promise<string> {
+    return await this.program.methods
+      .bond(TEST_DEPLOYMENT.instanceId, new BN(amount))
+      .accountsPartial({
+        validator: validator.publicKey,
+        validatorBond: this.pdas.validatorBond(validator.publicKey),
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .signers([validator])
+      .rpc();
+  }
+
+  /**
+   * Call unbond instruction
+   */
+  async unbond(validator: web3.Keypair, amount: number | BN): Promise<string> {
+    return await this.program.methods
+      .unbond(TEST_DEPLOYMENT.instanceId, new BN(amount))
+      .accountsPartial({
+        validator: validator.publicKey,
+        validatorSet: this.pdas.validatorSet(),
+        validatorBond: this.pdas.validatorBond(validator.publicKey),
+      })
+      .signers([validator])
+      .rpc();
+  }
+
+  /**
+   * Call withdraw_bond instruction
+   */
+  async withdraw(validator: web3.Keypair): Promise<string> {
+    return await this.program.methods
+      .withdrawBond(TEST_DEPLOYMENT.instanceId)
+      .accountsPartial({
+        validator: validator.publicKey,
+        validatorBond: this.pdas.validatorBond(validator.publicKey),
+      })
+      .signers([validator])
+      .rpc();
+  }
+
+  /**
+   * Call report_equivocation instruction; `attestations` carry the signed approvals
+   */
+  async reportEquivocation(params: {
+    validator: web3.PublicKey;
+    batchId: number;
+    firstHash: number[];
+    secondHash: number[];
+    attestations: web3.TransactionInstruction[];
+    bonded?: boolean;
+    reporter?: web3.Keypair;
+  }): Promise<string> {
+    const reporter = params.reporter || this.defaultPayer;
+
+    return await this.program.methods
+      .reportEquivocation(
+        TEST_DEPLOYMENT.instanceId,
+params.validator,
+        new BN(params.batchId),
+        params.firstHash,
+        params.secondHash
+      )
+      .accountsPartial({
+        reporter: reporter.publicKey,
+        validatorSet: this.pdas.validatorSet(),
+        validatorBond:
+          params.bonded === false
+            ? null
+            : this.pdas.validatorBond(params.validator),
+        treasury: this.pdas.treasury(),
+      })
+      .preInstructions(params.attestations)
+      .signers([reporter])
+      .rpc();
+  }
+
+  /**
+   * Fetch ValidatorBond account or null if it doesn't exist
+   */
+  async fetch(validator: web3.PublicKey): Promise<any | null> {
+    return await this.program.account.validatorBond.fetchNullable(
+      this.pdas.validatorBond(validator)
+    );
+  }
+}
+
+// ============================================================================
+// GUARDIAN COUNCIL HELPERS
+// ============================================================================
+
+/**
+ * Fixture for guardian council proposals
+ */
+export class GuardianFixture {
+  private program: Program<SkylineProgram>;
+  private pdas: PDAs;
+  private defaultPayer: web3.Keypair;
+
+  constructor(
+    program: Program<SkylineProgram>,
+    pdas: PDAs,
+    defaultPayer: web3.Keypair
+  ) {
+    this.program = program;
+    this.pdas = pdas;
+    this.defaultPayer = defaultPayer;
+  }
+
+  /**
+   * Call submit_guardian_proposal instruction.
+   * Keys of a recovered validator set sign their admission alongside the members.
+   */
+  async submit(params: {
+    action: any;
+    batchId: number;
+    payer?: web3.Keypair;
+    signers: web3.Keypair[];
+    actionAccounts?: web3.PublicKey[];
+  }): Promise<string> {
+    const payer = params.payer || this.defaultPayer;
+    const actionAccounts = [...(params.actionAccounts ?? [])];
+    if ("recoverValidatorSet" in params.action) {
+      actionAccounts.push(await nextSnapshot(this.program));
+    }
+
+    return await this.program.methods
+      .submitGuardianProposal(
+        TEST_DEPLOYMENT.instanceId,
+        params.action,
+        new BN(params.batchId)
+      )
+      .accountsPartial({
+        payer: payer.publicKey,
+        guardianCouncil: this.pdas.guardianCouncil(),
+        validatorSet: this.pdas.validatorSet(),
+        validatorStats: this.pdas.validatorStats(),
+        proposal: this.pdas.guardianProposal(params.batchId),
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        ...params.signers.map((signer) => ({
+          pubkey: signer.publicKey,
+          isWritable: false,
+          isSigner: true,
+        })),
+        ...actionAccountMetas(actionAccounts),
+      ])
+      .signers([payer, ...params.signers])
+      .rpc();
+  }
+
+  /**
+   * Call execute_guardian_proposal instruction
+   */
+  async execute(batchId: number, payer?: web3.Keypair): Promise<string> {
+    const signer = payer || this.defaultPayer;
+
+    return await this.program.methods
+      .executeGuardianProposal(TEST_DEPLOYMENT.instanceId, new BN(batchId))
+      .accountsPartial({
+        payer: signer.publicKey,
+        guardianCouncil: this.pdas.guardianCouncil(),
+        validatorSet: this.pdas.validatorSet(),
+        validatorStats: this.pdas.validatorStats(),
+        proposal: this.pdas.guardianProposal(batchId),
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .remainingAccounts(actionAccountMetas([await nextSnapshot(this.program)]))
+      .signers([signer])
+      .rpc();
+  }
+
+  /**
+   * Fetch the guardian council, or null if none was appointed
+   */
+  async fetchCouncil(): Promise<any | null> {
+    return await this.program.account.guardianCouncil.fetchNullable(
+      this.pdas.guardianCouncil()
+    );
+  }
+
+  /**
+   * Fetch a guardian proposal, or null if it doesn't exist
+   */
+  async fetchProposal(batchId: number): Promise<any | null> {
+    return await this.program.account.guardianProposal.fetchNullable(
+      this.pdas.guardianProposal(batchId)
+    );
+  }
+
+  /**
+   * Next unused council batch ID
+   */
+  async nextBatchId(): Promise<number> {
+    const council = await this.fetchCouncil();
+    return council.lastBatchId.toNumber() + 1;
+  }
+}
+
+// ============================================================================
+// PROGRAM UPGRADE HELPERS
+// ============================================================================
+
+/**
+ * Size of the upgradeable loader buffer metadata preceding the program bytes
+ */
+const BUFFER_METADATA_SIZE = 37;
+
+/**
+ * Program bytes written to a loader buffer per transaction
+ */
+const BUFFER_WRITE_CHUNK = 900;
+
+/**
+ * SHA-256 hash of program bytes, as approved by `UpgradeProgram` proposals
+ */
+export function programHash(bytes: Buffer): number[] {
+  return Array.from(createHash("sha256").update(bytes).digest());
+}
+
+/**
+ * Fixture for program upgrades approved through governance
+ */
+export class ProgramUpgradeFixture {
+  private program: Program<SkylineProgram>;
+  private connection: web3.Connection;
+  private pdas: PDAs;
+  private defaultPayer: web3.Keypair;
+
+  constructor(
+    program: Program<SkylineProgram>,
+    connection: web3.Connection,
+    pdas: PDAs,
+    defaultPayer: web3.Keypair
+  ) {
+    this.program = program;
+    this.connection = connection;
+    this.pdas = pdas;
+    this.defaultPayer = defaultPayer;
+  }
+
+  /**
+   * Create a loader buffer holding `bytes`, writable by the default payer, and
+   * write the bytes in transaction sized chunks
+   */
+  async createBuffer(bytes: Buffer): Promise<web3.PublicKey> {
+    const buffer = web3.Keypair.generate();
+    const space = BUFFER_METADATA_SIZE + bytes.length;
+    const lamports = await this.connection.getMinimumBalanceForRentExemption(
+      space
+    );
+
+    const tx = new web3.Transaction().add(
+      web3.SystemProgram.createAccount({
+        fromPubkey: this.defaultPayer.publicKey,
+        newAccountPubkey: buffer.publicKey,
+        lamports,
+        space,
+        programId: BPF_LOADER_UPGRADEABLE_ID,
+      }),
+      new web3.TransactionInstruction({
+        programId: BPF_LOADER_UPGRADEABLE_ID,
+        keys: [
+          { pubkey: buffer.publicKey, isSigner: false, isWritable: true },
+          {
+            pubkey: this.defaultPayer.publicKey,
+            isSigner: false,
+            isWritable: false,
+          },
+        ],
+        data: Buffer.from([0, 0, 0, 0]), // InitializeBuffer
+      })
+    );
+    await web3.sendAndConfirmTransaction(this.connection, tx, [
+      this.defaultPayer,
+      buffer,
+    ]);
+
+    for (let offset = 0; offset < bytes.length; offset += BUFFER_WRITE_CHUNK) {
+      const chunk = bytes.subarray(offset, offset + BUFFER_WRITE_CHUNK);
+      const write = Buffer.alloc(16 + chunk.length);
+      write.writeUInt32LE(1, 0); // Write
+      write.writeUInt32LE(offset, 4);
+      write.writeBigUInt64LE(BigInt(chunk.length), 8);
+      chunk.copy(write, 16);
+
+      await web3.sendAndConfirmTransaction(
+        this.connection,
+        new web3.Transaction().add(
+          new web3.TransactionInstruction({
+            programId: BPF_LOADER_UPGRADEABLE_ID,
+            keys: [
+              { pubkey: buffer.publicKey, isSigner: false, isWritable: true },
+              {
+                pubkey: this.defaultPayer.publicKey,
+                isSigner: true,
+                isWritable: false,
+              },
+            ],
+            data: write,
+          })
+        ),
+        [this.defaultPayer]
+      );
+    }
+
+    return buffer.publicKey;
+  }
+
+  /**
+   * Hand the authority of `buffer` and of the program from the default payer to
+   * the upgrade authority PDA, as the deployer does before the first upgrade
+   */
+  async handOver(buffer: web3.PublicKey): Promise<void> {
+    const setAuthority = (account: web3.PublicKey) =>
+      new web3.TransactionInstruction({
+        programId: BPF_LOADER_UPGRADEABLE_ID,
+        keys: [
+          { pubkey: account, isSigner: false, isWritable: true },
+          {
+            pubkey: this.defaultPayer.publicKey,
+            isSigner: true,
+            isWritable: false,
+          },
+          {
+            pubkey: this.pdas.upgradeAuthority(),
+            isSigner: false,
+            isWritable: false,
+          },
+        ],
+        data: Buffer.from([4, 0, 0, 0]), // SetAuthority
+      });
+
+    await web3.sendAndConfirmTransaction(
+      this.connection,
+      new web3.Transaction().add(
+        setAuthority(buffer),
+        setAuthority(this.pdas.programData())
+      ),
+      [this.defaultPayer]
+    );
+  }
+
+  /**
+   * Accounts an `UpgradeProgram` proposal needs when it is activated
+   */
+  actionAccounts(buffer: web3.PublicKey): web3.PublicKey[] {
+    return [
+      this.program.programId,
+      this.pdas.programData(),
+      buffer,
+      this.pdas.upgradeAuthority(),
+      web3.SYSVAR_RENT_PUBKEY,
+      web3.SYSVAR_CLOCK_PUBKEY,
+      BPF_LOADER_UPGRADEABLE_ID,
+    ];
+  }
+}
+
+/**
+ * Fixture for migrating accounts to the current layout version
+ */
+export class MigrationFixture {
+  private program: Program<SkylineProgram>;
+  private payer: web3.Keypair;
+
+  constructor(program: Program<SkylineProgram>, payer: web3.Keypair) {
+    this.program = program;
+    this.payer = payer;
+  }
+
+  /**
+   * Call migrate instruction, moving a version 1 account into an instance
+   */
+  async migrate(
+    account: web3.PublicKey,
+    migrated: web3.PublicKey,
+    remainingAccounts: web3.PublicKey[] = [],
+    instanceId: BN = TEST_DEPLOYMENT.instanceId
+  ): Promise<string> {
+    return await this.program.methods
+      .migrate(instanceId, TEST_DEPLOYMENT.chainId)
+      .accountsPartial({
+        payer: this.payer.publicKey,
+        account,
+        migrated,
+      })
+      .remainingAccounts(
+        remainingAccounts.map((pubkey) => ({
+          pubkey,
+          isSigner: false,
+          isWritable: true,
+        }))
+      )
+      .signers([this.payer])
+      .rpc();
+  }
+}
+
+/**
+ * Version 1 accounts loaded into the bank from tests/accounts, written
+ * with the layouts of the bridge before bridge instances existed
+ */
+export const LEGACY_ACCOUNTS = {
+  validatorSet: new web3.PublicKey(
+    "Gh9TDexu4uxD7D5VKVJubTG1TCxqt7ehppEnUFrWEnus"
+  ),
+  validators: [
+    new web3.PublicKey("CuxRnXrf9AxQQy8g5VBGEJQ6yy75HCqxdNitE3yx7711"),
+    new web3.PublicKey("4A3kjraU9m45ZXpuY2d22rQv5jyCBkjamf6WaBtcJJn9"),
+    new web3.PublicKey("5EdNLBz9FTdVWZVJguyCW28LS5ysFZq8u6SbP1qcU42u"),
+    new web3.PublicKey("HsWeCFaMzH7dyNqLbHhaiyMTAse1C8YZwYFLSqJKEZfw"),
+  ],
+  lastBatchId: 41,
+  bridgeRequestCount: 7,
+  bridgingTransaction: new web3.PublicKey(
+    "99J2V5ot4fVvv76A6Q1pCKivNVJZVrSrDshgfeq14tgW"
+  ),
+  batchId: 42,
+  amount: 1_000_000,
+  receiver: new web3.PublicKey(
+    "Dh8irqwVKU5Do7RDADjbmgKuFzKZ8Y9kgKD1MZcvXpDF"
+  ),
+  mint: new web3.PublicKey("FSiKFra4RB3MihBgWjt9Qu1KVnQ2biSm25kJSF48Z55D"),
+} as const;
+
+/**
+ * Configuration of a registered destination chain with permissive defaults
+ */
+export function testChain(chainId: number, overrides: Partial<any> = {}): any {
+  return {
+    chainId,
+    name: `chain-${chainId}`,
+    enabled: true,
+    addressFormat: { raw: {} },
+    minReceiverLen: 0,
+    maxReceiverLen: 255,
+    minAmount: new BN(0),
+    maxAmount: new BN("18446744073709551615"),
+    ...overrides,
+  };
+}
+
+/**
+ * Fixture for the registry of destination chains
+ */
+export class ChainRegistryFixture {
+  private program: Program<SkylineProgram>;
+  private pdas: PDAs;
+  private governance: GovernanceFixture;
+  private batchIds: BatchIdManager;
+
+  constructor(
+    program: Program<SkylineProgram>,
+    pdas: PDAs,
+    governance: GovernanceFixture,
+    batchIds: BatchIdManager
+  ) {
+    this.program = program;
+    this.pdas = pdas;
+    this.governance = governance;
+    this.batchIds = batchIds;
+  }
+
+  /**
+   * Register a chain or replace its configuration through a SetChain proposal
+   */
+  async set(chain: any, validators: web3.Keypair[]): Promise<string> {
+    const vs = await this.program.account.validatorSet.fetch(
+      this.pdas.validatorSet()
+    );
+    return await this.governance.submit({
+      action: { setChain: { chain } },
+      batchId: await this.batchIds.freshBatchId(),
+      signers: validators.slice(0, vs.threshold),
+      actionAccounts: [this.pdas.chainRegistry()],
+    });
+  }
+
+  /**
+   * Fetch the chain registry or null if no chain was registered yet
+   */
+  async fetch(): Promise<any | null> {
+    return await this.program.account.chainRegistry.fetchNullable(
+      this.pdas.chainRegistry()
+    );
+  }
+}
+
+/**
+ * Registry entry of a token in lock mode, allowed on every chain the tests use
+ * with the given decimals
+ */
+/**
+ * Build a chain entry of a token, without fees unless overridden
+ */
+export function testTokenChain(
+  chainId: number,
+  overrides: Partial<any> = {}
+): any {
+  return {
+    chainId,
+    foreignAsset: Buffer.alloc(20, chainId),
+    decimals: 9,
+    flatFee: new BN(0),
+    feeCurrency: { token: {} },
+    feeBps: 0,
+    ...overrides,
+  };
+}
+
+export function testToken(
+  mint: web3.PublicKey,
+  overrides: Partial<any> = {},
+  decimals: number = 9
+): any {
+  return {
+    mint,
+    mode: { lock: {} },
+    enabled: true,
+    chains: [
+      0, 1, 2, 3, 10, 12, 13, 20, 21, 22, 127, 128, 200, 254, 255,
+    ].map((chainId) => testTokenChain(chainId, { decimals })),
+    ...overrides,
+  };
+}
+
+/**
+ * Fixture for the registry of bridged tokens
+ */
+export class TokenRegistryFixture {
+  private program: Program<SkylineProgram>;
+  private pdas: PDAs;
+  private governance: GovernanceFixture;
+  private batchIds: BatchIdManager;
+
+  constructor(
+    program: Program<SkylineProgram>,
+    pdas: PDAs,
+    governance: GovernanceFixture,
+    batchIds: BatchIdManager
+  ) {
+    this.program = program;
+    this.pdas = pdas;
+    this.governance = governance;
+    this.batchIds = batchIds;
+  }
+
+  /**
+   * Register a token or replace its configuration through a SetToken proposal
+   */
+  async set(token: any, validators: web3.Keypair[]): Promise<string> {
+    const vs = await this.program.account.validatorSet.fetch(
+      this.pdas.validatorSet()
+    );
+    return await this.governance.submit({
+      action: { setToken: token },
+      batchId: await this.batchIds.freshBatchId(),
+      signers: validators.slice(0, vs.threshold),
+      actionAccounts: [this.pdas.tokenConfig(token.mint)],
+    });
+  }
+
+  /**
+   * Register a token with the test defaults, in burn mode if the vault is its
+   * mint authority and with the decimals of the mint on every chain
+   */
+  async register(
+    mint: web3.PublicKey,
+    validators: web3.Keypair[],
+    overrides: Partial<any> = {}
+  ): Promise<string> {
+    const info = await getMint(this.program.provider.connection, mint);
+    const burn = info.mintAuthority?.equals(this.pdas.vault()) ?? false;
+    const mode = burn ? { burn: {} } : { lock: {} };
+    return await this.set(
+      testToken(mint, { mode, ...overrides }, info.decimals),
+      validators
+    );
+  }
+
+  /**
+   * Fetch the registry entry of a token or null if it is not registered
+   */
+  async fetch(mint: web3.PublicKey): Promise<any | null> {
+    return await this.program.account.tokenConfig.fetchNullable(
+      this.pdas.tokenConfig(mint)
+    );
+  }
+}
+
+/**
+ * Fixture for the relayer and validator rewards paid from the fee vault
+ */
+export class RewardsFixture {
+  private program: Program<SkylineProgram>;
+  private pdas: PDAs;
+  private governance: GovernanceFixture;
+  private batchIds: BatchIdManager;
+
+  constructor(
+    program: Program<SkylineProgram>,
+    pdas: PDAs,
+    governance: GovernanceFixture,
+    batchIds: BatchIdManager
+  ) {
+    this.program = program;
+    this.pdas = pdas;
+    this.governance = governance;
+    this.batchIds = batchIds;
+  }
+
+  /**
+   * Set the rewards credited for executed bridge transactions through a
+   * SetRewards proposal
+   */
+  async set(
+    relayerReward: number | BN,
+    validatorReward: number | BN,
+    validators: web3.Keypair[]
+  ): Promise<string> {
+    const vs = await this.program.account.validatorSet.fetch(
+      this.pdas.validatorSet()
+    );
+    return await this.governance.submit({
+      action: {
+        setRewards: {
+          relayerReward: new BN(relayerReward),
+          validatorReward: new BN(validatorReward),
+        },
+      },
+      batchId: await this.batchIds.freshBatchId(),
+      signers: validators.slice(0, vs.threshold),
+      actionAccounts: [this.pdas.feeVault()],
+    });
+  }
+
+  /**
+   * Reward accounts of the given participants
+   */
+  accounts(participants: web3.PublicKey[]): web3.PublicKey[] {
+    return participants.map((participant) =>
+      this.pdas.rewardAccount(participant)
+    );
+  }
+
+  /**
+   * Call claim_rewards instruction (the participant signs and receives)
+   */
+  async claim(participant: web3.Keypair): Promise<string> {
+    return await this.program.methods
+      .claimRewards(TEST_DEPLOYMENT.instanceId)
+      .accountsPartial({
+        participant: participant.publicKey,
+        rewardAccount: this.pdas.rewardAccount(participant.publicKey),
+        feeVault: this.pdas.feeVault(),
+      })
+      .signers([participant])
+      .rpc();
+  }
+
+  /**
+   * Fetch the reward account of a participant or null if it was never credited
+   */
+  async fetch(participant: web3.PublicKey): Promise<any | null> {
+    return await this.program.account.rewardAccount.fetchNullable(
+      this.pdas.rewardAccount(participant)
+    );
+  }
+
+  /**
+   * Fetch the fee vault or null if it was not created yet
+   */
+  async fetchFeeVault(): Promise<any | null> {
+    return await this.program.account.feeVault.fetchNullable(
+      this.pdas.feeVault()
+    );
+  }
+}
+
+// ============================================================================
+// MAIN TEST FIXTURE CLASS
+// ============================================================================
+
+export class SkylineTestFixture {
+  public pdas: PDAs;
+  public accounts: AccountFetchers;
+  public initialize: InitializeHelper;
+  public bridgeTransaction: BridgeTransactionHelper;
+  public bridgeRequest: BridgeRequestHelper;
+  public mints: MintHelper;
+  public batchIds: BatchIdManager;
+  public tokenBalances: TokenBalanceHelper;
+  public events: EventParser;
+  public bridgeVSU: BridgeVSUFixture;
+  public governance: GovernanceFixture;
+  public validatorDeltas: ValidatorDeltaFixture;
+  public keyRotation: KeyRotationFixture;
+  public validatorInfo: ValidatorInfoFixture;
+  public suspension: SuspensionFixture;
+  public stats: StatsFixture;
+  public bonds: BondFixture;
+  public guardian: GuardianFixture;
+  public upgrades: ProgramUpgradeFixture;
+  public migrations: MigrationFixture;
+  public chains: ChainRegistryFixture;
+  public tokens: TokenRegistryFixture;
+  public rewards: RewardsFixture;
+
+  constructor(ctx: TestContext) {
+    this.pdas = new PDAs(ctx.program.programId);
+    this.accounts = new AccountFetchers(ctx.program);
+    this.initialize = new InitializeHelper(ctx.program, ctx.owner);
+    this.bridgeTransaction = new BridgeTransactionHelper(
+      ctx.program,
+      ctx.owner
+    );
+    this.bridgeRequest = new BridgeRequestHelper(
+      ctx.program,
+      ctx.owner,
+      this.pdas.vault(),
+      this.pdas.feeVault()
+    );
+    this.mints = new MintHelper(ctx.connection, ctx.owner.payer);
+    this.batchIds = new BatchIdManager(this.accounts, this.pdas.validatorSet());
+    this.tokenBalances = new TokenBalanceHelper(ctx.connection);
+    this.events = new EventParser(ctx.program, ctx.connection);
+
+    this.governance = new GovernanceFixture(
+      ctx.program,
+      ctx.connection,
+      this.pdas,
+      ctx.owner.payer
+    );
+    this.bridgeVSU = new BridgeVSUFixture(
+      ctx.program,
+      this.governance,
+      this.pdas.validatorSet(),
+      ctx.owner.payer
+    );
+    this.validatorDeltas = new ValidatorDeltaFixture(ctx.program, this.pdas);
+    this.keyRotation = new KeyRotationFixture(
+      ctx.program,
+      this.pdas,
+      ctx.owner.payer
+    );
+    this.validatorInfo = new ValidatorInfoFixture(
+      ctx.program,
+      this.pdas,
+      ctx.owner.payer
+    );
+    this.suspension = new SuspensionFixture(ctx.program, this.pdas);
+    this.stats = new StatsFixture(ctx.program, this.pdas);
+    this.bonds = new BondFixture(ctx.program, this.pdas, ctx.owner.payer);
+    this.guardian = new GuardianFixture(
+      ctx.program,
+      this.pdas,
+      ctx.owner.payer
+    );
+    this.upgrades = new ProgramUpgradeFixture(
+      ctx.program,
+      ctx.connection,
+      this.pdas,
+      ctx.owner.payer
+    );
+    this.migrations = new MigrationFixture(ctx.program, ctx.owner.payer);
+    this.chains = new ChainRegistryFixture(
+      ctx.program,
+      this.pdas,
+      this.governance,
+      this.batchIds
+    );
+    this.tokens = new TokenRegistryFixture(
+      ctx.program,
+      this.pdas,
+      this.governance,
+      this.batchIds
+    );
+    this.rewards = new RewardsFixture(
+      ctx.program,
+      this.pdas,
+      this.governance,
+      this.batchIds
+    );
+  }
+
+  /**
+   * Check if validator set is already initialized
+   */
+  async isInitialized(): Promise<boolean> {
+    const vsPDA = this.pdas.validatorSet();
+    const vs = await this.accounts.getValidatorSetNullable(vsPDA);
+    return vs !== null;
+  }
+
+  /**
+   * Get current validator set or throw if not initialized
+   */
+  async getValidatorSet(): Promise<ValidatorSetData> {
+    const vsPDA = this.pdas.validatorSet();
+    return await this.accounts.getValidatorSet(vsPDA);
+  }
+
+  /**
+   * Get the archived validator set of an epoch, or null if it was never archived
+   */
+  async getValidatorSetSnapshot(epoch: number | BN): Promise<any | null> {
+    return await this.accounts.getValidatorSetSnapshotNullable(
+      this.pdas.validatorSetSnapshot(epoch)
+    );
   }
 
   /**
@@ -1,25 +1,43 @@
 // tests/skyline-program.ts
 import * as anchor from "@coral-xyz/anchor";
-import { Program, web3 } from "@coral-xyz/anchor";
+import { Program, web3, BN } from "@coral-xyz/anchor";
 import { SkylineProgram } from "../target/types/skyline_program";
 import { assert, expect } from "chai";
 import {
   SkylineTestFixture,
   TestContext,
   generateValidators,
+  admissionProof,
+  validatorMetadata,
+  approvalAttestation,
+  proposalHash,
   calculateExpectedThreshold,
+  calculateExpectedThresholdWeight,
   assertValidatorSetState,
   assertNoBridgingTransaction,
   assertBridgingTransactionSigners,
   assertBridgingTransactionState,
   LIMITS,
   assertValidBump,
+  warpToSlot,
+  TEST_CONFIG,
+  TEST_DEPLOYMENT,
+  PDAs,
+  testChain,
+  testToken,
+  testTokenChain,
+  programHash,
+  ACCOUNT_VERSION,
+  LEGACY_ACCOUNTS,
 } from "./fixtures";
 import {
+  TOKEN_PROGRAM_ID,
   getAccount,
   getAssociatedTokenAddressSync,
   getOrCreateAssociatedTokenAccount,
 } from "@solana/spl-token";
+import { BankrunConnection, PROGRAM_DATA_METADATA_SIZE } from "./bankrun";
+import { readFileSync } from "fs";
 
 /**
  * Airdrop SOL to an account
@@ -42,20 +60,33 @@ describe("skyline-program", () => {
   // TEST SETUP
   // ============================================================================
 
-  anchor.setProvider(anchor.AnchorProvider.env());
+  // The program runs in an in-process bank, so the tests can warp past timelocks
+  const connection = new BankrunConnection();
+  const owner = new anchor.Wallet(web3.Keypair.generate());
+  anchor.setProvider(
+    new anchor.AnchorProvider(
+      connection,
+      owner,
+      anchor.AnchorProvider.defaultOptions()
+    )
+  );
   const provider = anchor.getProvider() as anchor.AnchorProvider;
   const program = anchor.workspace.skylineProgram as Program<SkylineProgram>;
-  const owner = provider.wallet as anchor.Wallet;
 
   const ctx: TestContext = {
     provider,
     program,
     owner,
-    connection: provider.connection,
+    connection,
   };
 
   const fixture = new SkylineTestFixture(ctx);
 
+  before(async () => {
+    // The owner deploys the program and holds its upgrade authority
+    await connection.start(program.programId, owner.publicKey);
+  });
+
   // Generate test validators once
   const validators = generateValidators(50);
 
@@ -100,6 +131,42 @@ describe("skyline-program", () => {
       it("fails with no validators provided", async () => {
         await fixture.initialize.expectError([], "MinValidatorsNotMet");
       });
+
+      it("fails when a validator has not proven possession of its key", async () => {
+        const validatorPubkeys = validators.slice(0, 4).map((v) => v.publicKey);
+
+        await fixture.initialize.expectError(
+          validatorPubkeys,
+          "MissingAdmissionProof",
+          0,
+          validators.slice(0, 3)
+        );
+      });
+
+      it("fails when weights do not match the validators", async () => {
+        const validatorPubkeys = validators.slice(0, 4).map((v) => v.publicKey);
+
+        await fixture.initialize.expectError(
+          validatorPubkeys,
+          "ValidatorWeightsMismatch",
+          0,
+          validators.slice(0, 4),
+          [1, 2]
+        );
+      });
+
+      it("fails when the signer is not the program upgrade authority", async () => {
+        const validatorPubkeys = validators.slice(0, 4).map((v) => v.publicKey);
+
+        await fixture.initialize.expectError(
+          validatorPubkeys,
+          "UnauthorizedInitializer",
+          0,
+          validators.slice(0, 4),
+          null,
+          web3.Keypair.generate()
+        );
+      });
     });
 
     describe("Success Case", () => {
@@ -152,7 +219,11 @@ describe("skyline-program", () => {
         }
 
         // Not initialized - perform initialization
-        await fixture.initialize.call(validatorPubkeys, 0);
+        await fixture.initialize.call(
+          validatorPubkeys,
+          0,
+          validators.slice(0, validatorCount)
+        );
 
         // Verify validator set
         const vsPDA = fixture.pdas.validatorSet();
@@ -165,6 +236,13 @@ describe("skyline-program", () => {
           bridgeRequestCount: 0,
         });
 
+        // Verify the initializer and deployment configuration
+        expect(vs.initializer.toBase58()).to.equal(owner.publicKey.toBase58());
+        expect(vs.deployment.chainId).to.equal(TEST_DEPLOYMENT.chainId);
+        expect(vs.deployment.instanceId.toString()).to.equal(
+          TEST_DEPLOYMENT.instanceId.toString()
+        );
+
         // Verify vault
         const vaultPDA = fixture.pdas.vault();
         const vault = await fixture.accounts.getVault(vaultPDA);
@@ -227,6 +305,9 @@ describe("skyline-program", () => {
 
       // Mint where vault is authority (for mint tests)
       mintVaultAuthority = await fixture.mints.create(vaultPDA, 9);
+
+      await fixture.tokens.register(mint, validators);
+      await fixture.tokens.register(mintVaultAuthority, validators);
     });
 
     describe("First Submission Validations", () => {
@@ -697,6 +778,9 @@ describe("skyline-program", () => {
       });
 
       it("second submission: same batch_id, different mint_token", async () => {
+        const otherMint = await fixture.mints.create(owner.publicKey, 9);
+        await fixture.tokens.register(otherMint, validators);
+
         const batchId = await fixture.batchIds.freshBatchId();
 
         await fixture.bridgeTransaction.call({
@@ -708,8 +792,6 @@ describe("skyline-program", () => {
           vaultPDA,
         });
 
-        const otherMint = await fixture.mints.create(owner.publicKey, 9);
-
         let thrown = false;
         try {
           await fixture.bridgeTransaction.callWithCustomAccounts(
@@ -1069,10 +1151,11 @@ describe("skyline-program", () => {
 
     describe("Insufficient Vault Balance", () => {
       it("fails when vault doesn't have enough tokens (transfer branch)", async () => {
-        const batchId = await fixture.batchIds.freshBatchId();
-
         // Create a new mint with limited funds in vault
         const limitedMint = await fixture.mints.create(owner.publicKey, 9);
+        await fixture.tokens.register(limitedMint, validators);
+
+        const batchId = await fixture.batchIds.freshBatchId();
 
         // Fund vault with only 50 tokens
         await fixture.mints.mintTo(limitedMint, vaultPDA, 50, true);
@@ -1126,10 +1209,11 @@ describe("skyline-program", () => {
     });
     describe("Missing Vault ATA", () => {
       it("fails when vault_ata doesn't exist (transfer branch)", async () => {
-        const batchId = await fixture.batchIds.freshBatchId();
-
         // Create a new mint but DON'T create vault ATA
         const noVaultAtaMint = await fixture.mints.create(owner.publicKey, 9);
+        await fixture.tokens.register(noVaultAtaMint, validators);
+
+        const batchId = await fixture.batchIds.freshBatchId();
 
         const vaultAta = getAssociatedTokenAddressSync(
           noVaultAtaMint,
@@ -1265,8 +1349,6 @@ describe("skyline-program", () => {
 
     describe("Frozen Mint", () => {
       it("fails when recipient token account is frozen", async () => {
-        const batchId = await fixture.batchIds.freshBatchId();
-
         // Create mint with freeze authority
         const freezeAuthority = anchor.web3.Keypair.generate();
         const freezableMint = await fixture.mints.createWithFreezeAuthority(
@@ -1274,6 +1356,9 @@ describe("skyline-program", () => {
           freezeAuthority.publicKey,
           9
         );
+        await fixture.tokens.register(freezableMint, validators);
+
+        const batchId = await fixture.batchIds.freshBatchId();
 
         // Fund vault for this mint
         await fixture.mints.mintTo(freezableMint, vaultPDA, 1000, true);
@@ -1330,8 +1415,6 @@ describe("skyline-program", () => {
       });
 
       it("succeeds when mint has freeze authority but account is not frozen", async () => {
-        const batchId = await fixture.batchIds.freshBatchId();
-
         // Create mint with freeze authority but don't freeze the account
         const freezeAuthority = anchor.web3.Keypair.generate();
         const freezableMint = await fixture.mints.createWithFreezeAuthority(
@@ -1339,6 +1422,9 @@ describe("skyline-program", () => {
           freezeAuthority.publicKey,
           9
         );
+        await fixture.tokens.register(freezableMint, validators);
+
+        const batchId = await fixture.batchIds.freshBatchId();
 
         await fixture.mints.mintTo(freezableMint, vaultPDA, 1000, true);
 
@@ -1386,8 +1472,14 @@ describe("skyline-program", () => {
       "0x1234567890abcdef1234567890abcdef12345678"
     );
     const destinationChain = 1; // Ethereum
+    const registeredChains = [0, 1, 2, 3, 127, 128, 254, 255];
 
     before(async () => {
+      // Register the destination chains the requests go to
+      for (const chainId of registeredChains) {
+        await fixture.chains.set(testChain(chainId), validators);
+      }
+
       // Airdrop to user
       await provider.connection.requestAirdrop(
         user.publicKey,
@@ -1409,6 +1501,9 @@ describe("skyline-program", () => {
 
       // Transfer mint authority to vault
       await fixture.mints.setMintAuthority(burnMint, vaultPDA);
+
+      await fixture.tokens.register(transferMint, validators);
+      await fixture.tokens.register(burnMint, validators);
     });
 
     // ============================================================================
@@ -1466,6 +1561,9 @@ describe("skyline-program", () => {
         // Verify event was emitted
         const event = await fixture.events.parseBridgeRequestEvent(signature);
         expect(event).to.not.equal(null);
+        expect(event!.instanceId.toString()).to.equal(
+          TEST_DEPLOYMENT.instanceId.toString()
+        );
         expect(event!.sender.toBase58()).to.equal(user.publicKey.toBase58());
         expect(event!.amount.toNumber()).to.equal(100);
         expect(Buffer.from(event!.receiver).toString("hex")).to.equal(
@@ -1480,6 +1578,7 @@ describe("skyline-program", () => {
         // Create new mint without vault ATA
         const newMint = await fixture.mints.create(owner.publicKey, 9);
         await fixture.mints.mintTo(newMint, user.publicKey, 1000);
+        await fixture.tokens.register(newMint, validators);
 
         const vaultAta = getAssociatedTokenAddressSync(newMint, vaultPDA, true);
 
@@ -1606,6 +1705,7 @@ describe("skyline-program", () => {
 
         // NOW transfer mint authority to vault
         await fixture.mints.setMintAuthority(freshBurnMint, vaultPDA);
+        await fixture.tokens.register(freshBurnMint, validators);
 
         const userAta = getAssociatedTokenAddressSync(
           freshBurnMint,
@@ -1659,6 +1759,7 @@ describe("skyline-program", () => {
       it("rejects when user has zero balance", async () => {
         // Create new mint and don't fund user
         const emptyMint = await fixture.mints.create(owner.publicKey, 9);
+        await fixture.tokens.register(emptyMint, validators);
 
         // Create user's ATA with zero balance
         await getOrCreateAssociatedTokenAccount(
@@ -1850,6 +1951,7 @@ describe("skyline-program", () => {
       it("handles large amount (close to u64::MAX)", async () => {
         // Create mint with large supply
         const largeMint = await fixture.mints.create(owner.publicKey, 0);
+        await fixture.tokens.register(largeMint, validators);
 
         const largeAmount = BigInt("18446744073709551615"); // u64::MAX
         const testAmount = largeAmount / BigInt(1000); // Use 1/1000th to avoid overflow
@@ -1920,7 +2022,7 @@ describe("skyline-program", () => {
         });
       });
 
-      it("handles all destination chain IDs (0-255)", async () => {
+      it("handles every registered destination chain ID (0-255)", async () => {
         // Test edge values
         const chainIds = [0, 1, 127, 128, 254, 255];
 
@@ -1949,6 +2051,8 @@ describe("skyline-program", () => {
           9
         );
 
+        await fixture.tokens.register(freezableMint, validators);
+
         // Fund user
         await fixture.mints.mintTo(freezableMint, user.publicKey, 1000);
 
@@ -1987,7 +2091,7 @@ describe("skyline-program", () => {
       it("empty receiver array edge case", async () => {
         const emptyReceiver = Buffer.from([]);
 
-        // Should succeed technically (no validation on receiver content)
+        // Accepted by raw-format chains without a minimum receiver length
         const signature = await fixture.bridgeRequest.call({
           amount: 5,
           receiver: emptyReceiver,
@@ -2079,793 +2183,4100 @@ describe("skyline-program", () => {
     });
   });
 
-  describe("Validator Set Update (VSU)", () => {
-    let newValidators: web3.Keypair[];
+  describe("Chain Registry", () => {
+    let mint: web3.PublicKey;
+    const user = anchor.web3.Keypair.generate();
+    const receiver = Buffer.from(
+      "1234567890abcdef1234567890abcdef12345678",
+      "hex"
+    );
+
+    const expectRequestError = async (
+      destinationChain: number,
+      amount: number,
+      code: string
+    ) =>
+      fixture.bridgeRequest.expectError(
+        { amount, receiver, destinationChain, mint, signer: user },
+        code
+      );
 
     before(async () => {
-      // Create new validator keypairs for testing
-      newValidators = Array.from({ length: 3 }, () => web3.Keypair.generate());
+      await airdrop(provider.connection, user.publicKey);
+      mint = await fixture.mints.create(owner.publicKey, 9);
+      await fixture.mints.mintTo(mint, user.publicKey, 10000);
+      await fixture.tokens.register(mint, validators);
     });
 
-    // Helper to compare Pubkey arrays
-    const expectPubkeyArrayEqual = (
-      actual: web3.PublicKey[],
-      expected: web3.PublicKey[]
-    ) => {
-      expect(actual.length).to.equal(expected.length);
-      for (let i = 0; i < actual.length; i++) {
-        expect(actual[i].toBase58()).to.equal(expected[i].toBase58());
-      }
-    };
+    it("registers a destination chain", async () => {
+      const chain = testChain(10, {
+        name: "Cardano",
+        addressFormat: { cardano: { networkId: 1 } },
+        minReceiverLen: 29,
+        maxReceiverLen: 57,
+        minAmount: new BN(5),
+        maxAmount: new BN(1000),
+      });
 
-    describe("First Submission (Create Proposal)", () => {
-      describe("Happy Path", () => {
-        it("successfully creates VSU proposal with valid parameters", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
-          const added = [newValidators[0].publicKey];
-          const removed: web3.PublicKey[] = [];
+      await fixture.chains.set(chain, validators);
 
-          const vsBefore = await fixture.getValidatorSet();
-          const validatorCountBefore = vsBefore.signers.length;
+      const registry = await fixture.chains.fetch();
+      const entry = registry.chains.find((c) => c.chainId === 10);
+      expect(entry.name).to.equal("Cardano");
+      expect(entry.enabled).to.be.true;
+      expect(entry.addressFormat).to.deep.equal({
+        cardano: { networkId: 1 },
+      });
+      expect(entry.minReceiverLen).to.equal(29);
+      expect(entry.maxReceiverLen).to.equal(57);
+      expect(entry.minAmount.toNumber()).to.equal(5);
+      expect(entry.maxAmount.toNumber()).to.equal(1000);
+      expect(registry.version).to.equal(ACCOUNT_VERSION);
+    });
 
-          await fixture.bridgeVSU.call({
-            added,
-            removed,
-            batchId,
-            signers: [validators[0]],
-          });
+    it("replaces the configuration of a registered chain", async () => {
+      const before = (await fixture.chains.fetch()).chains.length;
 
-          // Verify proposal created
-          const vscAccount = await fixture.bridgeVSU.fetchValidatorSetChange(
-            batchId
-          );
-          expect(vscAccount).to.not.be.null;
-          expect(vscAccount.added.length).to.equal(1);
-          expect(vscAccount.added[0].toBase58()).to.equal(added[0].toBase58());
-          expect(vscAccount.removed.length).to.equal(0);
-          expect(vscAccount.batchId.toNumber()).to.equal(batchId);
-          expect(vscAccount.signers.length).to.equal(1);
-          expect(vscAccount.signers[0].toBase58()).to.equal(
-            validators[0].publicKey.toBase58()
-          );
+      await fixture.chains.set(
+        testChain(10, { name: "Cardano mainnet" }),
+        validators
+      );
 
-          // Verify validator set NOT updated yet (below threshold)
-          const vsAfter = await fixture.getValidatorSet();
-          expect(vsAfter.signers.length).to.equal(validatorCountBefore);
-        });
+      const registry = await fixture.chains.fetch();
+      expect(registry.chains.length).to.equal(before);
+      expect(registry.chains.find((c) => c.chainId === 10).name).to.equal(
+        "Cardano mainnet"
+      );
+    });
 
-        it("creates proposal with multiple additions", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
-          const added = [
-            newValidators[1].publicKey,
-            newValidators[2].publicKey,
-          ];
-          const removed: web3.PublicKey[] = [];
+    it("rejects registering the chain ID of this chain", async () => {
+      try {
+        await fixture.chains.set(
+          testChain(TEST_DEPLOYMENT.chainId),
+          validators
+        );
+        expect.fail("Should have thrown InvalidChainConfig");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("InvalidChainConfig");
+      }
+    });
 
-          await fixture.bridgeVSU.call({
-            added,
-            removed,
-            batchId,
-            signers: [validators[0]],
-          });
+    it("rejects inverted amount bounds", async () => {
+      try {
+        await fixture.chains.set(
+          testChain(11, { minAmount: new BN(10), maxAmount: new BN(1) }),
+          validators
+        );
+        expect.fail("Should have thrown InvalidChainConfig");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("InvalidChainConfig");
+      }
+    });
 
-          const vscAccount = await fixture.bridgeVSU.fetchValidatorSetChange(
-            batchId
-          );
-          expect(vscAccount.added.length).to.equal(2);
-          expect(vscAccount.removed.length).to.equal(0);
-        });
+    it("rejects bridge requests to an unregistered chain", async () => {
+      await expectRequestError(200, 10, "ChainNotRegistered");
+    });
 
-        it("creates proposal with removals", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
-          const vs = await fixture.getValidatorSet();
+    it("rejects bridge requests to a disabled chain", async () => {
+      await fixture.chains.set(testChain(12, { enabled: false }), validators);
 
-          const added: web3.PublicKey[] = [];
-          // Now we pass actual pubkeys instead of indices
-          const removed = [vs.signers[0], vs.signers[1]];
+      await expectRequestError(12, 10, "ChainDisabled");
+    });
 
-          await fixture.bridgeVSU.call({
-            added,
-            removed,
-            batchId,
-            signers: [validators[0]],
-          });
+    it("rejects amounts outside the bounds of the chain", async () => {
+      await fixture.chains.set(
+        testChain(13, { minAmount: new BN(5), maxAmount: new BN(100) }),
+        validators
+      );
 
-          const vscAccount = await fixture.bridgeVSU.fetchValidatorSetChange(
-            batchId
-          );
-          expect(vscAccount.added.length).to.equal(0);
-          expect(vscAccount.removed.length).to.equal(2);
-          expectPubkeyArrayEqual(vscAccount.removed, removed);
-        });
+      await expectRequestError(13, 4, "AmountOutOfRange");
+      await expectRequestError(13, 101, "AmountOutOfRange");
+      await fixture.bridgeRequest.call({
+        amount: 100,
+        receiver,
+        destinationChain: 13,
+        mint,
+        signer: user,
+      });
+    });
+  });
 
-        it("creates proposal with both additions and removals", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
-          const vs = await fixture.getValidatorSet();
+  describe("Token Registry", () => {
+    let mint: web3.PublicKey;
+    const user = anchor.web3.Keypair.generate();
+    const recipient = web3.Keypair.generate().publicKey;
+    const receiver = Buffer.from(
+      "1234567890abcdef1234567890abcdef12345678",
+      "hex"
+    );
 
-          const added = [newValidators[0].publicKey];
-          const removed = [vs.signers[0]]; // Remove first validator by pubkey
+    const expectRequestError = async (
+      tokenMint: web3.PublicKey,
+      destinationChain: number,
+      code: string
+    ) =>
+      fixture.bridgeRequest.expectError(
+        {
+          amount: 10,
+          receiver,
+          destinationChain,
+          mint: tokenMint,
+          signer: user,
+        },
+        code
+      );
 
-          await fixture.bridgeVSU.call({
-            added,
-            removed,
-            batchId,
-            signers: [validators[1]],
-          });
+    const expectTransactionError = async (
+      tokenMint: web3.PublicKey,
+      sourceChain: number,
+      code: string
+    ) =>
+      fixture.bridgeTransaction.expectError(
+        {
+          amount: 10,
+          batchId: await fixture.batchIds.freshBatchId(),
+          recipient,
+          mint: tokenMint,
+          validators: validators.slice(
+            0,
+            (await fixture.getValidatorSet()).threshold
+          ),
+          vaultPDA: fixture.pdas.vault(),
+          sourceChain,
+        },
+        code
+      );
 
-          const vscAccount = await fixture.bridgeVSU.fetchValidatorSetChange(
-            batchId
-          );
-          expect(vscAccount.added.length).to.equal(1);
-          expect(vscAccount.removed.length).to.equal(1);
-          expect(vscAccount.removed[0].toBase58()).to.equal(
-            vs.signers[0].toBase58()
-          );
-        });
-      });
+    const expectConfigError = async (token: any, code: string) => {
+      try {
+        await fixture.tokens.set(token, validators);
+        expect.fail(`Should have thrown ${code}`);
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal(code);
+      }
+    };
 
-      describe("Error Cases", () => {
-        it("rejects with InvalidBatchId when batch_id <= last_batch_id", async () => {
-          const vs = await fixture.getValidatorSet();
-          const oldBatchId = vs.lastBatchId.toNumber();
+    before(async () => {
+      await airdrop(provider.connection, user.publicKey);
+      mint = await fixture.mints.create(owner.publicKey, 9);
+      await fixture.mints.mintTo(mint, user.publicKey, 10000);
+    });
 
-          try {
-            await fixture.bridgeVSU.call({
-              added: [newValidators[0].publicKey],
-              removed: [],
-              batchId: oldBatchId,
-              signers: [validators[0]],
-            });
-            expect.fail("Should have thrown InvalidBatchId");
-          } catch (err: any) {
-            const errorCode = err.error?.errorCode?.code || err.code;
-            expect(errorCode).to.equal("InvalidBatchId");
-          }
-        });
+    it("rejects tokens that are not registered", async () => {
+      expect(await fixture.tokens.fetch(mint)).to.equal(null);
 
-        it("rejects with AddingExistingSigner when adding current validator", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
-          const vs = await fixture.getValidatorSet();
-          const existingValidator = vs.signers[0];
+      await expectRequestError(mint, 1, "TokenNotRegistered");
+      await expectTransactionError(mint, 1, "TokenNotRegistered");
+    });
 
-          try {
-            await fixture.bridgeVSU.call({
-              added: [existingValidator],
-              removed: [],
-              batchId,
-              signers: [validators[0]],
-            });
-            expect.fail("Should have thrown AddingExistingSigner");
-          } catch (err: any) {
-            const errorCode = err.error?.errorCode?.code || err.code;
-            expect(errorCode).to.equal("AddingExistingSigner");
-          }
-        });
+    it("registers a token", async () => {
+      const policyId = Buffer.alloc(28, 7);
+      const assetName = Buffer.from("wSOL");
+      await fixture.tokens.set(
+        testToken(mint, {
+          chains: [
+            testTokenChain(1, {
+              foreignAsset: Buffer.concat([policyId, assetName]),
+            }),
+            testTokenChain(2),
+          ],
+        }),
+        validators
+      );
 
-        it("rejects when adding more than MAX_VALIDATORS_CHANGE (10) in one call", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
+      const token = await fixture.tokens.fetch(mint);
+      expect(token.version).to.equal(ACCOUNT_VERSION);
+      expect(token.mint.toBase58()).to.equal(mint.toBase58());
+      expect(token.mode).to.deep.equal({ lock: {} });
+      expect(token.enabled).to.be.true;
+      expect(token.chains.map((c) => c.chainId)).to.deep.equal([1, 2]);
+      expect(Buffer.from(token.chains[0].foreignAsset)).to.deep.equal(
+        Buffer.concat([policyId, assetName])
+      );
 
-          // Try to add 11 validators - will fail at serialization/account constraint
-          const tooManyVals = Array.from(
-            { length: LIMITS.MAX_VALIDATORS_CHANGE + 1 },
-            () => web3.Keypair.generate().publicKey
-          );
+      await fixture.bridgeRequest.call({
+        amount: 10,
+        receiver,
+        destinationChain: 1,
+        mint,
+        signer: user,
+      });
+    });
 
-          try {
-            await fixture.bridgeVSU.call({
-              added: tooManyVals,
-              removed: [],
-              batchId,
-              signers: [validators[0]],
-            });
-            expect.fail("Should have thrown account constraint error");
-          } catch (err: any) {
-            expect(err.message).to.match(
-              /invalid|serialize|constraint|max length/i
-            );
-          }
-        });
+    it("rejects chains the token is not registered for", async () => {
+      await expectRequestError(mint, 3, "TokenChainNotAllowed");
+      await expectTransactionError(mint, 3, "TokenChainNotAllowed");
+    });
 
-        it.skip("rejects when removing more than MAX_VALIDATORS_CHANGE (10) in one call", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
+    it("rejects a mode that does not match the mint authority", async () => {
+      await fixture.tokens.set(
+        testToken(mint, { mode: { burn: {} } }),
+        validators
+      );
 
-          // Generate 11 fake pubkeys for removal
-          const tooManyRemovals = Array.from(
-            { length: LIMITS.MAX_VALIDATORS_CHANGE + 1 },
-            () => web3.Keypair.generate().publicKey
-          );
+      await expectRequestError(mint, 1, "TokenModeMismatch");
+      await expectTransactionError(mint, 1, "TokenModeMismatch");
+    });
 
-          try {
-            await fixture.bridgeVSU.call({
-              added: [],
-              removed: tooManyRemovals,
-              batchId,
+    it("rejects disabled tokens", async () => {
+      await fixture.tokens.set(testToken(mint, { enabled: false }), validators);
+
+      await expectRequestError(mint, 1, "TokenDisabled");
+      await expectTransactionError(mint, 1, "TokenDisabled");
+
+      await fixture.tokens.set(testToken(mint), validators);
+      await fixture.bridgeRequest.call({
+        amount: 10,
+        receiver,
+        destinationChain: 1,
+        mint,
+        signer: user,
+      });
+    });
+
+    it("rejects invalid token configurations", async () => {
+      const chain = (chainId: number, foreignAsset = Buffer.alloc(20, 1)) =>
+        testTokenChain(chainId, { foreignAsset });
+
+      await expectConfigError(
+        testToken(mint, { chains: [chain(1), chain(1)] }),
+        "InvalidTokenConfig"
+      );
+      await expectConfigError(
+        testToken(mint, { chains: [chain(TEST_DEPLOYMENT.chainId)] }),
+        "InvalidTokenConfig"
+      );
+      await expectConfigError(
+        testToken(mint, { chains: [chain(1, Buffer.alloc(0))] }),
+        "InvalidTokenConfig"
+      );
+      await expectConfigError(
+        testToken(mint, { chains: [chain(1, Buffer.alloc(65, 1))] }),
+        "InvalidTokenConfig"
+      );
+      await expectConfigError(
+        testToken(mint, { chains: [testTokenChain(1, { feeBps: 10_001 })] }),
+        "InvalidTokenConfig"
+      );
+    });
+  });
+
+  describe("Decimal Normalization", () => {
+    let mint: web3.PublicKey;
+    const user = anchor.web3.Keypair.generate();
+    const recipient = web3.Keypair.generate().publicKey;
+    const receiver = Buffer.from(
+      "1234567890abcdef1234567890abcdef12345678",
+      "hex"
+    );
+    const sixDecimalsChain = 1;
+    const eighteenDecimalsChain = 2;
+
+    const bridgeIn = async (amount: BN, sourceChain: number) =>
+      fixture.bridgeTransaction.call({
+        amount,
+        batchId: await fixture.batchIds.freshBatchId(),
+        recipient,
+        mint,
+        validators: validators.slice(
+          0,
+          (await fixture.getValidatorSet()).threshold
+        ),
+        vaultPDA: fixture.pdas.vault(),
+        sourceChain,
+      });
+
+    before(async () => {
+      await airdrop(provider.connection, user.publicKey);
+      mint = await fixture.mints.create(owner.publicKey, 9);
+      await fixture.mints.mintTo(mint, user.publicKey, 10_000_000_000);
+      await fixture.mints.mintTo(
+        mint,
+        fixture.pdas.vault(),
+        100_000_000_000,
+        true
+      );
+      await fixture.tokens.set(
+        testToken(mint, {
+          chains: [
+            testTokenChain(sixDecimalsChain, {
+              foreignAsset: Buffer.alloc(28, 1),
+              decimals: 6,
+            }),
+            testTokenChain(eighteenDecimalsChain, {
+              foreignAsset: Buffer.alloc(20, 2),
+              decimals: 18,
+            }),
+          ],
+        }),
+        validators
+      );
+    });
+
+    it("truncates outbound dust and leaves it with the sender", async () => {
+      const userAta = getAssociatedTokenAddressSync(mint, user.publicKey);
+      const before = await fixture.tokenBalances.getBalance(userAta);
+
+      const signature = await fixture.bridgeRequest.call({
+        amount: 1_234_567_891,
+        receiver,
+        destinationChain: sixDecimalsChain,
+        mint,
+        signer: user,
+      });
+
+      const event = await fixture.events.parseBridgeRequestEvent(signature);
+      expect(event!.amount.toNumber()).to.equal(1_234_567_000);
+      expect(event!.normalizedAmount.toNumber()).to.equal(1_234_567);
+
+      const after = await fixture.tokenBalances.getBalance(userAta);
+      expect(before - after).to.equal(BigInt(1_234_567_000));
+    });
+
+    it("scales outbound amounts up to more foreign decimals", async () => {
+      const signature = await fixture.bridgeRequest.call({
+        amount: 5,
+        receiver,
+        destinationChain: eighteenDecimalsChain,
+        mint,
+        signer: user,
+      });
+
+      const event = await fixture.events.parseBridgeRequestEvent(signature);
+      expect(event!.amount.toNumber()).to.equal(5);
+      expect(event!.normalizedAmount.toString()).to.equal("5000000000");
+    });
+
+    it("rejects outbound amounts below the foreign precision", async () => {
+      await fixture.bridgeRequest.expectError(
+        {
+          amount: 999,
+          receiver,
+          destinationChain: sixDecimalsChain,
+          mint,
+          signer: user,
+        },
+        "AmountTooSmall"
+      );
+    });
+
+    it("converts inbound amounts to the mint decimals", async () => {
+      const recipientAta = getAssociatedTokenAddressSync(mint, recipient);
+
+      await bridgeIn(new BN(1_234_567), sixDecimalsChain);
+      expect(await fixture.tokenBalances.getBalance(recipientAta)).to.equal(
+        BigInt(1_234_567_000)
+      );
+
+      // Above u64::MAX in 18 decimals, 25 tokens in 9 decimals
+      const before = await fixture.tokenBalances.getBalance(recipientAta);
+      await bridgeIn(new BN("25000000000000000000"), eighteenDecimalsChain);
+      const after = await fixture.tokenBalances.getBalance(recipientAta);
+      expect(after - before).to.equal(BigInt(25_000_000_000));
+    });
+
+    it("reports the truncated inbound dust", async () => {
+      // 25 tokens in 9 decimals, and 123 below the mint precision
+      const signature = await bridgeIn(
+        new BN("25000000000000000123"),
+        eighteenDecimalsChain
+      );
+
+      const event = await fixture.events.parseTransactionExecutedEvent(
+        signature
+      );
+      expect(event!.dust.toString()).to.equal("123");
+    });
+
+    it("rejects approvals of another source amount or chain", async () => {
+      const batchId = await fixture.batchIds.freshBatchId();
+      const approve = (amount: BN, sourceChain: number, from: number) => ({
+        amount,
+        batchId,
+        recipient,
+        mint,
+        validators: validators.slice(from, from + 1),
+        vaultPDA: fixture.pdas.vault(),
+        sourceChain,
+      });
+
+      await fixture.bridgeTransaction.call(
+        approve(new BN("25000000000000000000"), eighteenDecimalsChain, 0)
+      );
+      const bt = await fixture.accounts.getBridgingTransaction(
+        fixture.pdas.bridgingTransaction(batchId)
+      );
+      expect(bt.sourceChain).to.equal(eighteenDecimalsChain);
+      expect(bt.sourceAmount.toString()).to.equal("25000000000000000000");
+
+      // Both convert to the stored 25 tokens in the mint decimals
+      await fixture.bridgeTransaction.expectError(
+        approve(new BN("25000000000000000001"), eighteenDecimalsChain, 1),
+        "BridgingTransactionMismatch"
+      );
+      await fixture.bridgeTransaction.expectError(
+        approve(new BN(25_000_000), sixDecimalsChain, 1),
+        "BridgingTransactionMismatch"
+      );
+    });
+
+    it("rejects inbound amounts below the mint precision", async () => {
+      await fixture.bridgeTransaction.expectError(
+        {
+          amount: new BN(999_999_999),
+          batchId: await fixture.batchIds.freshBatchId(),
+          recipient,
+          mint,
+          validators: validators.slice(
+            0,
+            (await fixture.getValidatorSet()).threshold
+          ),
+          vaultPDA: fixture.pdas.vault(),
+          sourceChain: eighteenDecimalsChain,
+        },
+        "AmountTooSmall"
+      );
+    });
+  });
+
+  describe("Receiver Validation", () => {
+    let mint: web3.PublicKey;
+    const user = anchor.web3.Keypair.generate();
+    const evmChain = 20;
+    const cardanoChain = 21;
+    const boundedChain = 22;
+
+    const request = (destinationChain: number, receiver: Buffer) =>
+      fixture.bridgeRequest.call({
+        amount: 10,
+        receiver,
+        destinationChain,
+        mint,
+        signer: user,
+      });
+
+    const expectInvalid = (destinationChain: number, receiver: Buffer) =>
+      fixture.bridgeRequest.expectError(
+        { amount: 10, receiver, destinationChain, mint, signer: user },
+        "InvalidReceiverAddress"
+      );
+
+    // Shelley address: header (type and network ID) followed by credentials
+    const cardanoAddress = (header: number, length: number) =>
+      Buffer.concat([Buffer.from([header]), Buffer.alloc(length - 1, 9)]);
+
+    before(async () => {
+      await airdrop(provider.connection, user.publicKey);
+      mint = await fixture.mints.create(owner.publicKey, 9);
+      await fixture.mints.mintTo(mint, user.publicKey, 10000);
+      await fixture.tokens.register(mint, validators);
+
+      await fixture.chains.set(
+        testChain(evmChain, { addressFormat: { evm: {} } }),
+        validators
+      );
+      await fixture.chains.set(
+        testChain(cardanoChain, {
+          addressFormat: { cardano: { networkId: 1 } },
+        }),
+        validators
+      );
+      await fixture.chains.set(
+        testChain(boundedChain, { minReceiverLen: 4, maxReceiverLen: 8 }),
+        validators
+      );
+    });
+
+    it("accepts only 20-byte EVM addresses", async () => {
+      await expectInvalid(evmChain, Buffer.alloc(19, 1));
+      await expectInvalid(evmChain, Buffer.alloc(21, 1));
+      await request(evmChain, Buffer.alloc(20, 1));
+    });
+
+    it("accepts Shelley payment addresses of the chain's network", async () => {
+      await request(cardanoChain, cardanoAddress(0x01, 57)); // base
+      await request(cardanoChain, cardanoAddress(0x41, 32)); // pointer
+      await request(cardanoChain, cardanoAddress(0x61, 29)); // enterprise
+    });
+
+    it("rejects malformed Cardano addresses", async () => {
+      // Testnet network ID
+      await expectInvalid(cardanoChain, cardanoAddress(0x60, 29));
+      // Wrong length for the address type
+      await expectInvalid(cardanoChain, cardanoAddress(0x61, 57));
+      await expectInvalid(cardanoChain, cardanoAddress(0x01, 29));
+      // Byron and reward addresses
+      await expectInvalid(cardanoChain, cardanoAddress(0x82, 29));
+      await expectInvalid(cardanoChain, cardanoAddress(0xe1, 29));
+      // Bech32 text instead of the binary address
+      await expectInvalid(
+        cardanoChain,
+        Buffer.from("addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8")
+      );
+      await expectInvalid(cardanoChain, Buffer.alloc(0));
+    });
+
+    it("enforces the receiver length bounds before moving tokens", async () => {
+      const userAta = getAssociatedTokenAddressSync(mint, user.publicKey);
+      const before = await fixture.tokenBalances.getBalance(userAta);
+
+      await expectInvalid(boundedChain, Buffer.alloc(3, 1));
+      await expectInvalid(boundedChain, Buffer.alloc(9, 1));
+      expect(await fixture.tokenBalances.getBalance(userAta)).to.equal(before);
+
+      await request(boundedChain, Buffer.alloc(4, 1));
+      await request(boundedChain, Buffer.alloc(8, 1));
+    });
+  });
+
+  describe("Bridge Fees", () => {
+    let mint: web3.PublicKey;
+    let burnMint: web3.PublicKey;
+    const user = anchor.web3.Keypair.generate();
+    const receiver = Buffer.from(
+      "1234567890abcdef1234567890abcdef12345678",
+      "hex"
+    );
+    const tokenFeeChain = 1;
+    const solFeeChain = 2;
+    const fullFeeChain = 3;
+    const solFee = 10_000_000;
+
+    const request = (
+      tokenMint: web3.PublicKey,
+      amount: number,
+      destinationChain: number
+    ) =>
+      fixture.bridgeRequest.call({
+        amount,
+        receiver,
+        destinationChain,
+        mint: tokenMint,
+        signer: user,
+      });
+
+    const withdrawFees = async (
+      tokenMint: web3.PublicKey | null,
+      recipient: web3.PublicKey,
+      amount: number,
+      actionAccounts: web3.PublicKey[]
+    ) =>
+      fixture.governance.submit({
+        action: {
+          withdrawFees: { mint: tokenMint, recipient, amount: new BN(amount) },
+        },
+        batchId: await fixture.batchIds.freshBatchId(),
+        signers: validators.slice(
+          0,
+          (await fixture.getValidatorSet()).threshold
+        ),
+        actionAccounts: [fixture.pdas.feeVault(), recipient, ...actionAccounts],
+      });
+
+    before(async () => {
+      await airdrop(provider.connection, user.publicKey);
+      mint = await fixture.mints.create(owner.publicKey, 9);
+      await fixture.mints.mintTo(mint, user.publicKey, 10_000_000);
+      burnMint = await fixture.mints.create(owner.publicKey, 9);
+      await fixture.mints.mintTo(burnMint, user.publicKey, 10_000_000);
+      await fixture.mints.setMintAuthority(burnMint, fixture.pdas.vault());
+
+      const chains = [
+        testTokenChain(tokenFeeChain, { flatFee: new BN(100), feeBps: 50 }),
+        testTokenChain(solFeeChain, {
+          flatFee: new BN(solFee),
+          feeCurrency: { sol: {} },
+        }),
+        testTokenChain(fullFeeChain, { feeBps: 10_000 }),
+      ];
+      await fixture.tokens.set(testToken(mint, { chains }), validators);
+      await fixture.tokens.register(burnMint, validators, { chains });
+    });
+
+    it("collects a flat and a basis points fee in the bridged token", async () => {
+      const userAta = getAssociatedTokenAddressSync(mint, user.publicKey);
+      const vaultAta = getAssociatedTokenAddressSync(
+        mint,
+        fixture.pdas.vault(),
+        true
+      );
+      const feeVaultAta = fixture.bridgeRequest.feeVaultAta(mint);
+      const userBefore = await fixture.tokenBalances.getBalance(userAta);
+      const vaultBefore = await fixture.tokenBalances.getBalance(vaultAta);
+
+      const signature = await request(mint, 100_000, tokenFeeChain);
+
+      // 100 flat and 0.5% of 100_000
+      const event = await fixture.events.parseBridgeRequestEvent(signature);
+      expect(event!.amount.toNumber()).to.equal(100_000);
+      expect(event!.fee.toNumber()).to.equal(600);
+      expect(event!.netAmount.toNumber()).to.equal(99_400);
+      expect(event!.normalizedAmount.toNumber()).to.equal(99_400);
+      expect(event!.solFee.toNumber()).to.equal(0);
+
+      expect(
+        userBefore - (await fixture.tokenBalances.getBalance(userAta))
+      ).to.equal(BigInt(100_000));
+      expect(
+        (await fixture.tokenBalances.getBalance(vaultAta)) - vaultBefore
+      ).to.equal(BigInt(99_400));
+      expect(await fixture.tokenBalances.getBalance(feeVaultAta)).to.equal(
+        BigInt(600)
+      );
+    });
+
+    it("collects a flat fee in SOL", async () => {
+      const feeVault = fixture.pdas.feeVault();
+      const before = await provider.connection.getBalance(feeVault);
+
+      const signature = await request(mint, 1_000, solFeeChain);
+
+      const event = await fixture.events.parseBridgeRequestEvent(signature);
+      expect(event!.amount.toNumber()).to.equal(1_000);
+      expect(event!.fee.toNumber()).to.equal(0);
+      expect(event!.netAmount.toNumber()).to.equal(1_000);
+      expect(event!.solFee.toNumber()).to.equal(solFee);
+      expect(
+        (await provider.connection.getBalance(feeVault)) - before
+      ).to.equal(solFee);
+    });
+
+    it("burns the net amount and keeps the fee of a burned token", async () => {
+      const supplyBefore = (await fixture.mints.getMintInfo(burnMint)).supply;
+
+      const signature = await request(burnMint, 10_000, tokenFeeChain);
+
+      const event = await fixture.events.parseBridgeRequestEvent(signature);
+      expect(event!.fee.toNumber()).to.equal(150);
+      expect(event!.netAmount.toNumber()).to.equal(9_850);
+      expect(
+        supplyBefore - (await fixture.mints.getMintInfo(burnMint)).supply
+      ).to.equal(BigInt(9_850));
+      expect(
+        await fixture.tokenBalances.getBalance(
+          fixture.bridgeRequest.feeVaultAta(burnMint)
+        )
+      ).to.equal(BigInt(150));
+    });
+
+    it("rejects requests the fee would take entirely", async () => {
+      await fixture.bridgeRequest.expectError(
+        {
+          amount: 1_000,
+          receiver,
+          destinationChain: fullFeeChain,
+          mint,
+          signer: user,
+        },
+        "FeeExceedsAmount"
+      );
+      await fixture.bridgeRequest.expectError(
+        {
+          amount: 100,
+          receiver,
+          destinationChain: tokenFeeChain,
+          mint,
+          signer: user,
+        },
+        "FeeExceedsAmount"
+      );
+    });
+
+    it("quotes a request without moving any tokens", async () => {
+      const userAta = getAssociatedTokenAddressSync(mint, user.publicKey);
+      const userBefore = await fixture.tokenBalances.getBalance(userAta);
+
+      const quote = await fixture.bridgeRequest.quote({
+        amount: 100_000,
+        receiver,
+        destinationChain: tokenFeeChain,
+        mint,
+      });
+
+      expect(quote.rejection).to.be.null;
+      expect(quote.amount.toNumber()).to.equal(100_000);
+      expect(quote.fee.toNumber()).to.equal(600);
+      expect(quote.solFee.toNumber()).to.equal(0);
+      expect(quote.netAmount.toNumber()).to.equal(99_400);
+      expect(quote.normalizedAmount.toNumber()).to.equal(99_400);
+      expect(quote.requestHeadroom.toString()).to.equal(
+        new BN("18446744073709551615").subn(100_000).toString()
+      );
+      expect(await fixture.tokenBalances.getBalance(userAta)).to.equal(
+        userBefore
+      );
+    });
+
+    it("quotes the reason a request would be rejected", async () => {
+      const errorCode = (name: string) =>
+        program.idl.errors.find((e) => e.name === name)!.code;
+
+      const tooSmall = await fixture.bridgeRequest.quote({
+        amount: 1_000,
+        receiver,
+        destinationChain: fullFeeChain,
+        mint,
+      });
+      expect(tooSmall.rejection).to.equal(errorCode("FeeExceedsAmount"));
+      expect(tooSmall.fee.toNumber()).to.equal(0);
+
+      const unknownChain = await fixture.bridgeRequest.quote({
+        amount: 1_000,
+        receiver,
+        destinationChain: 200,
+        mint,
+      });
+      expect(unknownChain.rejection).to.equal(errorCode("ChainNotRegistered"));
+      expect(unknownChain.requestHeadroom.toNumber()).to.equal(0);
+    });
+
+    it("withdraws token fees through governance", async () => {
+      const recipient = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        owner.payer,
+        mint,
+        web3.Keypair.generate().publicKey
+      );
+      const feeVaultAta = fixture.bridgeRequest.feeVaultAta(mint);
+
+      await withdrawFees(mint, recipient.address, 600, [
+        feeVaultAta,
+        mint,
+        TOKEN_PROGRAM_ID,
+      ]);
+
+      expect(
+        await fixture.tokenBalances.getBalance(recipient.address)
+      ).to.equal(BigInt(600));
+      expect(await fixture.tokenBalances.getBalance(feeVaultAta)).to.equal(
+        BigInt(0)
+      );
+    });
+
+    it("rejects token withdrawals without a fee vault token account", async () => {
+      const feelessMint = await fixture.mints.create(owner.publicKey, 9);
+      const recipient = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        owner.payer,
+        feelessMint,
+        web3.Keypair.generate().publicKey
+      );
+
+      try {
+        await withdrawFees(feelessMint, recipient.address, 1, [
+          fixture.bridgeRequest.feeVaultAta(feelessMint),
+          feelessMint,
+          TOKEN_PROGRAM_ID,
+        ]);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("invalid account data");
+      }
+    });
+
+    it("withdraws SOL fees through governance", async () => {
+      const recipient = web3.Keypair.generate().publicKey;
+
+      await withdrawFees(null, recipient, solFee, []);
+
+      expect(await provider.connection.getBalance(recipient)).to.equal(solFee);
+    });
+
+    it("rejects withdrawing more than the collected fees", async () => {
+      const recipient = web3.Keypair.generate().publicKey;
+
+      try {
+        await withdrawFees(null, recipient, solFee, []);
+        expect.fail("Should have thrown InsufficientFeeBalance");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("InsufficientFeeBalance");
+      }
+    });
+  });
+
+  describe("Rewards", () => {
+    let mint: web3.PublicKey;
+    let signers: web3.Keypair[];
+    const recipient = anchor.web3.Keypair.generate();
+    const relayerReward = 1_000_000;
+    const validatorReward = 100_000;
+    const funding = 100_000_000;
+
+    const execute = async (
+      rewardAccounts?: web3.PublicKey[],
+      batchId?: number,
+      approving: web3.Keypair[] = signers
+    ) =>
+      fixture.bridgeTransaction.call({
+        amount: 100,
+        batchId: batchId ?? (await fixture.batchIds.freshBatchId()),
+        recipient: recipient.publicKey,
+        mint,
+        validators: approving,
+        vaultPDA: fixture.pdas.vault(),
+        rewardAccounts,
+      });
+
+    before(async () => {
+      mint = await fixture.mints.create(owner.publicKey, 9);
+      await fixture.mints.mintTo(mint, fixture.pdas.vault(), 10_000, true);
+      await fixture.tokens.register(mint, validators);
+
+      const vs = await fixture.getValidatorSet();
+      signers = validators.slice(0, vs.threshold);
+
+      await provider.sendAndConfirm(
+        new web3.Transaction().add(
+          web3.SystemProgram.transfer({
+            fromPubkey: owner.publicKey,
+            toPubkey: fixture.pdas.feeVault(),
+            lamports: funding,
+          })
+        )
+      );
+      await fixture.rewards.set(relayerReward, validatorReward, validators);
+    });
+
+    after(async () => {
+      await fixture.rewards.set(0, 0, validators);
+    });
+
+    it("stores the rewards in the fee vault", async () => {
+      const feeVault = await fixture.rewards.fetchFeeVault();
+      expect(feeVault.relayerReward.toNumber()).to.equal(relayerReward);
+      expect(feeVault.validatorReward.toNumber()).to.equal(validatorReward);
+    });
+
+    it("credits the relayer and the approving validators", async () => {
+      const before = await fixture.rewards.fetchFeeVault();
+
+      await execute(
+        fixture.rewards.accounts([
+          owner.publicKey,
+          ...signers.map((v) => v.publicKey),
+        ])
+      );
+
+      const relayer = await fixture.rewards.fetch(owner.publicKey);
+      expect(relayer.accrued.toNumber()).to.equal(relayerReward);
+      for (const validator of signers) {
+        const account = await fixture.rewards.fetch(validator.publicKey);
+        expect(account.accrued.toNumber()).to.equal(validatorReward);
+      }
+
+      const after = await fixture.rewards.fetchFeeVault();
+      expect(
+        after.reservedRewards.toNumber() - before.reservedRewards.toNumber()
+      ).to.equal(relayerReward + validatorReward * signers.length);
+    });
+
+    it("skips the relayer when its reward account is missing", async () => {
+      const relayerBefore = await fixture.rewards.fetch(owner.publicKey);
+      const validatorBefore = await fixture.rewards.fetch(signers[0].publicKey);
+
+      await execute(fixture.rewards.accounts(signers.map((v) => v.publicKey)));
+
+      const relayerAfter = await fixture.rewards.fetch(owner.publicKey);
+      expect(relayerAfter.accrued.toNumber()).to.equal(
+        relayerBefore.accrued.toNumber()
+      );
+      const validatorAfter = await fixture.rewards.fetch(signers[0].publicKey);
+      expect(
+        validatorAfter.accrued.toNumber() - validatorBefore.accrued.toNumber()
+      ).to.equal(validatorReward);
+    });
+
+    it("rejects executions without the validators' reward accounts", async () => {
+      const batchId = await fixture.batchIds.freshBatchId();
+
+      try {
+        await execute(fixture.rewards.accounts([owner.publicKey]), batchId);
+        expect.fail("Should have thrown MissingActionAccount");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("MissingActionAccount");
+      }
+
+      // The approvals that did not reach quorum were kept, the remaining ones
+      // execute the transaction once every reward account is passed
+      const bt = await fixture.accounts.getBridgingTransactionNullable(
+        fixture.pdas.bridgingTransaction(batchId)
+      );
+      const remaining = signers.filter(
+        (v) => !(bt?.signers ?? []).some((key) => key.equals(v.publicKey))
+      );
+      await execute(
+        fixture.rewards.accounts([
+          owner.publicKey,
+          ...signers.map((v) => v.publicKey),
+        ]),
+        batchId,
+        remaining
+      );
+      expect(
+        (await fixture.getValidatorSet()).lastBatchId.toNumber()
+      ).to.equal(batchId);
+    });
+
+    it("keeps reserved rewards out of fee withdrawals", async () => {
+      try {
+        await fixture.governance.submit({
+          action: {
+            withdrawFees: {
+              mint: null,
+              recipient: recipient.publicKey,
+              amount: new BN(funding),
+            },
+          },
+          batchId: await fixture.batchIds.freshBatchId(),
+          signers,
+          actionAccounts: [fixture.pdas.feeVault(), recipient.publicKey],
+        });
+        expect.fail("Should have thrown InsufficientFeeBalance");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("InsufficientFeeBalance");
+      }
+    });
+
+    it("pays out accrued rewards once", async () => {
+      const validator = signers[0];
+      const before = await provider.connection.getBalance(validator.publicKey);
+      const vaultBefore = await fixture.rewards.fetchFeeVault();
+      const accrued = (await fixture.rewards.fetch(validator.publicKey)).accrued;
+
+      await fixture.rewards.claim(validator);
+
+      expect(
+        (await provider.connection.getBalance(validator.publicKey)) - before
+      ).to.equal(accrued.toNumber());
+      const account = await fixture.rewards.fetch(validator.publicKey);
+      expect(account.accrued.toNumber()).to.equal(0);
+      expect(account.claimed.toNumber()).to.equal(accrued.toNumber());
+      const vaultAfter = await fixture.rewards.fetchFeeVault();
+      expect(
+        vaultBefore.reservedRewards.toNumber() -
+          vaultAfter.reservedRewards.toNumber()
+      ).to.equal(accrued.toNumber());
+
+      try {
+        await fixture.rewards.claim(validator);
+        expect.fail("Should have thrown NothingToClaim");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("NothingToClaim");
+      }
+    });
+
+    it("credits rewards pro rata while the fee vault is short", async () => {
+      const feeVault = await provider.connection.getAccountInfo(
+        fixture.pdas.feeVault()
+      );
+      const rentExempt =
+        await provider.connection.getMinimumBalanceForRentExemption(
+          feeVault.data.length
+        );
+      const { reservedRewards } = await fixture.rewards.fetchFeeVault();
+      const available =
+        feeVault.lamports - rentExempt - reservedRewards.toNumber();
+      await fixture.rewards.set(available * 2, 0, validators);
+      const before = await fixture.rewards.fetch(owner.publicKey);
+
+      await execute(fixture.rewards.accounts([owner.publicKey]));
+
+      const after = await fixture.rewards.fetch(owner.publicKey);
+      expect(after.accrued.toNumber() - before.accrued.toNumber()).to.equal(
+        available
+      );
+      const vault = await fixture.rewards.fetchFeeVault();
+      expect(vault.reservedRewards.toNumber()).to.equal(
+        reservedRewards.toNumber() + available
+      );
+    });
+
+    it("counts the skipped relayer's reward in the pro rata split", async () => {
+      await provider.sendAndConfirm(
+        new web3.Transaction().add(
+          web3.SystemProgram.transfer({
+            fromPubkey: owner.publicKey,
+            toPubkey: fixture.pdas.feeVault(),
+            lamports: funding,
+          })
+        )
+      );
+      const feeVault = await provider.connection.getAccountInfo(
+        fixture.pdas.feeVault()
+      );
+      const rentExempt =
+        await provider.connection.getMinimumBalanceForRentExemption(
+          feeVault.data.length
+        );
+      const { reservedRewards } = await fixture.rewards.fetchFeeVault();
+      const available =
+        feeVault.lamports - rentExempt - reservedRewards.toNumber();
+      // Covers the validators alone, not together with the relayer
+      await fixture.rewards.set(available, validatorReward, validators);
+      const before = await fixture.rewards.fetch(signers[0].publicKey);
+
+      await execute(fixture.rewards.accounts(signers.map((v) => v.publicKey)));
+
+      const total = available + validatorReward * signers.length;
+      const after = await fixture.rewards.fetch(signers[0].publicKey);
+      expect(after.accrued.toNumber() - before.accrued.toNumber()).to.equal(
+        Math.floor((validatorReward * available) / total)
+      );
+    });
+  });
+
+  describe("Validator Set Update (VSU)", () => {
+    let newValidators: web3.Keypair[];
+
+    before(async () => {
+      // Create new validator keypairs for testing
+      newValidators = Array.from({ length: 3 }, () => web3.Keypair.generate());
+    });
+
+    // Helper to compare Pubkey arrays
+    const expectPubkeyArrayEqual = (
+      actual: web3.PublicKey[],
+      expected: web3.PublicKey[]
+    ) => {
+      expect(actual.length).to.equal(expected.length);
+      for (let i = 0; i < actual.length; i++) {
+        expect(actual[i].toBase58()).to.equal(expected[i].toBase58());
+      }
+    };
+
+    describe("First Submission (Create Proposal)", () => {
+      describe("Happy Path", () => {
+        it("successfully creates VSU proposal with valid parameters", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+          const added = [newValidators[0].publicKey];
+          const removed: web3.PublicKey[] = [];
+
+          const vsBefore = await fixture.getValidatorSet();
+          const validatorCountBefore = vsBefore.signers.length;
+
+          await fixture.bridgeVSU.call({
+            added,
+            removed,
+            batchId,
+            signers: [validators[0]],
+          });
+
+          // Verify proposal created
+          const vscAccount = await fixture.bridgeVSU.fetchValidatorSetChange(
+            batchId
+          );
+          expect(vscAccount).to.not.be.null;
+          expect(vscAccount.action.updateValidators.added.length).to.equal(1);
+          expect(vscAccount.action.updateValidators.added[0].toBase58()).to.equal(added[0].toBase58());
+          expect(vscAccount.action.updateValidators.removed.length).to.equal(0);
+          expect(vscAccount.batchId.toNumber()).to.equal(batchId);
+          expect(vscAccount.signers.length).to.equal(1);
+          expect(vscAccount.signers[0].toBase58()).to.equal(
+            validators[0].publicKey.toBase58()
+          );
+
+          // Verify validator set NOT updated yet (below threshold)
+          const vsAfter = await fixture.getValidatorSet();
+          expect(vsAfter.signers.length).to.equal(validatorCountBefore);
+        });
+
+        it("creates proposal with multiple additions", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+          const added = [
+            newValidators[1].publicKey,
+            newValidators[2].publicKey,
+          ];
+          const removed: web3.PublicKey[] = [];
+
+          await fixture.bridgeVSU.call({
+            added,
+            removed,
+            batchId,
+            signers: [validators[0]],
+          });
+
+          const vscAccount = await fixture.bridgeVSU.fetchValidatorSetChange(
+            batchId
+          );
+          expect(vscAccount.action.updateValidators.added.length).to.equal(2);
+          expect(vscAccount.action.updateValidators.removed.length).to.equal(0);
+        });
+
+        it("creates proposal with removals", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+          const vs = await fixture.getValidatorSet();
+
+          const added: web3.PublicKey[] = [];
+          // Now we pass actual pubkeys instead of indices
+          const removed = [vs.signers[0], vs.signers[1]];
+
+          await fixture.bridgeVSU.call({
+            added,
+            removed,
+            batchId,
+            signers: [validators[0]],
+          });
+
+          const vscAccount = await fixture.bridgeVSU.fetchValidatorSetChange(
+            batchId
+          );
+          expect(vscAccount.action.updateValidators.added.length).to.equal(0);
+          expect(vscAccount.action.updateValidators.removed.length).to.equal(2);
+          expectPubkeyArrayEqual(vscAccount.action.updateValidators.removed, removed);
+        });
+
+        it("creates proposal with both additions and removals", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+          const vs = await fixture.getValidatorSet();
+
+          const added = [newValidators[0].publicKey];
+          const removed = [vs.signers[0]]; // Remove first validator by pubkey
+
+          await fixture.bridgeVSU.call({
+            added,
+            removed,
+            batchId,
+            signers: [validators[1]],
+          });
+
+          const vscAccount = await fixture.bridgeVSU.fetchValidatorSetChange(
+            batchId
+          );
+          expect(vscAccount.action.updateValidators.added.length).to.equal(1);
+          expect(vscAccount.action.updateValidators.removed.length).to.equal(1);
+          expect(vscAccount.action.updateValidators.removed[0].toBase58()).to.equal(
+            vs.signers[0].toBase58()
+          );
+        });
+      });
+
+      describe("Error Cases", () => {
+        it("rejects with InvalidBatchId when batch_id <= last_batch_id", async () => {
+          const vs = await fixture.getValidatorSet();
+          const oldBatchId = vs.lastBatchId.toNumber();
+
+          try {
+            await fixture.bridgeVSU.call({
+              added: [newValidators[0].publicKey],
+              removed: [],
+              batchId: oldBatchId,
+              signers: [validators[0]],
+            });
+            expect.fail("Should have thrown InvalidBatchId");
+          } catch (err: any) {
+            const errorCode = err.error?.errorCode?.code || err.code;
+            expect(errorCode).to.equal("InvalidBatchId");
+          }
+        });
+
+        it("rejects with AddingExistingSigner when adding current validator", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+          const vs = await fixture.getValidatorSet();
+          const existingValidator = vs.signers[0];
+
+          try {
+            await fixture.bridgeVSU.call({
+              added: [existingValidator],
+              removed: [],
+              batchId,
+              signers: [validators[0]],
+            });
+            expect.fail("Should have thrown AddingExistingSigner");
+          } catch (err: any) {
+            const errorCode = err.error?.errorCode?.code || err.code;
+            expect(errorCode).to.equal("AddingExistingSigner");
+          }
+        });
+
+        it("rejects when adding more than MAX_VALIDATORS_CHANGE (10) in one call", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+
+          // Try to add 11 validators - will fail at serialization/account constraint
+          const tooManyVals = Array.from(
+            { length: LIMITS.MAX_VALIDATORS_CHANGE + 1 },
+            () => web3.Keypair.generate().publicKey
+          );
+
+          try {
+            await fixture.bridgeVSU.call({
+              added: tooManyVals,
+              removed: [],
+              batchId,
+              signers: [validators[0]],
+            });
+            expect.fail("Should have thrown account constraint error");
+          } catch (err: any) {
+            expect(err.message).to.match(
+              /invalid|serialize|constraint|max length/i
+            );
+          }
+        });
+
+        it.skip("rejects when removing more than MAX_VALIDATORS_CHANGE (10) in one call", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+
+          // Generate 11 fake pubkeys for removal
+          const tooManyRemovals = Array.from(
+            { length: LIMITS.MAX_VALIDATORS_CHANGE + 1 },
+            () => web3.Keypair.generate().publicKey
+          );
+
+          try {
+            await fixture.bridgeVSU.call({
+              added: [],
+              removed: tooManyRemovals,
+              batchId,
+              signers: [validators[0]],
+            });
+            expect.fail("Should have thrown account constraint error");
+          } catch (err: any) {
+            expect(err.message).to.match(
+              /invalid|serialize|constraint|max length/i
+            );
+          }
+        });
+
+        it("rejects with MinValidatorsNotMet when removing too many validators", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+          const vs = await fixture.getValidatorSet();
+          const currentCount = vs.signers.length;
+          const toRemove = currentCount - (LIMITS.MIN_VALIDATORS - 1);
+
+          // Get actual pubkeys to remove
+          const removePubkeys = vs.signers.slice(0, toRemove);
+
+          try {
+            await fixture.bridgeVSU.call({
+              added: [],
+              removed: removePubkeys,
+              batchId,
+              signers: [validators[0]],
+            });
+            expect.fail("Should have thrown MinValidatorsNotMet");
+          } catch (err: any) {
+            const errorCode = err.error?.errorCode?.code || err.code;
+            expect(errorCode).to.equal("MinValidatorsNotMet");
+          }
+        });
+
+        it("rejects with RemovingNonExistentSigner when pubkey not in validator set", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+          const nonExistentValidator = web3.Keypair.generate().publicKey;
+
+          try {
+            await fixture.bridgeVSU.call({
+              added: [],
+              removed: [nonExistentValidator],
+              batchId,
+              signers: [validators[0]],
+            });
+            expect.fail("Should have thrown RemovingNonExistentSigner");
+          } catch (err: any) {
+            const errorCode = err.error?.errorCode?.code || err.code;
+            expect(errorCode).to.equal("RemovingNonExistentSigner");
+          }
+        });
+
+        it("rejects with NoSignersProvided when no validators sign", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+
+          try {
+            await fixture.bridgeVSU.call({
+              added: [newValidators[0].publicKey],
+              removed: [],
+              batchId,
+              signers: [],
+            });
+            expect.fail("Should have thrown NoSignersProvided");
+          } catch (err: any) {
+            const errorCode = err.error?.errorCode?.code || err.code;
+            expect(errorCode).to.equal("NoSignersProvided");
+          }
+        });
+
+        it("rejects with InvalidSigner when non-validator signs", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+          const nonValidator = web3.Keypair.generate();
+
+          await airdrop(provider.connection, nonValidator.publicKey);
+
+          try {
+            await fixture.bridgeVSU.call({
+              added: [newValidators[0].publicKey],
+              removed: [],
+              batchId,
+              payer: nonValidator,
+              signers: [nonValidator],
+            });
+            expect.fail("Should have thrown InvalidSigner");
+          } catch (err: any) {
+            const errorCode = err.error?.errorCode?.code || err.code;
+            expect(errorCode).to.equal("InvalidSigner");
+          }
+        });
+
+        it("rejects with DuplicateSignersProvided in same transaction", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+
+          try {
+            await fixture.bridgeVSU.call({
+              added: [newValidators[0].publicKey],
+              removed: [],
+              batchId,
+              signers: [validators[0], validators[0]],
+            });
+            expect.fail("Should have thrown DuplicateSignersProvided");
+          } catch (err: any) {
+            const errorCode = err.error?.errorCode?.code || err.code;
+            expect(errorCode).to.equal("DuplicateSignersProvided");
+          }
+        });
+
+        it("rejects with DuplicateValidatorsInRemoved when duplicate pubkeys in removed", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+          const vs = await fixture.getValidatorSet();
+          const duplicateValidator = vs.signers[0];
+
+          try {
+            await fixture.bridgeVSU.call({
+              added: [],
+              removed: [duplicateValidator, duplicateValidator], // Duplicate!
+              batchId,
+              signers: [validators[0]],
+            });
+            expect.fail("Should have thrown DuplicateValidatorsInRemoved");
+          } catch (err: any) {
+            const errorCode = err.error?.errorCode?.code || err.code;
+            expect(errorCode).to.equal("DuplicateValidatorsInRemoved");
+          }
+        });
+
+        it("rejects with AddingAndRemovingSameSigner when same pubkey in added and removed", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+          const newValidator = newValidators[0].publicKey;
+
+          try {
+            await fixture.bridgeVSU.call({
+              added: [newValidator],
+              removed: [newValidator], // Same pubkey in both!
+              batchId,
+              signers: [validators[0]],
+            });
+            expect.fail("Should have thrown AddingAndRemovingSameSigner");
+          } catch (err: any) {
+            const errorCode = err.error?.errorCode?.code || err.code;
+            expect(errorCode).to.equal("AddingAndRemovingSameSigner");
+          }
+        });
+      });
+
+      describe("Edge Cases", () => {
+        it("handles adding validator at exactly MAX_VALIDATORS_CHANGE limit", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+          const vs = await fixture.getValidatorSet();
+          const currentCount = vs.signers.length;
+
+          if (currentCount < LIMITS.MAX_VALIDATORS_CHANGE) {
+            const toAdd = LIMITS.MAX_VALIDATORS_CHANGE - currentCount;
+            const newVals = Array.from(
+              { length: toAdd },
+              () => web3.Keypair.generate().publicKey
+            );
+
+            await fixture.bridgeVSU.call({
+              added: newVals,
+              removed: [],
+              batchId,
+              signers: [validators[0]],
+            });
+
+            const vscAccount = await fixture.bridgeVSU.fetchValidatorSetChange(
+              batchId
+            );
+            expect(vscAccount.action.updateValidators.added.length).to.equal(toAdd);
+          }
+        });
+
+        it("handles removing down to exactly MIN_VALIDATORS limit", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+          const vs = await fixture.getValidatorSet();
+          const currentCount = vs.signers.length;
+
+          if (currentCount > LIMITS.MIN_VALIDATORS) {
+            const toRemove = currentCount - LIMITS.MIN_VALIDATORS;
+            // Get actual pubkeys to remove (from the end to avoid removing active signers)
+            const removePubkeys = vs.signers.slice(-toRemove);
+
+            await fixture.bridgeVSU.call({
+              added: [],
+              removed: removePubkeys,
+              batchId,
+              signers: [validators[0]],
+            });
+
+            const vscAccount = await fixture.bridgeVSU.fetchValidatorSetChange(
+              batchId
+            );
+            expectPubkeyArrayEqual(vscAccount.action.updateValidators.removed, removePubkeys);
+          }
+        });
+
+        it("handles removing last validator in array", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+          const vs = await fixture.getValidatorSet();
+          const lastValidator = vs.signers[vs.signers.length - 1];
+
+          await fixture.bridgeVSU.call({
+            added: [newValidators[0].publicKey],
+            removed: [lastValidator],
+            batchId,
+            signers: [validators[0]],
+          });
+
+          const vscAccount = await fixture.bridgeVSU.fetchValidatorSetChange(
+            batchId
+          );
+          expect(vscAccount.action.updateValidators.removed[0].toBase58()).to.equal(
+            lastValidator.toBase58()
+          );
+        });
+
+        it("rejects duplicate validators in added array", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+          const duplicateVal = newValidators[0].publicKey;
+
+          try {
+            await fixture.bridgeVSU.call({
+              added: [duplicateVal, duplicateVal],
+              removed: [],
+              batchId,
+              signers: [validators[0]],
+            });
+            expect.fail("Should have thrown DuplicateValidatorsInAdded");
+          } catch (err: any) {
+            const errorCode = err.error?.errorCode?.code || err.code;
+            expect(errorCode).to.equal("DuplicateValidatorsInAdded");
+          }
+        });
+      });
+    });
+
+    describe("Second Submission (Approval)", () => {
+      describe("Happy Path", () => {
+        it("successfully adds approval from another validator", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+
+          await fixture.bridgeVSU.call({
+            added: [newValidators[0].publicKey],
+            removed: [],
+            batchId,
+            signers: [validators[0]],
+          });
+
+          const vscBefore = await fixture.bridgeVSU.fetchValidatorSetChange(
+            batchId
+          );
+          expect(vscBefore.signers.length).to.equal(1);
+
+          await fixture.bridgeVSU.call({
+            added: [newValidators[0].publicKey],
+            removed: [],
+            batchId,
+            signers: [validators[1]],
+          });
+
+          const vscAfter = await fixture.bridgeVSU.fetchValidatorSetChange(
+            batchId
+          );
+          expect(vscAfter.signers.length).to.equal(2);
+          expect(vscAfter.signers[1].toBase58()).to.equal(
+            validators[1].publicKey.toBase58()
+          );
+        });
+
+        it("allows multiple validators to approve in one transaction", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+
+          await fixture.bridgeVSU.call({
+            added: [newValidators[0].publicKey],
+            removed: [],
+            batchId,
+            signers: [validators[0]],
+          });
+
+          await fixture.bridgeVSU.call({
+            added: [newValidators[0].publicKey],
+            removed: [],
+            batchId,
+            signers: [validators[1], validators[2]],
+          });
+
+          const vscAfter = await fixture.bridgeVSU.fetchValidatorSetChange(
+            batchId
+          );
+          expect(vscAfter.signers.length).to.equal(3);
+        });
+      });
+
+      describe("Error Cases", () => {
+        it("rejects with SignerAlreadyApproved when validator approves twice", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+
+          await fixture.bridgeVSU.call({
+            added: [newValidators[0].publicKey],
+            removed: [],
+            batchId,
+            signers: [validators[0]],
+          });
+
+          try {
+            await fixture.bridgeVSU.call({
+              added: [newValidators[0].publicKey],
+              removed: [],
+              batchId,
               signers: [validators[0]],
             });
-            expect.fail("Should have thrown account constraint error");
+            expect.fail("Should have thrown SignerAlreadyApproved");
+          } catch (err: any) {
+            const errorCode = err.error?.errorCode?.code || err.code;
+            expect(errorCode).to.equal("SignerAlreadyApproved");
+          }
+        });
+
+        it("rejects with InvalidProposalHash when proposal parameters differ (added)", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+
+          await fixture.bridgeVSU.call({
+            added: [newValidators[0].publicKey],
+            removed: [],
+            batchId,
+            signers: [validators[0]],
+          });
+
+          try {
+            await fixture.bridgeVSU.call({
+              added: [newValidators[1].publicKey], // Different!
+              removed: [],
+              batchId,
+              signers: [validators[1]],
+            });
+            expect.fail("Should have thrown InvalidProposalHash");
           } catch (err: any) {
-            expect(err.message).to.match(
-              /invalid|serialize|constraint|max length/i
-            );
+            const errorCode = err.error?.errorCode?.code || err.code;
+            expect(errorCode).to.equal("InvalidProposalHash");
+          }
+        });
+
+        it("rejects with InvalidProposalHash when removed pubkeys differ", async () => {
+          const batchId = await fixture.batchIds.freshBatchId();
+          const vs = await fixture.getValidatorSet();
+
+          await fixture.bridgeVSU.call({
+            added: [],
+            removed: [vs.signers[0]],
+            batchId,
+            signers: [validators[0]],
+          });
+
+          try {
+            await fixture.bridgeVSU.call({
+              added: [],
+              removed: [vs.signers[1]], // Different validator!
+              batchId,
+              signers: [validators[1]],
+            });
+            expect.fail("Should have thrown InvalidProposalHash");
+          } catch (err: any) {
+            const errorCode = err.error?.errorCode?.code || err.code;
+            expect(errorCode).to.equal("InvalidProposalHash");
           }
         });
+      });
+    });
+
+    describe("Reaching Quorum (Execution)", () => {
+      it("executes VSU when threshold is met in one transaction", async () => {
+        const batchId = await fixture.batchIds.freshBatchId();
+        const vs = await fixture.getValidatorSet();
+        const threshold = vs.threshold;
+
+        const newValidator = web3.Keypair.generate();
+
+        // Provide exactly `threshold` unique signers
+        const uniqueSigners = validators.slice(0, threshold);
+
+        // Execute VSU in one transaction, the new validator co-signs its admission
+        await fixture.bridgeVSU.call({
+          added: [newValidator.publicKey],
+          removed: [],
+          batchId,
+          signers: [...uniqueSigners, newValidator],
+        });
+
+        const updatedVs = await fixture.getValidatorSet();
+
+        // Verify validator was added
+        expect(updatedVs.signers.length).to.equal(vs.signers.length + 1);
+        expect(updatedVs.lastBatchId.toString()).to.equal(batchId.toString());
+
+        const lastSigner = updatedVs.signers[updatedVs.signers.length - 1];
+        expect(lastSigner.toBase58()).to.equal(
+          newValidator.publicKey.toBase58()
+        );
+      });
+
+      it("executes VSU when threshold is met across multiple transactions", async () => {
+        const batchId = await fixture.batchIds.freshBatchId();
+        const vs = await fixture.getValidatorSet();
+        const threshold = vs.threshold;
+
+        const newValidator = web3.Keypair.generate();
+
+        const proposalParams = {
+          added: [newValidator.publicKey],
+          removed: [] as web3.PublicKey[],
+        };
+
+        // Step 1: First validator creates the proposal
+        await fixture.bridgeVSU.call({
+          ...proposalParams,
+          batchId,
+          signers: [validators[0]],
+        });
+
+        let vscAccount = await fixture.bridgeVSU.fetchValidatorSetChange(
+          batchId
+        );
+        expect(vscAccount.signers.length).to.equal(1);
+
+        // The new validator proves possession on its own
+        await fixture.bridgeVSU.call({
+          ...proposalParams,
+          batchId,
+          signers: [newValidator],
+        });
+
+        vscAccount = await fixture.bridgeVSU.fetchValidatorSetChange(batchId);
+        expect(vscAccount.signers.length).to.equal(1);
+        expect(vscAccount.admitted.length).to.equal(1);
+
+        // Step 2: Add approvals one by one until threshold - 1
+        for (let i = 1; i < threshold - 1; i++) {
+          await fixture.bridgeVSU.call({
+            ...proposalParams,
+            batchId,
+            signers: [validators[i]],
+          });
+
+          vscAccount = await fixture.bridgeVSU.fetchValidatorSetChange(batchId);
+          expect(vscAccount.signers.length).to.equal(i + 1);
+        }
+
+        // Step 3: Final approval that triggers execution
+        await fixture.bridgeVSU.call({
+          ...proposalParams,
+          batchId,
+          signers: [validators[threshold - 1]],
+        });
+
+        // Verify validator set was updated
+        const updatedVs = await fixture.getValidatorSet();
+        expect(updatedVs.signers.length).to.equal(vs.signers.length + 1);
+        expect(updatedVs.lastBatchId.toString()).to.equal(batchId.toString());
+
+        const newValidatorAdded = updatedVs.signers.some(
+          (signer) => signer.toBase58() === newValidator.publicKey.toBase58()
+        );
+        expect(newValidatorAdded).to.be.true;
+      });
+
+      it("executes removal of single validator correctly", async () => {
+        const batchId = await fixture.batchIds.freshBatchId();
+        const vs = await fixture.getValidatorSet();
+        const threshold = vs.threshold;
+
+        // Remove the last validator
+        const validatorToRemove = vs.signers[vs.signers.length - 1];
+
+        const proposalParams = {
+          added: [] as web3.PublicKey[],
+          removed: [validatorToRemove],
+        };
+
+        // Step 1: Create proposal
+        await fixture.bridgeVSU.call({
+          ...proposalParams,
+          batchId,
+          signers: [validators[0]],
+        });
+
+        // Step 2: Add approvals until threshold
+        for (let i = 1; i < threshold; i++) {
+          await fixture.bridgeVSU.call({
+            ...proposalParams,
+            batchId,
+            signers: [validators[i]],
+          });
+        }
+
+        // Verify execution
+        const updatedVs = await fixture.getValidatorSet();
+        expect(updatedVs.signers.length).to.equal(vs.signers.length - 1);
+        expect(updatedVs.lastBatchId.toString()).to.equal(batchId.toString());
+
+        // Verify the validator was actually removed
+        const removedValidatorStillExists = updatedVs.signers.some(
+          (signer) => signer.toBase58() === validatorToRemove.toBase58()
+        );
+        expect(removedValidatorStillExists).to.be.false;
+      });
+
+      it("executes both additions and removals correctly", async () => {
+        const batchId = await fixture.batchIds.freshBatchId();
+        const vs = await fixture.getValidatorSet();
+        const threshold = vs.threshold;
+
+        const newValidator = web3.Keypair.generate();
+        const validatorToRemove = vs.signers[vs.signers.length - 1];
+
+        const proposalParams = {
+          added: [newValidator.publicKey],
+          removed: [validatorToRemove],
+        };
+
+        // Step 1: Create proposal, the new validator co-signs its admission
+        await fixture.bridgeVSU.call({
+          ...proposalParams,
+          batchId,
+          signers: [validators[0], newValidator],
+        });
+
+        // Step 2: Add approvals until threshold
+        for (let i = 1; i < threshold; i++) {
+          await fixture.bridgeVSU.call({
+            ...proposalParams,
+            batchId,
+            signers: [validators[i]],
+          });
+        }
+
+        // Verify execution
+        const updatedVs = await fixture.getValidatorSet();
+
+        // Net change: +1 -1 = 0 (same count)
+        expect(updatedVs.signers.length).to.equal(vs.signers.length);
+        expect(updatedVs.lastBatchId.toString()).to.equal(batchId.toString());
+
+        // Verify removal happened
+        const removedValidatorStillExists = updatedVs.signers.some(
+          (signer) => signer.toBase58() === validatorToRemove.toBase58()
+        );
+        expect(removedValidatorStillExists).to.be.false;
+
+        // Verify addition happened
+        const newValidatorAdded = updatedVs.signers.some(
+          (signer) => signer.toBase58() === newValidator.publicKey.toBase58()
+        );
+        expect(newValidatorAdded).to.be.true;
+      });
+
+      it("executes removal of multiple validators in single proposal", async () => {
+        const batchId = await fixture.batchIds.freshBatchId();
+        const vs = await fixture.getValidatorSet();
+        const threshold = vs.threshold;
+
+        // Ensure we have enough validators to remove 2 and stay above MIN
+        if (vs.signers.length <= LIMITS.MIN_VALIDATORS + 1) {
+          console.log(
+            "Skipping: not enough validators to test multiple removal"
+          );
+          return;
+        }
+
+        // Remove last two validators
+        const validatorsToRemove = [
+          vs.signers[vs.signers.length - 1],
+          vs.signers[vs.signers.length - 2],
+        ];
+
+        const proposalParams = {
+          added: [] as web3.PublicKey[],
+          removed: validatorsToRemove,
+        };
+
+        // Collect enough approvals
+        for (let i = 0; i < threshold; i++) {
+          await fixture.bridgeVSU.call({
+            ...proposalParams,
+            batchId,
+            signers: [validators[i]],
+          });
+        }
+
+        // Verify execution
+        const updatedVs = await fixture.getValidatorSet();
+        expect(updatedVs.signers.length).to.equal(vs.signers.length - 2);
+
+        // Verify both validators were removed
+        for (const removed of validatorsToRemove) {
+          const stillExists = updatedVs.signers.some(
+            (s) => s.toBase58() === removed.toBase58()
+          );
+          expect(stillExists).to.be.false;
+        }
+      });
+    });
+  });
+
+  // ============================================================================
+  // GOVERNANCE PROPOSAL TESTS
+  // ============================================================================
+
+  describe("Governance Proposals", () => {
+    it("executes an UpdateValidators action through submit_proposal", async () => {
+      const batchId = await fixture.batchIds.freshBatchId();
+      const vs = await fixture.getValidatorSet();
+      const newValidator = web3.Keypair.generate();
+
+      await fixture.governance.submit({
+        action: {
+          updateValidators: {
+            added: [newValidator.publicKey],
+            removed: [],
+            foreignKeys: [],
+            weights: [],
+          },
+        },
+        batchId,
+        signers: [...validators.slice(0, vs.threshold), newValidator],
+      });
+
+      const updatedVs = await fixture.getValidatorSet();
+      expect(updatedVs.lastBatchId.toString()).to.equal(batchId.toString());
+      expect(
+        updatedVs.signers.some((s) => s.equals(newValidator.publicKey)),
+        "new validator should be added"
+      ).to.be.true;
+      expect(await fixture.governance.fetchProposal(batchId)).to.equal(null);
+    });
+
+    it("accepts approvals for a bridge_vsu proposal through submit_proposal", async () => {
+      const batchId = await fixture.batchIds.freshBatchId();
+      const newValidator = web3.Keypair.generate().publicKey;
+
+      await fixture.bridgeVSU.call({
+        added: [newValidator],
+        removed: [],
+        batchId,
+        signers: [validators[0]],
+      });
+
+      await fixture.governance.submit({
+        action: {
+          updateValidators: {
+            added: [newValidator],
+            removed: [],
+            foreignKeys: [],
+            weights: [],
+          },
+        },
+        batchId,
+        signers: [validators[1]],
+      });
+
+      const proposal = await fixture.governance.fetchProposal(batchId);
+      expect(proposal.signers.length).to.equal(2);
+    });
+
+    it("waits for every added key to prove possession before executing", async () => {
+      const batchId = await fixture.batchIds.freshBatchId();
+      const vs = await fixture.getValidatorSet();
+      const newValidator = web3.Keypair.generate();
+      const action = {
+        updateValidators: {
+          added: [newValidator.publicKey],
+          removed: [],
+          foreignKeys: [],
+          weights: [],
+        },
+      };
+
+      await fixture.governance.submit({
+        action,
+        batchId,
+        signers: validators.slice(0, vs.threshold),
+      });
+
+      const proposal = await fixture.governance.fetchProposal(batchId);
+      expect(proposal.signers.length).to.equal(vs.threshold);
+      expect(proposal.status).to.have.property("voting");
+      expect((await fixture.getValidatorSet()).signers.length).to.equal(
+        vs.signers.length
+      );
+
+      await fixture.governance.submit({
+        action,
+        batchId,
+        signers: [newValidator],
+      });
+
+      const updatedVs = await fixture.getValidatorSet();
+      expect(updatedVs.signers.some((s) => s.equals(newValidator.publicKey))).to
+        .be.true;
+      expect(await fixture.governance.fetchProposal(batchId)).to.equal(null);
+    });
+
+    it("accepts an ed25519 admission proof in place of a signature", async () => {
+      const batchId = await fixture.batchIds.freshBatchId();
+      const newValidator = web3.Keypair.generate();
+
+      await fixture.bridgeVSU.call({
+        added: [newValidator.publicKey],
+        removed: [],
+        batchId,
+        signers: [validators[0]],
+        preInstructions: [
+          admissionProof(fixture.pdas.validatorSet(), batchId, newValidator),
+        ],
+      });
+
+      const proposal = await fixture.governance.fetchProposal(batchId);
+      expect(proposal.admitted.length).to.equal(1);
+      expect(proposal.admitted[0].equals(newValidator.publicKey)).to.be.true;
+    });
+
+    it("ignores an admission proof signed for another batch", async () => {
+      const batchId = await fixture.batchIds.freshBatchId();
+      const newValidator = web3.Keypair.generate();
+
+      await fixture.bridgeVSU.call({
+        added: [newValidator.publicKey],
+        removed: [],
+        batchId,
+        signers: [validators[0]],
+        preInstructions: [
+          admissionProof(fixture.pdas.validatorSet(), batchId + 1, newValidator),
+        ],
+      });
+
+      const proposal = await fixture.governance.fetchProposal(batchId);
+      expect(proposal.admitted.length).to.equal(0);
+    });
+
+    it("rejects with InvalidProposalHash when the action differs", async () => {
+      const batchId = await fixture.batchIds.freshBatchId();
+
+      await fixture.governance.submit({
+        action: {
+          updateValidators: {
+            added: [web3.Keypair.generate().publicKey],
+            removed: [],
+            foreignKeys: [],
+            weights: [],
+          },
+        },
+        batchId,
+        signers: [validators[0]],
+      });
+
+      try {
+        await fixture.governance.submit({
+          action: {
+            updateValidators: {
+              added: [web3.Keypair.generate().publicKey],
+              removed: [],
+              foreignKeys: [],
+              weights: [],
+            },
+          },
+          batchId,
+          signers: [validators[1]],
+        });
+        expect.fail("Should have thrown InvalidProposalHash");
+      } catch (err: any) {
+        const errorCode = err.error?.errorCode?.code || err.code;
+        expect(errorCode).to.equal("InvalidProposalHash");
+      }
+    });
+  });
+
+  // ============================================================================
+  // TIMELOCK & VETO TESTS
+  // ============================================================================
+
+  describe("Timelocked Validator Set Updates", () => {
+    const guardian = web3.Keypair.generate();
+
+    it("schedules the update and keeps the outgoing set until activation", async () => {
+      const batchId = await fixture.batchIds.freshBatchId();
+      const vsBefore = await fixture.getValidatorSet();
+      const newValidator = web3.Keypair.generate();
+
+      await fixture.governance.schedule(
+        {
+          updateValidators: {
+            added: [newValidator.publicKey],
+            removed: [],
+            foreignKeys: [],
+            weights: [],
+          },
+        },
+        batchId,
+        validators,
+        [newValidator]
+      );
+
+      const scheduled = await fixture.getValidatorSet();
+      expect(scheduled.signers.length).to.equal(vsBefore.signers.length);
+      expect(scheduled.pendingUpdate.toString()).to.equal(batchId.toString());
+      expect(scheduled.lastBatchId.toString()).to.equal(batchId.toString());
+
+      const proposal = await fixture.governance.fetchProposal(batchId);
+      expect(proposal.status).to.have.property("scheduled");
+      expect(proposal.activationSlot.toNumber()).to.be.at.least(
+        (await connection.getSlot()) + TEST_CONFIG.timelockSlots.toNumber() - 1
+      );
+
+      await warpToSlot(connection, proposal.activationSlot.toNumber() - 1);
+      try {
+        await fixture.governance.execute(batchId);
+        expect.fail("Should have thrown TimelockNotExpired");
+      } catch (err: any) {
+        const errorCode = err.error?.errorCode?.code || err.code;
+        expect(errorCode).to.equal("TimelockNotExpired");
+      }
+
+      await fixture.governance.activateScheduled(batchId);
+
+      const activated = await fixture.getValidatorSet();
+      expect(activated.pendingUpdate).to.equal(null);
+      expect(activated.signers.some((s) => s.equals(newValidator.publicKey))).to
+        .be.true;
+      expect(await fixture.governance.fetchProposal(batchId)).to.equal(null);
+    });
+
+    it("rejects a second update while one is pending", async () => {
+      const batchId = await fixture.batchIds.freshBatchId();
+      const newValidator = web3.Keypair.generate();
+      await fixture.governance.schedule(
+        {
+          updateValidators: {
+            added: [newValidator.publicKey],
+            removed: [],
+            foreignKeys: [],
+            weights: [],
+          },
+        },
+        batchId,
+        validators,
+        [newValidator]
+      );
+
+      try {
+        await fixture.bridgeVSU.call({
+          added: [web3.Keypair.generate().publicKey],
+          removed: [],
+          batchId: await fixture.batchIds.freshBatchId(),
+          signers: [validators[0]],
+        });
+        expect.fail("Should have thrown ValidatorUpdatePending");
+      } catch (err: any) {
+        const errorCode = err.error?.errorCode?.code || err.code;
+        expect(errorCode).to.equal("ValidatorUpdatePending");
+      }
+
+      // Clean up through a blocking-minority veto
+      const vs = await fixture.getValidatorSet();
+      const blockingMinority = vs.signers.length - vs.threshold + 1;
+      await fixture.governance.veto({
+        batchId,
+        signers: validators.slice(0, blockingMinority),
+      });
+    });
+
+    it("cancels the update once a blocking minority vetoes it", async () => {
+      const batchId = await fixture.batchIds.freshBatchId();
+      const vsBefore = await fixture.getValidatorSet();
+      const blockingMinority = vsBefore.signers.length - vsBefore.threshold + 1;
+
+      const newValidator = web3.Keypair.generate();
+      await fixture.governance.schedule(
+        {
+          updateValidators: {
+            added: [newValidator.publicKey],
+            removed: [],
+            foreignKeys: [],
+            weights: [],
+          },
+        },
+        batchId,
+        validators,
+        [newValidator]
+      );
+
+      await fixture.governance.veto({
+        batchId,
+        signers: validators.slice(0, blockingMinority - 1),
+      });
+      const partial = await fixture.governance.fetchProposal(batchId);
+      expect(partial.vetoes.length).to.equal(blockingMinority - 1);
+
+      await fixture.governance.veto({
+        batchId,
+        signers: [validators[blockingMinority - 1]],
+      });
+
+      expect(await fixture.governance.fetchProposal(batchId)).to.equal(null);
+      const vsAfter = await fixture.getValidatorSet();
+      expect(vsAfter.pendingUpdate).to.equal(null);
+      expect(vsAfter.signers.length).to.equal(vsBefore.signers.length);
+    });
+
+    it("lets the guardian veto a scheduled update on its own", async () => {
+      const setGuardianBatch = await fixture.batchIds.freshBatchId();
+      await fixture.governance.pass(
+        { setGuardian: { guardian: guardian.publicKey } },
+        setGuardianBatch,
+        validators
+      );
+
+      const vs = await fixture.getValidatorSet();
+      expect(vs.guardian.equals(guardian.publicKey)).to.be.true;
+
+      const batchId = await fixture.batchIds.freshBatchId();
+      const newValidator = web3.Keypair.generate();
+      await fixture.governance.schedule(
+        {
+          updateValidators: {
+            added: [newValidator.publicKey],
+            removed: [],
+            foreignKeys: [],
+            weights: [],
+          },
+        },
+        batchId,
+        validators,
+        [newValidator]
+      );
+
+      await fixture.governance.veto({ batchId, signers: [guardian] });
+
+      expect(await fixture.governance.fetchProposal(batchId)).to.equal(null);
+      expect((await fixture.getValidatorSet()).pendingUpdate).to.equal(null);
+    });
+
+    it("rejects vetoes from keys that are neither guardian nor validator", async () => {
+      const batchId = await fixture.batchIds.freshBatchId();
+      const newValidator = web3.Keypair.generate();
+      await fixture.governance.schedule(
+        {
+          updateValidators: {
+            added: [newValidator.publicKey],
+            removed: [],
+            foreignKeys: [],
+            weights: [],
+          },
+        },
+        batchId,
+        validators,
+        [newValidator]
+      );
+
+      try {
+        await fixture.governance.veto({
+          batchId,
+          signers: [web3.Keypair.generate()],
+        });
+        expect.fail("Should have thrown InvalidSigner");
+      } catch (err: any) {
+        const errorCode = err.error?.errorCode?.code || err.code;
+        expect(errorCode).to.equal("InvalidSigner");
+      }
+
+      await fixture.governance.veto({ batchId, signers: [guardian] });
+    });
+
+    it("rejects a timelock below MIN_TIMELOCK_SLOTS", async () => {
+      const vsBefore = await fixture.getValidatorSet();
+      // The test configuration runs at the minimum
+      const config = {
+        ...TEST_CONFIG,
+        timelockSlots: TEST_CONFIG.timelockSlots.subn(1),
+      };
+
+      try {
+        await fixture.governance.pass(
+          { setConfig: { config } },
+          await fixture.batchIds.freshBatchId(),
+          validators
+        );
+        expect.fail("Should have thrown InvalidConfig");
+      } catch (err: any) {
+        const errorCode = err.error?.errorCode?.code || err.code;
+        expect(errorCode).to.equal("InvalidConfig");
+      }
+
+      const vs = await fixture.getValidatorSet();
+      expect(vs.config.timelockSlots.toString()).to.equal(
+        vsBefore.config.timelockSlots.toString()
+      );
+    });
+  });
+
+  // ============================================================================
+  // CHURN LIMIT TESTS
+  // ============================================================================
+
+  describe("Validator Churn Limit", () => {
+    const MAX_CHURN_BPS = 3_333;
+
+    const setConfig = async (config: any) =>
+      fixture.governance.pass(
+        { setConfig: { config } },
+        await fixture.batchIds.freshBatchId(),
+        validators
+      );
+
+    const allowedChurn = (setSize: number) =>
+      Math.max(
+        1,
+        Math.floor((setSize * MAX_CHURN_BPS) / LIMITS.BPS_DENOMINATOR)
+      );
+
+    after(async () => {
+      await setConfig(TEST_CONFIG);
+    });
+
+    it("rejects updates above the per-window churn budget", async () => {
+      await setConfig({ ...TEST_CONFIG, maxChurnBps: MAX_CHURN_BPS });
+      const vs = await fixture.getValidatorSet();
+      // Earlier suites may already have used this window; one more than the full budget never fits
+      const tooMany = Array.from(
+        { length: allowedChurn(vs.signers.length) + 1 },
+        () => web3.Keypair.generate().publicKey
+      );
+
+      try {
+        await fixture.bridgeVSU.call({
+          added: tooMany,
+          removed: [],
+          batchId: await fixture.batchIds.freshBatchId(),
+          signers: [validators[0]],
+        });
+        expect.fail("Should have thrown ChurnLimitExceeded");
+      } catch (err: any) {
+        const errorCode = err.error?.errorCode?.code || err.code;
+        expect(errorCode).to.equal("ChurnLimitExceeded");
+      }
+    });
+
+    it("accumulates churn across updates within one window", async () => {
+      // A one-slot window guarantees the next update opens a fresh window
+      await setConfig({
+        ...TEST_CONFIG,
+        maxChurnBps: MAX_CHURN_BPS,
+        churnWindowSlots: new BN(1),
+      });
+      const vs = await fixture.getValidatorSet();
+      const budget = allowedChurn(vs.signers.length);
+      const newValidators = Array.from({ length: budget }, () =>
+        web3.Keypair.generate()
+      );
+
+      await fixture.governance.pass(
+        {
+          updateValidators: {
+            added: newValidators.map((v) => v.publicKey),
+            removed: [],
+            foreignKeys: [],
+            weights: [],
+          },
+        },
+        await fixture.batchIds.freshBatchId(),
+        validators,
+        newValidators
+      );
+
+      const afterUpdate = await fixture.getValidatorSet();
+      expect(afterUpdate.churnInWindow).to.equal(budget);
+      expect(afterUpdate.churnWindowBase).to.equal(vs.signers.length);
+
+      // Stretch the window that was just opened: its budget is now used up
+      await setConfig({ ...TEST_CONFIG, maxChurnBps: MAX_CHURN_BPS });
+
+      try {
+        await fixture.bridgeVSU.call({
+          added: [web3.Keypair.generate().publicKey],
+          removed: [],
+          batchId: await fixture.batchIds.freshBatchId(),
+          signers: [validators[0]],
+        });
+        expect.fail("Should have thrown ChurnLimitExceeded");
+      } catch (err: any) {
+        const errorCode = err.error?.errorCode?.code || err.code;
+        expect(errorCode).to.equal("ChurnLimitExceeded");
+      }
+    });
+  });
+
+  // ============================================================================
+  // KEY ROTATION TESTS
+  // ============================================================================
+
+  describe("Validator Key Rotation", () => {
+    const ROTATION_TIMELOCK_SLOTS = 10;
+    const guardian = web3.Keypair.generate();
+
+    // The highest-index test validator still in the set, so quorum helpers keep working
+    const rotatableValidator = async (): Promise<web3.Keypair> => {
+      const vs = await fixture.getValidatorSet();
+      return validators
+        .slice(0, 7)
+        .filter((v) => vs.signers.some((s) => s.equals(v.publicKey)))
+        .pop();
+    };
+
+    it("replaces the key in place and keeps the threshold", async () => {
+      const oldKey = await rotatableValidator();
+      const newKey = web3.Keypair.generate();
+      const vsBefore = await fixture.getValidatorSet();
+      const index = vsBefore.signers.findIndex((s) => s.equals(oldKey.publicKey));
+
+      await fixture.keyRotation.rotate(oldKey, newKey);
+
+      const vsAfter = await fixture.getValidatorSet();
+      expect(vsAfter.signers[index].equals(newKey.publicKey)).to.be.true;
+      expect(vsAfter.signers.length).to.equal(vsBefore.signers.length);
+      expect(vsAfter.threshold).to.equal(vsBefore.threshold);
+      expect(await fixture.keyRotation.fetch(oldKey.publicKey)).to.equal(null);
+
+      // Rotate back so later suites can keep signing with the test validators
+      await fixture.keyRotation.rotate(newKey, oldKey);
+      const restored = await fixture.getValidatorSet();
+      expect(restored.signers[index].equals(oldKey.publicKey)).to.be.true;
+    });
+
+    it("moves the registry record and bond to the new key", async () => {
+      const oldKey = await rotatableValidator();
+      const newKey = web3.Keypair.generate();
+      const bond = web3.LAMPORTS_PER_SOL;
+      await airdrop(provider.connection, oldKey.publicKey);
+      await fixture.validatorInfo.update(
+        oldKey,
+        validatorMetadata("rotated", "https://rotated.example.com", {
+          cardano: Array(32).fill(1),
+          evm: [2, ...Array(32).fill(3)],
+        })
+      );
+      await fixture.bonds.bond(oldKey, bond);
+
+      await fixture.keyRotation.rotate(oldKey, newKey);
+
+      const info = await fixture.validatorInfo.fetch(newKey.publicKey);
+      expect(info.validator.equals(newKey.publicKey)).to.be.true;
+      expect(info.metadata.name).to.equal("rotated");
+      const moved = await fixture.bonds.fetch(newKey.publicKey);
+      expect(moved.validator.equals(newKey.publicKey)).to.be.true;
+      expect(moved.amount.toNumber()).to.equal(bond);
+      expect(await fixture.validatorInfo.fetch(oldKey.publicKey)).to.equal(
+        null
+      );
+      expect(await fixture.bonds.fetch(oldKey.publicKey)).to.equal(null);
+
+      // Rotating back moves the records back
+      await fixture.keyRotation.rotate(newKey, oldKey);
+      const back = await fixture.bonds.fetch(oldKey.publicKey);
+      expect(back.amount.toNumber()).to.equal(bond);
+      expect(await fixture.bonds.fetch(newKey.publicKey)).to.equal(null);
+    });
+
+    it("rejects a rotation to a key that is already a validator", async () => {
+      const oldKey = await rotatableValidator();
+
+      try {
+        await fixture.keyRotation.rotate(oldKey, validators[0]);
+        expect.fail("Should have thrown AddingExistingSigner");
+      } catch (err: any) {
+        const errorCode = err.error?.errorCode?.code || err.code;
+        expect(errorCode).to.equal("AddingExistingSigner");
+      }
+    });
+
+    it("rejects a rotation of a key that is not a validator", async () => {
+      try {
+        await fixture.keyRotation.rotate(
+          web3.Keypair.generate(),
+          web3.Keypair.generate()
+        );
+        expect.fail("Should have thrown InvalidSigner");
+      } catch (err: any) {
+        const errorCode = err.error?.errorCode?.code || err.code;
+        expect(errorCode).to.equal("InvalidSigner");
+      }
+    });
+
+    it("stops counting approvals recorded under the old key", async () => {
+      const oldKey = await rotatableValidator();
+      const newKey = web3.Keypair.generate();
+      const vs = await fixture.getValidatorSet();
+      const others = validators
+        .slice(0, 7)
+        .filter(
+          (v) =>
+            !v.publicKey.equals(oldKey.publicKey) &&
+            vs.signers.some((s) => s.equals(v.publicKey))
+        )
+        .slice(0, vs.threshold - 1);
+      const batchId = await fixture.batchIds.freshBatchId();
+      const action = { setConfig: { config: TEST_CONFIG } };
+
+      await fixture.governance.submit({ action, batchId, signers: [oldKey] });
+      await fixture.keyRotation.rotate(oldKey, newKey);
+
+      // threshold - 1 current validators plus a stale approval is not a quorum
+      await fixture.governance.submit({ action, batchId, signers: others });
+      const proposal = await fixture.governance.fetchProposal(batchId);
+      expect(proposal.signers.length).to.equal(vs.threshold);
+      expect(proposal.status).to.have.property("voting");
+
+      // The rotated seat approves again under its new key
+      await fixture.governance.submit({ action, batchId, signers: [newKey] });
+      expect(await fixture.governance.fetchProposal(batchId)).to.equal(null);
+
+      await fixture.keyRotation.rotate(newKey, oldKey);
+    });
+
+    describe("with a rotation timelock", () => {
+      before(async () => {
+        await fixture.governance.pass(
+          { setGuardian: { guardian: guardian.publicKey } },
+          await fixture.batchIds.freshBatchId(),
+          validators
+        );
+        await fixture.governance.pass(
+          {
+            setConfig: {
+              config: {
+                ...TEST_CONFIG,
+                rotationTimelockSlots: new BN(ROTATION_TIMELOCK_SLOTS),
+              },
+            },
+          },
+          await fixture.batchIds.freshBatchId(),
+          validators
+        );
+      });
+
+      after(async () => {
+        await fixture.governance.pass(
+          { setConfig: { config: TEST_CONFIG } },
+          await fixture.batchIds.freshBatchId(),
+          validators
+        );
+      });
+
+      it("activates the rotation only after the timelock", async () => {
+        const oldKey = await rotatableValidator();
+        const newKey = web3.Keypair.generate();
+
+        await fixture.keyRotation.rotate(oldKey, newKey);
+
+        const rotation = await fixture.keyRotation.fetch(oldKey.publicKey);
+        expect(rotation.newKey.equals(newKey.publicKey)).to.be.true;
+        expect(
+          (await fixture.getValidatorSet()).signers.some((s) =>
+            s.equals(oldKey.publicKey)
+          )
+        ).to.be.true;
+
+        try {
+          await fixture.keyRotation.execute(oldKey.publicKey);
+          expect.fail("Should have thrown TimelockNotExpired");
+        } catch (err: any) {
+          const errorCode = err.error?.errorCode?.code || err.code;
+          expect(errorCode).to.equal("TimelockNotExpired");
+        }
+
+        await warpToSlot(connection, rotation.activationSlot.toNumber());
+        await fixture.keyRotation.execute(oldKey.publicKey);
+
+        const vs = await fixture.getValidatorSet();
+        expect(vs.signers.some((s) => s.equals(newKey.publicKey))).to.be.true;
+        expect(vs.signers.some((s) => s.equals(oldKey.publicKey))).to.be.false;
+
+        // Rotate back through the same timelock
+        await fixture.keyRotation.rotate(newKey, oldKey);
+        const back = await fixture.keyRotation.fetch(newKey.publicKey);
+        await warpToSlot(connection, back.activationSlot.toNumber());
+        await fixture.keyRotation.execute(newKey.publicKey);
+      });
+
+      it("lets the guardian veto a scheduled rotation", async () => {
+        const oldKey = await rotatableValidator();
+        const newKey = web3.Keypair.generate();
+
+        await fixture.keyRotation.rotate(oldKey, newKey);
+
+        try {
+          await fixture.keyRotation.veto(oldKey.publicKey, validators[0]);
+          expect.fail("Should have thrown InvalidSigner");
+        } catch (err: any) {
+          const errorCode = err.error?.errorCode?.code || err.code;
+          expect(errorCode).to.equal("InvalidSigner");
+        }
+
+        await fixture.keyRotation.veto(oldKey.publicKey, guardian);
+
+        expect(await fixture.keyRotation.fetch(oldKey.publicKey)).to.equal(null);
+        const vs = await fixture.getValidatorSet();
+        expect(vs.signers.some((s) => s.equals(oldKey.publicKey))).to.be.true;
+      });
+    });
+  });
+
+  // ============================================================================
+  // VALIDATOR REGISTRY TESTS
+  // ============================================================================
+
+  describe("Validator Registry", () => {
+    const foreignKeys = {
+      cardano: Array(32).fill(7),
+      evm: [2, ...Array(32).fill(9)],
+    };
+
+    it("lets a validator set and update its own record", async () => {
+      const validator = validators[1];
+
+      await fixture.validatorInfo.update(
+        validator,
+        validatorMetadata("validator-1", "https://v1.example.com", foreignKeys)
+      );
+
+      let info = await fixture.validatorInfo.fetch(validator.publicKey);
+      expect(info.validator.equals(validator.publicKey)).to.be.true;
+      expect(info.metadata.name).to.equal("validator-1");
+      expect(info.metadata.endpoint).to.equal("https://v1.example.com");
+      expect(info.metadata.foreignKeys.cardano).to.deep.equal(
+        foreignKeys.cardano
+      );
+      expect(info.metadata.foreignKeys.evm).to.deep.equal(foreignKeys.evm);
+
+      await fixture.validatorInfo.update(
+        validator,
+        validatorMetadata("validator-one", "https://v1.example.com", foreignKeys)
+      );
+
+      info = await fixture.validatorInfo.fetch(validator.publicKey);
+      expect(info.metadata.name).to.equal("validator-one");
+    });
+
+    it("rejects a record update from a key outside the validator set", async () => {
+      try {
+        await fixture.validatorInfo.update(
+          web3.Keypair.generate(),
+          validatorMetadata("outsider", "")
+        );
+        expect.fail("Should have thrown InvalidSigner");
+      } catch (err: any) {
+        const errorCode = err.error?.errorCode?.code || err.code;
+        expect(errorCode).to.equal("InvalidSigner");
+      }
+    });
+
+    it("rejects a name longer than MAX_VALIDATOR_NAME_LEN", async () => {
+      try {
+        await fixture.validatorInfo.update(
+          validators[1],
+          validatorMetadata("x".repeat(LIMITS.MAX_VALIDATOR_NAME_LEN + 1), "")
+        );
+        expect.fail("Should have thrown InvalidValidatorMetadata");
+      } catch (err: any) {
+        const errorCode = err.error?.errorCode?.code || err.code;
+        expect(errorCode).to.equal("InvalidValidatorMetadata");
+      }
+    });
+
+    it("sets a record through a SetValidatorInfo proposal", async () => {
+      const validator = validators[2].publicKey;
+      const vs = await fixture.getValidatorSet();
+
+      await fixture.governance.submit({
+        action: {
+          setValidatorInfo: {
+            validator,
+            metadata: validatorMetadata("validator-2", "https://v2.example.com"),
+          },
+        },
+        batchId: await fixture.batchIds.freshBatchId(),
+        signers: validators.slice(0, vs.threshold),
+        actionAccounts: [fixture.pdas.validatorInfo(validator)],
+      });
+
+      const info = await fixture.validatorInfo.fetch(validator);
+      expect(info.metadata.name).to.equal("validator-2");
+      expect(info.metadata.endpoint).to.equal("https://v2.example.com");
+    });
+
+    it("records the foreign keys carried by a validator set update", async () => {
+      const batchId = await fixture.batchIds.freshBatchId();
+      const vs = await fixture.getValidatorSet();
+      const newValidator = web3.Keypair.generate();
+      const params = {
+        added: [newValidator.publicKey],
+        removed: [] as web3.PublicKey[],
+        batchId,
+        foreignKeys: [validatorMetadata("", "", foreignKeys).foreignKeys],
+      };
+
+      await fixture.bridgeVSU.call({
+        ...params,
+        signers: [validators[0], newValidator],
+      });
+      await fixture.bridgeVSU.call({
+        ...params,
+        signers: validators.slice(1, vs.threshold),
+        actionAccounts: [fixture.pdas.validatorInfo(newValidator.publicKey)],
+      });
 
-        it("rejects with MinValidatorsNotMet when removing too many validators", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
-          const vs = await fixture.getValidatorSet();
-          const currentCount = vs.signers.length;
-          const toRemove = currentCount - (LIMITS.MIN_VALIDATORS - 1);
+      const updatedVs = await fixture.getValidatorSet();
+      expect(updatedVs.signers.some((s) => s.equals(newValidator.publicKey))).to
+        .be.true;
 
-          // Get actual pubkeys to remove
-          const removePubkeys = vs.signers.slice(0, toRemove);
+      const info = await fixture.validatorInfo.fetch(newValidator.publicKey);
+      expect(info.metadata.name).to.equal("");
+      expect(info.metadata.foreignKeys.cardano).to.deep.equal(
+        foreignKeys.cardano
+      );
+      expect(info.metadata.foreignKeys.evm).to.deep.equal(foreignKeys.evm);
+    });
 
-          try {
-            await fixture.bridgeVSU.call({
-              added: [],
-              removed: removePubkeys,
-              batchId,
-              signers: [validators[0]],
-            });
-            expect.fail("Should have thrown MinValidatorsNotMet");
-          } catch (err: any) {
-            const errorCode = err.error?.errorCode?.code || err.code;
-            expect(errorCode).to.equal("MinValidatorsNotMet");
-          }
+    it("rejects foreign keys that don't match the added validators", async () => {
+      try {
+        await fixture.bridgeVSU.call({
+          added: [
+            web3.Keypair.generate().publicKey,
+            web3.Keypair.generate().publicKey,
+          ],
+          removed: [],
+          batchId: await fixture.batchIds.freshBatchId(),
+          signers: [validators[0]],
+          foreignKeys: [validatorMetadata("", "", foreignKeys).foreignKeys],
         });
+        expect.fail("Should have thrown ForeignKeysMismatch");
+      } catch (err: any) {
+        const errorCode = err.error?.errorCode?.code || err.code;
+        expect(errorCode).to.equal("ForeignKeysMismatch");
+      }
+    });
+  });
 
-        it("rejects with RemovingNonExistentSigner when pubkey not in validator set", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
-          const nonExistentValidator = web3.Keypair.generate().publicKey;
+  describe("Validator Suspension", () => {
+    // The highest-index test validator in the set, so quorum helpers keep working
+    const suspendableValidator = async (): Promise<web3.Keypair> => {
+      const vs = await fixture.getValidatorSet();
+      return validators
+        .filter((v) => vs.signers.some((s) => s.equals(v.publicKey)))
+        .pop();
+    };
 
-          try {
-            await fixture.bridgeVSU.call({
-              added: [],
-              removed: [nonExistentValidator],
-              batchId,
-              signers: [validators[0]],
-            });
-            expect.fail("Should have thrown RemovingNonExistentSigner");
-          } catch (err: any) {
-            const errorCode = err.error?.errorCode?.code || err.code;
-            expect(errorCode).to.equal("RemovingNonExistentSigner");
-          }
-        });
+    const expectError = async (call: Promise<any>, code: string) => {
+      try {
+        await call;
+        expect.fail(`Should have thrown ${code}`);
+      } catch (err: any) {
+        const errorCode = err.error?.errorCode?.code || err.code;
+        expect(errorCode).to.equal(code);
+      }
+    };
 
-        it("rejects with NoSignersProvided when no validators sign", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
+    it("lets a validator suspend and reinstate itself", async () => {
+      const validator = await suspendableValidator();
+      const vsBefore = await fixture.getValidatorSet();
 
-          try {
-            await fixture.bridgeVSU.call({
-              added: [newValidators[0].publicKey],
-              removed: [],
-              batchId,
-              signers: [],
-            });
-            expect.fail("Should have thrown NoSignersProvided");
-          } catch (err: any) {
-            const errorCode = err.error?.errorCode?.code || err.code;
-            expect(errorCode).to.equal("NoSignersProvided");
-          }
-        });
+      await fixture.suspension.set(validator, true);
 
-        it("rejects with InvalidSigner when non-validator signs", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
-          const nonValidator = web3.Keypair.generate();
+      let vs = await fixture.getValidatorSet();
+      expect(vs.signers.length).to.equal(vsBefore.signers.length);
+      expect(vs.suspended.length).to.equal(1);
+      expect(vs.suspended[0].validator.equals(validator.publicKey)).to.be.true;
+      expect(vs.suspended[0].byConsensus).to.be.false;
+      expect(vs.threshold).to.equal(
+        calculateExpectedThreshold(vs.signers.length - 1)
+      );
 
-          await airdrop(provider.connection, nonValidator.publicKey);
+      await fixture.suspension.set(validator, false);
 
-          try {
-            await fixture.bridgeVSU.call({
-              added: [newValidators[0].publicKey],
-              removed: [],
-              batchId,
-              payer: nonValidator,
-              signers: [nonValidator],
-            });
-            expect.fail("Should have thrown InvalidSigner");
-          } catch (err: any) {
-            const errorCode = err.error?.errorCode?.code || err.code;
-            expect(errorCode).to.equal("InvalidSigner");
-          }
-        });
+      vs = await fixture.getValidatorSet();
+      expect(vs.suspended.length).to.equal(0);
+      expect(vs.threshold).to.equal(vsBefore.threshold);
+    });
 
-        it("rejects with DuplicateSignersProvided in same transaction", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
+    it("rejects approvals from a suspended validator", async () => {
+      const validator = await suspendableValidator();
+      await fixture.suspension.set(validator, true);
+
+      await expectError(
+        fixture.governance.submit({
+          action: { setGuardian: { guardian: web3.PublicKey.default } },
+          batchId: await fixture.batchIds.freshBatchId(),
+          signers: [validator],
+        }),
+        "ValidatorSuspended"
+      );
 
-          try {
-            await fixture.bridgeVSU.call({
-              added: [newValidators[0].publicKey],
+      await fixture.suspension.set(validator, false);
+    });
+
+    it("rejects reinstating a validator that is not suspended", async () => {
+      await expectError(
+        fixture.suspension.set(await suspendableValidator(), false),
+        "ValidatorNotSuspended"
+      );
+    });
+
+    it("jails a validator through governance until governance reinstates it", async () => {
+      const validator = await suspendableValidator();
+      const vsBefore = await fixture.getValidatorSet();
+
+      await fixture.governance.submit({
+        action: {
+          setValidatorSuspension: {
+            validator: validator.publicKey,
+            suspended: true,
+          },
+        },
+        batchId: await fixture.batchIds.freshBatchId(),
+        signers: validators.slice(0, vsBefore.threshold),
+      });
+
+      let vs = await fixture.getValidatorSet();
+      expect(vs.suspended[0].validator.equals(validator.publicKey)).to.be.true;
+      expect(vs.suspended[0].byConsensus).to.be.true;
+
+      await expectError(
+        fixture.suspension.set(validator, false),
+        "ValidatorSuspended"
+      );
+
+      await fixture.governance.submit({
+        action: {
+          setValidatorSuspension: {
+            validator: validator.publicKey,
+            suspended: false,
+          },
+        },
+        batchId: await fixture.batchIds.freshBatchId(),
+        signers: validators.slice(0, vs.threshold),
+      });
+
+      vs = await fixture.getValidatorSet();
+      expect(vs.suspended.length).to.equal(0);
+      expect(vs.threshold).to.equal(vsBefore.threshold);
+    });
+  });
+
+  describe("Validator Statistics", () => {
+    it("records a heartbeat", async () => {
+      const validator = validators[0];
+
+      await fixture.stats.heartbeat(validator);
+
+      const entry = await fixture.stats.fetch(validator.publicKey);
+      const slot = await provider.connection.getSlot();
+      expect(entry.lastHeartbeatSlot.toNumber()).to.be.greaterThan(0);
+      expect(entry.lastHeartbeatSlot.toNumber()).to.be.at.most(slot);
+    });
+
+    it("rejects a heartbeat from a key outside the validator set", async () => {
+      try {
+        await fixture.stats.heartbeat(web3.Keypair.generate());
+        expect.fail("Should have thrown InvalidSigner");
+      } catch (err: any) {
+        const errorCode = err.error?.errorCode?.code || err.code;
+        expect(errorCode).to.equal("InvalidSigner");
+      }
+    });
+
+    it("counts approvals and charges missed batches to validators that didn't approve", async () => {
+      const vs = await fixture.getValidatorSet();
+      const approvers = validators.slice(0, vs.threshold);
+      const absent = vs.signers.find(
+        (s) => !approvers.some((a) => a.publicKey.equals(s))
+      );
+
+      const approverBefore = await fixture.stats.fetch(approvers[0].publicKey);
+      const absentBefore = await fixture.stats.fetch(absent);
+
+      await fixture.governance.submit({
+        action: { setGuardian: { guardian: web3.PublicKey.default } },
+        batchId: await fixture.batchIds.freshBatchId(),
+        signers: approvers,
+      });
+
+      const approverAfter = await fixture.stats.fetch(approvers[0].publicKey);
+      const absentAfter = await fixture.stats.fetch(absent);
+
+      expect(approverAfter.approvals.toNumber()).to.equal(
+        (approverBefore?.approvals.toNumber() ?? 0) + 1
+      );
+      expect(approverAfter.lastApprovalSlot.toNumber()).to.be.greaterThan(0);
+      expect(approverAfter.missedBatches.toNumber()).to.equal(
+        approverBefore?.missedBatches.toNumber() ?? 0
+      );
+      expect(absentAfter.missedBatches.toNumber()).to.equal(
+        (absentBefore?.missedBatches.toNumber() ?? 0) + 1
+      );
+    });
+  });
+
+  describe("Validator Bonds and Slashing", () => {
+    const BOND = 5 * web3.LAMPORTS_PER_SOL;
+    const firstHash = Array(32).fill(1);
+    const secondHash = Array(32).fill(2);
+
+    const expectError = async (call: Promise<any>, code: string) => {
+      try {
+        await call;
+        expect.fail(`Should have thrown ${code}`);
+      } catch (err: any) {
+        const errorCode = err.error?.errorCode?.code || err.code;
+        expect(errorCode).to.equal(code);
+      }
+    };
+
+    const fundedKeypair = async (): Promise<web3.Keypair> => {
+      const keypair = web3.Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        keypair.publicKey,
+        10 * web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+      return keypair;
+    };
+
+    const attestations = (validator: web3.Keypair, batchId: number) => [
+      approvalAttestation(fixture.pdas.validatorSet(), batchId, firstHash, validator),
+      approvalAttestation(fixture.pdas.validatorSet(), batchId, secondHash, validator),
+    ];
+
+    it("bonds, unbonds and withdraws lamports", async () => {
+      const validator = await fundedKeypair();
+
+      await fixture.bonds.bond(validator, BOND);
+      let bond = await fixture.bonds.fetch(validator.publicKey);
+      expect(bond.validator.equals(validator.publicKey)).to.be.true;
+      expect(bond.amount.toNumber()).to.equal(BOND);
+
+      await fixture.bonds.unbond(validator, BOND / 5);
+      bond = await fixture.bonds.fetch(validator.publicKey);
+      expect(bond.amount.toNumber()).to.equal(BOND - BOND / 5);
+      expect(bond.unbondingAmount.toNumber()).to.equal(BOND / 5);
+
+      const balanceBefore = await provider.connection.getBalance(
+        validator.publicKey
+      );
+      await fixture.bonds.withdraw(validator);
+      bond = await fixture.bonds.fetch(validator.publicKey);
+      expect(bond.unbondingAmount.toNumber()).to.equal(0);
+      expect(
+        (await provider.connection.getBalance(validator.publicKey)) -
+          balanceBefore
+      ).to.be.greaterThan(BOND / 5 - 10_000);
+    });
+
+    it("rejects unbonding more than is bonded", async () => {
+      const validator = await fundedKeypair();
+      await fixture.bonds.bond(validator, BOND);
+
+      await expectError(
+        fixture.bonds.unbond(validator, BOND + 1),
+        "InsufficientBond"
+      );
+    });
+
+    it("slashes the bond of a key that signed conflicting approvals", async () => {
+      const validator = await fundedKeypair();
+      const batchId = 7;
+      await fixture.bonds.bond(validator, BOND);
+
+      const treasuryBefore = await provider.connection.getBalance(
+        fixture.pdas.treasury()
+      );
+
+      await fixture.bonds.reportEquivocation({
+        validator: validator.publicKey,
+        batchId,
+        firstHash,
+        secondHash,
+        attestations: attestations(validator, batchId),
+      });
+
+      const bond = await fixture.bonds.fetch(validator.publicKey);
+      expect(bond.amount.toNumber()).to.equal(0);
+      expect(bond.unbondingAmount.toNumber()).to.equal(0);
+
+      const reporterShare = (BOND * TEST_CONFIG.slashReporterBps) / 10_000;
+      expect(
+        (await provider.connection.getBalance(fixture.pdas.treasury())) -
+          treasuryBefore
+      ).to.equal(BOND - reporterShare);
+
+      // Nothing is left to slash and the key is not in the validator set
+      await expectError(
+        fixture.bonds.reportEquivocation({
+          validator: validator.publicKey,
+          batchId,
+          firstHash,
+          secondHash,
+          attestations: attestations(validator, batchId),
+        }),
+        "NothingToSlash"
+      );
+    });
+
+    it("withdraws from the treasury through governance", async () => {
+      const vs = await fixture.getValidatorSet();
+      const recipient = web3.Keypair.generate().publicKey;
+
+      await fixture.governance.submit({
+        action: {
+          withdrawTreasury: {
+            recipient,
+            amount: new BN(web3.LAMPORTS_PER_SOL),
+          },
+        },
+        batchId: await fixture.batchIds.freshBatchId(),
+        signers: validators.slice(0, vs.threshold),
+        actionAccounts: [fixture.pdas.treasury(), recipient],
+      });
+
+      expect(await provider.connection.getBalance(recipient)).to.equal(
+        web3.LAMPORTS_PER_SOL
+      );
+    });
+
+    it("rejects a report without two conflicting signed approvals", async () => {
+      const validator = await fundedKeypair();
+      const batchId = 8;
+      await fixture.bonds.bond(validator, BOND);
+
+      await expectError(
+        fixture.bonds.reportEquivocation({
+          validator: validator.publicKey,
+          batchId,
+          firstHash,
+          secondHash: firstHash,
+          attestations: attestations(validator, batchId),
+        }),
+        "InvalidEquivocationProof"
+      );
+
+      await expectError(
+        fixture.bonds.reportEquivocation({
+          validator: validator.publicKey,
+          batchId,
+          firstHash,
+          secondHash,
+          attestations: attestations(validator, batchId).slice(0, 1),
+        }),
+        "InvalidEquivocationProof"
+      );
+    });
+
+    it("queues an equivocating validator of the set for removal", async () => {
+      const vsBefore = await fixture.getValidatorSet();
+      const validator = validators
+        .filter((v) => vsBefore.signers.some((s) => s.equals(v.publicKey)))
+        .pop();
+      const batchId = await fixture.batchIds.freshBatchId();
+
+      // A recorded approval is one half of the proof
+      const action = {
+        withdrawTreasury: {
+          recipient: web3.Keypair.generate().publicKey,
+          amount: new BN(1),
+        },
+      };
+      await fixture.governance.submit({
+        action,
+        batchId,
+        signers: [validator],
+      });
+      const recordedHash = Array.from(proposalHash(program, action));
+
+      await fixture.bonds.reportEquivocation({
+        validator: validator.publicKey,
+        batchId,
+        firstHash: recordedHash,
+        secondHash,
+        attestations: [
+          approvalAttestation(
+            fixture.pdas.validatorSet(),
+            batchId,
+            recordedHash,
+            validator
+          ),
+          approvalAttestation(
+            fixture.pdas.validatorSet(),
+            batchId,
+            secondHash,
+            validator
+          ),
+        ],
+        bonded: false,
+      });
+
+      let vs = await fixture.getValidatorSet();
+      const suspension = vs.suspended.find((s) =>
+        s.validator.equals(validator.publicKey)
+      );
+      expect(suspension.byConsensus).to.be.true;
+      expect(vs.removalQueue.map((k) => k.toBase58())).to.deep.equal([
+        validator.publicKey.toBase58(),
+      ]);
+      expect(vs.threshold).to.equal(
+        calculateExpectedThreshold(vs.signers.length - 1)
+      );
+
+      // The next validator set update removes the queued validator as well
+      const replacement = validators.find(
+        (v) => !vs.signers.some((s) => s.equals(v.publicKey))
+      );
+      await fixture.governance.pass(
+        {
+          updateValidators: {
+            added: [replacement.publicKey],
+            removed: [],
+            foreignKeys: [],
+            weights: [],
+          },
+        },
+        await fixture.batchIds.freshBatchId(),
+        validators,
+        [replacement]
+      );
+
+      vs = await fixture.getValidatorSet();
+      expect(vs.signers.some((s) => s.equals(validator.publicKey))).to.be
+        .false;
+      expect(vs.signers.some((s) => s.equals(replacement.publicKey))).to.be
+        .true;
+      expect(vs.signers.length).to.equal(vsBefore.signers.length);
+      expect(vs.suspended.length).to.equal(0);
+      expect(vs.removalQueue.length).to.equal(0);
+
+      // Restore the validator set for the remaining tests
+      await fixture.governance.pass(
+        {
+          updateValidators: {
+            added: [validator.publicKey],
+            removed: [replacement.publicKey],
+            foreignKeys: [],
+            weights: [],
+          },
+        },
+        await fixture.batchIds.freshBatchId(),
+        validators,
+        [validator]
+      );
+    });
+  });
+
+  describe("Stake-Weighted Voting", () => {
+    const expectError = async (call: Promise<any>, code: string) => {
+      try {
+        await call;
+        expect.fail(`Should have thrown ${code}`);
+      } catch (err: any) {
+        const errorCode = err.error?.errorCode?.code || err.code;
+        expect(errorCode).to.equal(code);
+      }
+    };
+
+    // Test validators currently in the set, in the order of `validators`
+    const members = async (): Promise<web3.Keypair[]> => {
+      const vs = await fixture.getValidatorSet();
+      return validators.filter((v) =>
+        vs.signers.some((s) => s.equals(v.publicKey))
+      );
+    };
+
+    const weightOf = (vs: any, validator: web3.Keypair): number => {
+      const index = vs.signers.findIndex((s) => s.equals(validator.publicKey));
+      return vs.weights[index].toNumber();
+    };
+
+    it("starts every validator with the default weight", async () => {
+      const vs = await fixture.getValidatorSet();
+
+      expect(vs.weights.length).to.equal(vs.signers.length);
+      expect(vs.weights.every((w) => w.toNumber() === 1)).to.be.true;
+      expect(vs.thresholdWeight.toNumber()).to.equal(vs.threshold);
+    });
+
+    it("sets a validator weight through governance", async () => {
+      const [heavy] = await members();
+      const vsBefore = await fixture.getValidatorSet();
+      const weight = vsBefore.signers.length;
+
+      await fixture.governance.pass(
+        {
+          setValidatorWeight: {
+            validator: heavy.publicKey,
+            weight: new BN(weight),
+          },
+        },
+        await fixture.batchIds.freshBatchId(),
+        validators
+      );
+
+      const vs = await fixture.getValidatorSet();
+      expect(weightOf(vs, heavy)).to.equal(weight);
+      expect(vs.thresholdWeight.toNumber()).to.equal(
+        calculateExpectedThresholdWeight(2 * vsBefore.signers.length - 1)
+      );
+      // The count threshold is informational and unchanged
+      expect(vs.threshold).to.equal(vsBefore.threshold);
+    });
+
+    it("reaches quorum with fewer, heavier approvals", async () => {
+      const [heavy, ...rest] = await members();
+      const vs = await fixture.getValidatorSet();
+      const needed = vs.thresholdWeight.toNumber() - weightOf(vs, heavy);
+      expect(needed + 1).to.be.lessThan(vs.threshold);
+
+      // Restore the default weight, approved by the heavy validator and a few others
+      const batchId = await fixture.batchIds.freshBatchId();
+      const action = {
+        setValidatorWeight: { validator: heavy.publicKey, weight: new BN(1) },
+      };
+
+      await fixture.governance.submit({
+        action,
+        batchId,
+        signers: [heavy, ...rest.slice(0, needed - 1)],
+      });
+      expect(await fixture.governance.fetchProposal(batchId)).to.not.equal(null);
+
+      await fixture.governance.submit({
+        action,
+        batchId,
+        signers: [rest[needed - 1]],
+      });
+      expect(await fixture.governance.fetchProposal(batchId)).to.equal(null);
+
+      const vsAfter = await fixture.getValidatorSet();
+      expect(weightOf(vsAfter, heavy)).to.equal(1);
+      expect(vsAfter.thresholdWeight.toNumber()).to.equal(vsAfter.threshold);
+    });
+
+    it("rejects a zero weight", async () => {
+      const [validator] = await members();
+
+      await expectError(
+        fixture.governance.submit({
+          action: {
+            setValidatorWeight: {
+              validator: validator.publicKey,
+              weight: new BN(0),
+            },
+          },
+          batchId: await fixture.batchIds.freshBatchId(),
+          signers: [validator],
+        }),
+        "InvalidValidatorWeight"
+      );
+    });
+
+    it("rejects weights that do not match the added validators", async () => {
+      const [validator] = await members();
+
+      await expectError(
+        fixture.governance.submit({
+          action: {
+            updateValidators: {
+              added: [web3.Keypair.generate().publicKey],
               removed: [],
-              batchId,
-              signers: [validators[0], validators[0]],
-            });
-            expect.fail("Should have thrown DuplicateSignersProvided");
-          } catch (err: any) {
-            const errorCode = err.error?.errorCode?.code || err.code;
-            expect(errorCode).to.equal("DuplicateSignersProvided");
-          }
-        });
+              foreignKeys: [],
+              weights: [new BN(1), new BN(1)],
+            },
+          },
+          batchId: await fixture.batchIds.freshBatchId(),
+          signers: [validator],
+        }),
+        "ValidatorWeightsMismatch"
+      );
+    });
+  });
 
-        it("rejects with DuplicateValidatorsInRemoved when duplicate pubkeys in removed", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
-          const vs = await fixture.getValidatorSet();
-          const duplicateValidator = vs.signers[0];
+  describe("Chunked Validator Set Updates", () => {
+    const expectError = async (call: Promise<any>, code: string) => {
+      try {
+        await call;
+        expect.fail(`Should have thrown ${code}`);
+      } catch (err: any) {
+        const errorCode = err.error?.errorCode?.code || err.code;
+        expect(errorCode).to.equal(code);
+      }
+    };
 
-          try {
-            await fixture.bridgeVSU.call({
-              added: [],
-              removed: [duplicateValidator, duplicateValidator], // Duplicate!
-              batchId,
-              signers: [validators[0]],
-            });
-            expect.fail("Should have thrown DuplicateValidatorsInRemoved");
-          } catch (err: any) {
-            const errorCode = err.error?.errorCode?.code || err.code;
-            expect(errorCode).to.equal("DuplicateValidatorsInRemoved");
-          }
-        });
+    const newValidators = generateValidators(4);
+    let proposer: web3.Keypair;
 
-        it("rejects with AddingAndRemovingSameSigner when same pubkey in added and removed", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
-          const newValidator = newValidators[0].publicKey;
+    // Approve the sealed delta of `batchId` with exactly `threshold` keys of the set
+    const applyDelta = async (batchId: number) => {
+      const vs = await fixture.getValidatorSet();
+      const delta = await fixture.validatorDeltas.fetch(batchId);
+      const members = [...validators, ...newValidators].filter((v) =>
+        vs.signers.some((s) => s.equals(v.publicKey))
+      );
 
-          try {
-            await fixture.bridgeVSU.call({
-              added: [newValidator],
-              removed: [newValidator], // Same pubkey in both!
-              batchId,
-              signers: [validators[0]],
-            });
-            expect.fail("Should have thrown AddingAndRemovingSameSigner");
-          } catch (err: any) {
-            const errorCode = err.error?.errorCode?.code || err.code;
-            expect(errorCode).to.equal("AddingAndRemovingSameSigner");
-          }
-        });
+      await fixture.governance.submit({
+        action: { applyValidatorDelta: { deltaHash: delta.deltaHash } },
+        batchId,
+        signers: members.slice(0, vs.threshold),
+        actionAccounts: [fixture.pdas.validatorDelta(batchId)],
       });
+    };
 
-      describe("Edge Cases", () => {
-        it("handles adding validator at exactly MAX_VALIDATORS_CHANGE limit", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
-          const vs = await fixture.getValidatorSet();
-          const currentCount = vs.signers.length;
-
-          if (currentCount < LIMITS.MAX_VALIDATORS_CHANGE) {
-            const toAdd = LIMITS.MAX_VALIDATORS_CHANGE - currentCount;
-            const newVals = Array.from(
-              { length: toAdd },
-              () => web3.Keypair.generate().publicKey
-            );
+    before(async () => {
+      const vs = await fixture.getValidatorSet();
+      proposer = validators.find((v) =>
+        vs.signers.some((s) => s.equals(v.publicKey))
+      );
+      await airdrop(provider.connection, proposer.publicKey);
+    });
 
-            await fixture.bridgeVSU.call({
-              added: newVals,
-              removed: [],
-              batchId,
-              signers: [validators[0]],
-            });
+    it("applies an update assembled across chunks with one quorum", async () => {
+      const batchId = await fixture.batchIds.freshBatchId();
+      const vsBefore = await fixture.getValidatorSet();
 
-            const vscAccount = await fixture.bridgeVSU.fetchValidatorSetChange(
-              batchId
-            );
-            expect(vscAccount.added.length).to.equal(toAdd);
-          }
+      for (const chunk of [newValidators.slice(0, 2), newValidators.slice(2)]) {
+        await fixture.validatorDeltas.append({
+          batchId,
+          proposer,
+          added: chunk.map((v) => v.publicKey),
+          admissions: chunk,
         });
+      }
+      const delta = await fixture.validatorDeltas.fetch(batchId);
+      expect(delta.added.length).to.equal(4);
+      expect(delta.sealed).to.be.false;
 
-        it("handles removing down to exactly MIN_VALIDATORS limit", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
-          const vs = await fixture.getValidatorSet();
-          const currentCount = vs.signers.length;
+      await fixture.validatorDeltas.seal(batchId, proposer);
+      expect((await fixture.validatorDeltas.fetch(batchId)).sealed).to.be.true;
 
-          if (currentCount > LIMITS.MIN_VALIDATORS) {
-            const toRemove = currentCount - LIMITS.MIN_VALIDATORS;
-            // Get actual pubkeys to remove (from the end to avoid removing active signers)
-            const removePubkeys = vs.signers.slice(-toRemove);
+      await applyDelta(batchId);
 
-            await fixture.bridgeVSU.call({
-              added: [],
-              removed: removePubkeys,
-              batchId,
-              signers: [validators[0]],
-            });
+      const vs = await fixture.getValidatorSet();
+      expect(vs.signers.length).to.equal(vsBefore.signers.length + 4);
+      expect(vs.lastBatchId.toString()).to.equal(batchId.toString());
+      expect(await fixture.governance.fetchProposal(batchId)).to.equal(null);
 
-            const vscAccount = await fixture.bridgeVSU.fetchValidatorSetChange(
-              batchId
-            );
-            expectPubkeyArrayEqual(vscAccount.removed, removePubkeys);
-          }
-        });
+      await fixture.validatorDeltas.close(batchId, proposer);
+      expect(await fixture.validatorDeltas.fetch(batchId)).to.equal(null);
+    });
 
-        it("handles removing last validator in array", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
-          const vs = await fixture.getValidatorSet();
-          const lastValidator = vs.signers[vs.signers.length - 1];
+    it("removes validators through a delta", async () => {
+      const batchId = await fixture.batchIds.freshBatchId();
 
-          await fixture.bridgeVSU.call({
-            added: [newValidators[0].publicKey],
-            removed: [lastValidator],
-            batchId,
-            signers: [validators[0]],
-          });
+      await fixture.validatorDeltas.append({
+        batchId,
+        proposer,
+        removed: newValidators.map((v) => v.publicKey),
+      });
+      await fixture.validatorDeltas.seal(batchId, proposer);
+      await applyDelta(batchId);
 
-          const vscAccount = await fixture.bridgeVSU.fetchValidatorSetChange(
-            batchId
-          );
-          expect(vscAccount.removed[0].toBase58()).to.equal(
-            lastValidator.toBase58()
-          );
-        });
+      const vs = await fixture.getValidatorSet();
+      expect(
+        newValidators.some((v) => vs.signers.some((s) => s.equals(v.publicKey)))
+      ).to.be.false;
 
-        it("rejects duplicate validators in added array", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
-          const duplicateVal = newValidators[0].publicKey;
+      await fixture.validatorDeltas.close(batchId, proposer);
+    });
 
-          try {
-            await fixture.bridgeVSU.call({
-              added: [duplicateVal, duplicateVal],
-              removed: [],
-              batchId,
-              signers: [validators[0]],
-            });
-            expect.fail("Should have thrown DuplicateValidatorsInAdded");
-          } catch (err: any) {
-            const errorCode = err.error?.errorCode?.code || err.code;
-            expect(errorCode).to.equal("DuplicateValidatorsInAdded");
-          }
-        });
+    it("rejects approvals before the delta is sealed", async () => {
+      const batchId = await fixture.batchIds.freshBatchId();
+      const newValidator = web3.Keypair.generate();
+
+      await fixture.validatorDeltas.append({
+        batchId,
+        proposer,
+        added: [newValidator.publicKey],
+        admissions: [newValidator],
       });
+
+      await expectError(
+        fixture.governance.submit({
+          action: { applyValidatorDelta: { deltaHash: Array(32).fill(0) } },
+          batchId,
+          signers: [proposer],
+          actionAccounts: [fixture.pdas.validatorDelta(batchId)],
+        }),
+        "ValidatorDeltaNotSealed"
+      );
+
+      await fixture.validatorDeltas.close(batchId, proposer);
     });
 
-    describe("Second Submission (Approval)", () => {
-      describe("Happy Path", () => {
-        it("successfully adds approval from another validator", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
+    it("rejects chunks once the delta is sealed", async () => {
+      const batchId = await fixture.batchIds.freshBatchId();
+      const [first, second] = generateValidators(2);
 
-          await fixture.bridgeVSU.call({
-            added: [newValidators[0].publicKey],
-            removed: [],
-            batchId,
-            signers: [validators[0]],
-          });
+      await fixture.validatorDeltas.append({
+        batchId,
+        proposer,
+        added: [first.publicKey],
+        admissions: [first],
+      });
+      await fixture.validatorDeltas.seal(batchId, proposer);
 
-          const vscBefore = await fixture.bridgeVSU.fetchValidatorSetChange(
-            batchId
-          );
-          expect(vscBefore.signers.length).to.equal(1);
+      await expectError(
+        fixture.validatorDeltas.append({
+          batchId,
+          proposer,
+          added: [second.publicKey],
+          admissions: [second],
+        }),
+        "ValidatorDeltaSealed"
+      );
 
-          await fixture.bridgeVSU.call({
-            added: [newValidators[0].publicKey],
-            removed: [],
-            batchId,
-            signers: [validators[1]],
-          });
+      await fixture.validatorDeltas.close(batchId, proposer);
+    });
 
-          const vscAfter = await fixture.bridgeVSU.fetchValidatorSetChange(
-            batchId
-          );
-          expect(vscAfter.signers.length).to.equal(2);
-          expect(vscAfter.signers[1].toBase58()).to.equal(
-            validators[1].publicKey.toBase58()
-          );
-        });
+    it("rejects an added key without an admission proof", async () => {
+      await expectError(
+        fixture.validatorDeltas.append({
+          batchId: await fixture.batchIds.freshBatchId(),
+          proposer,
+          added: [web3.Keypair.generate().publicKey],
+        }),
+        "MissingAdmissionProof"
+      );
+    });
+  });
 
-        it("allows multiple validators to approve in one transaction", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
+  describe("Validator Set Archive", () => {
+    const expectMatchesSet = (snapshot: any, vs: any) => {
+      expect(snapshot.epoch.toString()).to.equal(vs.epoch.toString());
+      expect(snapshot.signers.map((s) => s.toBase58())).to.deep.equal(
+        vs.signers.map((s) => s.toBase58())
+      );
+      expect(snapshot.weights.map((w) => w.toString())).to.deep.equal(
+        vs.weights.map((w) => w.toString())
+      );
+      expect(snapshot.threshold).to.equal(vs.threshold);
+      expect(snapshot.thresholdWeight.toString()).to.equal(
+        vs.thresholdWeight.toString()
+      );
+    };
 
-          await fixture.bridgeVSU.call({
-            added: [newValidators[0].publicKey],
-            removed: [],
-            batchId,
-            signers: [validators[0]],
-          });
+    it("archives the initial validator set as epoch 0", async () => {
+      const snapshot = await fixture.getValidatorSetSnapshot(0);
 
-          await fixture.bridgeVSU.call({
-            added: [newValidators[0].publicKey],
-            removed: [],
-            batchId,
-            signers: [validators[1], validators[2]],
-          });
+      expect(snapshot).to.not.equal(null);
+      expect(snapshot.epoch.toNumber()).to.equal(0);
+      expect(snapshot.instanceId.toString()).to.equal(
+        TEST_DEPLOYMENT.instanceId.toString()
+      );
+      expect(snapshot.threshold).to.equal(
+        calculateExpectedThreshold(snapshot.signers.length)
+      );
+      expect(snapshot.setHash.some((b) => b !== 0)).to.be.true;
+      assertValidBump(snapshot.bump);
+    });
 
-          const vscAfter = await fixture.bridgeVSU.fetchValidatorSetChange(
-            batchId
-          );
-          expect(vscAfter.signers.length).to.equal(3);
-        });
-      });
+    it("archives the current validator set under the current epoch", async () => {
+      const vs = await fixture.getValidatorSet();
+      expect(vs.epoch.toNumber()).to.be.greaterThan(0);
 
-      describe("Error Cases", () => {
-        it("rejects with SignerAlreadyApproved when validator approves twice", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
+      expectMatchesSet(await fixture.getValidatorSetSnapshot(vs.epoch), vs);
+    });
 
-          await fixture.bridgeVSU.call({
-            added: [newValidators[0].publicKey],
-            removed: [],
-            batchId,
-            signers: [validators[0]],
-          });
+    it("archives a new epoch on every validator set change", async () => {
+      const vsBefore = await fixture.getValidatorSet();
+      const previous = await fixture.getValidatorSetSnapshot(vsBefore.epoch);
+      const [validator] = validators.filter((v) =>
+        vsBefore.signers.some((s) => s.equals(v.publicKey))
+      );
+      const batchId = await fixture.batchIds.freshBatchId();
 
-          try {
-            await fixture.bridgeVSU.call({
-              added: [newValidators[0].publicKey],
-              removed: [],
-              batchId,
-              signers: [validators[0]],
-            });
-            expect.fail("Should have thrown SignerAlreadyApproved");
-          } catch (err: any) {
-            const errorCode = err.error?.errorCode?.code || err.code;
-            expect(errorCode).to.equal("SignerAlreadyApproved");
-          }
-        });
+      await fixture.governance.pass(
+        {
+          setValidatorWeight: {
+            validator: validator.publicKey,
+            weight: new BN(1),
+          },
+        },
+        batchId,
+        validators
+      );
 
-        it("rejects with InvalidProposalHash when proposal parameters differ (added)", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
+      const vs = await fixture.getValidatorSet();
+      expect(vs.epoch.toNumber()).to.equal(vsBefore.epoch.toNumber() + 1);
 
-          await fixture.bridgeVSU.call({
-            added: [newValidators[0].publicKey],
-            removed: [],
-            batchId,
-            signers: [validators[0]],
-          });
+      const snapshot = await fixture.getValidatorSetSnapshot(vs.epoch);
+      expectMatchesSet(snapshot, vs);
+      expect(snapshot.activationBatchId.toString()).to.equal(
+        batchId.toString()
+      );
+      // Archived sets are never rewritten
+      const unchanged = await fixture.getValidatorSetSnapshot(vsBefore.epoch);
+      expect(unchanged.setHash).to.deep.equal(previous.setHash);
+    });
 
-          try {
-            await fixture.bridgeVSU.call({
-              added: [newValidators[1].publicKey], // Different!
-              removed: [],
-              batchId,
-              signers: [validators[1]],
-            });
-            expect.fail("Should have thrown InvalidProposalHash");
-          } catch (err: any) {
-            const errorCode = err.error?.errorCode?.code || err.code;
-            expect(errorCode).to.equal("InvalidProposalHash");
-          }
-        });
+    it("archives a new epoch on a key rotation", async () => {
+      const vsBefore = await fixture.getValidatorSet();
+      const oldKey = validators
+        .slice(0, 7)
+        .filter((v) => vsBefore.signers.some((s) => s.equals(v.publicKey)))
+        .pop();
+      const newKey = web3.Keypair.generate();
+
+      await fixture.keyRotation.rotate(oldKey, newKey);
+      const rotated = await fixture.getValidatorSet();
+      expect(rotated.epoch.toNumber()).to.equal(vsBefore.epoch.toNumber() + 1);
+      expectMatchesSet(
+        await fixture.getValidatorSetSnapshot(rotated.epoch),
+        rotated
+      );
 
-        it("rejects with InvalidProposalHash when removed pubkeys differ", async () => {
-          const batchId = await fixture.batchIds.freshBatchId();
-          const vs = await fixture.getValidatorSet();
+      // Rotate back so later suites can keep signing with the test validators
+      await fixture.keyRotation.rotate(newKey, oldKey);
+      const restored = await fixture.getValidatorSet();
+      expect(restored.epoch.toNumber()).to.equal(rotated.epoch.toNumber() + 1);
+    });
+  });
 
-          await fixture.bridgeVSU.call({
-            added: [],
-            removed: [vs.signers[0]],
-            batchId,
-            signers: [validators[0]],
-          });
+  describe("Account Sizing", () => {
+    it("grows a proposal account with every approval", async () => {
+      const vs = await fixture.getValidatorSet();
+      const batchId = await fixture.batchIds.freshBatchId();
+      const action = { setGuardian: { guardian: vs.guardian } };
+      const size = async (): Promise<number> =>
+        (
+          await provider.connection.getAccountInfo(
+            fixture.pdas.governanceProposal(batchId)
+          )
+        ).data.length;
+
+      await fixture.governance.submit({
+        action,
+        batchId,
+        signers: [validators[0]],
+      });
+      const first = await size();
 
-          try {
-            await fixture.bridgeVSU.call({
-              added: [],
-              removed: [vs.signers[1]], // Different validator!
-              batchId,
-              signers: [validators[1]],
-            });
-            expect.fail("Should have thrown InvalidProposalHash");
-          } catch (err: any) {
-            const errorCode = err.error?.errorCode?.code || err.code;
-            expect(errorCode).to.equal("InvalidProposalHash");
-          }
-        });
+      await fixture.governance.submit({
+        action,
+        batchId,
+        signers: [validators[1]],
+      });
+      expect(await size()).to.equal(first + 32);
+
+      // Reach quorum so the batch is consumed
+      await fixture.governance.submit({
+        action,
+        batchId,
+        signers: validators.slice(2, vs.threshold),
       });
+      expect(await fixture.governance.fetchProposal(batchId)).to.equal(null);
     });
 
-    describe("Reaching Quorum (Execution)", () => {
-      it("executes VSU when threshold is met in one transaction", async () => {
-        const batchId = await fixture.batchIds.freshBatchId();
-        const vs = await fixture.getValidatorSet();
-        const threshold = vs.threshold;
+    it("sizes the validator set account for its validators", async () => {
+      const vs = await fixture.getValidatorSet();
+      const info = await provider.connection.getAccountInfo(
+        fixture.pdas.validatorSet()
+      );
+
+      // Discriminator and fixed fields, then signer, weight and suspension room per validator
+      expect(info.data.length).to.be.at.least(143 + vs.signers.length * 73);
+      expect(info.data.length).to.be.below(143 + LIMITS.MAX_VALIDATORS * 73);
+    });
+  });
+
+  // ============================================================================
+  // GUARDIAN COUNCIL TESTS
+  // ============================================================================
+
+  describe("Guardian Council", () => {
+    const guardians = generateValidators(3);
+
+    const expectError = async (call: Promise<any>, code: string) => {
+      try {
+        await call;
+        expect.fail(`Should have thrown ${code}`);
+      } catch (err: any) {
+        const errorCode = err.error?.errorCode?.code || err.code;
+        expect(errorCode).to.equal(code);
+      }
+    };
+
+    const setConfig = async (config: any) =>
+      fixture.governance.pass(
+        { setConfig: { config } },
+        await fixture.batchIds.freshBatchId(),
+        validators
+      );
 
-        const newValidator = web3.Keypair.generate().publicKey;
+    it("is appointed by the validators", async () => {
+      const vs = await fixture.getValidatorSet();
+      await fixture.governance.submit({
+        action: {
+          setGuardianCouncil: {
+            members: guardians.map((g) => g.publicKey),
+            threshold: 2,
+          },
+        },
+        batchId: await fixture.batchIds.freshBatchId(),
+        signers: validators.slice(0, vs.threshold),
+        actionAccounts: [fixture.pdas.guardianCouncil()],
+      });
 
-        // Provide exactly `threshold` unique signers
-        const uniqueSigners = validators.slice(0, threshold);
+      const council = await fixture.guardian.fetchCouncil();
+      expect(council.members.map((m) => m.toBase58())).to.deep.equal(
+        guardians.map((g) => g.publicKey.toBase58())
+      );
+      expect(council.threshold).to.equal(2);
+    });
 
-        // Execute VSU in one transaction
-        await fixture.bridgeVSU.call({
-          added: [newValidator],
-          removed: [],
+    it("rejects a council threshold below a majority", async () => {
+      const vs = await fixture.getValidatorSet();
+      await expectError(
+        fixture.governance.submit({
+          action: {
+            setGuardianCouncil: {
+              members: guardians.map((g) => g.publicKey),
+              threshold: 1,
+            },
+          },
+          batchId: await fixture.batchIds.freshBatchId(),
+          signers: validators.slice(0, vs.threshold),
+          actionAccounts: [fixture.pdas.guardianCouncil()],
+        }),
+        "InvalidGuardianCouncil"
+      );
+    });
+
+    it("pauses and resumes bridging", async () => {
+      const mint = await fixture.mints.create(fixture.pdas.vault(), 9);
+      const setPaused = async (paused: boolean) => {
+        const batchId = await fixture.guardian.nextBatchId();
+        await fixture.guardian.submit({
+          action: { setPaused: { paused } },
           batchId,
-          signers: uniqueSigners,
+          signers: guardians.slice(0, 2),
         });
+        expect(await fixture.guardian.fetchProposal(batchId)).to.equal(null);
+      };
 
-        const updatedVs = await fixture.getValidatorSet();
-
-        // Verify validator was added
-        expect(updatedVs.signers.length).to.equal(vs.signers.length + 1);
-        expect(updatedVs.lastBatchId.toString()).to.equal(batchId.toString());
+      await setPaused(true);
+      expect((await fixture.getValidatorSet()).paused).to.equal(true);
 
-        const lastSigner = updatedVs.signers[updatedVs.signers.length - 1];
-        expect(lastSigner.toBase58()).to.equal(newValidator.toBase58());
-      });
+      await fixture.bridgeTransaction.expectError(
+        {
+          amount: 100,
+          batchId: await fixture.batchIds.freshBatchId(),
+          recipient: web3.Keypair.generate().publicKey,
+          mint,
+          validators: validators.slice(
+            0,
+            (await fixture.getValidatorSet()).threshold
+          ),
+          vaultPDA: fixture.pdas.vault(),
+        },
+        "BridgePaused"
+      );
 
-      it("executes VSU when threshold is met across multiple transactions", async () => {
-        const batchId = await fixture.batchIds.freshBatchId();
-        const vs = await fixture.getValidatorSet();
-        const threshold = vs.threshold;
+      await setPaused(false);
+      expect((await fixture.getValidatorSet()).paused).to.equal(false);
+    });
 
-        const newValidator = web3.Keypair.generate().publicKey;
+    it("needs the council threshold before acting", async () => {
+      const batchId = await fixture.guardian.nextBatchId();
+      await fixture.guardian.submit({
+        action: { setPaused: { paused: true } },
+        batchId,
+        signers: [guardians[0]],
+      });
 
-        const proposalParams = {
-          added: [newValidator],
-          removed: [] as web3.PublicKey[],
-        };
+      expect((await fixture.getValidatorSet()).paused).to.equal(false);
+      const proposal = await fixture.guardian.fetchProposal(batchId);
+      expect(proposal.signers.length).to.equal(1);
 
-        // Step 1: First validator creates the proposal
-        await fixture.bridgeVSU.call({
-          ...proposalParams,
+      await expectError(
+        fixture.guardian.submit({
+          action: { setPaused: { paused: true } },
           batchId,
           signers: [validators[0]],
-        });
+        }),
+        "InvalidSigner"
+      );
 
-        let vscAccount = await fixture.bridgeVSU.fetchValidatorSetChange(
-          batchId
-        );
-        expect(vscAccount.signers.length).to.equal(1);
+      await expectError(
+        fixture.guardian.submit({
+          action: { setPaused: { paused: false } },
+          batchId,
+          signers: [guardians[1]],
+        }),
+        "InvalidProposalHash"
+      );
+      await fixture.guardian.submit({
+        action: { setPaused: { paused: true } },
+        batchId,
+        signers: [guardians[1]],
+      });
+      expect((await fixture.getValidatorSet()).paused).to.equal(true);
 
-        // Step 2: Add approvals one by one until threshold - 1
-        for (let i = 1; i < threshold - 1; i++) {
-          await fixture.bridgeVSU.call({
-            ...proposalParams,
-            batchId,
-            signers: [validators[i]],
-          });
+      // Resume for the remaining tests
+      await fixture.guardian.submit({
+        action: { setPaused: { paused: false } },
+        batchId: await fixture.guardian.nextBatchId(),
+        signers: guardians.slice(1, 3),
+      });
+    });
 
-          vscAccount = await fixture.bridgeVSU.fetchValidatorSetChange(batchId);
-          expect(vscAccount.signers.length).to.equal(i + 1);
-        }
+    it("vetoes a scheduled governance proposal", async () => {
+      const batchId = await fixture.batchIds.freshBatchId();
+      await fixture.governance.schedule(
+        { setGuardian: { guardian: web3.Keypair.generate().publicKey } },
+        batchId,
+        validators
+      );
+      const proposal = await fixture.governance.fetchProposal(batchId);
+      expect(proposal.status).to.have.property("scheduled");
+
+      await fixture.guardian.submit({
+        action: { vetoProposal: { batchId: new BN(batchId) } },
+        batchId: await fixture.guardian.nextBatchId(),
+        signers: guardians.slice(0, 2),
+        actionAccounts: [fixture.pdas.governanceProposal(batchId)],
+      });
+      expect(await fixture.governance.fetchProposal(batchId)).to.equal(null);
+    });
 
-        // Step 3: Final approval that triggers execution
-        await fixture.bridgeVSU.call({
-          ...proposalParams,
-          batchId,
-          signers: [validators[threshold - 1]],
-        });
+    it("rejects recovering a set that still reaches quorum", async () => {
+      await expectError(
+        fixture.guardian.submit({
+          action: {
+            recoverValidatorSet: {
+              signers: validators.slice(0, 4).map((v) => v.publicKey),
+              weights: [],
+            },
+          },
+          batchId: await fixture.guardian.nextBatchId(),
+          signers: [guardians[0], ...validators.slice(0, 4)],
+        }),
+        "ValidatorSetNotStalled"
+      );
+    });
 
-        // Verify validator set was updated
-        const updatedVs = await fixture.getValidatorSet();
-        expect(updatedVs.signers.length).to.equal(vs.signers.length + 1);
-        expect(updatedVs.lastBatchId.toString()).to.equal(batchId.toString());
+    it("rejects recovering an idle set whose validators send heartbeats", async () => {
+      const STALL_SLOTS = 20;
+      await setConfig({
+        ...TEST_CONFIG,
+        recoveryStallSlots: new BN(STALL_SLOTS),
+      });
+      const vs = await fixture.getValidatorSet();
+      await warpToSlot(connection, vs.lastQuorumSlot.toNumber() + STALL_SLOTS);
+      for (const validator of validators.slice(0, vs.threshold)) {
+        await fixture.stats.heartbeat(validator);
+      }
 
-        const newValidatorAdded = updatedVs.signers.some(
-          (signer) => signer.toBase58() === newValidator.toBase58()
-        );
-        expect(newValidatorAdded).to.be.true;
+      await expectError(
+        fixture.guardian.submit({
+          action: {
+            recoverValidatorSet: {
+              signers: validators.slice(0, 4).map((v) => v.publicKey),
+              weights: [],
+            },
+          },
+          batchId: await fixture.guardian.nextBatchId(),
+          signers: [guardians[0], ...validators.slice(0, 4)],
+        }),
+        "ValidatorSetNotStalled"
+      );
+    });
+
+    it("replaces a stalled validator set", async () => {
+      const STALL_SLOTS = 20;
+      await setConfig({
+        ...TEST_CONFIG,
+        recoveryStallSlots: new BN(STALL_SLOTS),
       });
+      const vsBefore = await fixture.getValidatorSet();
+      await warpToSlot(
+        connection,
+        vsBefore.lastQuorumSlot.toNumber() + STALL_SLOTS
+      );
 
-      it("executes removal of single validator correctly", async () => {
-        const batchId = await fixture.batchIds.freshBatchId();
-        const vs = await fixture.getValidatorSet();
-        const threshold = vs.threshold;
+      const recovered = validators.slice(0, 4);
+      const action = {
+        recoverValidatorSet: {
+          signers: recovered.map((v) => v.publicKey),
+          weights: [],
+        },
+      };
+      const batchId = await fixture.guardian.nextBatchId();
+      await fixture.guardian.submit({
+        action,
+        batchId,
+        signers: [guardians[0], ...recovered],
+      });
+      await fixture.guardian.submit({
+        action,
+        batchId,
+        signers: [guardians[1]],
+      });
 
-        // Remove the last validator
-        const validatorToRemove = vs.signers[vs.signers.length - 1];
+      const vs = await fixture.getValidatorSet();
+      expect(vs.signers.map((s) => s.toBase58())).to.deep.equal(
+        recovered.map((v) => v.publicKey.toBase58())
+      );
+      expect(vs.epoch.toNumber()).to.equal(vsBefore.epoch.toNumber() + 1);
+      expect(await fixture.getValidatorSetSnapshot(vs.epoch)).to.not.equal(null);
+      expect(await fixture.guardian.fetchProposal(batchId)).to.equal(null);
+
+      // The recovered set governs from now on
+      await fixture.governance.pass(
+        { setConfig: { config: TEST_CONFIG } },
+        await fixture.batchIds.freshBatchId(),
+        recovered
+      );
+    });
+  });
 
-        const proposalParams = {
-          added: [] as web3.PublicKey[],
-          removed: [validatorToRemove],
-        };
+  // ============================================================================
+  // ACCOUNT VERSIONING TESTS
+  // ============================================================================
 
-        // Step 1: Create proposal
-        await fixture.bridgeVSU.call({
-          ...proposalParams,
-          batchId,
-          signers: [validators[0]],
-        });
+  describe("Account Versioning", () => {
+    // The version 1 accounts are migrated into an instance of their own
+    const legacyInstance = new BN(2);
+    const legacy = new PDAs(program.programId, legacyInstance);
+    // Created alongside a migrated validator set
+    const validatorSetAccounts = [
+      legacy.treasury(),
+      legacy.validatorStats(),
+      legacy.validatorSetSnapshot(0),
+    ];
+
+    it("stores the current layout version in new accounts", async () => {
+      const vs = await fixture.getValidatorSet();
+      const vault = await fixture.accounts.getVault(fixture.pdas.vault());
+
+      expect(vs.version).to.equal(ACCOUNT_VERSION);
+      expect(vault.version).to.equal(ACCOUNT_VERSION);
+    });
 
-        // Step 2: Add approvals until threshold
-        for (let i = 1; i < threshold; i++) {
-          await fixture.bridgeVSU.call({
-            ...proposalParams,
-            batchId,
-            signers: [validators[i]],
-          });
-        }
+    it("rejects migrating an account at the current version", async () => {
+      const before = await provider.connection.getAccountInfo(
+        fixture.pdas.validatorSet()
+      );
 
-        // Verify execution
-        const updatedVs = await fixture.getValidatorSet();
-        expect(updatedVs.signers.length).to.equal(vs.signers.length - 1);
-        expect(updatedVs.lastBatchId.toString()).to.equal(batchId.toString());
+      try {
+        await fixture.migrations.migrate(
+          fixture.pdas.validatorSet(),
+          legacy.validatorSet()
+        );
+        expect.fail("Should have thrown AccountNotMigratable");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("AccountNotMigratable");
+      }
 
-        // Verify the validator was actually removed
-        const removedValidatorStillExists = updatedVs.signers.some(
-          (signer) => signer.toBase58() === validatorToRemove.toBase58()
+      const after = await provider.connection.getAccountInfo(
+        fixture.pdas.validatorSet()
+      );
+      expect(after.data.equals(before.data)).to.equal(true);
+    });
+
+    it("rejects migrating an account of another program", async () => {
+      try {
+        await fixture.migrations.migrate(
+          provider.wallet.publicKey,
+          legacy.validatorSet()
         );
-        expect(removedValidatorStillExists).to.be.false;
-      });
+        expect.fail("Should have thrown AccountNotMigratable");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("AccountNotMigratable");
+      }
+    });
 
-      it("executes both additions and removals correctly", async () => {
-        const batchId = await fixture.batchIds.freshBatchId();
-        const vs = await fixture.getValidatorSet();
-        const threshold = vs.threshold;
+    it("rejects migrating to another address", async () => {
+      try {
+        await fixture.migrations.migrate(
+          LEGACY_ACCOUNTS.validatorSet,
+          legacy.vault(),
+          validatorSetAccounts,
+          legacyInstance
+        );
+        expect.fail("Should have thrown AccountNotMigratable");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("AccountNotMigratable");
+      }
+    });
 
-        const newValidator = web3.Keypair.generate().publicKey;
-        const validatorToRemove = vs.signers[vs.signers.length - 1];
+    it("migrates a version 1 validator set", async () => {
+      await fixture.migrations.migrate(
+        LEGACY_ACCOUNTS.validatorSet,
+        legacy.validatorSet(),
+        validatorSetAccounts,
+        legacyInstance
+      );
 
-        const proposalParams = {
-          added: [newValidator],
-          removed: [validatorToRemove],
-        };
+      const vs = await fixture.accounts.getValidatorSet(legacy.validatorSet());
+      const validators = LEGACY_ACCOUNTS.validators.length;
+      expect(vs.version).to.equal(ACCOUNT_VERSION);
+      expect(vs.signers.map((key) => key.toBase58())).to.deep.equal(
+        LEGACY_ACCOUNTS.validators.map((key) => key.toBase58())
+      );
+      expect(vs.weights.map((weight) => weight.toNumber())).to.deep.equal(
+        LEGACY_ACCOUNTS.validators.map(() => 1)
+      );
+      expect(vs.threshold).to.equal(calculateExpectedThreshold(validators));
+      expect(vs.thresholdWeight.toNumber()).to.equal(
+        calculateExpectedThresholdWeight(validators)
+      );
+      expect(vs.lastBatchId.toNumber()).to.equal(LEGACY_ACCOUNTS.lastBatchId);
+      expect(vs.bridgeRequestCount.toNumber()).to.equal(
+        LEGACY_ACCOUNTS.bridgeRequestCount
+      );
+      expect(vs.epoch.toNumber()).to.equal(0);
+      expect(vs.suspended).to.be.empty;
+      expect(vs.removalQueue).to.be.empty;
+      expect(vs.initializer.toBase58()).to.equal(
+        provider.wallet.publicKey.toBase58()
+      );
+      expect(vs.deployment.instanceId.toString()).to.equal(
+        legacyInstance.toString()
+      );
+      expect(vs.deployment.chainId).to.equal(TEST_DEPLOYMENT.chainId);
 
-        // Step 1: Create proposal
-        await fixture.bridgeVSU.call({
-          ...proposalParams,
-          batchId,
-          signers: [validators[0]],
-        });
+      const snapshot = await fixture.accounts.getValidatorSetSnapshotNullable(
+        legacy.validatorSetSnapshot(0)
+      );
+      expect(snapshot.signers).to.have.length(validators);
+      expect(
+        await provider.connection.getAccountInfo(legacy.treasury())
+      ).to.not.equal(null);
+      expect(
+        await provider.connection.getAccountInfo(legacy.validatorStats())
+      ).to.not.equal(null);
+      expect(
+        await provider.connection.getAccountInfo(LEGACY_ACCOUNTS.validatorSet)
+      ).to.equal(null);
+    });
 
-        // Step 2: Add approvals until threshold
-        for (let i = 1; i < threshold; i++) {
-          await fixture.bridgeVSU.call({
-            ...proposalParams,
-            batchId,
-            signers: [validators[i]],
-          });
-        }
+    it("migrates a version 1 bridging transaction", async () => {
+      const migrated = legacy.bridgingTransaction(LEGACY_ACCOUNTS.batchId);
 
-        // Verify execution
-        const updatedVs = await fixture.getValidatorSet();
+      await fixture.migrations.migrate(
+        LEGACY_ACCOUNTS.bridgingTransaction,
+        migrated,
+        [],
+        legacyInstance
+      );
 
-        // Net change: +1 -1 = 0 (same count)
-        expect(updatedVs.signers.length).to.equal(vs.signers.length);
-        expect(updatedVs.lastBatchId.toString()).to.equal(batchId.toString());
+      const bt = await fixture.accounts.getBridgingTransaction(migrated);
+      expect(bt.version).to.equal(ACCOUNT_VERSION);
+      expect(bt.id.toBase58()).to.equal(migrated.toBase58());
+      expect(bt.amount.toNumber()).to.equal(LEGACY_ACCOUNTS.amount);
+      expect(bt.receiver.toBase58()).to.equal(
+        LEGACY_ACCOUNTS.receiver.toBase58()
+      );
+      expect(bt.mintToken.toBase58()).to.equal(LEGACY_ACCOUNTS.mint.toBase58());
+      expect(bt.signers.map((key) => key.toBase58())).to.deep.equal(
+        LEGACY_ACCOUNTS.validators.slice(0, 2).map((key) => key.toBase58())
+      );
+      expect(bt.batchId.toNumber()).to.equal(LEGACY_ACCOUNTS.batchId);
+      expect(bt.sourceAmount.toNumber()).to.equal(0);
+      expect(
+        await provider.connection.getAccountInfo(
+          LEGACY_ACCOUNTS.bridgingTransaction
+        )
+      ).to.equal(null);
+    });
 
-        // Verify removal happened
-        const removedValidatorStillExists = updatedVs.signers.some(
-          (signer) => signer.toBase58() === validatorToRemove.toBase58()
+    it("rejects migrating a version 1 account twice", async () => {
+      try {
+        await fixture.migrations.migrate(
+          LEGACY_ACCOUNTS.bridgingTransaction,
+          legacy.bridgingTransaction(LEGACY_ACCOUNTS.batchId),
+          [],
+          legacyInstance
         );
-        expect(removedValidatorStillExists).to.be.false;
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        // The version 1 account was closed, it is no longer program-owned
+        expect(err.error?.errorCode?.code).to.equal("AccountNotMigratable");
+      }
+    });
+  });
 
-        // Verify addition happened
-        const newValidatorAdded = updatedVs.signers.some(
-          (signer) => signer.toBase58() === newValidator.toBase58()
-        );
-        expect(newValidatorAdded).to.be.true;
-      });
+  describe("Bridge Instances", () => {
+    const otherInstance = new BN(1);
 
-      it("executes removal of multiple validators in single proposal", async () => {
-        const batchId = await fixture.batchIds.freshBatchId();
-        const vs = await fixture.getValidatorSet();
-        const threshold = vs.threshold;
+    it("derives every PDA from the instance ID", async () => {
+      const other = new PDAs(program.programId, otherInstance);
 
-        // Ensure we have enough validators to remove 2 and stay above MIN
-        if (vs.signers.length <= LIMITS.MIN_VALIDATORS + 1) {
-          console.log(
-            "Skipping: not enough validators to test multiple removal"
-          );
-          return;
-        }
+      expect(other.validatorSet().equals(fixture.pdas.validatorSet())).to.be
+        .false;
+      expect(other.vault().equals(fixture.pdas.vault())).to.be.false;
+      expect(
+        await provider.connection.getAccountInfo(other.validatorSet())
+      ).to.equal(null);
+    });
 
-        // Remove last two validators
-        const validatorsToRemove = [
-          vs.signers[vs.signers.length - 1],
-          vs.signers[vs.signers.length - 2],
-        ];
+    it("rejects the accounts of another instance", async () => {
+      const vs = await fixture.getValidatorSet();
+      const validator = validators.find((v) =>
+        vs.signers.some((s) => s.equals(v.publicKey))
+      );
 
-        const proposalParams = {
-          added: [] as web3.PublicKey[],
-          removed: validatorsToRemove,
-        };
+      try {
+        await program.methods
+          .heartbeat(otherInstance)
+          .accountsPartial({
+            payer: provider.wallet.publicKey,
+            validator: validator.publicKey,
+            validatorSet: fixture.pdas.validatorSet(),
+            validatorStats: fixture.pdas.validatorStats(),
+          })
+          .signers([validator])
+          .rpc();
+        expect.fail("Should have thrown ConstraintSeeds");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("ConstraintSeeds");
+      }
+    });
 
-        // Collect enough approvals
-        for (let i = 0; i < threshold; i++) {
-          await fixture.bridgeVSU.call({
-            ...proposalParams,
-            batchId,
-            signers: [validators[i]],
-          });
-        }
+    it("initializes another instance through an initializer grant", async () => {
+      const other = new PDAs(program.programId, otherInstance);
+      const initializer = web3.Keypair.generate();
+      await airdrop(provider.connection, initializer.publicKey);
+      const vs = await fixture.getValidatorSet();
+
+      await fixture.governance.submit({
+        action: {
+          authorizeInitializer: {
+            instanceId: otherInstance,
+            initializer: initializer.publicKey,
+          },
+        },
+        batchId: await fixture.batchIds.freshBatchId(),
+        signers: validators.slice(0, vs.threshold),
+        actionAccounts: [other.initializerGrant()],
+      });
+      const grant = await program.account.initializerGrant.fetch(
+        other.initializerGrant()
+      );
+      expect(grant.initializer.equals(initializer.publicKey)).to.equal(true);
 
-        // Verify execution
-        const updatedVs = await fixture.getValidatorSet();
-        expect(updatedVs.signers.length).to.equal(vs.signers.length - 2);
+      await fixture.initialize.callWithGrant(
+        otherInstance,
+        validators.slice(0, 4),
+        initializer
+      );
 
-        // Verify both validators were removed
-        for (const removed of validatorsToRemove) {
-          const stillExists = updatedVs.signers.some(
-            (s) => s.toBase58() === removed.toBase58()
-          );
-          expect(stillExists).to.be.false;
-        }
+      const otherVs = await program.account.validatorSet.fetch(
+        other.validatorSet()
+      );
+      expect(otherVs.initializer.equals(initializer.publicKey)).to.equal(true);
+      expect(otherVs.deployment.instanceId.eq(otherInstance)).to.equal(true);
+      expect(
+        await provider.connection.getAccountInfo(other.initializerGrant())
+      ).to.equal(null);
+    });
+
+    it("rejects deployment-wide proposals outside the governing instance", async () => {
+      const other = new PDAs(program.programId, otherInstance);
+      const action = {
+        upgradeProgram: {
+          buffer: web3.Keypair.generate().publicKey,
+          bufferHash: programHash(Buffer.from("another program")),
+        },
+      };
+
+      try {
+        await program.methods
+          .submitProposal(otherInstance, action, new BN(1))
+          .accountsPartial({
+            payer: provider.wallet.publicKey,
+            validatorSet: other.validatorSet(),
+            proposal: other.governanceProposal(1),
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail("Should have thrown NotGoverningInstance");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("NotGoverningInstance");
+      }
+    });
+  });
+
+  // ============================================================================
+  // PROGRAM UPGRADE TESTS
+  // ============================================================================
+
+  describe("Program Upgrades", () => {
+    it("rejects a buffer that does not hold the approved program", async () => {
+      const buffer = await fixture.upgrades.createBuffer(
+        Buffer.from("skyline program v2")
+      );
+      const vs = await fixture.getValidatorSet();
+
+      try {
+        await fixture.governance.submit({
+          action: {
+            upgradeProgram: {
+              buffer,
+              bufferHash: programHash(Buffer.from("another program")),
+            },
+          },
+          batchId: await fixture.batchIds.freshBatchId(),
+          signers: validators.slice(0, vs.threshold),
+          actionAccounts: fixture.upgrades.actionAccounts(buffer),
+        });
+        expect.fail("Should have thrown ProgramBufferMismatch");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("ProgramBufferMismatch");
+      }
+    });
+
+    // Runs last: afterwards the upgrade authority PDA holds the program, so the
+    // owner can no longer migrate accounts or initialize instances
+    it("upgrades the program from an approved buffer", async () => {
+      const program = readFileSync("target/deploy/skyline_program.so");
+      const buffer = await fixture.upgrades.createBuffer(program);
+      await fixture.upgrades.handOver(buffer);
+      const vs = await fixture.getValidatorSet();
+      const batchId = await fixture.batchIds.freshBatchId();
+
+      const tx = await fixture.governance.submit({
+        action: {
+          upgradeProgram: { buffer, bufferHash: programHash(program) },
+        },
+        batchId,
+        signers: validators.slice(0, vs.threshold),
+        actionAccounts: fixture.upgrades.actionAccounts(buffer),
       });
+
+      const event = await fixture.events.parseProgramUpgradedEvent(tx);
+      expect(event.buffer.toBase58()).to.equal(buffer.toBase58());
+      expect(event.programHash).to.deep.equal(programHash(program));
+
+      expect(await provider.connection.getAccountInfo(buffer)).to.equal(null);
+      const programData = await provider.connection.getAccountInfo(
+        fixture.pdas.programData()
+      );
+      expect(
+        programData.data.subarray(
+          PROGRAM_DATA_METADATA_SIZE,
+          PROGRAM_DATA_METADATA_SIZE + program.length
+        )
+      ).to.deep.equal(program);
+      expect(await fixture.governance.fetchProposal(batchId)).to.equal(null);
     });
   });
 });